@@ -19,7 +19,7 @@ impl Callback {
         Self { name, signature }
     }
 
-    pub fn dependencies(&self) -> Vec<winmd::TypeDef> {
+    pub fn dependencies(&self) -> Vec<winmd::TypeDef<'static>> {
         self.signature.dependencies()
     }
 
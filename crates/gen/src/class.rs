@@ -66,27 +66,22 @@ impl Class {
                     }
                 }
                 ("Windows.Foundation.Metadata", "ComposableAttribute") => {
-                    // One of the arguments is a CompositionType enum and the Public variant
-                    // has a value of 2 as a signed 32-bit integer.
+                    // One of the arguments is a CompositionType enum.
                     for (_name, arg) in attribute.args() {
-                        if let winmd::AttributeArg::I32(2) = arg {
+                        if enum_value(&arg) == Some(COMPOSITION_TYPE_PUBLIC) {
                             add_type(
                                 &mut interfaces,
                                 &attribute_factory(&attribute).unwrap(),
-                                &name.namespace,
+                                name.namespace,
                                 InterfaceKind::Composable,
                             );
                         }
                     }
                 }
                 ("Windows.Foundation.Metadata", "MarshalingBehaviorAttribute") => {
-                    // The only argument is a MarshalingType enum and the Agile variant
-                    // has a value of 2 as a signed 32-bit integer.
+                    // The only argument is a MarshalingType enum.
                     let (_name, arg) = &attribute.args()[0];
-
-                    if let winmd::AttributeArg::I32(2) = arg {
-                        is_agile = true;
-                    }
+                    is_agile |= enum_value(arg) == Some(MARSHALING_TYPE_AGILE);
                 }
                 _ => {}
             }
@@ -104,7 +99,7 @@ impl Class {
         }
     }
 
-    pub fn dependencies(&self) -> Vec<winmd::TypeDef> {
+    pub fn dependencies(&self) -> Vec<winmd::TypeDef<'static>> {
         self.interfaces
             .iter()
             .flat_map(|i| i.name.dependencies())
@@ -310,7 +305,23 @@ impl Class {
     }
 }
 
-fn attribute_factory(attribute: &winmd::Attribute) -> Option<winmd::TypeDef> {
+/// The value of `Windows.Foundation.Metadata.CompositionType.Public`
+const COMPOSITION_TYPE_PUBLIC: i32 = 2;
+
+/// The value of `Windows.Foundation.Metadata.MarshalingType.Agile`
+const MARSHALING_TYPE_AGILE: i32 = 2;
+
+/// The value of an `Int32` enum argument, which is read as a plain `Int32` when the enum's
+/// metadata isn't loaded
+fn enum_value(arg: &winmd::AttributeArg) -> Option<i32> {
+    match arg {
+        winmd::AttributeArg::Enum(_, value) => enum_value(value),
+        winmd::AttributeArg::I32(value) => Some(*value),
+        _ => None,
+    }
+}
+
+fn attribute_factory(attribute: &winmd::Attribute<'static>) -> Option<winmd::TypeDef<'static>> {
     for (_, arg) in attribute.args() {
         if let winmd::AttributeArg::TypeDef(def) = arg {
             return Some(def);
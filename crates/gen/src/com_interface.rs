@@ -241,7 +241,7 @@ impl ComInterface {
         }
     }
 
-    pub fn dependencies(&self) -> Vec<winmd::TypeDef> {
+    pub fn dependencies(&self) -> Vec<winmd::TypeDef<'static>> {
         self.methods
             .iter()
             .map(|method| method.signature.dependencies())
@@ -1,60 +1,88 @@
 use crate::*;
 use squote::{quote, TokenStream};
+use std::convert::TryFrom;
 
 #[derive(Debug)]
 pub struct Constant {
     pub name: TypeName,
-    pub field: winmd::Field,
+    pub field: winmd::Field<'static>,
 }
 
 impl Constant {
-    pub fn new(name: TypeName, field: &winmd::Field) -> Self {
+    pub fn new(name: TypeName, field: &winmd::Field<'static>) -> Self {
         Self {
             name,
             field: *field,
         }
     }
 
+    pub fn dependencies(&self) -> Vec<winmd::TypeDef<'static>> {
+        // Primitive constants need nothing else, while the others may be of any type.
+        if self.field.constant().is_some() {
+            Vec::new()
+        } else {
+            Type::from_field(&self.field, self.name.namespace)
+                .kind
+                .dependencies()
+        }
+    }
+
     pub fn gen(&self) -> TokenStream {
-        let name = self.field.name();
+        let name = format_ident(self.field.name());
 
-        // TODO: workaround for https://github.com/microsoft/win32metadata/issues/90
-        if name == "NaN" || name == "POSITIVE_INFINITY" || name == "NEGATIVE_INFINITY" {
-            return quote! {};
-        }
+        if let Some(constant) = self.field.constant() {
+            let value = ConstantValue::from_constant(self.field.name(), &constant);
+            let value = value.gen();
 
-        // TODO: workaround for https://github.com/microsoft/win32metadata/issues/88
-        if self.field.constant().is_none() {
-            return quote! {};
+            return quote! {
+                pub const #name: #value;
+            };
         }
 
-        let constant = self
-            .field
-            .constant()
-            .expect(&format!("Missing constant value: {}", name));
-
-        let mut value = constant.value();
+        // Constants that can't be stored in a `Constant` row, such as GUIDs and structs,
+        // are instead described by an attribute on the field.
+        let t = Type::from_field(&self.field, self.name.namespace);
 
-        let value = match constant.value_type() {
-            winmd::ElementType::I8 => ConstantValue::I8(value.read_i8()),
-            winmd::ElementType::U8 => ConstantValue::U8(value.read_u8()),
-            winmd::ElementType::I16 => ConstantValue::I16(value.read_i16()),
-            winmd::ElementType::U16 => ConstantValue::U16(value.read_u16()),
-            winmd::ElementType::I32 => ConstantValue::I32(value.read_i32()),
-            winmd::ElementType::U32 => ConstantValue::U32(value.read_u32()),
-            winmd::ElementType::I64 => ConstantValue::I64(value.read_i64()),
-            winmd::ElementType::U64 => ConstantValue::U64(value.read_u64()),
-            winmd::ElementType::F32 => ConstantValue::F32(value.read_f32()),
-            winmd::ElementType::F64 => ConstantValue::F64(value.read_f64()),
-            winmd::ElementType::String => ConstantValue::String(value.read_utf16()),
-            value_type => panic!("Unsupported constant: {} ({:?})", name, value_type),
+        let value = if t.kind == TypeKind::Guid && t.pointers == 0 && t.array.is_empty() {
+            TypeGuid::from_field(&self.field).map(|guid| gen_guid(&guid))
+        } else {
+            None
         };
 
-        let name = format_ident(name);
-        let value = value.gen();
+        let value = value.or_else(|| {
+            let value = self.field.attributes().find_map(|attribute| {
+                if attribute.name() != ("Windows.Win32.Interop", "ConstantAttribute") {
+                    return None;
+                }
+
+                match attribute.args().pop() {
+                    Some((_, winmd::AttributeArg::String(value))) => Some(value),
+                    _ => None,
+                }
+            })?;
+
+            let mut values = parse_values(&value)?;
+
+            // A struct's value may or may not be wrapped in braces.
+            let value = if values.len() == 1 {
+                values.remove(0)
+            } else {
+                AttributeValue::List(values)
+            };
+
+            gen_value(&t, &value, self.name.namespace)
+        });
 
-        quote! {
-            pub const #name: #value;
+        // Constants whose value can't be decoded are left out rather than guessed at.
+        match value {
+            Some(value) => {
+                let kind = t.gen_field();
+
+                quote! {
+                    pub const #name: #kind = #value;
+                }
+            }
+            None => quote! {},
         }
     }
 }
@@ -75,6 +103,25 @@ pub enum ConstantValue {
 }
 
 impl ConstantValue {
+    fn from_constant(name: &str, constant: &winmd::Constant<'static>) -> Self {
+        let mut value = constant.value();
+
+        match constant.value_type() {
+            winmd::ElementType::I8 => Self::I8(value.read_i8()),
+            winmd::ElementType::U8 => Self::U8(value.read_u8()),
+            winmd::ElementType::I16 => Self::I16(value.read_i16()),
+            winmd::ElementType::U16 => Self::U16(value.read_u16()),
+            winmd::ElementType::I32 => Self::I32(value.read_i32()),
+            winmd::ElementType::U32 => Self::U32(value.read_u32()),
+            winmd::ElementType::I64 => Self::I64(value.read_i64()),
+            winmd::ElementType::U64 => Self::U64(value.read_u64()),
+            winmd::ElementType::F32 => Self::F32(value.read_f32()),
+            winmd::ElementType::F64 => Self::F64(value.read_f64()),
+            winmd::ElementType::String => Self::String(value.read_utf16()),
+            value_type => panic!("Unsupported constant: {} ({:?})", name, value_type),
+        }
+    }
+
     fn gen(&self) -> TokenStream {
         match self {
             Self::U8(value) => quote! { u8 = #value },
@@ -85,9 +132,633 @@ impl ConstantValue {
             Self::I32(value) => quote! { i32 = #value },
             Self::U64(value) => quote! { u64 = #value },
             Self::I64(value) => quote! { i64 = #value },
-            Self::F32(value) => quote! { f32 = #value },
-            Self::F64(value) => quote! { f64 = #value },
+            Self::F32(value) => {
+                let value = gen_f32(*value);
+                quote! { f32 = #value }
+            }
+            Self::F64(value) => {
+                let value = gen_f64(*value);
+                quote! { f64 = #value }
+            }
             Self::String(value) => quote! { &'static str = #value },
         }
     }
 }
+
+// Literals can't express the special floating point values so they are named instead.
+fn gen_f32(value: f32) -> TokenStream {
+    if value.is_nan() {
+        quote! { ::std::f32::NAN }
+    } else if value == f32::INFINITY {
+        quote! { ::std::f32::INFINITY }
+    } else if value == f32::NEG_INFINITY {
+        quote! { ::std::f32::NEG_INFINITY }
+    } else {
+        quote! { #value }
+    }
+}
+
+fn gen_f64(value: f64) -> TokenStream {
+    if value.is_nan() {
+        quote! { ::std::f64::NAN }
+    } else if value == f64::INFINITY {
+        quote! { ::std::f64::INFINITY }
+    } else if value == f64::NEG_INFINITY {
+        quote! { ::std::f64::NEG_INFINITY }
+    } else {
+        quote! { #value }
+    }
+}
+
+fn gen_guid(guid: &TypeGuid) -> TokenStream {
+    let guid = guid.gen();
+
+    quote! {
+        ::windows::Guid::from_values(#guid)
+    }
+}
+
+/// A value written as text in a `ConstantAttribute`, such as the `PROPERTYKEY` value
+/// `{b725f130-47ef-101a-a5f1-02608c9eebac}, 10`, where braces group the fields of a
+/// struct or the elements of an array
+#[derive(Debug, PartialEq)]
+enum AttributeValue {
+    Scalar(String),
+    List(Vec<AttributeValue>),
+}
+
+fn parse_values(value: &str) -> Option<Vec<AttributeValue>> {
+    let mut chars = value.chars().peekable();
+    let values = parse_list(&mut chars)?;
+
+    if chars.next().is_some() {
+        None
+    } else {
+        Some(values)
+    }
+}
+
+fn parse_list(chars: &mut std::iter::Peekable<std::str::Chars>) -> Option<Vec<AttributeValue>> {
+    let mut values = Vec::new();
+
+    loop {
+        while matches!(chars.peek(), Some(c) if c.is_whitespace()) {
+            chars.next();
+        }
+
+        if chars.peek() == Some(&'{') {
+            chars.next();
+            values.push(AttributeValue::List(parse_list(chars)?));
+
+            if chars.next() != Some('}') {
+                return None;
+            }
+        } else {
+            let mut scalar = String::new();
+
+            while let Some(c) = chars.peek() {
+                if *c == ',' || *c == '}' {
+                    break;
+                }
+
+                scalar.push(*c);
+                chars.next();
+            }
+
+            let scalar = scalar.trim();
+
+            if scalar.is_empty() {
+                // Only an empty list, like `{}`, may have no value.
+                return if values.is_empty() && chars.peek() != Some(&',') {
+                    Some(values)
+                } else {
+                    None
+                };
+            }
+
+            values.push(AttributeValue::Scalar(scalar.to_string()));
+        }
+
+        while matches!(chars.peek(), Some(c) if c.is_whitespace()) {
+            chars.next();
+        }
+
+        if chars.peek() == Some(&',') {
+            chars.next();
+        } else {
+            return Some(values);
+        }
+    }
+}
+
+/// Parses a C integer literal, which may be hexadecimal and have a sign or suffix
+fn parse_integer(value: &str) -> Option<i128> {
+    let value = value.trim_end_matches(&['u', 'U', 'l', 'L'][..]);
+
+    let (negative, value) = match value.strip_prefix('-') {
+        Some(value) => (true, value),
+        None => (false, value),
+    };
+
+    let value = match value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        Some(value) => i128::from_str_radix(value, 16).ok()?,
+        None => value.parse().ok()?,
+    };
+
+    Some(if negative { -value } else { value })
+}
+
+fn parse_float(value: &str) -> Option<f64> {
+    match value.trim_end_matches(&['f', 'F'][..]) {
+        "NaN" => Some(f64::NAN),
+        "Infinity" => Some(f64::INFINITY),
+        "-Infinity" => Some(f64::NEG_INFINITY),
+        value => value.parse().ok(),
+    }
+}
+
+fn scalar(value: &AttributeValue) -> Option<&str> {
+    match value {
+        AttributeValue::Scalar(value) => Some(value),
+        AttributeValue::List(_) => None,
+    }
+}
+
+/// Generates an expression of type `t` from its textual value, if the value suits the type
+fn gen_value(
+    t: &Type,
+    value: &AttributeValue,
+    calling_namespace: &'static str,
+) -> Option<TokenStream> {
+    if let Some((len, dimensions)) = t.array.split_first() {
+        let element = Type {
+            array: dimensions.to_vec(),
+            ..t.clone()
+        };
+
+        let values = match value {
+            AttributeValue::List(values) if values.len() == *len as usize => values,
+            _ => return None,
+        };
+
+        let values = values
+            .iter()
+            .map(|value| gen_value(&element, value, calling_namespace))
+            .collect::<Option<Vec<_>>>()?;
+
+        return Some(quote! { [#(#values),*] });
+    }
+
+    if t.pointers > 0 {
+        if let TypeKind::Delegate(_) = t.kind {
+            return None;
+        }
+
+        return match parse_integer(scalar(value)?)? {
+            0 if t.is_const => Some(quote! { ::std::ptr::null() }),
+            0 => Some(quote! { ::std::ptr::null_mut() }),
+            _ => None,
+        };
+    }
+
+    macro_rules! integer {
+        ($ty:ty) => {{
+            let value = <$ty>::try_from(parse_integer(scalar(value)?)?).ok()?;
+            Some(quote! { #value })
+        }};
+    }
+
+    match &t.kind {
+        TypeKind::Bool => match scalar(value)? {
+            "true" => Some(quote! { true }),
+            "false" => Some(quote! { false }),
+            value => Some(if parse_integer(value)? != 0 {
+                quote! { true }
+            } else {
+                quote! { false }
+            }),
+        },
+        TypeKind::I8 => integer!(i8),
+        TypeKind::U8 => integer!(u8),
+        TypeKind::I16 => integer!(i16),
+        TypeKind::Char | TypeKind::U16 => integer!(u16),
+        TypeKind::I32 => integer!(i32),
+        TypeKind::U32 => integer!(u32),
+        TypeKind::I64 => integer!(i64),
+        TypeKind::U64 => integer!(u64),
+        TypeKind::ISize => integer!(isize),
+        TypeKind::USize => integer!(usize),
+        TypeKind::F32 => Some(gen_f32(parse_float(scalar(value)?)? as f32)),
+        TypeKind::F64 => Some(gen_f64(parse_float(scalar(value)?)?)),
+        TypeKind::Bool32 => {
+            let value = integer!(i32)?;
+            Some(quote! { ::windows::BOOL(#value) })
+        }
+        TypeKind::ErrorCode => {
+            let value = integer!(u32)?;
+            Some(quote! { ::windows::ErrorCode(#value) })
+        }
+        TypeKind::Guid => gen_guid_value(value),
+        TypeKind::Enum(name) => {
+            let underlying_type = name
+                .def
+                .fields()
+                .find(|field| !field.flags().literal())
+                .map(|field| Type::from_field(&field, calling_namespace))?;
+
+            let value = gen_value(&underlying_type, value, calling_namespace)?;
+            let name = name.gen();
+            Some(quote! { #name(#value) })
+        }
+        TypeKind::Struct(name) => gen_struct_value(name, value, calling_namespace),
+        _ => None,
+    }
+}
+
+/// A GUID may be written as a string, with or without braces, or as its eleven parts
+fn gen_guid_value(value: &AttributeValue) -> Option<TokenStream> {
+    let values = match value {
+        AttributeValue::Scalar(value) => {
+            return TypeGuid::from_string(value).map(|guid| gen_guid(&guid))
+        }
+        AttributeValue::List(values) => values,
+    };
+
+    if let [AttributeValue::Scalar(value)] = values.as_slice() {
+        return TypeGuid::from_string(value).map(|guid| gen_guid(&guid));
+    }
+
+    let mut parts = Vec::new();
+
+    for value in values {
+        match value {
+            AttributeValue::Scalar(value) => parts.push(parse_integer(value)?),
+            AttributeValue::List(values) => {
+                for value in values {
+                    parts.push(parse_integer(scalar(value)?)?);
+                }
+            }
+        }
+    }
+
+    if parts.len() != 11 {
+        return None;
+    }
+
+    let mut guid = TypeGuid::default();
+    guid.0[0] = GuidConstant::U32(u32::try_from(parts[0]).ok()?);
+    guid.0[1] = GuidConstant::U16(u16::try_from(parts[1]).ok()?);
+    guid.0[2] = GuidConstant::U16(u16::try_from(parts[2]).ok()?);
+
+    for (index, part) in parts.iter().enumerate().skip(3) {
+        guid.0[index] = GuidConstant::U8(u8::try_from(*part).ok()?);
+    }
+
+    Some(gen_guid(&guid))
+}
+
+fn gen_struct_value(
+    name: &TypeName,
+    value: &AttributeValue,
+    calling_namespace: &'static str,
+) -> Option<TokenStream> {
+    let def = Struct::from_type_name(name.clone());
+
+    // GUID structs are generated as constants, and unions and structs with a declared layout
+    // don't describe their fields' values well enough to be written this way.
+    if def.guid != TypeGuid::default()
+        || def.is_union
+        || def.packing.is_some()
+        || !def.offsets.is_empty()
+    {
+        return None;
+    }
+
+    // The field types are needed relative to the constant rather than the struct.
+    let fields: Vec<Type> = name
+        .def
+        .fields()
+        .map(|field| Type::from_field(&field, calling_namespace))
+        .collect();
+
+    if fields.is_empty() {
+        return None;
+    }
+
+    let struct_name = name.gen();
+
+    if def.is_typedef {
+        let value = gen_value(&fields[0], value, calling_namespace)?;
+        return Some(quote! { #struct_name(#value) });
+    }
+
+    let values = match value {
+        AttributeValue::List(values) if values.len() == fields.len() => values,
+        _ => return None,
+    };
+
+    let values = def
+        .fields
+        .iter()
+        .zip(&fields)
+        .zip(values)
+        .map(|(((name, _), t), value)| {
+            let name = format_ident(name);
+            let value = gen_value(t, value, calling_namespace)?;
+            Some(quote! { #name: #value })
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(quote! { #struct_name { #(#values),* } })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use winmd::writer::{
+        AttributeArg, AttributeType, HasAttribute, MemberRefParent, MethodSig, TypeDefOrRef,
+        TypeSig, Writer,
+    };
+
+    fn scalar(value: &str) -> AttributeValue {
+        AttributeValue::Scalar(value.to_string())
+    }
+
+    /// The generated code without whitespace, which varies with how it was quoted
+    fn text(tokens: TokenStream) -> String {
+        tokens
+            .into_string()
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect()
+    }
+
+    fn attribute_type(
+        writer: &mut Writer,
+        namespace: &str,
+        name: &str,
+        params: Vec<TypeSig>,
+    ) -> AttributeType {
+        let parent = match writer.type_ref(None, namespace, name) {
+            TypeDefOrRef::TypeRef(index) => MemberRefParent::TypeRef(index),
+            _ => unreachable!(),
+        };
+
+        let constructor =
+            writer.member_ref(parent, ".ctor", &MethodSig::new(TypeSig::Void, params));
+        AttributeType::MemberRef(constructor)
+    }
+
+    fn guid_args() -> Vec<AttributeArg> {
+        let mut args = vec![
+            AttributeArg::U32(0x1234_5678),
+            AttributeArg::U16(0x9abc),
+            AttributeArg::U16(0xdef0),
+        ];
+
+        args.extend((1..=8).map(AttributeArg::U8));
+        args
+    }
+
+    /// Reads structs and a Win32 GUID constant, each of which is a test case
+    fn reader() -> &'static winmd::TypeReader {
+        let mut writer = Writer::new("Constants.winmd");
+        let value_type = writer.type_ref(None, "System", "ValueType");
+        let object = writer.type_ref(None, "System", "Object");
+        let guid = writer.type_ref(None, "System", "Guid");
+
+        let guid_params = vec![TypeSig::U32, TypeSig::U16, TypeSig::U16]
+            .into_iter()
+            .chain((0..8).map(|_| TypeSig::U8))
+            .collect::<Vec<_>>();
+
+        let win32_guid = attribute_type(
+            &mut writer,
+            "Windows.Win32.Interop",
+            "GuidAttribute",
+            guid_params,
+        );
+        let interop_guid = attribute_type(
+            &mut writer,
+            "System.Runtime.InteropServices",
+            "GuidAttribute",
+            vec![TypeSig::String],
+        );
+        let typedef = attribute_type(
+            &mut writer,
+            "Windows.Win32.Interop",
+            "NativeTypedefAttribute",
+            Vec::new(),
+        );
+
+        // Public | SequentialLayout | Sealed
+        writer.type_def(0x109, "Test", "POINT", Some(value_type));
+        writer.field(0x6, "x", &TypeSig::I32);
+        writer.field(0x6, "y", &TypeSig::I32);
+
+        let handle = writer.type_def(0x109, "Test", "HANDLE", Some(value_type));
+        writer.field(0x6, "Value", &TypeSig::ISize);
+        writer.attribute(HasAttribute::TypeDef(handle), typedef, &[], &[]);
+
+        // Public | ExplicitLayout | Sealed
+        writer.type_def(0x111, "Test", "UNION", Some(value_type));
+        writer.field(0x6, "a", &TypeSig::I32);
+        writer.field(0x6, "b", &TypeSig::F32);
+
+        let guid_struct = writer.type_def(0x109, "Test", "GUID_STRUCT", Some(value_type));
+        writer.field(0x6, "a", &TypeSig::I32);
+        writer.attribute(
+            HasAttribute::TypeDef(guid_struct),
+            interop_guid,
+            &[AttributeArg::String(Some(
+                "b725f130-47ef-101a-a5f1-02608c9eebac".to_string(),
+            ))],
+            &[],
+        );
+
+        // Win32's `GuidAttribute` only describes constants, so this is an ordinary struct.
+        let win32_struct = writer.type_def(0x109, "Test", "WIN32_STRUCT", Some(value_type));
+        writer.field(0x6, "a", &TypeSig::I32);
+        writer.attribute(
+            HasAttribute::TypeDef(win32_struct),
+            win32_guid,
+            &guid_args(),
+            &[],
+        );
+
+        // Public | Abstract | Sealed
+        writer.type_def(0x181, "Test", "Apis", Some(object));
+        let field = writer.field(0x16, "GUID_CONSTANT", &TypeSig::ValueType(guid));
+        writer.attribute(HasAttribute::Field(field), win32_guid, &guid_args(), &[]);
+
+        let reader = winmd::TypeReader::builder()
+            .bytes("Constants.winmd", writer.into_bytes())
+            .exclude_default(true)
+            .build()
+            .unwrap();
+
+        Box::leak(Box::new(reader))
+    }
+
+    fn type_name(reader: &'static winmd::TypeReader, name: &str) -> TypeName {
+        TypeName::from_type_def(&reader.expect_type_def(("Test", name)), "Test")
+    }
+
+    #[test]
+    fn values() {
+        assert_eq!(parse_values("1"), Some(vec![scalar("1")]));
+        assert_eq!(
+            parse_values(" -1 , 0x2 "),
+            Some(vec![scalar("-1"), scalar("0x2")])
+        );
+        assert_eq!(
+            parse_values("{}"),
+            Some(vec![AttributeValue::List(Vec::new())])
+        );
+
+        assert_eq!(
+            parse_values("{{0x1, 2}, {}}, 3.5f"),
+            Some(vec![
+                AttributeValue::List(vec![
+                    AttributeValue::List(vec![scalar("0x1"), scalar("2")]),
+                    AttributeValue::List(Vec::new()),
+                ]),
+                scalar("3.5f"),
+            ])
+        );
+
+        assert_eq!(parse_values("{1, 2"), None);
+        assert_eq!(parse_values("1}"), None);
+        assert_eq!(parse_values("1, , 2"), None);
+        assert_eq!(parse_values("1,"), None);
+        assert_eq!(parse_values("{,}"), None);
+    }
+
+    #[test]
+    fn integers() {
+        assert_eq!(parse_integer("10"), Some(10));
+        assert_eq!(parse_integer("-1"), Some(-1));
+        assert_eq!(parse_integer("0x10"), Some(16));
+        assert_eq!(parse_integer("0X1f"), Some(31));
+        assert_eq!(parse_integer("-0x80"), Some(-128));
+        assert_eq!(parse_integer("10u"), Some(10));
+        assert_eq!(parse_integer("10L"), Some(10));
+        assert_eq!(parse_integer("0xFFFFFFFFul"), Some(0xffff_ffff));
+        assert_eq!(
+            parse_integer("0xFFFFFFFFFFFFFFFFULL"),
+            Some(u64::MAX as i128)
+        );
+
+        assert_eq!(parse_integer(""), None);
+        assert_eq!(parse_integer("0x"), None);
+        assert_eq!(parse_integer("1.5"), None);
+        assert_eq!(parse_integer("ten"), None);
+    }
+
+    #[test]
+    fn floats() {
+        assert_eq!(parse_float("1.5"), Some(1.5));
+        assert_eq!(parse_float("1.5f"), Some(1.5));
+        assert_eq!(parse_float("-2e3F"), Some(-2000.0));
+        assert_eq!(parse_float("Infinity"), Some(f64::INFINITY));
+        assert_eq!(parse_float("-Infinity"), Some(f64::NEG_INFINITY));
+        assert!(parse_float("NaN").unwrap().is_nan());
+        assert_eq!(parse_float("one"), None);
+
+        assert_eq!(text(gen_f32(f32::NAN)), "::std::f32::NAN");
+        assert_eq!(text(gen_f64(f64::NEG_INFINITY)), "::std::f64::NEG_INFINITY");
+        assert_eq!(text(gen_f64(1.5)), "1.5f64");
+    }
+
+    #[test]
+    fn guid_values() {
+        let guid = TypeGuid::from_string("b725f130-47ef-101a-a5f1-02608c9eebac").unwrap();
+        assert!(guid.0[0] == GuidConstant::U32(0xb725_f130));
+        assert!(guid.0[10] == GuidConstant::U8(0xac));
+        let expected = text(gen_guid(&guid));
+
+        // As a string, with or without braces
+        let value = scalar("b725f130-47ef-101a-a5f1-02608c9eebac");
+        assert_eq!(gen_guid_value(&value).map(text), Some(expected.clone()));
+
+        let value = AttributeValue::List(vec![value]);
+        assert_eq!(gen_guid_value(&value).map(text), Some(expected.clone()));
+
+        // As its eleven parts, with or without the last eight grouped
+        let values = parse_values(
+            "0xb725f130, 0x47ef, 0x101a, {0xa5, 0xf1, 0x02, 0x60, 0x8c, 0x9e, 0xeb, 0xac}",
+        )
+        .unwrap();
+        assert_eq!(
+            gen_guid_value(&AttributeValue::List(values)).map(text),
+            Some(expected.clone())
+        );
+
+        let values = parse_values(
+            "0xb725f130, 0x47ef, 0x101a, 0xa5, 0xf1, 0x02, 0x60, 0x8c, 0x9e, 0xeb, 0xac",
+        )
+        .unwrap();
+        assert_eq!(
+            gen_guid_value(&AttributeValue::List(values)).map(text),
+            Some(expected)
+        );
+
+        assert!(TypeGuid::from_string("{b725f130-47ef-101a-a5f1-02608c9eebac}").is_none());
+        assert!(TypeGuid::from_string("b725f130-47ef-101a-a5f1-02608c9eebaz").is_none());
+        assert!(gen_guid_value(&scalar("nonsense")).is_none());
+
+        let values = parse_values("1, 2, 3, {4, 5, 6, 7, 8, 9, 10}").unwrap();
+        assert!(gen_guid_value(&AttributeValue::List(values)).is_none());
+
+        let values = parse_values("1, 0x10000, 3, {4, 5, 6, 7, 8, 9, 10, 11}").unwrap();
+        assert!(gen_guid_value(&AttributeValue::List(values)).is_none());
+    }
+
+    #[test]
+    fn struct_values() {
+        let reader = reader();
+
+        let point = type_name(reader, "POINT");
+        let value = AttributeValue::List(parse_values("1, -2").unwrap());
+        assert_eq!(
+            gen_struct_value(&point, &value, "Test").map(text),
+            Some("POINT{x:1i32,y:-2i32}".to_string())
+        );
+
+        let value = AttributeValue::List(parse_values("1").unwrap());
+        assert!(gen_struct_value(&point, &value, "Test").is_none());
+        assert!(gen_struct_value(&point, &scalar("1"), "Test").is_none());
+
+        let handle = type_name(reader, "HANDLE");
+        assert_eq!(
+            gen_struct_value(&handle, &scalar("-1"), "Test").map(text),
+            Some("HANDLE(-1isize)".to_string())
+        );
+
+        let value = AttributeValue::List(parse_values("1, 2").unwrap());
+        assert!(gen_struct_value(&type_name(reader, "UNION"), &value, "Test").is_none());
+
+        let value = AttributeValue::List(parse_values("1").unwrap());
+        assert!(gen_struct_value(&type_name(reader, "GUID_STRUCT"), &value, "Test").is_none());
+        assert_eq!(
+            gen_struct_value(&type_name(reader, "WIN32_STRUCT"), &value, "Test").map(text),
+            Some("WIN32_STRUCT{a:1i32}".to_string())
+        );
+    }
+
+    #[test]
+    fn guid_constants() {
+        let reader = reader();
+        let apis = reader.expect_type_def(("Test", "Apis"));
+        let field = apis.fields().next().unwrap();
+        let constant = Constant::new(TypeName::from_type_def(&apis, "Test"), &field);
+
+        assert_eq!(
+            text(constant.gen()),
+            "pubconstGUID_CONSTANT:::windows::Guid=::windows::Guid::from_values(305419896,39612,57072,[1,2,3,4,5,6,7,8],);"
+        );
+    }
+}
@@ -20,7 +20,7 @@ impl Delegate {
         Self { name, method, guid }
     }
 
-    pub fn dependencies(&self) -> Vec<winmd::TypeDef> {
+    pub fn dependencies(&self) -> Vec<winmd::TypeDef<'static>> {
         self.method.dependencies()
     }
 
@@ -1,25 +1,88 @@
 use crate::*;
-use squote::{format_ident, quote, Literal, TokenStream};
+use squote::{quote, Literal, TokenStream};
 
 #[derive(Debug)]
 pub struct Enum {
     pub name: TypeName,
     pub fields: Vec<(&'static str, EnumConstant)>,
-    pub underlying_type: winmd::ElementType,
+    pub underlying_type: winmd::ElementType<'static>,
     pub signature: String,
+    pub is_flags: bool,
 }
 
 #[derive(Copy, Clone, PartialEq, PartialOrd, Eq, Ord, Debug)]
 pub enum EnumConstant {
-    U32(u32),
+    I8(i8),
+    U8(u8),
+    I16(i16),
+    U16(u16),
     I32(i32),
+    U32(u32),
+    I64(i64),
+    U64(u64),
 }
 
 impl EnumConstant {
+    /// Converts a value to the enum's underlying type, wrapping as a C cast would, since
+    /// metadata sometimes stores a constant with a different type than its enum
+    fn new(underlying_type: &winmd::ElementType<'static>, value: i128) -> Self {
+        match underlying_type {
+            winmd::ElementType::I8 => Self::I8(value as i8),
+            winmd::ElementType::U8 => Self::U8(value as u8),
+            winmd::ElementType::I16 => Self::I16(value as i16),
+            winmd::ElementType::U16 => Self::U16(value as u16),
+            winmd::ElementType::I32 => Self::I32(value as i32),
+            winmd::ElementType::U32 => Self::U32(value as u32),
+            winmd::ElementType::I64 => Self::I64(value as i64),
+            winmd::ElementType::U64 => Self::U64(value as u64),
+            _ => panic!("Unexpected enum underlying type: {:?}", underlying_type),
+        }
+    }
+
+    fn from_constant(
+        underlying_type: &winmd::ElementType<'static>,
+        constant: &winmd::Constant<'static>,
+    ) -> Self {
+        let mut value = constant.value();
+
+        let value = match constant.value_type() {
+            winmd::ElementType::I8 => value.read_i8() as i128,
+            winmd::ElementType::U8 => value.read_u8() as i128,
+            winmd::ElementType::I16 => value.read_i16() as i128,
+            winmd::ElementType::U16 => value.read_u16() as i128,
+            winmd::ElementType::I32 => value.read_i32() as i128,
+            winmd::ElementType::U32 => value.read_u32() as i128,
+            winmd::ElementType::I64 => value.read_i64() as i128,
+            winmd::ElementType::U64 => value.read_u64() as i128,
+            _ => panic!("Enum::from_type_def"),
+        };
+
+        Self::new(underlying_type, value)
+    }
+
     fn next(&self) -> Self {
         match self {
-            Self::U32(value) => Self::U32(value + 1),
-            Self::I32(value) => Self::I32(value + 1),
+            Self::I8(value) => Self::I8(value.wrapping_add(1)),
+            Self::U8(value) => Self::U8(value.wrapping_add(1)),
+            Self::I16(value) => Self::I16(value.wrapping_add(1)),
+            Self::U16(value) => Self::U16(value.wrapping_add(1)),
+            Self::I32(value) => Self::I32(value.wrapping_add(1)),
+            Self::U32(value) => Self::U32(value.wrapping_add(1)),
+            Self::I64(value) => Self::I64(value.wrapping_add(1)),
+            Self::U64(value) => Self::U64(value.wrapping_add(1)),
+        }
+    }
+
+    pub fn gen(&self) -> TokenStream {
+        match self {
+            Self::I8(value) => quote! { #value },
+            Self::U8(value) => quote! { #value },
+            Self::I16(value) => quote! { #value },
+            Self::U16(value) => quote! { #value },
+            Self::I32(value) => quote! { #value },
+            Self::U32(value) => quote! { #value },
+            Self::I64(value) => quote! { #value },
+            Self::U64(value) => quote! { #value },
         }
     }
 }
@@ -32,78 +95,115 @@ impl Enum {
             String::new()
         };
 
-        let mut fields = Vec::new();
-        let mut underlying_type = None;
+        // The underlying type is that of the enum's only instance field, which usually
+        // but not always comes before its constants.
+        let underlying_type = name
+            .def
+            .fields()
+            .find(|field| !field.flags().literal())
+            .and_then(|field| field.signature().ty.element_type())
+            .expect("Enum.from_type_name");
+
+        let mut fields: Vec<(&'static str, EnumConstant)> = Vec::new();
 
         for field in name.def.fields() {
             if field.flags().literal() {
-                if let Some(constant) = field.constant() {
-                    let mut value = constant.value();
-
-                    let value = match constant.value_type() {
-                        winmd::ElementType::I32 => EnumConstant::I32(value.read_i32()),
-                        winmd::ElementType::U32 => EnumConstant::U32(value.read_u32()),
-                        _ => panic!("Enum::from_type_def"),
-                    };
-
-                    fields.push((field.name(), value));
-                } else if fields.is_empty() {
-                    fields.push((field.name(), EnumConstant::I32(0)));
+                let value = if let Some(constant) = field.constant() {
+                    EnumConstant::from_constant(&underlying_type, &constant)
+                } else if let Some((_, last)) = fields.last() {
+                    last.next()
                 } else {
-                    fields.push((field.name(), fields.last().unwrap().1.next()));
-                }
-            } else {
-                let blob = &mut field.sig();
-                blob.read_unsigned();
-                blob.read_modifiers();
+                    EnumConstant::new(&underlying_type, 0)
+                };
 
-                blob.read_expected(0x1D);
-                blob.read_modifiers();
-
-                underlying_type = Some(winmd::ElementType::from_blob(blob));
+                fields.push((field.name(), value));
             }
         }
+
+        // Unsigned enums, such as WinRT flags and Win32's `DXGI_ADAPTER_FLAG`, are treated as
+        // flags even without the attribute.
+        let is_flags = name.def.has_attribute(("System", "FlagsAttribute"))
+            || matches!(
+                underlying_type,
+                winmd::ElementType::U8
+                    | winmd::ElementType::U16
+                    | winmd::ElementType::U32
+                    | winmd::ElementType::U64
+            );
+
         Self {
             name,
             fields,
-            underlying_type: underlying_type.expect("Enum.from_type_name"),
+            underlying_type,
             signature,
+            is_flags,
         }
     }
 
     pub fn gen(&self) -> TokenStream {
         let name = self.name.gen();
 
-        let (underlying_type, bitwise) = match self.underlying_type {
-            winmd::ElementType::I32 => (format_ident!("i32"), TokenStream::new()),
-            winmd::ElementType::U32 => (
-                format_ident!("u32"),
-                quote! {
-                    impl ::std::ops::BitOr for #name {
-                        type Output = Self;
+        let underlying_type = self.gen_underlying_type();
 
-                        fn bitor(self, rhs: Self) -> Self {
-                            Self(self.0 | rhs.0)
-                        }
+        let (debug, flags) = if self.is_flags {
+            (self.gen_flags_debug(), self.gen_flags(&name))
+        } else {
+            (
+                quote! {
+                    match self.name() {
+                        ::std::option::Option::Some(name) => f.write_str(name),
+                        ::std::option::Option::None => write!(f, "{:?}", self.0),
                     }
-                    impl ::std::ops::BitAnd for #name {
-                        type Output = Self;
+                },
+                TokenStream::new(),
+            )
+        };
+
+        let invalid_arg = gen_invalid_arg();
 
-                        fn bitand(self, rhs: Self) -> Self {
-                            Self(self.0 & rhs.0)
-                        }
+        // Any value converts to an enum with `From`. Since the standard library's blanket
+        // `TryFrom` is implemented for any `From` conversion, enums other than flags check
+        // their values with an inherent `try_from` instead.
+        let try_from = if self.is_flags {
+            TokenStream::new()
+        } else {
+            quote! {
+                pub fn try_from(value: #underlying_type) -> ::std::result::Result<Self, ::windows::Error> {
+                    let value = Self(value);
+
+                    if value.name().is_some() {
+                        ::std::result::Result::Ok(value)
+                    } else {
+                        ::std::result::Result::Err(#invalid_arg)
                     }
-                },
-            ),
-            _ => panic!("Unexpected enum underlying type: {}", name),
+                }
+            }
         };
 
+        let from_str = self.gen_from_str();
+
+        // Values with more than one name are known by the first.
+        let mut unique = Vec::new();
+
+        for (name, value) in &self.fields {
+            if !unique.iter().any(|(_, unique)| unique == value) {
+                unique.push((*name, *value));
+            }
+        }
+
+        let variants = unique.iter().map(|(name, _)| {
+            let name = format_ident(name);
+            quote! { Self::#name }
+        });
+
+        let names = unique.iter().map(|(name, value)| {
+            let value = value.gen();
+            quote! { #value => ::std::option::Option::Some(#name), }
+        });
+
         let fields = self.fields.iter().map(|(name, value)| {
             let name = format_ident(&name);
-            let value = match value {
-                EnumConstant::U32(value) => quote! { #value },
-                EnumConstant::I32(value) => quote! { #value },
-            };
+            let value = value.gen();
 
             quote! {
                 pub const #name: Self = Self(#value);
@@ -144,9 +244,15 @@ impl Enum {
             }
             impl ::std::fmt::Debug for #name {
                 fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
-                    write!(f, "{:?}", self.0)
+                    #debug
                 }
             }
+            impl ::std::fmt::Display for #name {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    ::std::fmt::Debug::fmt(self, f)
+                }
+            }
+            #from_str
             impl ::std::cmp::PartialEq for #name {
                 fn eq(&self, other: &Self) -> bool {
                     self.0 == other.0
@@ -157,12 +263,469 @@ impl Enum {
             impl #name {
                 #![allow(non_upper_case_globals)]
                 #(#fields)*
+                pub const VARIANTS: &'static [Self] = &[#(#variants),*];
+                pub fn name(&self) -> ::std::option::Option<&'static str> {
+                    match self.0 {
+                        #(#names)*
+                        _ => ::std::option::Option::None,
+                    }
+                }
+                #try_from
             }
             unsafe impl ::windows::Abi for #name {
                 type Abi = Self;
             }
             #runtime_type
-            #bitwise
+            #flags
+        }
+    }
+
+    // Flags enums also parse names and hexadecimal values joined by `|`, as they are
+    // displayed.
+    fn gen_from_str(&self) -> TokenStream {
+        let name = self.name.gen();
+        let invalid_arg = gen_invalid_arg();
+
+        let values = self.fields.iter().map(|(name, _)| {
+            let ident = format_ident(name);
+            quote! { #name => Self::#ident, }
+        });
+
+        let body = if self.is_flags {
+            let underlying_type = self.gen_underlying_type();
+
+            // Signed values are displayed as their two's complement bits.
+            let hex = match self.underlying_type {
+                winmd::ElementType::I8 => {
+                    quote! { u8::from_str_radix(hex, 16).map(|value| Self(value as i8)) }
+                }
+                winmd::ElementType::I16 => {
+                    quote! { u16::from_str_radix(hex, 16).map(|value| Self(value as i16)) }
+                }
+                winmd::ElementType::I32 => {
+                    quote! { u32::from_str_radix(hex, 16).map(|value| Self(value as i32)) }
+                }
+                winmd::ElementType::I64 => {
+                    quote! { u64::from_str_radix(hex, 16).map(|value| Self(value as i64)) }
+                }
+                _ => quote! { #underlying_type::from_str_radix(hex, 16).map(Self) },
+            };
+
+            quote! {
+                let mut flags = Self(0);
+
+                for value in value.split('|') {
+                    let value = value.trim();
+
+                    flags |= match value {
+                        #(#values)*
+                        _ => match value.strip_prefix("0x").map(|hex| #hex) {
+                            ::std::option::Option::Some(::std::result::Result::Ok(value)) => value,
+                            _ => return ::std::result::Result::Err(#invalid_arg),
+                        },
+                    };
+                }
+
+                ::std::result::Result::Ok(flags)
+            }
+        } else {
+            quote! {
+                ::std::result::Result::Ok(match value {
+                    #(#values)*
+                    _ => return ::std::result::Result::Err(#invalid_arg),
+                })
+            }
+        };
+
+        quote! {
+            impl ::std::str::FromStr for #name {
+                type Err = ::windows::Error;
+
+                fn from_str(value: &str) -> ::std::result::Result<Self, Self::Err> {
+                    #body
+                }
+            }
+        }
+    }
+
+    fn gen_underlying_type(&self) -> TokenStream {
+        match self.underlying_type {
+            winmd::ElementType::I8 => quote! { i8 },
+            winmd::ElementType::U8 => quote! { u8 },
+            winmd::ElementType::I16 => quote! { i16 },
+            winmd::ElementType::U16 => quote! { u16 },
+            winmd::ElementType::I32 => quote! { i32 },
+            winmd::ElementType::U32 => quote! { u32 },
+            winmd::ElementType::I64 => quote! { i64 },
+            winmd::ElementType::U64 => quote! { u64 },
+            _ => panic!("Unexpected enum underlying type: {}", self.name.gen()),
         }
     }
+
+    fn gen_flags(&self, name: &TokenStream) -> TokenStream {
+        let all = if self.fields.is_empty() {
+            quote! { 0 }
+        } else {
+            let values = self.fields.iter().map(|(_, value)| value.gen());
+            quote! { #(#values)|* }
+        };
+
+        quote! {
+            impl #name {
+                pub fn all() -> Self {
+                    Self(#all)
+                }
+                pub fn is_empty(&self) -> bool {
+                    self.0 == 0
+                }
+                pub fn contains(&self, other: Self) -> bool {
+                    self.0 & other.0 == other.0
+                }
+                pub fn intersects(&self, other: Self) -> bool {
+                    self.0 & other.0 != 0
+                }
+                pub fn insert(&mut self, other: Self) {
+                    self.0 |= other.0;
+                }
+                pub fn remove(&mut self, other: Self) {
+                    self.0 &= !other.0;
+                }
+                pub fn toggle(&mut self, other: Self) {
+                    self.0 ^= other.0;
+                }
+            }
+            impl ::std::ops::BitOr for #name {
+                type Output = Self;
+
+                fn bitor(self, rhs: Self) -> Self {
+                    Self(self.0 | rhs.0)
+                }
+            }
+            impl ::std::ops::BitAnd for #name {
+                type Output = Self;
+
+                fn bitand(self, rhs: Self) -> Self {
+                    Self(self.0 & rhs.0)
+                }
+            }
+            impl ::std::ops::BitXor for #name {
+                type Output = Self;
+
+                fn bitxor(self, rhs: Self) -> Self {
+                    Self(self.0 ^ rhs.0)
+                }
+            }
+            impl ::std::ops::Not for #name {
+                type Output = Self;
+
+                fn not(self) -> Self {
+                    Self(!self.0)
+                }
+            }
+            impl ::std::ops::BitOrAssign for #name {
+                fn bitor_assign(&mut self, rhs: Self) {
+                    self.0 |= rhs.0;
+                }
+            }
+            impl ::std::ops::BitAndAssign for #name {
+                fn bitand_assign(&mut self, rhs: Self) {
+                    self.0 &= rhs.0;
+                }
+            }
+            impl ::std::ops::BitXorAssign for #name {
+                fn bitxor_assign(&mut self, rhs: Self) {
+                    self.0 ^= rhs.0;
+                }
+            }
+        }
+    }
+
+    // Prints the names of the flags that are set, followed by any remaining bits in hex,
+    // such as `Sunday | Monday | 0x80`. A value with no bits set prints the name of the
+    // zero flag, if there is one.
+    fn gen_flags_debug(&self) -> TokenStream {
+        let empty = EnumConstant::new(&self.underlying_type, 0);
+
+        let zero = self
+            .fields
+            .iter()
+            .find(|(_, value)| *value == empty)
+            .map(|(name, _)| *name);
+
+        let zero = match zero {
+            Some(name) => quote! { f.write_str(#name) },
+            None => quote! { write!(f, "{:#x}", self.0) },
+        };
+
+        let flags = self.fields.iter().filter_map(|(name, value)| {
+            if *value == empty {
+                None
+            } else {
+                let value = value.gen();
+                Some(quote! { (#name, #value) })
+            }
+        });
+
+        let underlying_type = self.gen_underlying_type();
+
+        quote! {
+            if self.0 == 0 {
+                return #zero;
+            }
+
+            let flags: &[(&str, #underlying_type)] = &[#(#flags),*];
+
+            let mut remaining = self.0;
+            let mut first = true;
+
+            for (name, value) in flags {
+                if self.0 & *value == *value {
+                    if !first {
+                        f.write_str(" | ")?;
+                    }
+
+                    f.write_str(name)?;
+                    remaining &= !*value;
+                    first = false;
+                }
+            }
+
+            if remaining != 0 {
+                if !first {
+                    f.write_str(" | ")?;
+                }
+
+                write!(f, "{:#x}", remaining)?;
+            }
+
+            Ok(())
+        }
+    }
+}
+
+/// The error for a name or value that the enum does not define, using `E_INVALIDARG`
+fn gen_invalid_arg() -> TokenStream {
+    quote! {
+        ::windows::Error::fast_error(::windows::ErrorCode(0x8007_0057))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use squote::TokenStream;
+    use winmd::writer::{ConstantValue, HasConstant, TypeSig, Writer};
+
+    /// The type flags, name, underlying type and constants of an enum, where a constant of
+    /// `None` leaves the value implicit
+    type EnumDef<'a> = (
+        u32,
+        &'a str,
+        TypeSig,
+        &'a [(&'a str, Option<ConstantValue>)],
+    );
+
+    fn enums(enums: &[EnumDef]) -> &'static winmd::TypeReader {
+        let mut writer = Writer::new("Enums.winmd");
+        let base = writer.type_ref(None, "System", "Enum");
+
+        for (flags, name, ty, values) in enums {
+            writer.type_def(*flags, "Test", name, Some(base));
+            writer.field(0x0606, "value__", ty);
+
+            for (name, value) in *values {
+                let field = writer.field(0x8056, name, ty);
+
+                if let Some(value) = value {
+                    writer.constant(HasConstant::Field(field), value);
+                }
+            }
+        }
+
+        let reader = winmd::TypeReader::builder()
+            .bytes("Enums.winmd", writer.into_bytes())
+            .exclude_default(true)
+            .build()
+            .unwrap();
+
+        Box::leak(Box::new(reader))
+    }
+
+    /// Whether the generated code contains the expected code, ignoring whitespace
+    fn contains(tokens: &TokenStream, expected: &str) -> bool {
+        let strip = |code: &str| {
+            code.chars()
+                .filter(|c| !c.is_whitespace())
+                .collect::<String>()
+        };
+        strip(&tokens.clone().into_string()).contains(&strip(expected))
+    }
+
+    fn from_name(reader: &'static winmd::TypeReader, name: &str) -> Enum {
+        let def = reader.expect_type_def(("Test", name));
+        Enum::from_type_name(TypeName::from_type_def(&def, "Test"))
+    }
+
+    #[test]
+    fn underlying_types() {
+        // Public | Sealed
+        let reader = enums(&[
+            (
+                0x101,
+                "Small",
+                TypeSig::U8,
+                &[
+                    ("A", Some(ConstantValue::U8(1))),
+                    ("B", Some(ConstantValue::U8(0x80))),
+                ],
+            ),
+            (
+                0x101,
+                "Short",
+                TypeSig::I16,
+                &[("A", Some(ConstantValue::I16(-1))), ("B", None)],
+            ),
+            (
+                0x101,
+                "Wide",
+                TypeSig::U16,
+                &[("A", Some(ConstantValue::U16(0xffff))), ("B", None)],
+            ),
+            (
+                0x101,
+                "Signed",
+                TypeSig::I64,
+                &[
+                    ("A", Some(ConstantValue::I64(-5))),
+                    ("B", Some(ConstantValue::I64(1 << 40))),
+                ],
+            ),
+            (
+                0x101,
+                "Big",
+                TypeSig::U64,
+                &[("A", Some(ConstantValue::U64(u64::MAX))), ("B", None)],
+            ),
+        ]);
+
+        let small = from_name(reader, "Small");
+        assert_eq!(
+            small.fields,
+            [("A", EnumConstant::U8(1)), ("B", EnumConstant::U8(0x80))]
+        );
+        let tokens = small.gen();
+        assert!(contains(&tokens, "pub struct Small(pub u8);"));
+        assert!(contains(&tokens, "pub const B: Self = Self(128u8);"));
+
+        let short = from_name(reader, "Short");
+        assert_eq!(
+            short.fields,
+            [("A", EnumConstant::I16(-1)), ("B", EnumConstant::I16(0))]
+        );
+        let tokens = short.gen();
+        assert!(contains(&tokens, "pub struct Short(pub i16);"));
+        assert!(contains(&tokens, "pub const A: Self = Self(-1i16);"));
+
+        // Implicit values wrap around rather than overflowing.
+        let wide = from_name(reader, "Wide");
+        assert_eq!(
+            wide.fields,
+            [
+                ("A", EnumConstant::U16(0xffff)),
+                ("B", EnumConstant::U16(0))
+            ]
+        );
+        let tokens = wide.gen();
+        assert!(contains(&tokens, "pub struct Wide(pub u16);"));
+        assert!(contains(&tokens, "pub const A: Self = Self(65535u16);"));
+
+        let signed = from_name(reader, "Signed");
+        assert_eq!(
+            signed.fields,
+            [
+                ("A", EnumConstant::I64(-5)),
+                ("B", EnumConstant::I64(1 << 40))
+            ]
+        );
+        let tokens = signed.gen();
+        assert!(contains(&tokens, "pub struct Signed(pub i64);"));
+        assert!(contains(
+            &tokens,
+            "pub const B: Self = Self(1099511627776i64);"
+        ));
+
+        let big = from_name(reader, "Big");
+        assert_eq!(
+            big.fields,
+            [
+                ("A", EnumConstant::U64(u64::MAX)),
+                ("B", EnumConstant::U64(0))
+            ]
+        );
+        let tokens = big.gen();
+        assert!(contains(&tokens, "pub struct Big(pub u64);"));
+        assert!(contains(
+            &tokens,
+            "pub const A: Self = Self(18446744073709551615u64);"
+        ));
+
+        // Unsigned enums are flags even without the attribute.
+        for enum_type in &[small, wide, big] {
+            assert!(enum_type.is_flags);
+        }
+
+        for enum_type in &[short, signed] {
+            assert!(!enum_type.is_flags);
+        }
+    }
+
+    #[test]
+    fn flags() {
+        // Public | Sealed | WindowsRuntime
+        let reader = enums(&[
+            (
+                0x4101,
+                "Unsigned",
+                TypeSig::U32,
+                &[("A", Some(ConstantValue::U32(1)))],
+            ),
+            (
+                0x4101,
+                "Signed",
+                TypeSig::I32,
+                &[("A", Some(ConstantValue::I32(1)))],
+            ),
+        ]);
+
+        let unsigned = from_name(reader, "Unsigned");
+        assert!(unsigned.is_flags);
+        assert!(contains(
+            &unsigned.gen(),
+            "impl ::std::ops::BitOr for Unsigned"
+        ));
+
+        assert!(contains(
+            &unsigned.gen(),
+            "u32::from_str_radix(hex, 16).map(Self)"
+        ));
+
+        let signed = from_name(reader, "Signed");
+        assert!(!signed.is_flags);
+        assert!(!contains(
+            &signed.gen(),
+            "impl ::std::ops::BitOr for Signed"
+        ));
+
+        // Both convert from any value, and the enum that isn't flags also checks values.
+        assert!(contains(
+            &unsigned.gen(),
+            "impl ::std::convert::From<u32> for Unsigned"
+        ));
+        assert!(!contains(&unsigned.gen(), "pub fn try_from"));
+        assert!(contains(
+            &signed.gen(),
+            "impl ::std::convert::From<i32> for Signed"
+        ));
+        assert!(contains(&signed.gen(), "pub fn try_from(value: i32)"));
+    }
 }
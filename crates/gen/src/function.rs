@@ -9,7 +9,7 @@ pub struct Function {
 }
 
 impl Function {
-    pub fn new(name: TypeName, method: &winmd::MethodDef) -> Self {
+    pub fn new(name: TypeName, method: &winmd::MethodDef<'static>) -> Self {
         let signature = Signature::new(method, &[], &name.namespace);
         Self { name, signature }
     }
@@ -46,7 +46,7 @@ impl Function {
         }
     }
 
-    pub fn dependencies(&self) -> Vec<winmd::TypeDef> {
+    pub fn dependencies(&self) -> Vec<winmd::TypeDef<'static>> {
         self.signature.dependencies()
     }
 }
@@ -31,7 +31,7 @@ impl Interface {
         }
     }
 
-    pub fn dependencies(&self) -> Vec<winmd::TypeDef> {
+    pub fn dependencies(&self) -> Vec<winmd::TypeDef<'static>> {
         let mut dependencies = Vec::new();
 
         for interface in &self.interfaces {
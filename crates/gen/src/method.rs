@@ -12,7 +12,7 @@ pub struct Method {
 
 impl Method {
     pub fn from_method_def(
-        method: &winmd::MethodDef,
+        method: &winmd::MethodDef<'static>,
         vtable_offset: u32,
         generics: &[TypeKind],
         calling_namespace: &'static str,
@@ -46,11 +46,11 @@ impl Method {
         }
     }
 
-    pub fn dependencies(&self) -> Vec<winmd::TypeDef> {
+    pub fn dependencies(&self) -> Vec<winmd::TypeDef<'static>> {
         self.signature.dependencies()
     }
 
-    fn name(method: &winmd::MethodDef) -> String {
+    fn name(method: &winmd::MethodDef<'static>) -> String {
         for attribute in method.attributes() {
             if attribute.name() == ("Windows.Foundation.Metadata", "OverloadAttribute") {
                 for (_, arg) in attribute.args() {
@@ -31,7 +31,7 @@ impl Ord for RequiredInterface {
 
 impl RequiredInterface {
     fn from_type_def(
-        def: &winmd::TypeDef,
+        def: &winmd::TypeDef<'static>,
         calling_namespace: &'static str,
         kind: InterfaceKind,
     ) -> Self {
@@ -124,7 +124,7 @@ impl RequiredInterface {
 
 pub fn add_type(
     vec: &mut Vec<RequiredInterface>,
-    def: &winmd::TypeDef,
+    def: &winmd::TypeDef<'static>,
     calling_namespace: &'static str,
     kind: InterfaceKind,
 ) {
@@ -2,18 +2,18 @@ use crate::*;
 
 #[derive(Debug)]
 pub struct Signature {
-    pub method: winmd::MethodDef,
+    pub method: winmd::MethodDef<'static>,
     pub params: Vec<Type>,
     pub return_type: Option<Type>,
 }
 
 impl Signature {
     pub fn new(
-        method: &winmd::MethodDef,
+        method: &winmd::MethodDef<'static>,
         generics: &[TypeKind],
         calling_namespace: &'static str,
     ) -> Self {
-        let mut params: Vec<winmd::Param> = method.params().collect();
+        let mut params: Vec<winmd::Param<'static>> = method.params().collect();
 
         let return_param = if !params.is_empty() && params[0].sequence() == 0 {
             Some(params.remove(0))
@@ -21,20 +21,22 @@ impl Signature {
             None
         };
 
-        let mut blob = method.sig();
-        blob.read_unsigned(); // First byte of MethodDefSig is not used.
-        let param_count = blob.read_unsigned() as usize;
+        let sig = method.signature();
 
-        let return_type =
-            Type::from_blob(&mut blob, return_param, generics, calling_namespace, true);
+        let return_type = Type::from_sig(
+            &sig.return_type,
+            return_param,
+            generics,
+            calling_namespace,
+            true,
+        );
 
-        debug_assert!(params.len() == param_count);
-        let mut param_types = Vec::with_capacity(param_count);
+        debug_assert!(params.len() == sig.params.len());
+        let mut param_types = Vec::with_capacity(sig.params.len());
 
-        for param in params {
+        for (param, param_sig) in params.into_iter().zip(&sig.params) {
             param_types.push(
-                Type::from_blob(&mut blob, Some(param), generics, calling_namespace, false)
-                    .unwrap(),
+                Type::from_sig(param_sig, Some(param), generics, calling_namespace, false).unwrap(),
             );
         }
 
@@ -45,7 +47,7 @@ impl Signature {
         }
     }
 
-    pub fn dependencies(&self) -> Vec<winmd::TypeDef> {
+    pub fn dependencies(&self) -> Vec<winmd::TypeDef<'static>> {
         let mut defs = Vec::new();
 
         if let Some(t) = &self.return_type {
@@ -9,6 +9,13 @@ pub struct Struct {
     pub signature: String,
     pub is_typedef: bool,
     pub guid: TypeGuid,
+    pub is_union: bool,
+    pub packing: Option<u32>,
+    /// The declared offset of each field, when explicit layout describes something other
+    /// than a union
+    pub offsets: Vec<u32>,
+    /// The declared size of a type with explicit layout, if any
+    pub size: Option<u32>,
 }
 
 impl Struct {
@@ -61,7 +68,7 @@ impl Struct {
             let t = Type {
                 kind: TypeKind::U8,
                 pointers: 0,
-                array: None,
+                array: Vec::new(),
                 by_ref: false,
                 modifiers: Vec::new(),
                 param: None,
@@ -74,9 +81,50 @@ impl Struct {
             fields.push(("reserved".to_string(), t));
         }
 
-        let is_typedef = name
-            .def
-            .has_attribute(("Windows.Win32.Interop", "NativeTypedefAttribute"));
+        let is_typedef = Self::is_typedef(&name.def);
+        let is_union = Self::is_union(&name.def);
+        let packing = Self::packing(&name.def);
+        let mut offsets = Vec::new();
+        let mut size = None;
+
+        // Any other explicit layout is generated with padding between its fields, which
+        // must not overlap since only a union can describe that.
+        if !is_typedef && !is_union && name.def.flags().explicit_layout() {
+            let mut ordered: Vec<_> = name
+                .def
+                .fields()
+                .map(|field| {
+                    field.offset().unwrap_or_else(|| {
+                        panic!(
+                            "Struct::from_type_name {}.{} has no offset for {}",
+                            name.namespace,
+                            name.name,
+                            field.name()
+                        )
+                    })
+                })
+                .zip(fields)
+                .collect();
+
+            ordered.sort_by_key(|(offset, _)| *offset);
+
+            if ordered.windows(2).any(|pair| pair[0].0 == pair[1].0) {
+                panic!(
+                    "Struct::from_type_name {}.{} has overlapping fields",
+                    name.namespace, name.name
+                );
+            }
+
+            let (ordered_offsets, ordered_fields) = ordered.into_iter().unzip();
+            offsets = ordered_offsets;
+            fields = ordered_fields;
+
+            size = name
+                .def
+                .class_layout()
+                .map(|layout| layout.class_size())
+                .filter(|size| *size > 0);
+        }
 
         Self {
             name,
@@ -84,13 +132,42 @@ impl Struct {
             signature,
             is_typedef,
             guid,
+            is_union,
+            packing,
+            offsets,
+            size,
         }
     }
 
-    pub fn dependencies(&self) -> Vec<winmd::TypeDef> {
+    pub fn is_typedef(def: &winmd::TypeDef) -> bool {
+        def.has_attribute(("Windows.Win32.Interop", "NativeTypedefAttribute"))
+    }
+
+    /// Whether the type is a union, which the metadata describes as explicit layout with
+    /// every field at offset zero
+    pub fn is_union(def: &winmd::TypeDef) -> bool {
+        !Self::is_typedef(def)
+            && def.flags().explicit_layout()
+            && def.fields().all(|field| field.offset().unwrap_or(0) == 0)
+    }
+
+    /// Whether the type is generated as a union or packed struct, or with explicit padding,
+    /// rather than as a plain struct
+    pub fn has_layout(def: &winmd::TypeDef) -> bool {
+        !Self::is_typedef(def) && (def.flags().explicit_layout() || Self::packing(def).is_some())
+    }
+
+    fn packing(def: &winmd::TypeDef) -> Option<u32> {
+        def.class_layout()
+            .map(|layout| layout.packing_size())
+            .filter(|packing| *packing > 0)
+    }
+
+    pub fn dependencies(&self) -> Vec<winmd::TypeDef<'static>> {
         self.fields
             .iter()
             .flat_map(|i| i.1.kind.dependencies())
+            .chain(self.name.def.nested_types())
             .collect()
     }
 
@@ -105,6 +182,11 @@ impl Struct {
             };
         }
 
+        if !self.is_typedef && (self.is_union || self.packing.is_some() || !self.offsets.is_empty())
+        {
+            return self.gen_layout();
+        }
+
         // TODO: if the struct is blittable then don't generate a separate abi type.
         let abi_ident = format_ident!("{}_abi", self.name.name);
 
@@ -216,12 +298,10 @@ impl Struct {
             let fields = self.fields.iter().enumerate().map(|(index, (name, t))| {
                 let name_ident = format_ident(&name);
 
-                if let TypeKind::Delegate(name) = &t.kind {
-                    if !name.def.is_winrt() {
-                        return quote! {
-                            self.#name_ident.map(|f| f as usize) == other.#name_ident.map(|f| f as usize)
-                        };
-                    }
+                if let Some(compare) = gen_fn_compare(t) {
+                    return quote! {
+                        ({ let (a, b) = (&self.#name_ident, &other.#name_ident); #compare })
+                    };
                 }
 
                 if self.is_typedef {
@@ -242,6 +322,20 @@ impl Struct {
             }
         };
 
+        // A struct holding a union can't be compared.
+        let compare = if self.fields.iter().all(|(_, t)| t.is_comparable()) {
+            quote! {
+                impl ::std::cmp::PartialEq for #name {
+                    fn eq(&self, other: &Self) -> bool {
+                        #compare_fields
+                    }
+                }
+                impl ::std::cmp::Eq for #name {}
+            }
+        } else {
+            quote! {}
+        };
+
         let abi = self.fields.iter().map(|field| field.1.gen_abi());
 
         let runtime_type = if self.signature.is_empty() {
@@ -265,7 +359,7 @@ impl Struct {
             quote! {}
         };
 
-        let debug_name = self.name.name;
+        let debug_name = &self.name.name;
 
         quote! {
             #[repr(C)]
@@ -294,14 +388,345 @@ impl Struct {
                     #clones
                 }
             }
-            impl ::std::cmp::PartialEq for #name {
-                fn eq(&self, other: &Self) -> bool {
-                    #compare_fields
+            #compare
+            #copy
+            #runtime_type
+        }
+    }
+
+    // Unions and structs with a declared layout have no separate abi type and only hold
+    // blittable fields. Packed fields can't be referenced, so each field is copied out before
+    // it is printed or compared, and unions are neither printed field by field nor compared.
+    fn gen_layout(&self) -> TokenStream {
+        let name = self.name.gen();
+        let abi_ident = format_ident!("{}_abi", self.name.name);
+        let debug_name = &self.name.name;
+
+        if let Some((field, _)) = self.fields.iter().find(|(_, t)| !t.is_blittable()) {
+            panic!(
+                "Struct::gen_layout {}.{} has a field that owns a resource: {}",
+                self.name.namespace, self.name.name, field
+            );
+        }
+
+        let repr = match self.packing {
+            Some(packing) => {
+                let packing = Literal::u32_unsuffixed(packing);
+                quote! { #[repr(C, packed(#packing))] }
+            }
+            None => quote! { #[repr(C)] },
+        };
+
+        let mut fields = Vec::new();
+        let mut checks = Vec::new();
+        // The end of the previous field when the fields have declared offsets
+        let mut end = quote! { 0 };
+
+        for (index, (name, t)) in self.fields.iter().enumerate() {
+            let name = format_ident(name);
+            let kind = t.gen_field();
+
+            if let Some(&offset) = self.offsets.get(index) {
+                let offset = Literal::u32_unsuffixed(offset);
+
+                if index > 0 || self.offsets[0] > 0 {
+                    let padding = format_ident!("_padding{}", index);
+                    fields.push(quote! { #padding: [u8; #offset - #end] });
+                }
+
+                // The padding only places the field at its offset if that is aligned.
+                let align = quote! { ::std::mem::align_of::<#kind>() };
+                let align = match self.packing {
+                    Some(packing) => {
+                        let packing = Literal::u32_unsuffixed(packing);
+                        quote! { [#align, #packing][(#align > #packing) as usize] }
+                    }
+                    None => align,
+                };
+
+                checks.push(quote! {
+                    const _: [(); 0] = [(); #offset % #align];
+                });
+
+                end = quote! { (#offset + ::std::mem::size_of::<#kind>()) };
+            }
+
+            if self.is_union && !t.is_copy() {
+                fields.push(quote! { pub #name: ::std::mem::ManuallyDrop<#kind> });
+            } else {
+                fields.push(quote! { pub #name: #kind });
+            }
+        }
+
+        if let Some(size) = self.size {
+            let size = Literal::u32_unsuffixed(size);
+            let padding = format_ident!("_padding{}", self.fields.len());
+            fields.push(quote! { #padding: [u8; #size - #end] });
+
+            checks.push(quote! {
+                const _: [(); #size] = [(); ::std::mem::size_of::<#name>()];
+            });
+        }
+
+        let definition = if self.is_union {
+            quote! {
+                #repr
+                #[allow(non_snake_case)]
+                pub union #name { #(#fields),* }
+            }
+        } else {
+            quote! {
+                #repr
+                #[allow(non_snake_case)]
+                pub struct #name { #(#fields),* }
+            }
+        };
+
+        let read = |value: TokenStream, name: &str| {
+            let name = format_ident(name);
+            quote! { ::std::ptr::addr_of!(#value.#name).read_unaligned() }
+        };
+
+        let debug_fields = self.fields.iter().filter_map(|(name, t)| {
+            if self.is_union || gen_fn_compare(t).is_some() {
+                return None;
+            }
+
+            let value = read(quote! { self }, name);
+
+            Some(quote! {
+                .field(#name, &unsafe { #value })
+            })
+        });
+
+        let (clone, copy) = if self.fields.iter().all(|(_, t)| t.is_copy()) {
+            (
+                quote! { *self },
+                quote! { impl ::std::marker::Copy for #name {} },
+            )
+        } else {
+            // The fields are all plain data, so a bitwise copy is a clone.
+            (quote! { unsafe { ::std::ptr::read(self) } }, quote! {})
+        };
+
+        let compare = if self.is_union || !self.fields.iter().all(|(_, t)| t.is_comparable()) {
+            quote! {}
+        } else {
+            let fields = self.fields.iter().map(|(name, t)| {
+                let a = read(quote! { self }, name);
+                let b = read(quote! { other }, name);
+                let compare = gen_fn_compare(t).unwrap_or_else(|| quote! { a == b });
+
+                quote! {
+                    ({ let (a, b) = unsafe { (#a, #b) }; #compare })
+                }
+            });
+
+            quote! {
+                impl ::std::cmp::PartialEq for #name {
+                    fn eq(&self, other: &Self) -> bool {
+                        #(#fields)&&*
+                    }
+                }
+                impl ::std::cmp::Eq for #name {}
+            }
+        };
+
+        quote! {
+            #definition
+            #(#checks)*
+            #[doc(hidden)]
+            pub type #abi_ident = #name;
+            unsafe impl ::windows::Abi for #name {
+                type Abi = Self;
+            }
+            impl ::std::default::Default for #name {
+                fn default() -> Self {
+                    unsafe { ::std::mem::zeroed() }
                 }
             }
-            impl ::std::cmp::Eq for #name {}
+            impl ::std::fmt::Debug for #name {
+                fn fmt(&self, fmt: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    fmt.debug_struct(#debug_name)
+                        #(#debug_fields)*
+                        .finish()
+                }
+            }
+            impl ::std::clone::Clone for #name {
+                fn clone(&self) -> Self {
+                    #clone
+                }
+            }
+            #compare
             #copy
-            #runtime_type
         }
     }
 }
+
+/// Compares `a` and `b` when they are function pointers, which are compared by address,
+/// or arrays of them, which are compared element by element
+fn gen_fn_compare(t: &Type) -> Option<TokenStream> {
+    match &t.kind {
+        TypeKind::Delegate(name) if !name.def.is_winrt() => Some(t.array.iter().fold(
+            quote! { a.map(|f| f as usize) == b.map(|f| f as usize) },
+            |compare, _| quote! { a.iter().zip(b.iter()).all(|(a, b)| #compare) },
+        )),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use squote::TokenStream;
+    use winmd::writer::{ClassLayout, FieldLayout, TypeDefOrRef, TypeSig, Writer};
+
+    fn structs() -> &'static winmd::TypeReader {
+        let mut writer = Writer::new("Structs.winmd");
+        let value_type = writer.type_ref(None, "System", "ValueType");
+
+        // Public | SequentialLayout | Sealed
+        let point = writer.type_def(0x109, "Test", "POINT", Some(value_type));
+        writer.field(0x6, "x", &TypeSig::I32);
+        writer.field(0x6, "y", &TypeSig::I32);
+        let point = TypeSig::ValueType(TypeDefOrRef::TypeDef(point));
+
+        // Public | ExplicitLayout | Sealed
+        let union = writer.type_def(0x111, "Test", "UNION", Some(value_type));
+
+        for (name, ty) in &[("point", point.clone()), ("value", TypeSig::I64)] {
+            let field = writer.field(0x6, name, ty);
+            writer
+                .tables
+                .field_layout
+                .push(FieldLayout { offset: 0, field });
+        }
+
+        let explicit = writer.type_def(0x111, "Test", "EXPLICIT", Some(value_type));
+
+        for (name, ty, offset) in &[("first", TypeSig::U8, 0), ("second", TypeSig::I64, 8)] {
+            let field = writer.field(0x6, name, ty);
+            writer.tables.field_layout.push(FieldLayout {
+                offset: *offset,
+                field,
+            });
+        }
+
+        writer.tables.class_layout.push(ClassLayout {
+            packing_size: 0,
+            class_size: 24,
+            parent: explicit,
+        });
+
+        let packed = writer.type_def(0x109, "Test", "PACKED", Some(value_type));
+        writer.field(0x6, "a", &TypeSig::U8);
+        writer.field(0x6, "b", &TypeSig::U16);
+
+        writer.tables.class_layout.push(ClassLayout {
+            packing_size: 1,
+            class_size: 0,
+            parent: packed,
+        });
+
+        writer.type_def(0x109, "Test", "HOLDER", Some(value_type));
+        writer.field(
+            0x6,
+            "union",
+            &TypeSig::ValueType(TypeDefOrRef::TypeDef(union)),
+        );
+
+        // A pointer to an array has no Rust equivalent.
+        writer.type_def(0x109, "Test", "OPAQUE", Some(value_type));
+        writer.field(
+            0x6,
+            "buffer",
+            &TypeSig::Ptr(
+                Vec::new(),
+                Box::new(TypeSig::Array(
+                    Box::new(TypeSig::U8),
+                    winmd::ArrayShape {
+                        rank: 1,
+                        sizes: vec![4],
+                        lower_bounds: Vec::new(),
+                    },
+                )),
+            ),
+        );
+
+        let reader = winmd::TypeReader::builder()
+            .bytes("Structs.winmd", writer.into_bytes())
+            .exclude_default(true)
+            .build()
+            .unwrap();
+
+        Box::leak(Box::new(reader))
+    }
+
+    /// The code generated for a struct, without whitespace
+    fn gen(reader: &'static winmd::TypeReader, name: &str) -> String {
+        let def = reader.expect_type_def(("Test", name));
+        let tokens: TokenStream =
+            Struct::from_type_name(TypeName::from_type_def(&def, "Test")).gen();
+
+        tokens
+            .into_string()
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect()
+    }
+
+    #[test]
+    fn sequential() {
+        let code = gen(structs(), "POINT");
+
+        assert!(code.contains("#[repr(C)]"));
+        assert!(code.contains("impl::std::cmp::PartialEqforPOINT"));
+        assert!(!code.contains("Copy"));
+    }
+
+    #[test]
+    fn union() {
+        let code = gen(structs(), "UNION");
+
+        assert!(
+            code.contains("pubunionUNION{pubpoint:::std::mem::ManuallyDrop<POINT>,pubvalue:i64}")
+        );
+        assert!(!code.contains("PartialEq"));
+        assert!(!code.contains("Copy"));
+
+        // A struct holding a union can't be compared either.
+        let code = gen(structs(), "HOLDER");
+        assert!(!code.contains("PartialEq"));
+    }
+
+    #[test]
+    fn packed() {
+        let code = gen(structs(), "PACKED");
+
+        assert!(code.contains("#[repr(C,packed(1))]"));
+        assert!(code.contains("::std::ptr::addr_of!(self.b).read_unaligned()"));
+        assert!(code.contains("impl::std::marker::CopyforPACKED{}"));
+        assert!(!code.contains("from_raw_parts"));
+    }
+
+    #[test]
+    fn explicit() {
+        let code = gen(structs(), "EXPLICIT");
+
+        assert!(code.contains(
+            "pubstructEXPLICIT{pubfirst:u8,\
+             _padding1:[u8;8-(0+::std::mem::size_of::<u8>())],\
+             pubsecond:i64,\
+             _padding2:[u8;24-(8+::std::mem::size_of::<i64>())]}"
+        ));
+        assert!(code.contains("const_:[();0]=[();8%::std::mem::align_of::<i64>()];"));
+        assert!(code.contains("const_:[();24]=[();::std::mem::size_of::<EXPLICIT>()];"));
+    }
+
+    #[test]
+    fn opaque_pointer() {
+        let code = gen(structs(), "OPAQUE");
+
+        assert!(code.contains("pubbuffer:*mut::std::ffi::c_void"));
+    }
+}
@@ -1,73 +1,17 @@
 use crate::MethodKind;
+pub use winmd::to_snake;
 
 /// Change a CamelCase method name to snake case and prepends an optional
 /// preamble depending on the kind of method.
 pub fn method_to_snake(camel: &str, kind: MethodKind) -> String {
     debug_assert!(!camel.is_empty());
-    let mut snake = String::with_capacity(camel.len());
+    let snake = to_snake(camel);
 
     match kind {
-        MethodKind::Set => snake.push_str("set_"),
-        MethodKind::Remove => snake.push_str("remove_"),
-        _ => {}
-    };
-
-    append_to_snake(snake, camel)
-}
-
-/// Change a CamelCase name to snake case.
-pub fn to_snake(camel: &str) -> String {
-    debug_assert!(!camel.is_empty());
-    let snake = String::with_capacity(camel.len());
-    append_to_snake(snake, camel)
-}
-
-fn append_to_snake(mut snake: String, camel: &str) -> String {
-    // Add any manual fixups here, anything that isn't handle automatically by the algorithm below.
-    if camel == "WinRT" {
-        return "winrt".to_string();
-    }
-
-    let mut since_last_underscore = 0;
-    let mut chars = camel.chars();
-    // first character as lowercased
-    for c in chars.next().unwrap().to_lowercase() {
-        since_last_underscore += 1;
-        snake.push(c);
-    }
-
-    // zip together iterator of previous characters and next characters
-    for (previous, next) in camel.chars().zip(camel.chars().skip(2)) {
-        // safe to unwrap since the iterator of next chars produced something
-        let current = chars.next().unwrap();
-
-        // If the current character isn't uppercase we can just push it and move on
-        if !current.is_uppercase() {
-            since_last_underscore += 1;
-            snake.push(current);
-            continue;
-        }
-
-        if previous.is_lowercase() || next.is_lowercase() && since_last_underscore > 1 {
-            since_last_underscore = 0;
-
-            if previous != '_' {
-                snake.push('_');
-            }
-        }
-
-        for c in current.to_lowercase() {
-            since_last_underscore += 1;
-
-            snake.push(c);
-        }
-    }
-
-    if let Some(last) = chars.next() {
-        snake.extend(last.to_lowercase());
+        MethodKind::Set => format!("set_{}", snake),
+        MethodKind::Remove => format!("remove_{}", snake),
+        _ => snake,
     }
-
-    snake
 }
 
 #[cfg(test)]
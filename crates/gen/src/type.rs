@@ -1,15 +1,15 @@
 use crate::*;
-use squote::{quote, TokenStream};
-use winmd::Decode;
+use squote::{quote, Literal, TokenStream};
 
 #[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord)]
 pub struct Type {
     pub kind: TypeKind,
     pub pointers: usize,
-    pub array: Option<usize>,
+    /// The dimensions of a fixed-size array, outermost first, or empty if the type is not one
+    pub array: Vec<u32>,
     pub by_ref: bool,
-    pub modifiers: Vec<winmd::TypeDefOrRef>,
-    pub param: Option<winmd::Param>,
+    pub modifiers: Vec<winmd::TypeDefOrRef<'static>>,
+    pub param: Option<winmd::Param<'static>>,
     pub name: String,
     pub is_const: bool,
     pub is_array: bool,
@@ -49,72 +49,66 @@ pub enum TypeKind {
 }
 
 impl Type {
-    pub fn from_blob(
-        blob: &mut winmd::Blob,
-        param: Option<winmd::Param>,
+    pub fn from_sig(
+        sig: &winmd::ParamSig<'static>,
+        param: Option<winmd::Param<'static>>,
         generics: &[TypeKind],
         calling_namespace: &'static str,
         is_return_type: bool,
     ) -> Option<Self> {
-        let modifiers = blob.read_modifiers();
-        let mut by_ref = blob.read_expected(0x10);
-
-        if blob.read_expected(0x01) {
+        if sig.ty == winmd::TypeSig::Void {
             return None;
         }
 
-        let is_array = blob.read_expected(0x1D);
+        let mut modifiers: Vec<winmd::TypeDefOrRef<'static>> =
+            sig.modifiers.iter().map(|modifier| modifier.def).collect();
+
+        let mut by_ref = sig.by_ref;
+        let mut ty = &sig.ty;
+        let mut is_array = false;
+
+        if let winmd::TypeSig::SZArray(inner_modifiers, inner) = ty {
+            is_array = true;
+            modifiers.extend(inner_modifiers.iter().map(|modifier| modifier.def));
+            ty = inner;
+        }
 
         let mut pointers = 0;
+        let mut array = Vec::new();
+
+        // Multi-dimensional arrays may be encoded either with a rank greater than one or
+        // as arrays of arrays. Dimensions without a size, like a trailing `ANYSIZE_ARRAY`,
+        // hold a single element.
+        while let winmd::TypeSig::Array(inner, shape) = ty {
+            array.extend(
+                (0..shape.rank as usize).map(|index| shape.sizes.get(index).copied().unwrap_or(1)),
+            );
+            ty = inner;
+        }
 
-        while blob.read_expected(0x0f) {
+        // As in C, an array parameter decays to a pointer to its first element.
+        if !array.is_empty() && (param.is_some() || is_return_type) {
+            array.clear();
             pointers += 1;
         }
 
-        let kind = match blob.read_unsigned() {
-            0x01 => TypeKind::Void,
-            0x02 => TypeKind::Bool,
-            0x03 => TypeKind::Char,
-            0x04 => TypeKind::I8,
-            0x05 => TypeKind::U8,
-            0x06 => TypeKind::I16,
-            0x07 => TypeKind::U16,
-            0x08 => TypeKind::I32,
-            0x09 => TypeKind::U32,
-            0x0A => TypeKind::I64,
-            0x0B => TypeKind::U64,
-            0x0C => TypeKind::F32,
-            0x0D => TypeKind::F64,
-            0x18 => TypeKind::ISize,
-            0x19 => TypeKind::USize,
-            0x0E => TypeKind::String,
-            0x1C => TypeKind::Object,
-            0x11 | 0x12 => {
-                let def =
-                    winmd::TypeDefOrRef::decode(blob.reader, blob.read_unsigned(), blob.file_index);
-
-                if def.name().0.is_empty() {
-                    // TODO: handle nested types
-                    TypeKind::Bool
-                } else {
-                    TypeKind::from_type_def_or_ref(&def, generics, calling_namespace)
+        while let winmd::TypeSig::Ptr(inner_modifiers, inner) = ty {
+            pointers += 1;
+            modifiers.extend(inner_modifiers.iter().map(|modifier| modifier.def));
+            ty = inner;
+        }
+
+        // Function pointers, and anything else without a `TypeKind` such as a pointer to an
+        // array, are passed as opaque pointers.
+        let kind = match TypeKind::from_sig(ty, generics, calling_namespace) {
+            Some(kind) => kind,
+            None => {
+                if pointers == 0 || matches!(ty, winmd::TypeSig::FnPtr(_)) {
+                    pointers += 1;
                 }
+
+                TypeKind::Void
             }
-            0x13 => generics[blob.read_unsigned() as usize].clone(),
-            0x14 => {
-                // TODO: handle win32 arrays
-                // type
-                // rank (dimensions)
-                // bounds count
-                // bound
-                TypeKind::Bool
-            }
-            0x15 => TypeKind::from_type_name(TypeName::from_type_spec_blob(
-                blob,
-                generics,
-                calling_namespace,
-            )),
-            unused => panic!("Type::from_blob 0x{:X}", unused),
         };
 
         let mut is_input = false;
@@ -152,7 +146,7 @@ impl Type {
             by_ref,
             kind,
             pointers,
-            array: None,
+            array,
             modifiers,
             param,
             name,
@@ -162,11 +156,24 @@ impl Type {
         })
     }
 
-    pub fn from_field(field: &winmd::Field, calling_namespace: &'static str) -> Self {
-        let mut blob = field.sig();
-        blob.read_unsigned();
-        blob.read_modifiers();
-        Self::from_blob(&mut blob, None, &Vec::new(), calling_namespace, false).unwrap()
+    pub fn from_field(field: &winmd::Field<'static>, calling_namespace: &'static str) -> Self {
+        Self::from_sig(&field.signature(), None, &[], calling_namespace, false).unwrap()
+    }
+
+    /// Whether the type can be copied bit for bit and contains nothing that needs
+    /// to be dropped
+    pub fn is_blittable(&self) -> bool {
+        self.pointers > 0 || self.kind.is_blittable()
+    }
+
+    /// Whether the generated type implements `Copy`
+    pub fn is_copy(&self) -> bool {
+        self.pointers > 0 || self.kind.is_copy()
+    }
+
+    /// Whether the generated type implements `PartialEq`, which unions don't
+    pub fn is_comparable(&self) -> bool {
+        self.pointers > 0 || self.kind.is_comparable()
     }
 
     pub fn gen_field(&self) -> TokenStream {
@@ -192,7 +199,15 @@ impl Type {
             _ => tokens.combine(&kind),
         };
 
-        tokens
+        self.gen_array(tokens)
+    }
+
+    /// Wraps an element type or value in the dimensions of a fixed-size array, if any
+    fn gen_array(&self, element: TokenStream) -> TokenStream {
+        self.array.iter().rev().fold(element, |tokens, len| {
+            let len = Literal::u32_unsuffixed(*len);
+            quote! { [#tokens; #len] }
+        })
     }
 
     pub fn gen_clone(&self, name: &TokenStream) -> TokenStream {
@@ -235,26 +250,116 @@ impl Type {
         let mut tokens = self.gen_abi_pointer_part();
 
         tokens.combine(&self.kind.gen_abi());
-        tokens
+        self.gen_array(tokens)
     }
 
     pub fn gen_full_abi(&self) -> TokenStream {
         let mut tokens = self.gen_abi_pointer_part();
 
         tokens.combine(&self.kind.gen_full_abi());
-        tokens
+        self.gen_array(tokens)
     }
 
     pub fn gen_default(&self) -> TokenStream {
-        if self.pointers > 0 {
+        let element = if self.pointers > 0 {
             quote! { ::std::ptr::null_mut() }
         } else {
             self.kind.gen_default()
+        };
+
+        if self.array.is_empty() {
+            return element;
+        }
+
+        // `Default` is only implemented for arrays of up to 32 elements, so arrays are
+        // repeated from their element's default instead. That needs a `Copy` element,
+        // and the other elements are all nullable or plain data, so they start out zeroed.
+        if !self.is_copy() {
+            return quote! { unsafe { ::std::mem::zeroed() } };
         }
+
+        self.gen_array(element)
     }
 }
 
 impl TypeKind {
+    pub fn is_blittable(&self) -> bool {
+        match self {
+            Self::Bool
+            | Self::Char
+            | Self::I8
+            | Self::U8
+            | Self::I16
+            | Self::U16
+            | Self::I32
+            | Self::U32
+            | Self::I64
+            | Self::U64
+            | Self::F32
+            | Self::F64
+            | Self::ISize
+            | Self::USize
+            | Self::Guid
+            | Self::ErrorCode
+            | Self::Bool32
+            | Self::Enum(_) => true,
+            Self::Struct(name) => {
+                !name.def.is_winrt()
+                    && name
+                        .def
+                        .fields()
+                        .all(|field| Type::from_field(&field, name.namespace).is_blittable())
+            }
+            Self::Delegate(name) => !name.def.is_winrt(),
+            _ => false,
+        }
+    }
+
+    pub fn is_copy(&self) -> bool {
+        match self {
+            Self::Bool
+            | Self::Char
+            | Self::I8
+            | Self::U8
+            | Self::I16
+            | Self::U16
+            | Self::I32
+            | Self::U32
+            | Self::I64
+            | Self::U64
+            | Self::F32
+            | Self::F64
+            | Self::ISize
+            | Self::USize
+            | Self::ErrorCode
+            | Self::Bool32
+            | Self::Enum(_) => true,
+            Self::Struct(name) => {
+                Struct::is_typedef(&name.def)
+                    || (Struct::has_layout(&name.def)
+                        && name
+                            .def
+                            .fields()
+                            .all(|field| Type::from_field(&field, name.namespace).is_copy()))
+            }
+            Self::Delegate(name) => !name.def.is_winrt(),
+            _ => false,
+        }
+    }
+
+    pub fn is_comparable(&self) -> bool {
+        match self {
+            Self::Struct(name) => {
+                !Struct::is_union(&name.def)
+                    && name
+                        .def
+                        .fields()
+                        .all(|field| Type::from_field(&field, name.namespace).is_comparable())
+            }
+            _ => true,
+        }
+    }
+
     pub fn signature(&self) -> String {
         match self {
             Self::Bool => "b1".to_owned(),
@@ -308,6 +413,42 @@ impl TypeKind {
         }
     }
 
+    pub fn from_sig(
+        sig: &winmd::TypeSig<'static>,
+        generics: &[Self],
+        calling_namespace: &'static str,
+    ) -> Option<Self> {
+        let kind = match sig {
+            winmd::TypeSig::Void => Self::Void,
+            winmd::TypeSig::Bool => Self::Bool,
+            winmd::TypeSig::Char => Self::Char,
+            winmd::TypeSig::I8 => Self::I8,
+            winmd::TypeSig::U8 => Self::U8,
+            winmd::TypeSig::I16 => Self::I16,
+            winmd::TypeSig::U16 => Self::U16,
+            winmd::TypeSig::I32 => Self::I32,
+            winmd::TypeSig::U32 => Self::U32,
+            winmd::TypeSig::I64 => Self::I64,
+            winmd::TypeSig::U64 => Self::U64,
+            winmd::TypeSig::F32 => Self::F32,
+            winmd::TypeSig::F64 => Self::F64,
+            winmd::TypeSig::ISize => Self::ISize,
+            winmd::TypeSig::USize => Self::USize,
+            winmd::TypeSig::String => Self::String,
+            winmd::TypeSig::Object => Self::Object,
+            winmd::TypeSig::ValueType(def) | winmd::TypeSig::Class(def) => {
+                Self::from_type_def_or_ref(def, generics, calling_namespace)
+            }
+            winmd::TypeSig::TypeGeneric(index) => generics.get(*index as usize)?.clone(),
+            winmd::TypeSig::GenericInst { def, args, .. } => Self::from_type_name(
+                TypeName::from_generic_inst(def, args, generics, calling_namespace)?,
+            ),
+            _ => return None,
+        };
+
+        Some(kind)
+    }
+
     fn from_type_name(name: TypeName) -> Self {
         match name.def.category() {
             winmd::TypeCategory::Interface => Self::Interface(name),
@@ -319,11 +460,14 @@ impl TypeKind {
         }
     }
 
-    pub fn from_type_def(def: &winmd::TypeDef, calling_namespace: &'static str) -> Self {
+    pub fn from_type_def(def: &winmd::TypeDef<'static>, calling_namespace: &'static str) -> Self {
         Self::from_type_name(TypeName::from_type_def(def, calling_namespace))
     }
 
-    pub fn from_type_ref(type_ref: &winmd::TypeRef, calling_namespace: &'static str) -> Self {
+    pub fn from_type_ref(
+        type_ref: &winmd::TypeRef<'static>,
+        calling_namespace: &'static str,
+    ) -> Self {
         match type_ref.name() {
             ("System", "Guid") | ("Windows.Win32.Com", "Guid") => Self::Guid,
             ("Windows.Win32.Com", "IUnknown") => Self::IUnknown,
@@ -334,15 +478,12 @@ impl TypeKind {
             ("Windows.Win32.SystemServices", "LARGE_INTEGER") => Self::I64,
             ("Windows.Win32.SystemServices", "ULARGE_INTEGER") => Self::U64,
             ("Windows.Win32.Direct2D", "D2D_MATRIX_3X2_F") => Self::Matrix3x2,
-            (namespace, name) => Self::from_type_def(
-                &type_ref.reader.expect_type_def((namespace, name)),
-                calling_namespace,
-            ),
+            _ => Self::from_type_def(&type_ref.resolve(), calling_namespace),
         }
     }
 
     pub fn from_type_spec(
-        spec: &winmd::TypeSpec,
+        spec: &winmd::TypeSpec<'static>,
         generics: &[Self],
         calling_namespace: &'static str,
     ) -> Self {
@@ -350,7 +491,7 @@ impl TypeKind {
     }
 
     fn from_type_def_or_ref(
-        code: &winmd::TypeDefOrRef,
+        code: &winmd::TypeDefOrRef<'static>,
         generics: &[Self],
         calling_namespace: &'static str,
     ) -> Self {
@@ -363,7 +504,7 @@ impl TypeKind {
         }
     }
 
-    pub fn dependencies(&self) -> Vec<winmd::TypeDef> {
+    pub fn dependencies(&self) -> Vec<winmd::TypeDef<'static>> {
         match self {
             Self::Class(name) => name.dependencies(),
             Self::Interface(name) => name.dependencies(),
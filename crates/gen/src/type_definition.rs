@@ -16,7 +16,7 @@ pub enum TypeDefinition {
 }
 
 impl TypeDefinition {
-    pub fn from_type_row(row: &winmd::Type) -> Self {
+    pub fn from_type_row(row: &winmd::Type<'static>) -> Self {
         match row {
             winmd::Type::TypeDef(def) => Self::from_type_def(def),
             winmd::Type::MethodDef((def, method)) => Self::Function(Function::new(
@@ -30,18 +30,21 @@ impl TypeDefinition {
         }
     }
 
-    pub fn from_method_def(def: &winmd::TypeDef, method: &winmd::MethodDef) -> Self {
+    pub fn from_method_def(
+        def: &winmd::TypeDef<'static>,
+        method: &winmd::MethodDef<'static>,
+    ) -> Self {
         let name = TypeName::from_type_def(def, def.name().0);
         TypeDefinition::Function(Function::new(name, method))
     }
 
-    pub fn from_field(def: &winmd::TypeDef, field: &winmd::Field) -> Self {
+    pub fn from_field(def: &winmd::TypeDef<'static>, field: &winmd::Field<'static>) -> Self {
         let name = TypeName::from_type_def(def, def.name().0);
         TypeDefinition::Constant(Constant::new(name, field))
     }
 
-    pub fn from_type_def(def: &winmd::TypeDef) -> Self {
-        let name = TypeName::from_type_def(def, def.name().0);
+    pub fn from_type_def(def: &winmd::TypeDef<'static>) -> Self {
+        let name = TypeName::from_type_def(def, TypeName::namespace_of(def));
 
         match def.category() {
             winmd::TypeCategory::Interface => {
@@ -94,7 +97,7 @@ impl TypeDefinition {
         }
     }
 
-    pub fn dependencies(&self) -> Vec<winmd::TypeDef> {
+    pub fn dependencies(&self) -> Vec<winmd::TypeDef<'static>> {
         match self {
             Self::Class(t) => t.dependencies(),
             Self::Interface(t) => t.dependencies(),
@@ -103,6 +106,7 @@ impl TypeDefinition {
             Self::ComInterface(t) => t.dependencies(),
             Self::Callback(t) => t.dependencies(),
             Self::Function(t) => t.dependencies(),
+            Self::Constant(t) => t.dependencies(),
             _ => Vec::new(),
         }
     }
@@ -22,7 +22,7 @@ impl std::fmt::Debug for GuidConstant {
 }
 
 impl GuidConstant {
-    fn from_arg(arg: &winmd::AttributeArg) -> GuidConstant {
+    fn from_arg(arg: &winmd::AttributeArg<'static>) -> GuidConstant {
         match *arg {
             winmd::AttributeArg::U32(value) => GuidConstant::U32(value),
             winmd::AttributeArg::U16(value) => GuidConstant::U16(value),
@@ -77,45 +77,17 @@ impl Default for GuidConstant {
 }
 
 impl TypeGuid {
-    pub fn from_type_def(def: &winmd::TypeDef) -> Self {
+    pub fn from_type_def(def: &winmd::TypeDef<'static>) -> Self {
         for attribute in def.attributes() {
             match attribute.name() {
                 ("Windows.Foundation.Metadata", "GuidAttribute") => {
-                    let args = attribute.args();
-
-                    return Self([
-                        GuidConstant::from_arg(&args[0].1),
-                        GuidConstant::from_arg(&args[1].1),
-                        GuidConstant::from_arg(&args[2].1),
-                        GuidConstant::from_arg(&args[3].1),
-                        GuidConstant::from_arg(&args[4].1),
-                        GuidConstant::from_arg(&args[5].1),
-                        GuidConstant::from_arg(&args[6].1),
-                        GuidConstant::from_arg(&args[7].1),
-                        GuidConstant::from_arg(&args[8].1),
-                        GuidConstant::from_arg(&args[9].1),
-                        GuidConstant::from_arg(&args[10].1),
-                    ]);
+                    return Self::from_args(&attribute.args());
                 }
                 ("System.Runtime.InteropServices", "GuidAttribute") => {
                     let args = attribute.args();
 
                     if let winmd::AttributeArg::String(guid) = &args[0].1 {
-                        let guid = GuidAttribute::new(&guid);
-
-                        return Self([
-                            GuidConstant::U32(guid.a),
-                            GuidConstant::U16(guid.b),
-                            GuidConstant::U16(guid.c),
-                            GuidConstant::U8(guid.d),
-                            GuidConstant::U8(guid.e),
-                            GuidConstant::U8(guid.f),
-                            GuidConstant::U8(guid.g),
-                            GuidConstant::U8(guid.h),
-                            GuidConstant::U8(guid.i),
-                            GuidConstant::U8(guid.j),
-                            GuidConstant::U8(guid.k),
-                        ]);
+                        return Self::from_guid_attribute(&GuidAttribute::new(guid));
                     }
                 }
                 _ => {}
@@ -125,6 +97,62 @@ impl TypeGuid {
         Self::default()
     }
 
+    /// The value of a GUID constant, which Win32 metadata gives with a `GuidAttribute` on
+    /// the field
+    pub fn from_field(field: &winmd::Field<'static>) -> Option<Self> {
+        field
+            .attributes()
+            .find(|attribute| attribute.name() == ("Windows.Win32.Interop", "GuidAttribute"))
+            .map(|attribute| Self::from_args(&attribute.args()))
+    }
+
+    fn from_args(args: &[(String, winmd::AttributeArg<'static>)]) -> Self {
+        Self([
+            GuidConstant::from_arg(&args[0].1),
+            GuidConstant::from_arg(&args[1].1),
+            GuidConstant::from_arg(&args[2].1),
+            GuidConstant::from_arg(&args[3].1),
+            GuidConstant::from_arg(&args[4].1),
+            GuidConstant::from_arg(&args[5].1),
+            GuidConstant::from_arg(&args[6].1),
+            GuidConstant::from_arg(&args[7].1),
+            GuidConstant::from_arg(&args[8].1),
+            GuidConstant::from_arg(&args[9].1),
+            GuidConstant::from_arg(&args[10].1),
+        ])
+    }
+
+    /// Parses a GUID in its `xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx` form
+    pub fn from_string(value: &str) -> Option<Self> {
+        let valid = value.len() == 36
+            && value.chars().enumerate().all(|(index, c)| match index {
+                8 | 13 | 18 | 23 => c == '-',
+                _ => c.is_ascii_hexdigit(),
+            });
+
+        if valid {
+            Some(Self::from_guid_attribute(&GuidAttribute::new(value)))
+        } else {
+            None
+        }
+    }
+
+    fn from_guid_attribute(guid: &GuidAttribute) -> Self {
+        Self([
+            GuidConstant::U32(guid.a),
+            GuidConstant::U16(guid.b),
+            GuidConstant::U16(guid.c),
+            GuidConstant::U8(guid.d),
+            GuidConstant::U8(guid.e),
+            GuidConstant::U8(guid.f),
+            GuidConstant::U8(guid.g),
+            GuidConstant::U8(guid.h),
+            GuidConstant::U8(guid.i),
+            GuidConstant::U8(guid.j),
+            GuidConstant::U8(guid.k),
+        ])
+    }
+
     pub fn gen(&self) -> TokenStream {
         let mut iter = self.0.iter().map(|value| match value {
             GuidConstant::U32(value) => Literal::u32_unsuffixed(*value),
@@ -1,7 +1,6 @@
 use crate::*;
 use squote::{quote, Ident, Literal, TokenStream};
 use std::iter::FromIterator;
-use winmd::Decode;
 
 /// A type's name including module namespace and generics
 #[derive(Debug, Clone)]
@@ -13,11 +12,14 @@ pub struct TypeName {
     /// The type's unqualified name without generics as a string
     ///
     /// e.g. "MyType"
-    pub name: &'static str,
+    ///
+    /// Nested types are named after their enclosing type and their position
+    /// within it, e.g. "MyType_0"
+    pub name: String,
     /// A collection of the types generics
     pub generics: Vec<TypeKind>,
     /// The type definition for this type
-    pub def: winmd::TypeDef,
+    pub def: winmd::TypeDef<'static>,
 
     // The namespace of the type being tokenized.
     calling_namespace: &'static str,
@@ -25,12 +27,11 @@ pub struct TypeName {
 
 impl TypeName {
     pub fn new(
-        def: &winmd::TypeDef,
+        def: &winmd::TypeDef<'static>,
         generics: Vec<TypeKind>,
         calling_namespace: &'static str,
     ) -> Self {
-        let (namespace, name) = def.name();
-        //let calling_namespace = calling_namespace.to_string();
+        let (namespace, name) = nested_name(def);
 
         Self {
             namespace,
@@ -41,6 +42,18 @@ impl TypeName {
         }
     }
 
+    /// The namespace a type is generated in, which for a nested type is the
+    /// namespace of its outermost enclosing type
+    pub fn namespace_of(def: &winmd::TypeDef<'static>) -> &'static str {
+        let mut def = *def;
+
+        while let Some(enclosing) = def.enclosing_type() {
+            def = enclosing;
+        }
+
+        def.name().0
+    }
+
     pub fn gen_constraint(&self) -> TokenStream {
         TokenStream::from_iter(self.generics.iter().map(|generic| {
             let generic = generic.gen();
@@ -49,7 +62,7 @@ impl TypeName {
     }
 
     pub fn from_type_def_or_ref(
-        code: &winmd::TypeDefOrRef,
+        code: &winmd::TypeDefOrRef<'static>,
         generics: &[TypeKind],
         calling_namespace: &'static str,
     ) -> Self {
@@ -62,11 +75,11 @@ impl TypeName {
         }
     }
 
-    fn from_type_ref(type_ref: &winmd::TypeRef, calling_namespace: &'static str) -> Self {
+    fn from_type_ref(type_ref: &winmd::TypeRef<'static>, calling_namespace: &'static str) -> Self {
         Self::from_type_def(&type_ref.resolve(), calling_namespace)
     }
 
-    pub fn from_type_def(def: &winmd::TypeDef, calling_namespace: &'static str) -> Self {
+    pub fn from_type_def(def: &winmd::TypeDef<'static>, calling_namespace: &'static str) -> Self {
         let mut generics = Vec::new();
 
         for generic in def.generics() {
@@ -76,34 +89,34 @@ impl TypeName {
         Self::new(def, generics, calling_namespace)
     }
 
-    pub fn from_type_spec_blob(
-        blob: &mut winmd::Blob,
+    pub fn from_generic_inst(
+        def: &winmd::TypeDefOrRef<'static>,
+        args: &[winmd::TypeSig<'static>],
         generics: &[TypeKind],
         calling_namespace: &'static str,
-    ) -> Self {
-        blob.read_unsigned();
-
-        let def = winmd::TypeDefOrRef::decode(blob.reader, blob.read_unsigned(), blob.file_index)
-            .resolve();
-
-        let mut args = Vec::with_capacity(blob.read_unsigned() as usize);
-
-        for _ in 0..args.capacity() {
-            let t = Type::from_blob(blob, None, generics, calling_namespace, false).unwrap();
-            args.push(t.kind);
-        }
+    ) -> Option<Self> {
+        let args = args
+            .iter()
+            .map(|arg| TypeKind::from_sig(arg, generics, calling_namespace))
+            .collect::<Option<_>>()?;
 
-        Self::new(&def, args, calling_namespace)
+        Some(Self::new(&def.resolve(), args, calling_namespace))
     }
 
     pub fn from_type_spec(
-        spec: &winmd::TypeSpec,
+        spec: &winmd::TypeSpec<'static>,
         generics: &[TypeKind],
         calling_namespace: &'static str,
     ) -> Self {
-        let mut blob = spec.sig();
-        blob.read_unsigned();
-        Self::from_type_spec_blob(&mut blob, generics, calling_namespace)
+        let signature = spec.signature();
+
+        if let winmd::TypeSig::GenericInst { def, args, .. } = &signature {
+            if let Some(name) = Self::from_generic_inst(def, args, generics, calling_namespace) {
+                return name;
+            }
+        }
+
+        panic!("TypeName::from_type_spec {:?}", signature)
     }
 
     pub fn gen_signature(&self, signature: &str) -> TokenStream {
@@ -256,7 +269,7 @@ impl TypeName {
         result
     }
 
-    pub fn dependencies(&self) -> Vec<winmd::TypeDef> {
+    pub fn dependencies(&self) -> Vec<winmd::TypeDef<'static>> {
         std::iter::once(self.def)
             .chain(self.generics.iter().flat_map(|i| i.dependencies()))
             .collect()
@@ -355,6 +368,26 @@ impl Ord for TypeName {
     }
 }
 
+/// Returns the namespace and name of a type, flattening nested types into uniquely
+/// named siblings of their outermost enclosing type.
+fn nested_name(def: &winmd::TypeDef<'static>) -> (&'static str, String) {
+    match def.enclosing_type() {
+        Some(enclosing) => {
+            let index = enclosing
+                .nested_types()
+                .position(|nested| nested == *def)
+                .unwrap();
+
+            let (namespace, name) = nested_name(&enclosing);
+            (namespace, format!("{}_{}", name, index))
+        }
+        None => {
+            let (namespace, name) = def.name();
+            (namespace, name.to_string())
+        }
+    }
+}
+
 fn format_abi_ident(name: &str) -> Ident {
     squote::format_ident!("{}_abi", name)
 }
@@ -39,14 +39,14 @@ impl TypeTree {
     fn insert_if(
         &mut self,
         reader: &winmd::TypeReader,
-        set: &mut std::collections::BTreeSet<winmd::TypeDef>,
-        def: &winmd::Type,
+        set: &mut std::collections::BTreeSet<winmd::Row>,
+        def: &winmd::Type<'static>,
     ) {
         match def {
             winmd::Type::TypeDef(def) => match def.category() {
                 winmd::TypeCategory::Contract | winmd::TypeCategory::Attribute => {}
                 _ => {
-                    if set.insert(*def) {
+                    if set.insert(def.row) {
                         let t = TypeDefinition::from_type_def(def);
 
                         for def in t.dependencies() {
@@ -0,0 +1,318 @@
+//! Prints what the `TypeReader` sees in a set of Windows Metadata (winmd) files
+//!
+//! With `--json`, types are printed as a [`winmd::model::Metadata`] document holding just
+//! the types that were asked for, so the output follows the schema versioned by
+//! [`winmd::model::SCHEMA_VERSION`].
+mod text;
+
+extern crate windows_winmd as winmd;
+
+use serde::Serialize;
+use winmd::model::{Metadata, Namespace, TypeDefinition, SCHEMA_VERSION};
+
+const USAGE: &str = "\
+Usage: winmd-inspect [options] <command>
+
+Commands:
+  namespaces              List every namespace
+  types <namespace>       List the types in a namespace along with their category
+  type <namespace.name>   Show a type's GUID, attributes, fields, methods and parameters
+  search <text>           Find types whose full name contains the text, ignoring case
+  diff                    Compare the files given by `--old` with those given by `--new`,
+                          reporting which changes break the Rust projection
+
+Options:
+  --winmd <path>          Read the given winmd file rather than the default metadata,
+                          may be repeated
+  --old <path>            Compare from the given winmd file rather than the default
+                          metadata, may be repeated
+  --new <path>            Compare to the given winmd file rather than the default
+                          metadata, may be repeated
+  --json                  Print JSON rather than text
+  --help                  Print this message";
+
+struct Options {
+    paths: Vec<std::path::PathBuf>,
+    old_paths: Vec<std::path::PathBuf>,
+    new_paths: Vec<std::path::PathBuf>,
+    json: bool,
+    command: Vec<String>,
+}
+
+fn main() {
+    match run(std::env::args().skip(1)) {
+        Ok(output) => print!("{}", output),
+        Err(message) => {
+            eprintln!("{}", message);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run<I: Iterator<Item = String>>(args: I) -> Result<String, String> {
+    let options = parse(args)?;
+    let command: Vec<&str> = options.command.iter().map(String::as_str).collect();
+
+    if command == ["diff"] {
+        let old = reader(options.old_paths)?;
+        let new = reader(options.new_paths)?;
+        return diff(&old, &new, options.json);
+    }
+
+    let reader = reader(options.paths)?;
+
+    match command.as_slice() {
+        ["namespaces"] => namespaces(&reader, options.json),
+        ["types", namespace] => types(&reader, namespace, options.json),
+        ["type", name] => type_details(&reader, name, options.json),
+        ["search", text] => search(&reader, text, options.json),
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+fn parse<I: Iterator<Item = String>>(mut args: I) -> Result<Options, String> {
+    let mut options = Options {
+        paths: Vec::new(),
+        old_paths: Vec::new(),
+        new_paths: Vec::new(),
+        json: false,
+        command: Vec::new(),
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--winmd" => match args.next() {
+                Some(path) => options.paths.push(path.into()),
+                None => return Err("Expected a path after `--winmd`".to_string()),
+            },
+            "--old" => match args.next() {
+                Some(path) => options.old_paths.push(path.into()),
+                None => return Err("Expected a path after `--old`".to_string()),
+            },
+            "--new" => match args.next() {
+                Some(path) => options.new_paths.push(path.into()),
+                None => return Err("Expected a path after `--new`".to_string()),
+            },
+            "--json" => options.json = true,
+            "--help" | "-h" => return Err(USAGE.to_string()),
+            _ if arg.starts_with("--") => return Err(format!("Unknown option `{}`", arg)),
+            _ => options.command.push(arg),
+        }
+    }
+
+    Ok(options)
+}
+
+/// Reads the given files, or the default metadata if there are none
+fn reader(paths: Vec<std::path::PathBuf>) -> Result<winmd::TypeReader, String> {
+    if paths.is_empty() {
+        winmd::TypeReader::builder().build()
+    } else {
+        winmd::TypeReader::builder()
+            .paths(paths)
+            .exclude_default(true)
+            .build()
+    }
+    .map_err(|error| error.to_string())
+}
+
+fn namespaces(reader: &winmd::TypeReader, json: bool) -> Result<String, String> {
+    let namespaces: Vec<&str> = reader.namespaces().collect();
+
+    if json {
+        return to_json(&namespaces);
+    }
+
+    let mut text = String::new();
+
+    for namespace in &namespaces {
+        text.push_str(namespace);
+        text.push('\n');
+    }
+
+    Ok(text)
+}
+
+fn types(reader: &winmd::TypeReader, namespace: &str, json: bool) -> Result<String, String> {
+    let namespace = find_namespace(reader, namespace)?;
+
+    let types: Vec<winmd::TypeDef> = reader
+        .namespace_types(namespace)
+        .filter_map(|ty| match ty {
+            winmd::Type::TypeDef(def) => Some(def),
+            _ => None,
+        })
+        .collect();
+
+    summaries(types, json)
+}
+
+fn type_details(reader: &winmd::TypeReader, name: &str, json: bool) -> Result<String, String> {
+    let (namespace, type_name) = match name.rfind('.') {
+        Some(index) => (&name[..index], &name[index + 1..]),
+        None => {
+            return Err(format!(
+                "Expected a namespace-qualified name, not `{}`",
+                name
+            ))
+        }
+    };
+
+    let def = match reader.find_type_def((namespace, type_name)) {
+        Some(def) => def,
+        None => {
+            let lowercase = name.to_lowercase();
+
+            // Suggest the correctly cased name if there is one.
+            return Err(
+                match all_types(reader).find(|def| full_name(def).to_lowercase() == lowercase) {
+                    Some(def) => format!(
+                        "Type `{}` not found, did you mean `{}`?",
+                        name,
+                        full_name(&def)
+                    ),
+                    None => format!("Type `{}` not found", name),
+                },
+            );
+        }
+    };
+
+    if json {
+        return to_json(&metadata(vec![def])?);
+    }
+
+    let details = TypeDefinition::new(&def).map_err(|error| error.to_string())?;
+    let mut text = String::new();
+    text::write_details(&mut text, namespace, &details);
+    Ok(text)
+}
+
+fn search(reader: &winmd::TypeReader, text: &str, json: bool) -> Result<String, String> {
+    let lowercase = text.to_lowercase();
+
+    let types: Vec<winmd::TypeDef> = all_types(reader)
+        .filter(|def| full_name(def).to_lowercase().contains(&lowercase))
+        .collect();
+
+    summaries(types, json)
+}
+
+/// A change found by the `diff` command, as JSON
+#[derive(Serialize)]
+struct Change<'a> {
+    kind: &'a str,
+    #[serde(rename = "type")]
+    type_name: &'a str,
+    member: &'a Option<String>,
+    old: &'a Option<String>,
+    new: &'a Option<String>,
+    breaking: bool,
+}
+
+fn diff(old: &winmd::TypeReader, new: &winmd::TypeReader, json: bool) -> Result<String, String> {
+    let changes = winmd::diff::compare(old, new).map_err(|error| error.to_string())?;
+
+    if json {
+        let changes: Vec<Change> = changes
+            .iter()
+            .map(|change| Change {
+                kind: change.kind.description(),
+                type_name: &change.type_name,
+                member: &change.member,
+                old: &change.old,
+                new: &change.new,
+                breaking: change.breaking,
+            })
+            .collect();
+
+        return to_json(&changes);
+    }
+
+    let mut text = String::new();
+
+    for change in &changes {
+        text.push_str(&change.to_string());
+        text.push('\n');
+    }
+
+    let breaking = changes.iter().filter(|change| change.breaking).count();
+    text.push_str(&format!(
+        "{} changes, {} breaking\n",
+        changes.len(),
+        breaking
+    ));
+
+    Ok(text)
+}
+
+fn find_namespace<'a>(reader: &'a winmd::TypeReader, namespace: &str) -> Result<&'a str, String> {
+    if let Some(found) = reader.namespaces().find(|found| *found == namespace) {
+        return Ok(found);
+    }
+
+    match reader.find_lowercase_namespace(&namespace.to_lowercase()) {
+        Some(found) => Err(format!(
+            "Namespace `{}` not found, did you mean `{}`?",
+            namespace, found
+        )),
+        None => Err(format!("Namespace `{}` not found", namespace)),
+    }
+}
+
+fn all_types(reader: &winmd::TypeReader) -> impl Iterator<Item = winmd::TypeDef<'_>> + '_ {
+    reader.namespaces().flat_map(move |namespace| {
+        reader.namespace_types(namespace).filter_map(|ty| match ty {
+            winmd::Type::TypeDef(def) => Some(def),
+            _ => None,
+        })
+    })
+}
+
+fn full_name(def: &winmd::TypeDef<'_>) -> String {
+    let (namespace, name) = def.name();
+    text::full_name(namespace, name)
+}
+
+fn summaries(types: Vec<winmd::TypeDef<'_>>, json: bool) -> Result<String, String> {
+    if json {
+        return to_json(&metadata(types)?);
+    }
+
+    let mut text = String::new();
+
+    for def in &types {
+        text::write_summary(&mut text, def);
+    }
+
+    Ok(text)
+}
+
+/// Copies the given types into the metadata model, grouping them by namespace in the
+/// order they are given
+fn metadata(types: Vec<winmd::TypeDef<'_>>) -> Result<Metadata, String> {
+    let mut namespaces: Vec<Namespace> = Vec::new();
+
+    for def in types {
+        let namespace = def.name().0;
+        let ty = TypeDefinition::new(&def).map_err(|error| error.to_string())?;
+
+        match namespaces.last_mut() {
+            Some(last) if last.name == namespace => last.types.push(ty),
+            _ => namespaces.push(Namespace {
+                name: namespace.to_string(),
+                types: vec![ty],
+            }),
+        }
+    }
+
+    Ok(Metadata {
+        version: SCHEMA_VERSION,
+        namespaces,
+    })
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<String, String> {
+    serde_json::to_string_pretty(value)
+        .map(|json| format!("{}\n", json))
+        .map_err(|error| error.to_string())
+}
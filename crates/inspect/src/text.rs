@@ -0,0 +1,227 @@
+use std::fmt::Write;
+use winmd::model::*;
+
+/// Writes the line that the `types` and `search` commands print for a type
+pub fn write_summary(text: &mut String, def: &winmd::TypeDef<'_>) {
+    let (namespace, name) = def.name();
+    let category = format!("{:?}", def.category());
+    writeln!(text, "{:<10} {}", category, full_name(namespace, name)).unwrap();
+}
+
+/// Writes everything the `type` command shows about one type
+pub fn write_details(text: &mut String, namespace: &str, def: &TypeDefinition) {
+    let category = format!("{:?}", def.category);
+    writeln!(text, "{:<10} {}", category, full_name(namespace, &def.name)).unwrap();
+
+    if let Some(guid) = &def.guid {
+        writeln!(text, "  guid: {}", guid).unwrap();
+    }
+
+    if let Some(extends) = &def.extends {
+        writeln!(text, "  extends: {}", type_name(extends, &def.generics)).unwrap();
+    }
+
+    if !def.generics.is_empty() {
+        let generics: Vec<&str> = def
+            .generics
+            .iter()
+            .map(|param| param.name.as_str())
+            .collect();
+        writeln!(text, "  generics: {}", generics.join(", ")).unwrap();
+    }
+
+    if !def.interfaces.is_empty() {
+        writeln!(text, "  interfaces:").unwrap();
+
+        for interface in &def.interfaces {
+            writeln!(text, "    {}", type_name(&interface.ty, &def.generics)).unwrap();
+        }
+    }
+
+    if !def.attributes.is_empty() {
+        writeln!(text, "  attributes:").unwrap();
+
+        for attribute in &def.attributes {
+            writeln!(text, "    {}", attribute_text(attribute)).unwrap();
+        }
+    }
+
+    if !def.fields.is_empty() {
+        writeln!(text, "  fields:").unwrap();
+
+        for field in &def.fields {
+            let ty = type_name(&field.ty, &def.generics);
+
+            match &field.constant {
+                Some(value) => {
+                    writeln!(text, "    {}: {} = {}", field.name, ty, value_text(value)).unwrap()
+                }
+                None => writeln!(text, "    {}: {}", field.name, ty).unwrap(),
+            }
+        }
+    }
+
+    if !def.methods.is_empty() {
+        writeln!(text, "  methods:").unwrap();
+
+        for method in &def.methods {
+            for attribute in &method.attributes {
+                writeln!(text, "    {}", attribute_text(attribute)).unwrap();
+            }
+
+            let params: Vec<String> = method
+                .params
+                .iter()
+                .map(|param| {
+                    let flags = winmd::ParamFlags(param.flags);
+
+                    let direction = match (flags.input(), flags.output()) {
+                        (true, false) => "in ",
+                        (false, true) => "out ",
+                        (true, true) => "in out ",
+                        (false, false) => "",
+                    };
+
+                    format!(
+                        "{}{}: {}",
+                        direction,
+                        param.name,
+                        type_name(&param.ty, &def.generics)
+                    )
+                })
+                .collect();
+
+            writeln!(
+                text,
+                "    {}({}) -> {}",
+                method.name,
+                params.join(", "),
+                type_name(&method.return_type, &def.generics)
+            )
+            .unwrap();
+        }
+    }
+}
+
+pub fn full_name(namespace: &str, name: &str) -> String {
+    if namespace.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", namespace, name)
+    }
+}
+
+fn attribute_text(attribute: &Attribute) -> String {
+    let args = match &attribute.args {
+        Some(args) => args
+            .iter()
+            .map(|arg| match &arg.name {
+                Some(name) => format!("{} = {}", name, value_text(&arg.value)),
+                None => value_text(&arg.value),
+            })
+            .collect::<Vec<_>>()
+            .join(", "),
+        // Arguments of enum type can only be decoded if the file defining the enum was read.
+        None => "...".to_string(),
+    };
+
+    format!(
+        "[{}({})]",
+        full_name(&attribute.ty.namespace, &attribute.ty.name),
+        args
+    )
+}
+
+/// Renders a type using the names that Windows Metadata gives to the primitive types
+fn type_name(ty: &Type, generics: &[GenericParameter]) -> String {
+    match ty {
+        Type::Void => "Void".to_string(),
+        Type::Bool => "Boolean".to_string(),
+        Type::Char => "Char".to_string(),
+        Type::I8 => "Int8".to_string(),
+        Type::U8 => "UInt8".to_string(),
+        Type::I16 => "Int16".to_string(),
+        Type::U16 => "UInt16".to_string(),
+        Type::I32 => "Int32".to_string(),
+        Type::U32 => "UInt32".to_string(),
+        Type::I64 => "Int64".to_string(),
+        Type::U64 => "UInt64".to_string(),
+        Type::F32 => "Single".to_string(),
+        Type::F64 => "Double".to_string(),
+        Type::ISize => "IntPtr".to_string(),
+        Type::USize => "UIntPtr".to_string(),
+        Type::String => "String".to_string(),
+        Type::Object => "Object".to_string(),
+        Type::TypedByRef => "TypedReference".to_string(),
+        Type::Named {
+            namespace, name, ..
+        } => full_name(namespace, name),
+        Type::TypeParameter { index } => generics
+            .get(*index as usize)
+            .map(|param| param.name.clone())
+            .unwrap_or_else(|| format!("!{}", index)),
+        Type::MethodParameter { index } => format!("!!{}", index),
+        Type::Generic {
+            namespace,
+            name,
+            args,
+            ..
+        } => {
+            let name = full_name(namespace, name);
+            let name = name.split('`').next().unwrap_or_default();
+            let args: Vec<String> = args.iter().map(|arg| type_name(arg, generics)).collect();
+            format!("{}<{}>", name, args.join(", "))
+        }
+        Type::Pointer { element } => format!("{}*", type_name(element, generics)),
+        Type::Reference { element } => format!("ref {}", type_name(element, generics)),
+        Type::Array { element } => format!("{}[]", type_name(element, generics)),
+        Type::MultiArray { element, rank, .. } => format!(
+            "{}[{}]",
+            type_name(element, generics),
+            ",".repeat(rank.saturating_sub(1) as usize)
+        ),
+        Type::Function { signature } => {
+            let params: Vec<String> = signature
+                .params
+                .iter()
+                .map(|param| type_name(param, generics))
+                .collect();
+
+            format!(
+                "fn({}) -> {}",
+                params.join(", "),
+                type_name(&signature.return_type, generics)
+            )
+        }
+    }
+}
+
+fn value_text(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(value) => value.to_string(),
+        Value::Char(value) => format!("{:?}", value),
+        Value::I8(value) => value.to_string(),
+        Value::U8(value) => value.to_string(),
+        Value::I16(value) => value.to_string(),
+        Value::U16(value) => value.to_string(),
+        Value::I32(value) => value.to_string(),
+        Value::U32(value) => value.to_string(),
+        Value::I64(value) => value.to_string(),
+        Value::U64(value) => value.to_string(),
+        Value::F32(value) => value.to_string(),
+        Value::F64(value) => value.to_string(),
+        Value::String(value) => format!("{:?}", value),
+        Value::Type(name) => format!("typeof({})", full_name(&name.namespace, &name.name)),
+        Value::Array(values) => {
+            let values: Vec<String> = values.iter().map(value_text).collect();
+            format!("[{}]", values.join(", "))
+        }
+        Value::Enum { ty, value } => format!(
+            "{}({})",
+            full_name(&ty.namespace, &ty.name),
+            value_text(value)
+        ),
+        Value::Boxed(value) => value_text(value),
+    }
+}
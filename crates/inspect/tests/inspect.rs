@@ -0,0 +1,188 @@
+fn inspect(args: &[&str]) -> (bool, String, String) {
+    let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("../../.windows/winmd/TestComponent.winmd");
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_winmd-inspect"))
+        .arg("--winmd")
+        .arg(path)
+        .args(args)
+        .output()
+        .unwrap();
+
+    (
+        output.status.success(),
+        String::from_utf8(output.stdout).unwrap(),
+        String::from_utf8(output.stderr).unwrap(),
+    )
+}
+
+#[test]
+fn namespaces() {
+    let (success, text, _) = inspect(&["namespaces"]);
+    assert!(success);
+    assert!(text.lines().any(|line| line == "TestComponent"));
+
+    let (success, json, _) = inspect(&["--json", "namespaces"]);
+    assert!(success);
+    assert!(json.contains("\"TestComponent\""));
+}
+
+#[test]
+fn types() {
+    let (success, text, _) = inspect(&["types", "TestComponent"]);
+    assert!(success);
+    assert!(text.contains("Interface  TestComponent.ITests\n"));
+    assert!(text.contains("Struct     TestComponent.Blittable\n"));
+    assert!(text.contains("Delegate   TestComponent.TestHandler\n"));
+
+    let (success, json, _) = inspect(&["types", "TestComponent", "--json"]);
+    assert!(success);
+    let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(json["version"], windows_winmd::model::SCHEMA_VERSION);
+    assert_eq!(json["namespaces"][0]["name"], "TestComponent");
+
+    let composable = json["namespaces"][0]["types"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|ty| ty["name"] == "Composable")
+        .unwrap();
+    assert_eq!(composable["category"], "class");
+}
+
+#[test]
+fn namespace_casing() {
+    let (success, text, error) = inspect(&["types", "testcomponent"]);
+    assert!(!success);
+    assert!(text.is_empty());
+    assert_eq!(
+        error.trim(),
+        "Namespace `testcomponent` not found, did you mean `TestComponent`?"
+    );
+
+    let (success, _, error) = inspect(&["type", "testcomponent.itests"]);
+    assert!(!success);
+    assert_eq!(
+        error.trim(),
+        "Type `testcomponent.itests` not found, did you mean `TestComponent.ITests`?"
+    );
+}
+
+#[test]
+fn type_details() {
+    let (success, text, _) = inspect(&["type", "TestComponent.ITests"]);
+    assert!(success);
+    assert!(text.starts_with("Interface  TestComponent.ITests\n"));
+    assert!(text.contains("  guid: cc8647fe-6a28-5ae0-b4de-39ea156b5611\n"));
+    assert!(text.contains("    [Windows.Foundation.Metadata.VersionAttribute(1)]\n"));
+    assert!(text.contains("    Param7(in a: Int32, out b: ref Int32) -> Int32\n"));
+
+    let (success, text, _) = inspect(&["type", "TestComponent.Blittable"]);
+    assert!(success);
+    assert!(text.contains("  extends: System.ValueType\n"));
+    assert!(text.contains("  fields:\n    A: UInt8\n"));
+
+    // The JSON is the metadata model, which can be read back in.
+    let (success, json, _) = inspect(&["--json", "type", "TestComponent.ITests"]);
+    assert!(success);
+    let metadata: windows_winmd::model::Metadata = serde_json::from_str(&json).unwrap();
+    assert_eq!(metadata.version, windows_winmd::model::SCHEMA_VERSION);
+    assert_eq!(metadata.namespaces.len(), 1);
+    assert_eq!(metadata.namespaces[0].name, "TestComponent");
+
+    let tests = &metadata.namespaces[0].types[0];
+    assert_eq!(tests.name, "ITests");
+    assert_eq!(tests.category, windows_winmd::TypeCategory::Interface);
+    assert_eq!(
+        tests.guid.as_deref(),
+        Some("cc8647fe-6a28-5ae0-b4de-39ea156b5611")
+    );
+
+    let param7 = tests
+        .methods
+        .iter()
+        .find(|method| method.name == "Param7")
+        .unwrap();
+    assert_eq!(param7.return_type, windows_winmd::model::Type::I32);
+}
+
+#[test]
+fn search() {
+    let (success, text, _) = inspect(&["search", "required"]);
+    assert!(success);
+
+    let names: Vec<&str> = text.lines().collect();
+    assert_eq!(
+        names,
+        [
+            "Interface  TestComponent.IRequiredFour",
+            "Interface  TestComponent.IRequiredOne",
+            "Interface  TestComponent.IRequiredThree",
+            "Interface  TestComponent.IRequiredTwo",
+        ]
+    );
+
+    let (success, json, _) = inspect(&["--json", "search", "nomatch"]);
+    assert!(success);
+    let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(json["namespaces"], serde_json::json!([]));
+}
+
+#[test]
+fn usage() {
+    let (success, _, error) = inspect(&["frobnicate"]);
+    assert!(!success);
+    assert!(error.starts_with("Usage: winmd-inspect"));
+}
+
+/// Compiles MIDL to a winmd file in the test's temporary directory
+fn compile(name: &str, source: &str) -> std::path::PathBuf {
+    let mut path = std::path::PathBuf::from(env!("CARGO_TARGET_TMPDIR"));
+    path.push(name);
+    std::fs::write(&path, windows_winmd::midl::compile(name, source).unwrap()).unwrap();
+    path
+}
+
+#[test]
+fn diff() {
+    let diff = |old: &std::path::Path, new: &std::path::Path, args: &[&str]| {
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_winmd-inspect"))
+            .arg("--old")
+            .arg(old)
+            .arg("--new")
+            .arg(new)
+            .arg("diff")
+            .args(args)
+            .output()
+            .unwrap();
+
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    let mut component = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    component.push("../../.windows/winmd/TestComponent.winmd");
+    assert_eq!(diff(&component, &component, &[]), "0 changes, 0 breaking\n");
+
+    let old = compile(
+        "DiffOld.winmd",
+        "namespace Diff { enum Color { Red }; interface IShape { void Move(Int32 dx); } }",
+    );
+
+    let new = compile(
+        "DiffNew.winmd",
+        "namespace Diff { enum Color { Red, Green }; interface IShape { void Move(Int32 deltaX); } }",
+    );
+
+    assert_eq!(
+        diff(&old, &new, &[]),
+        "compatible: enum value added `Diff.Color.Green`: 1\n\
+         breaking: parameter renamed `Diff.IShape.Move`: dx -> deltaX\n\
+         2 changes, 1 breaking\n"
+    );
+
+    let json = diff(&old, &new, &["--json"]);
+    assert!(json
+        .starts_with("[\n  {\n    \"kind\": \"enum value added\",\n    \"type\": \"Diff.Color\","));
+    assert!(json.contains("\"old\": \"dx\",\n    \"new\": \"deltaX\",\n    \"breaking\": true\n"));
+}
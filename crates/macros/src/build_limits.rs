@@ -3,13 +3,17 @@ use std::convert::{TryFrom, TryInto};
 use syn::spanned::Spanned;
 use windows_gen::{NamespaceTypes, TypeLimit, TypeLimits, TypeTree};
 
-pub struct BuildLimits(pub std::collections::BTreeSet<TypesDeclaration>);
+pub struct BuildLimits {
+    /// The places given by `winmd = "path"` arguments to look for metadata
+    pub search_paths: Vec<std::path::PathBuf>,
+    pub limits: std::collections::BTreeSet<TypesDeclaration>,
+}
 
 impl BuildLimits {
     pub fn to_tokens_string(self) -> Result<String, proc_macro2::TokenStream> {
-        let is_foundation = self.0.is_empty();
+        let is_foundation = self.limits.is_empty();
 
-        let reader = winmd::TypeReader::get();
+        let reader = winmd::TypeReader::get_with(&self.search_paths);
 
         let mut limits = TypeLimits::new(reader);
 
@@ -31,7 +35,7 @@ impl BuildLimits {
             }
         }
 
-        for limit in self.0 {
+        for limit in self.limits {
             let types = limit.types;
             let syntax = limit.syntax;
             limits.insert(types).map_err(|ns| {
@@ -96,7 +100,49 @@ impl TryFrom<syn::UseTree> for TypesDeclaration {
 
 impl syn::parse::Parse for BuildLimits {
     fn parse(input: syn::parse::ParseStream) -> syn::parse::Result<Self> {
+        let mut search_paths = Vec::new();
+
+        // The paths must come first since the metadata is read to parse the types.
+        while input.peek(syn::Ident) && input.peek2(syn::Token![=]) {
+            let name: syn::Ident = input.parse()?;
+
+            if name != "winmd" {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!("Unknown option `{}`, expected `winmd`", name),
+                ));
+            }
+
+            input.parse::<syn::Token![=]>()?;
+            let path: syn::LitStr = input.parse()?;
+            search_paths.push(search_path(&path)?);
+
+            if !input.is_empty() {
+                input.parse::<syn::Token![,]>()?;
+            }
+        }
+
+        let reader = winmd::TypeReader::get_with(&search_paths);
+
+        // Only the first `build` in a crate reads the metadata, so later paths may be ignored.
+        for file in winmd::find_winmd_files(&search_paths) {
+            if !reader
+                .files()
+                .iter()
+                .any(|read| std::path::Path::new(read.name()) == file)
+            {
+                return Err(syn::Error::new(
+                    input.span(),
+                    format!(
+                        "`{}` was not read because the metadata was already read by an earlier `build` macro",
+                        file.display()
+                    ),
+                ));
+            }
+        }
+
         let mut limits = std::collections::BTreeSet::new();
+
         loop {
             if input.is_empty() {
                 break;
@@ -111,7 +157,28 @@ impl syn::parse::Parse for BuildLimits {
                 input.parse::<syn::Token![,]>()?;
             }
         }
-        Ok(Self(limits))
+
+        Ok(Self {
+            search_paths,
+            limits,
+        })
+    }
+}
+
+/// Resolves a `winmd` path relative to the crate whose build script uses the macro
+fn search_path(path: &syn::LitStr) -> syn::parse::Result<std::path::PathBuf> {
+    let mut resolved =
+        std::path::PathBuf::from(std::env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default());
+
+    resolved.push(path.value());
+
+    if resolved.exists() {
+        Ok(resolved)
+    } else {
+        Err(syn::Error::new(
+            path.span(),
+            format!("`{}` does not exist", resolved.display()),
+        ))
     }
 }
 
@@ -33,9 +33,79 @@ use syn::parse_macro_input;
 ///     microsoft::ai::machine_learning::*
 /// );
 /// ```
+///
+/// # Metadata
+/// Types are read from the metadata files found in the workspace's `.windows/winmd`
+/// directory, or else from the metadata that ships with the crate. Further files or
+/// directories may be listed in the `WINDOWS_WINMD_PATH` environment variable, separated
+/// like `PATH`, or given ahead of the types with `winmd` arguments, which are relative
+/// to the crate's manifest directory. Directories are searched recursively, and where
+/// several files define the same type the first one found wins: `winmd` arguments come
+/// first, then the environment variable and finally the workspace. The build script
+/// prints a warning for every definition that is ignored this way.
+///
+/// ```rust,ignore
+/// build!(
+///     winmd = "../shared/metadata",
+///     winmd = "Contoso.winmd",
+///     contoso::widgets::*
+/// );
+/// ```
+///
+/// The build script is rerun whenever one of the files that were found changes.
+///
+/// # Enums
+/// Enums are generated as structs wrapping the underlying integer, with a constant for
+/// each value. They implement `Debug`, `Display` and `FromStr` using the value names and
+/// list their values in `VARIANTS`.
+///
+/// Every enum converts from any value of its underlying type with `From`. Flags enums,
+/// which are unsigned enums and enums with the `FlagsAttribute`, also support the bitwise
+/// operators. Other enums have a checked `try_from` function, which fails for values the
+/// enum does not define. It is an inherent function rather than a `TryFrom` implementation,
+/// which the standard library already provides for any type implementing `From`:
+///
+/// ```rust,ignore
+/// let status: AsyncStatus = 1.into();
+/// assert!(AsyncStatus::try_from(7).is_err());
+/// ```
 #[proc_macro]
 pub fn build(stream: TokenStream) -> TokenStream {
     let build = parse_macro_input!(stream as BuildLimits);
+    let search_paths = winmd::winmd_search_paths(&build.search_paths);
+
+    let files: Vec<String> = winmd::find_winmd_files(&search_paths)
+        .iter()
+        .map(|path| path.to_string_lossy().into_owned())
+        .collect();
+
+    // Watching directories means adding a file to one also reruns the build script.
+    let dirs: Vec<String> = search_paths
+        .iter()
+        .filter(|path| path.is_dir())
+        .map(|path| path.to_string_lossy().into_owned())
+        .collect();
+
+    let winmd_path_var = winmd::WINMD_PATH_VAR;
+
+    // Only the first definition of a type is used so the build script reports any others.
+    let reader = winmd::TypeReader::get_with(&build.search_paths);
+
+    let duplicates: Vec<String> = reader
+        .duplicate_types()
+        .map(|duplicate| {
+            let (namespace, name) = duplicate.used.name();
+            let file = |def: winmd::TypeDef| reader.files()[def.row.file_index as usize].name();
+
+            format!(
+                "`{}.{}` in `{}` is ignored because `{}` already defines it",
+                namespace,
+                name,
+                file(duplicate.ignored),
+                file(duplicate.used)
+            )
+        })
+        .collect();
 
     let tokens = match build.to_tokens_string() {
         Ok(t) => t,
@@ -111,6 +181,11 @@ pub fn build(stream: TokenStream) -> TokenStream {
                 }
             }
 
+            println!("cargo:rerun-if-env-changed={}", #winmd_path_var);
+            #(println!("cargo:rerun-if-changed={}", #dirs);)*
+            #(println!("cargo:rerun-if-changed={}", #files);)*
+            #(println!("cargo:warning={}", #duplicates);)*
+
             if ::std::path::PathBuf::from(#workspace_windows_dir).exists() {
                 println!("cargo:rerun-if-changed={}", #workspace_windows_dir);
                 let source = ::std::path::PathBuf::from(#source);
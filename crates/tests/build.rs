@@ -38,7 +38,7 @@ fn main() {
             PROPENUMPROCW,
         },
         windows::win32::dxgi::{
-            DXGI_ADAPTER_FLAG, DXGI_FORMAT, DXGI_MODE_DESC, DXGI_MODE_SCALING,
+            DXGI_ADAPTER_DESC, DXGI_ADAPTER_FLAG, DXGI_FORMAT, DXGI_MODE_DESC, DXGI_MODE_SCALING,
             DXGI_MODE_SCANLINE_ORDER, DXGI_RATIONAL,IDXGIFactory7, CreateDXGIFactory1
         },
         windows::win32::display_devices::{
@@ -50,6 +50,7 @@ fn main() {
             WaitForSingleObject,
             WM_KEYUP,
             DXGI_ERROR_INVALID_CALL,
+            OVERLAPPED,
         },
         windows::win32::direct3d12::{
             D3D12_DEFAULT_BLEND_FACTOR_ALPHA
@@ -43,3 +43,79 @@ fn unsigned_enum() {
     assert!(weekend == 0x41.into());
     assert!(weekend.0 == 0x41);
 }
+
+#[test]
+fn flags() {
+    let mut days = AppointmentDaysOfWeek::default();
+    assert!(days.is_empty());
+
+    days.insert(AppointmentDaysOfWeek::Monday);
+    days |= AppointmentDaysOfWeek::Friday;
+    assert!(days.contains(AppointmentDaysOfWeek::Monday | AppointmentDaysOfWeek::Friday));
+    assert!(!days.contains(AppointmentDaysOfWeek::Monday | AppointmentDaysOfWeek::Sunday));
+    assert!(days.intersects(AppointmentDaysOfWeek::Monday | AppointmentDaysOfWeek::Sunday));
+
+    days.remove(AppointmentDaysOfWeek::Monday);
+    assert!(days == AppointmentDaysOfWeek::Friday);
+
+    days.toggle(AppointmentDaysOfWeek::Friday | AppointmentDaysOfWeek::Sunday);
+    assert!(days == AppointmentDaysOfWeek::Sunday);
+
+    days ^= AppointmentDaysOfWeek::Sunday;
+    assert!(days.is_empty());
+
+    assert!(AppointmentDaysOfWeek::all().0 == 0x7f);
+    assert!(AppointmentDaysOfWeek::all() & !AppointmentDaysOfWeek::Sunday == 0x7e.into());
+    assert!(AppointmentDaysOfWeek::Monday ^ AppointmentDaysOfWeek::all() == 0x7d.into());
+
+    let mut weekdays = AppointmentDaysOfWeek::all();
+    weekdays &= !(AppointmentDaysOfWeek::Saturday | AppointmentDaysOfWeek::Sunday);
+    assert!(weekdays.0 == 0x3e);
+
+    assert!(format!("{:?}", AppointmentDaysOfWeek::None) == "None");
+    assert!(
+        format!(
+            "{:?}",
+            AppointmentDaysOfWeek::Sunday | AppointmentDaysOfWeek::Saturday
+        ) == "Sunday | Saturday"
+    );
+    assert!(format!("{:?}", AppointmentDaysOfWeek(0x82)) == "Monday | 0x80");
+}
+
+#[test]
+fn names() {
+    assert!(AsyncStatus::Canceled.name() == Some("Canceled"));
+    assert!(AsyncStatus(7).name().is_none());
+
+    assert!(format!("{:?}", AsyncStatus::Completed) == "Completed");
+    assert!(format!("{}", AsyncStatus::Error) == "Error");
+    assert!(format!("{:?}", AsyncStatus(7)) == "7");
+
+    assert!("Started".parse::<AsyncStatus>().unwrap() == AsyncStatus::Started);
+    assert!("started".parse::<AsyncStatus>().is_err());
+    assert!(AsyncStatus::try_from(2).unwrap() == AsyncStatus::Canceled);
+    assert!(AsyncStatus::try_from(7).is_err());
+
+    assert!(AsyncStatus::VARIANTS.len() == 4);
+    assert!(AsyncStatus::VARIANTS.contains(&AsyncStatus::Canceled));
+
+    assert!(format!("{}", AppointmentDaysOfWeek::Monday) == "Monday");
+    assert!(
+        "Monday | Friday".parse::<AppointmentDaysOfWeek>().unwrap()
+            == AppointmentDaysOfWeek::Monday | AppointmentDaysOfWeek::Friday
+    );
+    assert!("Monday | Someday".parse::<AppointmentDaysOfWeek>().is_err());
+    assert!("Monday | 0x800".parse::<AppointmentDaysOfWeek>().unwrap().0 == 0x802);
+    assert!("0x".parse::<AppointmentDaysOfWeek>().is_err());
+
+    // Flags parse back from the way they are displayed.
+    for days in &[
+        AppointmentDaysOfWeek::None,
+        AppointmentDaysOfWeek::Monday | AppointmentDaysOfWeek::Friday,
+        AppointmentDaysOfWeek(0x82),
+        AppointmentDaysOfWeek(0x80),
+    ] {
+        assert!(days.to_string().parse::<AppointmentDaysOfWeek>().unwrap() == *days);
+    }
+    assert!(AppointmentDaysOfWeek::VARIANTS.len() == 8);
+}
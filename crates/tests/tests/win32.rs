@@ -8,15 +8,16 @@ use tests::{
     windows::win32::direct3d_hlsl::D3DCOMPILER_DLL,
     windows::win32::display_devices::RECT,
     windows::win32::dxgi::{
-        CreateDXGIFactory1, IDXGIFactory7, DXGI_ADAPTER_FLAG, DXGI_FORMAT, DXGI_MODE_DESC,
-        DXGI_MODE_SCALING, DXGI_MODE_SCANLINE_ORDER, DXGI_RATIONAL,
+        CreateDXGIFactory1, IDXGIFactory7, DXGI_ADAPTER_DESC, DXGI_ADAPTER_FLAG, DXGI_FORMAT,
+        DXGI_MODE_DESC, DXGI_MODE_SCALING, DXGI_MODE_SCANLINE_ORDER, DXGI_RATIONAL,
     },
     windows::win32::game_mode::HasExpandedResources,
     windows::win32::ldap::ldapsearch,
     windows::win32::security::ACCESS_MODE,
     windows::win32::structured_storage::{CreateStreamOnHGlobal, STREAM_SEEK},
     windows::win32::system_services::{
-        CreateEventW, SetEvent, WaitForSingleObject, DXGI_ERROR_INVALID_CALL, HANDLE, WM_KEYUP,
+        CreateEventW, SetEvent, WaitForSingleObject, DXGI_ERROR_INVALID_CALL, HANDLE, OVERLAPPED,
+        OVERLAPPED_0, OVERLAPPED_0_0, WM_KEYUP,
     },
     windows::win32::upnp::UIAnimationManager,
     windows::win32::upnp::UIAnimationTransitionLibrary,
@@ -47,6 +48,43 @@ fn unsigned_enum32() {
     assert!(both == 3.into());
 }
 
+#[test]
+fn nested_types() {
+    let mut overlapped = OVERLAPPED::default();
+
+    unsafe {
+        (*overlapped.anonymous.anonymous).offset = 1;
+        (*overlapped.anonymous.anonymous).offset_high = 2;
+
+        assert!(
+            *overlapped.anonymous.anonymous
+                == OVERLAPPED_0_0 {
+                    offset: 1,
+                    offset_high: 2
+                }
+        );
+    }
+}
+
+#[test]
+fn union() {
+    assert!(std::mem::size_of::<OVERLAPPED_0>() == 8);
+
+    let value = OVERLAPPED_0 {
+        anonymous: std::mem::ManuallyDrop::new(OVERLAPPED_0_0 {
+            offset: 1,
+            offset_high: 2,
+        }),
+    };
+
+    let copy = value.clone();
+
+    unsafe {
+        assert!(*copy.anonymous == *value.anonymous);
+        assert!(*OVERLAPPED_0::default().anonymous == OVERLAPPED_0_0::default());
+    }
+}
+
 #[test]
 fn rect() {
     let rect = RECT {
@@ -97,6 +135,7 @@ fn size64() {
     assert!(std::mem::size_of::<RECT>() == 16);
     assert!(std::mem::size_of::<DXGI_MODE_DESC>() == 28);
     assert!(std::mem::size_of::<CHOOSECOLORW>() == 72);
+    assert!(std::mem::size_of::<DXGI_ADAPTER_DESC>() == 304);
 }
 
 #[cfg(target_pointer_width = "32")]
@@ -107,6 +146,26 @@ fn size32() {
     assert!(std::mem::size_of::<RECT>() == 16);
     assert!(std::mem::size_of::<DXGI_MODE_DESC>() == 28);
     assert!(std::mem::size_of::<CHOOSECOLORW>() == 36);
+    assert!(std::mem::size_of::<DXGI_ADAPTER_DESC>() == 292);
+}
+
+#[test]
+fn fixed_size_array() {
+    let mut desc = DXGI_ADAPTER_DESC::default();
+    assert!(desc.description == [0; 128]);
+
+    let name: Vec<u16> = "adapter".encode_utf16().collect();
+    desc.description[..name.len()].copy_from_slice(&name);
+    desc.vendor_id = 123;
+
+    let clone = desc.clone();
+    assert!(clone.description[..name.len()] == name[..]);
+    assert!(clone.vendor_id == 123);
+    assert!(clone == desc);
+    assert!(format!("{:?}", clone).contains("description: [97, 100, 97,"));
+
+    desc.description[127] = 1;
+    assert!(clone != desc);
 }
 
 #[test]
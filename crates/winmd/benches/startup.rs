@@ -0,0 +1,49 @@
+//! Measures how long it takes to load the default metadata and answer a first query.
+//!
+//! The `eager` benchmark indexes the functions and constants of every namespace up front,
+//! as the reader did before namespaces were indexed lazily, and serves as the baseline
+//! for the `lazy` benchmark. Run with `cargo bench -p windows_winmd --bench startup`.
+
+extern crate windows_winmd as winmd;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+fn load() -> winmd::TypeReader {
+    winmd::TypeReader::builder().build().unwrap()
+}
+
+/// Answers a typical first query, which only needs a single namespace
+fn query(reader: &winmd::TypeReader) {
+    if black_box(reader.find_type_def(("Windows.Foundation", "Uri"))).is_some() {
+        black_box(reader.namespace_types("Windows.Foundation").count());
+    }
+}
+
+fn startup(c: &mut Criterion) {
+    let mut group = c.benchmark_group("startup");
+    group.sample_size(10);
+
+    group.bench_function("eager", |b| {
+        b.iter(|| {
+            let reader = load();
+
+            for namespace in reader.namespaces() {
+                black_box(reader.namespace_types(namespace).count());
+            }
+
+            query(&reader);
+        })
+    });
+
+    group.bench_function("lazy", |b| {
+        b.iter(|| {
+            let reader = load();
+            query(&reader);
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, startup);
+criterion_main!(benches);
@@ -16,6 +16,7 @@ pub fn type_code(args: TokenStream, input: TokenStream) -> TokenStream {
 
     let bits = &args[0];
     let name = &input.ident;
+    let generics = &input.generics;
     let mut variants = Vec::new();
     let mut decodes = Vec::new();
     let mut encodes = Vec::new();
@@ -32,7 +33,7 @@ pub fn type_code(args: TokenStream, input: TokenStream) -> TokenStream {
         }
 
         variants.push(quote!(
-            #name(#name),
+            #name(#name<'a>),
         ));
 
         decodes.push(quote!(
@@ -52,11 +53,11 @@ pub fn type_code(args: TokenStream, input: TokenStream) -> TokenStream {
 
     let output = quote!(
         #[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Eq, Ord)]
-        pub enum #name {
+        pub enum #name #generics {
             #variants
         }
-        impl Decode for #name {
-            fn decode(reader: &'static TypeReader, code: u32, file:u16) -> Self {
+        impl<'a> Decode<'a> for #name<'a> {
+            fn decode(reader: &'a TypeReader, code: u32, file:u16) -> Self {
                 let code = (code & ((1 << #bits) - 1), (code >> #bits) - 1);
                 match code.0 {
                     #decodes
@@ -64,7 +65,7 @@ pub fn type_code(args: TokenStream, input: TokenStream) -> TokenStream {
                 }
             }
         }
-        impl #name {
+        impl #name<'_> {
             pub fn encode(&self) -> u32 {
                 match self {
                     #encodes
@@ -0,0 +1,780 @@
+//! Compares two sets of Windows Metadata and reports how the Rust projection changes
+//!
+//! [`compare`] matches types by their full names and members by the names the projection
+//! gives them, and classifies each difference as breaking or not. A change is breaking when
+//! code written against the old projection may no longer compile or may behave differently,
+//! for example when a method is removed, a parameter's type changes or an interface's
+//! vtable is reordered. Adding a type, a method to a class or a value to an enum is not.
+
+use crate::*;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// What kind of difference a [`Change`] describes
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ChangeKind {
+    TypeAdded,
+    TypeRemoved,
+    CategoryChanged,
+    GuidChanged,
+    BaseChanged,
+    GenericsChanged,
+    InterfaceAdded,
+    InterfaceRemoved,
+    DefaultInterfaceChanged,
+    MethodAdded,
+    MethodRemoved,
+    MethodsReordered,
+    ReturnTypeChanged,
+    ParamsChanged,
+    ParamTypeChanged,
+    ParamDirectionChanged,
+    ParamRenamed,
+    FieldAdded,
+    FieldRemoved,
+    FieldTypeChanged,
+    FieldsReordered,
+    EnumValueAdded,
+    EnumValueRemoved,
+    EnumValueChanged,
+    ConstantAdded,
+    ConstantRemoved,
+    ConstantChanged,
+}
+
+impl ChangeKind {
+    /// A short lowercase description, such as `method removed`
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::TypeAdded => "type added",
+            Self::TypeRemoved => "type removed",
+            Self::CategoryChanged => "category changed",
+            Self::GuidChanged => "guid changed",
+            Self::BaseChanged => "base class changed",
+            Self::GenericsChanged => "generic parameters changed",
+            Self::InterfaceAdded => "interface added",
+            Self::InterfaceRemoved => "interface removed",
+            Self::DefaultInterfaceChanged => "default interface changed",
+            Self::MethodAdded => "method added",
+            Self::MethodRemoved => "method removed",
+            Self::MethodsReordered => "methods reordered",
+            Self::ReturnTypeChanged => "return type changed",
+            Self::ParamsChanged => "parameters changed",
+            Self::ParamTypeChanged => "parameter type changed",
+            Self::ParamDirectionChanged => "parameter direction changed",
+            Self::ParamRenamed => "parameter renamed",
+            Self::FieldAdded => "field added",
+            Self::FieldRemoved => "field removed",
+            Self::FieldTypeChanged => "field type changed",
+            Self::FieldsReordered => "fields reordered",
+            Self::EnumValueAdded => "enum value added",
+            Self::EnumValueRemoved => "enum value removed",
+            Self::EnumValueChanged => "enum value changed",
+            Self::ConstantAdded => "constant added",
+            Self::ConstantRemoved => "constant removed",
+            Self::ConstantChanged => "constant changed",
+        }
+    }
+}
+
+/// A single difference between two sets of metadata
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Change {
+    pub kind: ChangeKind,
+    /// The full name of the type, such as `Windows.Foundation.Uri`
+    pub type_name: String,
+    /// The method, field or interface that changed, if the change isn't to the type as a whole
+    pub member: Option<String>,
+    /// The old text of what changed, or the signature of a removed member
+    pub old: Option<String>,
+    /// The new text of what changed, or the signature of an added member
+    pub new: Option<String>,
+    /// Whether code written against the old projection may break
+    pub breaking: bool,
+}
+
+impl Change {
+    /// The type's full name followed by the member's name, if any
+    pub fn path(&self) -> String {
+        match &self.member {
+            Some(member) => format!("{}.{}", self.type_name, member),
+            None => self.type_name.clone(),
+        }
+    }
+}
+
+impl std::fmt::Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {} `{}`",
+            if self.breaking {
+                "breaking"
+            } else {
+                "compatible"
+            },
+            self.kind.description(),
+            self.path()
+        )?;
+
+        match (&self.old, &self.new) {
+            (Some(old), Some(new)) => write!(f, ": {} -> {}", old, new),
+            (Some(text), None) | (None, Some(text)) => write!(f, ": {}", text),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+/// Compares the types defined by two readers, returning the changes ordered by type name
+///
+/// Nested types are compared along with their enclosing types and are named after them,
+/// such as `Windows.Win32.Foo.Outer.Inner`.
+pub fn compare(old: &TypeReader, new: &TypeReader) -> Result<Vec<Change>, Error> {
+    let old = type_defs(old);
+    let new = type_defs(new);
+    let names: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+    let mut changes = Vec::new();
+
+    for name in names {
+        let mut diff = Diff {
+            type_name: name,
+            changes: &mut changes,
+        };
+
+        match (old.get(name), new.get(name)) {
+            (Some(old), Some(new)) => diff.types(&TypeInfo::new(old)?, &TypeInfo::new(new)?),
+            (Some(old), None) => {
+                let category = format!("{:?}", old.category());
+                diff.push(ChangeKind::TypeRemoved, None, Some(category), None, true)
+            }
+            (None, Some(new)) => {
+                let category = format!("{:?}", new.category());
+                diff.push(ChangeKind::TypeAdded, None, None, Some(category), false)
+            }
+            (None, None) => unreachable!(),
+        }
+    }
+
+    Ok(changes)
+}
+
+/// Every type definition, including nested types, by full name
+fn type_defs(reader: &TypeReader) -> BTreeMap<String, TypeDef<'_>> {
+    fn insert<'a>(types: &mut BTreeMap<String, TypeDef<'a>>, name: String, def: TypeDef<'a>) {
+        for nested in def.nested_types() {
+            insert(types, format!("{}.{}", name, nested.name().1), nested);
+        }
+
+        types.insert(name, def);
+    }
+
+    let mut types = BTreeMap::new();
+
+    for namespace in reader.namespaces() {
+        for ty in reader.namespace_types(namespace) {
+            if let Type::TypeDef(def) = ty {
+                insert(&mut types, full_name(def.name()), def);
+            }
+        }
+    }
+
+    types
+}
+
+/// Everything about a type that matters to the comparison, with types rendered as text
+struct TypeInfo {
+    category: TypeCategory,
+    guid: Option<String>,
+    extends: Option<String>,
+    generics: Vec<String>,
+    /// The required or implemented interfaces and whether each is the default
+    interfaces: Vec<(String, bool)>,
+    fields: Vec<FieldInfo>,
+    methods: Vec<MethodInfo>,
+}
+
+struct FieldInfo {
+    name: String,
+    ty: String,
+    value: Option<String>,
+}
+
+struct MethodInfo {
+    /// The name the projection uses, which accounts for `OverloadAttribute` and repeated names
+    key: String,
+    return_type: String,
+    params: Vec<ParamInfo>,
+}
+
+struct ParamInfo {
+    name: String,
+    ty: String,
+    direction: &'static str,
+}
+
+impl MethodInfo {
+    fn signature(&self) -> String {
+        let params: Vec<String> = self.params.iter().map(ParamInfo::text).collect();
+        format!("({}) -> {}", params.join(", "), self.return_type)
+    }
+}
+
+impl ParamInfo {
+    fn text(&self) -> String {
+        format!("{}{}: {}", self.direction, self.name, self.ty)
+    }
+}
+
+impl TypeInfo {
+    fn new(def: &TypeDef<'_>) -> Result<Self, Error> {
+        let category = def.category();
+        let generics: Vec<String> = def
+            .generics()
+            .map(|param| param.name().to_string())
+            .collect();
+
+        let extends = if category == TypeCategory::Class && def.reader.u32(def.row, 3) != 0 {
+            Some(type_def_or_ref_name(&def.extends(), &generics)?)
+        } else {
+            None
+        };
+
+        let mut interfaces = Vec::new();
+
+        for interface in def.interfaces() {
+            interfaces.push((
+                type_def_or_ref_name(&interface.interface(), &generics)?,
+                interface.is_default(),
+            ));
+        }
+
+        let mut fields = Vec::new();
+
+        for field in def.fields() {
+            fields.push(FieldInfo {
+                name: field.name().to_string(),
+                ty: param_name(&field.try_signature()?, &generics),
+                value: field
+                    .constant()
+                    .map(|constant| constant_text(&constant))
+                    .transpose()?,
+            });
+        }
+
+        let mut methods = Vec::new();
+        let mut counts = BTreeMap::new();
+
+        for method in def.methods() {
+            let sig = method.try_signature()?;
+            let mut params = Vec::new();
+
+            for param in method.params() {
+                // The return value's parameter, if present, has a sequence of zero.
+                if let Some(ty) = (param.sequence() as usize)
+                    .checked_sub(1)
+                    .and_then(|index| sig.params.get(index))
+                {
+                    params.push(ParamInfo {
+                        name: param.name().to_string(),
+                        ty: param_name(ty, &generics),
+                        direction: match (param.flags().input(), param.flags().output()) {
+                            (true, false) => "in ",
+                            (false, true) => "out ",
+                            (true, true) => "in out ",
+                            (false, false) => "",
+                        },
+                    });
+                }
+            }
+
+            let name = overload_name(&method).unwrap_or_else(|| method.name().to_string());
+            let count = counts.entry(name.clone()).or_insert(0);
+            *count += 1;
+
+            methods.push(MethodInfo {
+                key: if *count > 1 {
+                    format!("{}{}", name, count)
+                } else {
+                    name
+                },
+                return_type: param_name(&sig.return_type, &generics),
+                params,
+            });
+        }
+
+        Ok(Self {
+            category,
+            guid: def.guid().map(|guid| guid.to_string()),
+            extends,
+            generics,
+            interfaces,
+            fields,
+            methods,
+        })
+    }
+
+    fn field(&self, name: &str) -> Option<&FieldInfo> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    fn method(&self, key: &str) -> Option<&MethodInfo> {
+        self.methods.iter().find(|method| method.key == key)
+    }
+}
+
+/// The name given by the method's `OverloadAttribute`, if it has one
+fn overload_name(method: &MethodDef<'_>) -> Option<String> {
+    let attribute = method.attributes().find(|attribute| {
+        attribute.name() == ("Windows.Foundation.Metadata", "OverloadAttribute")
+    })?;
+
+    attribute
+        .try_args()
+        .ok()?
+        .into_iter()
+        .find_map(|(_, arg)| match arg {
+            AttributeArg::String(name) => Some(name),
+            _ => None,
+        })
+}
+
+/// Collects the changes to one type
+struct Diff<'a> {
+    type_name: &'a str,
+    changes: &'a mut Vec<Change>,
+}
+
+impl<'a> Diff<'a> {
+    fn push(
+        &mut self,
+        kind: ChangeKind,
+        member: Option<&str>,
+        old: Option<String>,
+        new: Option<String>,
+        breaking: bool,
+    ) {
+        self.changes.push(Change {
+            kind,
+            type_name: self.type_name.to_string(),
+            member: member.map(str::to_string),
+            old,
+            new,
+            breaking,
+        });
+    }
+
+    fn types(&mut self, old: &TypeInfo, new: &TypeInfo) {
+        // Members of types of different categories aren't comparable.
+        if old.category != new.category {
+            self.push(
+                ChangeKind::CategoryChanged,
+                None,
+                Some(format!("{:?}", old.category)),
+                Some(format!("{:?}", new.category)),
+                true,
+            );
+
+            return;
+        }
+
+        if old.guid != new.guid {
+            let text = |guid: &Option<String>| guid.clone().or_else(|| Some("none".to_string()));
+            self.push(
+                ChangeKind::GuidChanged,
+                None,
+                text(&old.guid),
+                text(&new.guid),
+                true,
+            );
+        }
+
+        if old.extends != new.extends {
+            self.push(
+                ChangeKind::BaseChanged,
+                None,
+                old.extends.clone(),
+                new.extends.clone(),
+                true,
+            );
+        }
+
+        // The names of generic parameters aren't part of the projection's API.
+        if old.generics.len() != new.generics.len() {
+            let text = |generics: &[String]| Some(format!("<{}>", generics.join(", ")));
+            self.push(
+                ChangeKind::GenericsChanged,
+                None,
+                text(&old.generics),
+                text(&new.generics),
+                true,
+            );
+        }
+
+        self.interfaces(old, new);
+        self.fields(old, new);
+        self.methods(old, new);
+    }
+
+    fn interfaces(&mut self, old: &TypeInfo, new: &TypeInfo) {
+        let contains = |info: &TypeInfo, name: &str| {
+            info.interfaces
+                .iter()
+                .any(|(interface, _)| interface == name)
+        };
+
+        for (name, _) in &old.interfaces {
+            if !contains(new, name) {
+                self.push(ChangeKind::InterfaceRemoved, Some(name), None, None, true);
+            }
+        }
+
+        for (name, _) in &new.interfaces {
+            if !contains(old, name) {
+                self.push(ChangeKind::InterfaceAdded, Some(name), None, None, false);
+            }
+        }
+
+        let default = |info: &TypeInfo| {
+            info.interfaces
+                .iter()
+                .find(|(_, is_default)| *is_default)
+                .map(|(name, _)| name.clone())
+        };
+
+        let (old_default, new_default) = (default(old), default(new));
+
+        if old_default != new_default {
+            self.push(
+                ChangeKind::DefaultInterfaceChanged,
+                None,
+                old_default,
+                new_default,
+                true,
+            );
+        }
+    }
+
+    fn fields(&mut self, old: &TypeInfo, new: &TypeInfo) {
+        let is_enum = old.category == TypeCategory::Enum;
+
+        // The projection's structs are constructed with every field so any change to the
+        // fields of a struct breaks, but other types' fields aren't projected as fields.
+        let is_struct = matches!(old.category, TypeCategory::Struct | TypeCategory::Attribute);
+
+        for old_field in &old.fields {
+            let name = Some(old_field.name.as_str());
+
+            match (new.field(&old_field.name), &old_field.value) {
+                (None, None) => {
+                    self.push(
+                        ChangeKind::FieldRemoved,
+                        name,
+                        Some(old_field.ty.clone()),
+                        None,
+                        true,
+                    );
+                }
+                (None, Some(value)) => {
+                    let kind = if is_enum {
+                        ChangeKind::EnumValueRemoved
+                    } else {
+                        ChangeKind::ConstantRemoved
+                    };
+
+                    self.push(kind, name, Some(value.clone()), None, true);
+                }
+                (Some(new_field), _) => {
+                    if old_field.ty != new_field.ty {
+                        self.push(
+                            ChangeKind::FieldTypeChanged,
+                            name,
+                            Some(old_field.ty.clone()),
+                            Some(new_field.ty.clone()),
+                            true,
+                        );
+                    } else if old_field.value != new_field.value {
+                        let kind = if is_enum {
+                            ChangeKind::EnumValueChanged
+                        } else {
+                            ChangeKind::ConstantChanged
+                        };
+
+                        self.push(
+                            kind,
+                            name,
+                            old_field.value.clone(),
+                            new_field.value.clone(),
+                            true,
+                        );
+                    }
+                }
+            }
+        }
+
+        for new_field in &new.fields {
+            if old.field(&new_field.name).is_some() {
+                continue;
+            }
+
+            let name = Some(new_field.name.as_str());
+
+            match &new_field.value {
+                Some(value) => {
+                    let kind = if is_enum {
+                        ChangeKind::EnumValueAdded
+                    } else {
+                        ChangeKind::ConstantAdded
+                    };
+
+                    self.push(kind, name, None, Some(value.clone()), false);
+                }
+                None => {
+                    self.push(
+                        ChangeKind::FieldAdded,
+                        name,
+                        None,
+                        Some(new_field.ty.clone()),
+                        is_struct,
+                    );
+                }
+            }
+        }
+
+        if is_struct {
+            let order = |from: &TypeInfo, to: &TypeInfo| -> Vec<String> {
+                from.fields
+                    .iter()
+                    .filter(|field| field.value.is_none() && to.field(&field.name).is_some())
+                    .map(|field| field.name.clone())
+                    .collect()
+            };
+
+            let (old_order, new_order) = (order(old, new), order(new, old));
+
+            if old_order != new_order {
+                self.push(
+                    ChangeKind::FieldsReordered,
+                    None,
+                    Some(old_order.join(", ")),
+                    Some(new_order.join(", ")),
+                    true,
+                );
+            }
+        }
+    }
+
+    fn methods(&mut self, old: &TypeInfo, new: &TypeInfo) {
+        // An interface's methods are its vtable, so adding or moving one breaks any
+        // implementation of the interface as well as the layout the projection expects.
+        let is_interface = old.category == TypeCategory::Interface;
+
+        for old_method in &old.methods {
+            let key = Some(old_method.key.as_str());
+
+            let new_method = match new.method(&old_method.key) {
+                Some(new_method) => new_method,
+                None => {
+                    self.push(
+                        ChangeKind::MethodRemoved,
+                        key,
+                        Some(old_method.signature()),
+                        None,
+                        true,
+                    );
+
+                    continue;
+                }
+            };
+
+            if old_method.return_type != new_method.return_type {
+                self.push(
+                    ChangeKind::ReturnTypeChanged,
+                    key,
+                    Some(old_method.return_type.clone()),
+                    Some(new_method.return_type.clone()),
+                    true,
+                );
+            }
+
+            if old_method.params.len() != new_method.params.len() {
+                self.push(
+                    ChangeKind::ParamsChanged,
+                    key,
+                    Some(old_method.signature()),
+                    Some(new_method.signature()),
+                    true,
+                );
+
+                continue;
+            }
+
+            for (old_param, new_param) in old_method.params.iter().zip(&new_method.params) {
+                if old_param.ty != new_param.ty {
+                    self.push(
+                        ChangeKind::ParamTypeChanged,
+                        key,
+                        Some(old_param.text()),
+                        Some(new_param.text()),
+                        true,
+                    );
+                } else if old_param.direction != new_param.direction {
+                    self.push(
+                        ChangeKind::ParamDirectionChanged,
+                        key,
+                        Some(old_param.text()),
+                        Some(new_param.text()),
+                        true,
+                    );
+                } else if old_param.name != new_param.name {
+                    // Only the snake case name appears in the projection, so a change of case
+                    // such as `Value` to `value` goes unnoticed.
+                    let breaking = to_snake(&old_param.name) != to_snake(&new_param.name);
+
+                    self.push(
+                        ChangeKind::ParamRenamed,
+                        key,
+                        Some(old_param.name.clone()),
+                        Some(new_param.name.clone()),
+                        breaking,
+                    );
+                }
+            }
+        }
+
+        for new_method in &new.methods {
+            if old.method(&new_method.key).is_none() {
+                self.push(
+                    ChangeKind::MethodAdded,
+                    Some(&new_method.key),
+                    None,
+                    Some(new_method.signature()),
+                    is_interface,
+                );
+            }
+        }
+
+        if is_interface {
+            let order = |from: &TypeInfo, to: &TypeInfo| -> Vec<String> {
+                from.methods
+                    .iter()
+                    .filter(|method| to.method(&method.key).is_some())
+                    .map(|method| method.key.clone())
+                    .collect()
+            };
+
+            let (old_order, new_order) = (order(old, new), order(new, old));
+
+            if old_order != new_order {
+                self.push(
+                    ChangeKind::MethodsReordered,
+                    None,
+                    Some(old_order.join(", ")),
+                    Some(new_order.join(", ")),
+                    true,
+                );
+            }
+        }
+    }
+}
+
+fn full_name((namespace, name): (&str, &str)) -> String {
+    if namespace.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", namespace, name)
+    }
+}
+
+fn type_def_or_ref_name(def: &TypeDefOrRef<'_>, generics: &[String]) -> Result<String, Error> {
+    match def {
+        TypeDefOrRef::TypeSpec(spec) => Ok(type_name(&spec.try_signature()?, generics)),
+        _ => Ok(full_name(def.name())),
+    }
+}
+
+fn param_name(param: &ParamSig<'_>, generics: &[String]) -> String {
+    if param.by_ref {
+        format!("ref {}", type_name(&param.ty, generics))
+    } else {
+        type_name(&param.ty, generics)
+    }
+}
+
+/// Renders a type using the names that Windows Metadata gives to the primitive types
+fn type_name(sig: &TypeSig<'_>, generics: &[String]) -> String {
+    match sig {
+        TypeSig::Void => "Void".to_string(),
+        TypeSig::Bool => "Boolean".to_string(),
+        TypeSig::Char => "Char".to_string(),
+        TypeSig::I8 => "Int8".to_string(),
+        TypeSig::U8 => "UInt8".to_string(),
+        TypeSig::I16 => "Int16".to_string(),
+        TypeSig::U16 => "UInt16".to_string(),
+        TypeSig::I32 => "Int32".to_string(),
+        TypeSig::U32 => "UInt32".to_string(),
+        TypeSig::I64 => "Int64".to_string(),
+        TypeSig::U64 => "UInt64".to_string(),
+        TypeSig::F32 => "Single".to_string(),
+        TypeSig::F64 => "Double".to_string(),
+        TypeSig::ISize => "IntPtr".to_string(),
+        TypeSig::USize => "UIntPtr".to_string(),
+        TypeSig::String => "String".to_string(),
+        TypeSig::Object => "Object".to_string(),
+        TypeSig::TypedByRef => "TypedReference".to_string(),
+        TypeSig::ValueType(def) | TypeSig::Class(def) => full_name(def.name()),
+        TypeSig::TypeGeneric(index) => generics
+            .get(*index as usize)
+            .cloned()
+            .unwrap_or_else(|| format!("!{}", index)),
+        TypeSig::MethodGeneric(index) => format!("!!{}", index),
+        TypeSig::GenericInst { def, args, .. } => {
+            let name = full_name(def.name());
+            let name = name.split('`').next().unwrap_or_default();
+            let args: Vec<String> = args.iter().map(|arg| type_name(arg, generics)).collect();
+            format!("{}<{}>", name, args.join(", "))
+        }
+        TypeSig::Ptr(_, ty) => format!("{}*", type_name(ty, generics)),
+        TypeSig::SZArray(_, ty) => format!("{}[]", type_name(ty, generics)),
+        TypeSig::Array(ty, shape) => format!(
+            "{}[{}]",
+            type_name(ty, generics),
+            ",".repeat(shape.rank.saturating_sub(1) as usize)
+        ),
+        TypeSig::FnPtr(method) => {
+            let params: Vec<String> = method
+                .params
+                .iter()
+                .map(|param| param_name(param, generics))
+                .collect();
+
+            format!(
+                "fn({}) -> {}",
+                params.join(", "),
+                param_name(&method.return_type, generics)
+            )
+        }
+    }
+}
+
+fn constant_text(constant: &Constant<'_>) -> Result<String, Error> {
+    let mut value = constant.value();
+
+    Ok(match constant.value_type() {
+        ElementType::Bool => (value.try_read_u8()? != 0).to_string(),
+        ElementType::Char => format!(
+            "{:?}",
+            std::char::from_u32(value.try_read_u16()? as u32).unwrap_or('\u{fffd}')
+        ),
+        ElementType::I8 => value.try_read_i8()?.to_string(),
+        ElementType::U8 => value.try_read_u8()?.to_string(),
+        ElementType::I16 => value.try_read_i16()?.to_string(),
+        ElementType::U16 => value.try_read_u16()?.to_string(),
+        ElementType::I32 => value.try_read_i32()?.to_string(),
+        ElementType::U32 => value.try_read_u32()?.to_string(),
+        ElementType::I64 => value.try_read_i64()?.to_string(),
+        ElementType::U64 => value.try_read_u64()?.to_string(),
+        ElementType::F32 => value.try_read_f32()?.to_string(),
+        ElementType::F64 => value.try_read_f64()?.to_string(),
+        ElementType::String => format!("{:?}", value.read_utf16()),
+        _ => "null".to_string(),
+    })
+}
@@ -0,0 +1,108 @@
+use crate::TableIndex;
+
+/// An error encountered while reading or writing Windows Metadata
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The file could not be read from or written to disk
+    Io { file: String, message: String },
+    /// The file's headers, streams or tables are malformed
+    InvalidFile { file: String, message: String },
+    /// A value stored in a specific table row is malformed
+    InvalidRow {
+        file: String,
+        table: TableIndex,
+        row: u32,
+        message: String,
+    },
+    /// The MIDL source could not be compiled
+    InvalidSource {
+        file: String,
+        /// The one-based line of the offending token
+        line: u32,
+        /// The one-based column of the offending token
+        column: u32,
+        message: String,
+    },
+}
+
+impl Error {
+    pub(crate) fn invalid_file<M: Into<String>>(file: &str, message: M) -> Self {
+        Self::InvalidFile {
+            file: file.to_string(),
+            message: message.into(),
+        }
+    }
+
+    pub(crate) fn invalid_row<M: Into<String>>(
+        file: &str,
+        table: TableIndex,
+        row: u32,
+        message: M,
+    ) -> Self {
+        Self::InvalidRow {
+            file: file.to_string(),
+            table,
+            row,
+            message: message.into(),
+        }
+    }
+
+    pub(crate) fn invalid_source<M: Into<String>>(
+        file: &str,
+        line: u32,
+        column: u32,
+        message: M,
+    ) -> Self {
+        Self::InvalidSource {
+            file: file.to_string(),
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// The name of the file in which the error was encountered
+    pub fn file(&self) -> &str {
+        match self {
+            Self::Io { file, .. }
+            | Self::InvalidFile { file, .. }
+            | Self::InvalidRow { file, .. }
+            | Self::InvalidSource { file, .. } => file,
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io { file, message } => {
+                write!(f, "Could not access file `{}`: {}", file, message)
+            }
+            Self::InvalidFile { file, message } => {
+                write!(f, "Invalid file `{}`: {}", file, message)
+            }
+            Self::InvalidRow {
+                file,
+                table,
+                row,
+                message,
+            } => write!(
+                f,
+                "Invalid file `{}`: {:?} row {}: {}",
+                file, table, row, message
+            ),
+            Self::InvalidSource {
+                file,
+                line,
+                column,
+                message,
+            } => write!(
+                f,
+                "Invalid source `{}`:{}:{}: {}",
+                file, line, column, message
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -1,4 +1,5 @@
 use crate::traits::*;
+use crate::{Error, Guid};
 
 #[derive(Default)]
 pub struct TableData {
@@ -11,14 +12,45 @@ pub struct TableData {
 /// A Windows Metadata File
 #[derive(Default)]
 pub struct File {
+    /// The name used to identify the file in errors
+    pub(crate) name: String,
     /// The files raw bytes
-    pub(crate) bytes: Vec<u8>,
-    /// The index of the strings data
-    pub(crate) strings: u32,
-    /// The index of the blobs data
-    pub(crate) blobs: u32,
+    pub(crate) bytes: Bytes,
+    /// The offset and size of the #Strings stream
+    pub(crate) strings: (u32, u32),
+    /// The offset and size of the #Blob stream
+    pub(crate) blobs: (u32, u32),
+    /// The offset and size of the #GUID stream
+    pub(crate) guids: (u32, u32),
+    /// The offset and size of the #US stream
+    pub(crate) user_strings: (u32, u32),
     /// The table data
-    pub(crate) tables: [TableData; 13],
+    pub(crate) tables: [TableData; 25],
+}
+
+/// The storage backing a [`File`]'s bytes
+///
+/// Either way the bytes stay at a fixed address for as long as the [`File`] is alive.
+pub(crate) enum Bytes {
+    Owned(Vec<u8>),
+    Mapped(memmap2::Mmap),
+}
+
+impl Default for Bytes {
+    fn default() -> Self {
+        Self::Owned(Vec::new())
+    }
+}
+
+impl std::ops::Deref for Bytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Self::Owned(bytes) => bytes,
+            Self::Mapped(map) => map,
+        }
+    }
 }
 
 /// A well-known index of data into the winmd tables array
@@ -38,6 +70,18 @@ pub enum TableIndex {
     TypeSpec,
     ImplMap,
     ModuleRef,
+    Property,
+    PropertyMap,
+    Event,
+    EventMap,
+    MethodSemantics,
+    NestedClass,
+    ClassLayout,
+    FieldLayout,
+    Module,
+    Assembly,
+    AssemblyRef,
+    GenericParamConstraint,
 }
 
 impl TableData {
@@ -71,7 +115,8 @@ impl TableData {
 
     fn set_data(&mut self, data: &mut u32) {
         if self.row_count != 0 {
-            let next = *data + self.row_count * self.row_size;
+            // Saturate so that a malformed row count is caught by the final bounds check.
+            let next = data.saturating_add(self.row_count.saturating_mul(self.row_size));
             self.data = *data;
             *data = next;
         }
@@ -79,76 +124,103 @@ impl TableData {
 }
 
 impl File {
-    pub(crate) fn from_bytes(bytes: Vec<u8>) -> Self {
+    /// Parses a Windows Metadata file from its raw bytes, using `name` to identify the file in errors
+    pub fn try_from_bytes<S: Into<String>>(name: S, bytes: Vec<u8>) -> Result<Self, Error> {
+        Self::try_from_storage(name.into(), Bytes::Owned(bytes))
+    }
+
+    fn try_from_storage(name: String, bytes: Bytes) -> Result<Self, Error> {
         let mut file = Self {
+            name,
             bytes,
             ..Default::default()
         };
 
-        let dos = file.bytes.view_as::<ImageDosHeader>(0);
+        let checked = Checked {
+            name: &file.name,
+            bytes: &file.bytes,
+        };
+
+        let dos = checked.view_as::<ImageDosHeader>(0)?;
 
         if dos.signature != IMAGE_DOS_SIGNATURE {
-            panic!("Invalid file: file does not appear to be a winmd file");
+            return Err(checked.error("file does not appear to be a winmd file"));
         }
 
-        let pe = file.bytes.view_as::<ImageNtHeader>(dos.lfanew as u32);
+        let pe = checked.view_as::<ImageNtHeader>(dos.lfanew as u32)?;
 
         let (com_virtual_address, sections) = match pe.optional_header.magic {
             MAGIC_PE32 => (
                 pe.optional_header.data_directory[IMAGE_DIRECTORY_ENTRY_COM_DESCRIPTOR as usize]
                     .virtual_address,
-                file.bytes.view_as_slice_of::<ImageSectionHeader>(
-                    dos.lfanew as u32 + sizeof::<ImageNtHeader>(),
+                checked.view_as_slice_of::<ImageSectionHeader>(
+                    (dos.lfanew as u32).saturating_add(sizeof::<ImageNtHeader>()),
                     pe.file_header.number_of_sections as u32,
-                ),
+                )?,
             ),
             MAGIC_PE32PLUS => (
-                file.bytes
-                    .view_as::<ImageNtHeaderPlus>(dos.lfanew as u32)
+                checked
+                    .view_as::<ImageNtHeaderPlus>(dos.lfanew as u32)?
                     .optional_header
                     .data_directory[IMAGE_DIRECTORY_ENTRY_COM_DESCRIPTOR as usize]
                     .virtual_address,
-                file.bytes.view_as_slice_of::<ImageSectionHeader>(
-                    dos.lfanew as u32 + sizeof::<ImageNtHeaderPlus>(),
+                checked.view_as_slice_of::<ImageSectionHeader>(
+                    (dos.lfanew as u32).saturating_add(sizeof::<ImageNtHeaderPlus>()),
                     pe.file_header.number_of_sections as u32,
-                ),
+                )?,
             ),
-            _ => panic!("Invalid file: invalid magic"),
+            _ => return Err(checked.error("invalid magic")),
         };
 
-        let cli = file.bytes.view_as::<ImageCorHeader>(offset_from_rva(
-            section_from_rva(sections, com_virtual_address),
+        let cli = checked.view_as::<ImageCorHeader>(offset_from_rva(
+            section_from_rva(&checked, sections, com_virtual_address)?,
             com_virtual_address,
-        ));
+        ))?;
 
         if cli.cb != sizeof::<ImageCorHeader>() {
-            panic!("Invalid file: invalid ImageCorHeader");
+            return Err(checked.error("invalid ImageCorHeader"));
         }
 
         let cli_offset = offset_from_rva(
-            section_from_rva(sections, cli.meta_data.virtual_address),
+            section_from_rva(&checked, sections, cli.meta_data.virtual_address)?,
             cli.meta_data.virtual_address,
         );
 
-        if file.bytes.copy_as::<u32>(cli_offset) != STORAGE_MAGIC_SIG {
-            panic!("Invalid file: invalid STORAGE_MAGIC_SIG");
+        if checked.copy_as::<u32>(cli_offset)? != STORAGE_MAGIC_SIG {
+            return Err(checked.error("invalid STORAGE_MAGIC_SIG"));
+        }
+
+        let version_length = checked.copy_as::<u32>(cli_offset + 12)?;
+
+        if version_length > checked.len() {
+            return Err(checked.error("invalid metadata version length"));
         }
 
-        let version_length = file.bytes.copy_as::<u32>(cli_offset + 12);
         let mut view = cli_offset + version_length + 20;
         let mut tables_data: (u32, u32) = (0, 0);
+        let mut strings = None;
+        let mut blobs = None;
+
+        for _ in 0..checked.copy_as::<u16>(cli_offset + version_length + 18)? {
+            let stream_offset = checked.copy_as::<u32>(view)?;
+            let stream_size = checked.copy_as::<u32>(view + 4)?;
+            let stream_name = checked.view_as_str(view + 8)?;
+
+            let stream_start = cli_offset as u64 + stream_offset as u64;
+
+            if stream_start + stream_size as u64 > checked.len() as u64 {
+                return Err(checked.error("stream extends beyond the end of the file"));
+            }
+
+            let stream_start = stream_start as u32;
 
-        for _ in 0..file.bytes.copy_as::<u16>(cli_offset + version_length + 18) {
-            let stream_offset = file.bytes.copy_as::<u32>(view);
-            let stream_size = file.bytes.copy_as::<u32>(view + 4);
-            let stream_name = file.bytes.view_as_str(view + 8);
             match stream_name {
-                b"#Strings" => file.strings = cli_offset + stream_offset,
-                b"#Blob" => file.blobs = cli_offset + stream_offset,
-                b"#~" => tables_data = (cli_offset + stream_offset, stream_size),
-                b"#GUID" => {}
-                b"#US" => {}
-                _ => panic!("Invalid file: invalid stream name"),
+                b"#Strings" => strings = Some((stream_start, stream_size)),
+                b"#Blob" => blobs = Some((stream_start, stream_size)),
+                b"#~" => tables_data = (stream_start, stream_size),
+                b"#GUID" => file.guids = (stream_start, stream_size),
+                b"#US" => file.user_strings = (stream_start, stream_size),
+                _ => return Err(checked.error("invalid stream name")),
             }
             let mut padding = 4 - stream_name.len() % 4;
             if padding == 0 {
@@ -157,39 +229,34 @@ impl File {
             view += (8 + stream_name.len() + padding) as u32;
         }
 
-        let heap_sizes = file.bytes.copy_as::<u8>(tables_data.0 + 6);
+        if tables_data.1 == 0 {
+            return Err(checked.error("missing #~ stream"));
+        }
+
+        file.strings = strings.ok_or_else(|| checked.error("missing #Strings stream"))?;
+        file.blobs = blobs.ok_or_else(|| checked.error("missing #Blob stream"))?;
+
+        let heap_sizes = checked.copy_as::<u8>(tables_data.0 + 6)?;
         let string_index_size = if (heap_sizes & 1) == 1 { 4 } else { 2 };
         let guid_index_size = if (heap_sizes >> 1 & 1) == 1 { 4 } else { 2 };
         let blob_index_size = if (heap_sizes >> 2 & 1) == 1 { 4 } else { 2 };
-        let valid_bits = file.bytes.copy_as::<u64>(tables_data.0 + 8);
+        let valid_bits = checked.copy_as::<u64>(tables_data.0 + 8)?;
         view = tables_data.0 + 24;
 
         // These tables are unused by WinRT, but needed temporarily to calculate sizes and offsets for subsequent tables.
         let unused_empty = TableData::default();
-        let mut unused_assembly = TableData::default();
         let mut unused_assembly_os = TableData::default();
         let mut unused_assembly_processor = TableData::default();
-        let mut unused_assembly_ref = TableData::default();
         let mut unused_assembly_ref_os = TableData::default();
         let mut unused_assembly_ref_processor = TableData::default();
-        let mut unused_class_layout = TableData::default();
         let mut unused_decl_security = TableData::default();
-        let mut unused_event = TableData::default();
-        let mut unused_event_map = TableData::default();
         let mut unused_exported_type = TableData::default();
-        let mut unused_field_layout = TableData::default();
         let mut unused_field_marshal = TableData::default();
         let mut unused_field_rva = TableData::default();
         let mut unused_file = TableData::default();
-        let mut unused_generic_param_constraint = TableData::default();
         let mut unused_manifest_resource = TableData::default();
         let mut unused_method_impl = TableData::default();
-        let mut unused_method_semantics = TableData::default();
         let mut unused_method_spec = TableData::default();
-        let mut unused_module = TableData::default();
-        let mut unused_nested_class = TableData::default();
-        let mut unused_property = TableData::default();
-        let mut unused_property_map = TableData::default();
         let mut unused_standalone_sig = TableData::default();
 
         for i in 0..64 {
@@ -197,11 +264,11 @@ impl File {
                 continue;
             }
 
-            let row_count = file.bytes.copy_as::<u32>(view);
+            let row_count = checked.copy_as::<u32>(view)?;
             view += 4;
 
             match i {
-                0x00 => unused_module.row_count = row_count,
+                0x00 => file.tables[TableIndex::Module as usize].row_count = row_count,
                 0x01 => file.tables[TableIndex::TypeRef as usize].row_count = row_count,
                 0x02 => file.tables[TableIndex::TypeDef as usize].row_count = row_count,
                 0x04 => file.tables[TableIndex::Field as usize].row_count = row_count,
@@ -213,33 +280,35 @@ impl File {
                 0x0c => file.tables[TableIndex::CustomAttribute as usize].row_count = row_count,
                 0x0d => unused_field_marshal.row_count = row_count,
                 0x0e => unused_decl_security.row_count = row_count,
-                0x0f => unused_class_layout.row_count = row_count,
-                0x10 => unused_field_layout.row_count = row_count,
+                0x0f => file.tables[TableIndex::ClassLayout as usize].row_count = row_count,
+                0x10 => file.tables[TableIndex::FieldLayout as usize].row_count = row_count,
                 0x11 => unused_standalone_sig.row_count = row_count,
-                0x12 => unused_event_map.row_count = row_count,
-                0x14 => unused_event.row_count = row_count,
-                0x15 => unused_property_map.row_count = row_count,
-                0x17 => unused_property.row_count = row_count,
-                0x18 => unused_method_semantics.row_count = row_count,
+                0x12 => file.tables[TableIndex::EventMap as usize].row_count = row_count,
+                0x14 => file.tables[TableIndex::Event as usize].row_count = row_count,
+                0x15 => file.tables[TableIndex::PropertyMap as usize].row_count = row_count,
+                0x17 => file.tables[TableIndex::Property as usize].row_count = row_count,
+                0x18 => file.tables[TableIndex::MethodSemantics as usize].row_count = row_count,
                 0x19 => unused_method_impl.row_count = row_count,
                 0x1a => file.tables[TableIndex::ModuleRef as usize].row_count = row_count,
                 0x1b => file.tables[TableIndex::TypeSpec as usize].row_count = row_count,
                 0x1c => file.tables[TableIndex::ImplMap as usize].row_count = row_count,
                 0x1d => unused_field_rva.row_count = row_count,
-                0x20 => unused_assembly.row_count = row_count,
+                0x20 => file.tables[TableIndex::Assembly as usize].row_count = row_count,
                 0x21 => unused_assembly_processor.row_count = row_count,
                 0x22 => unused_assembly_os.row_count = row_count,
-                0x23 => unused_assembly_ref.row_count = row_count,
+                0x23 => file.tables[TableIndex::AssemblyRef as usize].row_count = row_count,
                 0x24 => unused_assembly_ref_processor.row_count = row_count,
                 0x25 => unused_assembly_ref_os.row_count = row_count,
                 0x26 => unused_file.row_count = row_count,
                 0x27 => unused_exported_type.row_count = row_count,
                 0x28 => unused_manifest_resource.row_count = row_count,
-                0x29 => unused_nested_class.row_count = row_count,
+                0x29 => file.tables[TableIndex::NestedClass as usize].row_count = row_count,
                 0x2a => file.tables[TableIndex::GenericParam as usize].row_count = row_count,
                 0x2b => unused_method_spec.row_count = row_count,
-                0x2c => unused_generic_param_constraint.row_count = row_count,
-                _ => unreachable!(),
+                0x2c => {
+                    file.tables[TableIndex::GenericParamConstraint as usize].row_count = row_count
+                }
+                _ => return Err(checked.error(format!("unknown table 0x{:x}", i))),
             };
         }
 
@@ -252,7 +321,7 @@ impl File {
         let has_constant = composite_index_size(&[
             &file.tables[TableIndex::Field as usize],
             &file.tables[TableIndex::Param as usize],
-            &unused_property,
+            &file.tables[TableIndex::Property as usize],
         ]);
 
         let has_custom_attribute = composite_index_size(&[
@@ -263,19 +332,19 @@ impl File {
             &file.tables[TableIndex::Param as usize],
             &file.tables[TableIndex::InterfaceImpl as usize],
             &file.tables[TableIndex::MemberRef as usize],
-            &unused_module,
-            &unused_property,
-            &unused_event,
+            &file.tables[TableIndex::Module as usize],
+            &file.tables[TableIndex::Property as usize],
+            &file.tables[TableIndex::Event as usize],
             &unused_standalone_sig,
             &file.tables[TableIndex::ModuleRef as usize],
             &file.tables[TableIndex::TypeSpec as usize],
-            &unused_assembly,
-            &unused_assembly_ref,
+            &file.tables[TableIndex::Assembly as usize],
+            &file.tables[TableIndex::AssemblyRef as usize],
             &unused_file,
             &unused_exported_type,
             &unused_manifest_resource,
             &file.tables[TableIndex::GenericParam as usize],
-            &unused_generic_param_constraint,
+            &file.tables[TableIndex::GenericParamConstraint as usize],
             &unused_method_spec,
         ]);
 
@@ -287,7 +356,7 @@ impl File {
         let has_decl_security = composite_index_size(&[
             &file.tables[TableIndex::TypeDef as usize],
             &file.tables[TableIndex::MethodDef as usize],
-            &unused_assembly,
+            &file.tables[TableIndex::Assembly as usize],
         ]);
 
         let member_ref_parent = composite_index_size(&[
@@ -298,7 +367,10 @@ impl File {
             &file.tables[TableIndex::TypeSpec as usize],
         ]);
 
-        let has_semantics = composite_index_size(&[&unused_event, &unused_property]);
+        let has_semantics = composite_index_size(&[
+            &file.tables[TableIndex::Event as usize],
+            &file.tables[TableIndex::Property as usize],
+        ]);
 
         let method_def_or_ref = composite_index_size(&[
             &file.tables[TableIndex::MethodDef as usize],
@@ -310,8 +382,11 @@ impl File {
             &file.tables[TableIndex::MethodDef as usize],
         ]);
 
-        let implementation =
-            composite_index_size(&[&unused_file, &unused_assembly_ref, &unused_exported_type]);
+        let implementation = composite_index_size(&[
+            &unused_file,
+            &file.tables[TableIndex::AssemblyRef as usize],
+            &unused_exported_type,
+        ]);
 
         let custom_attribute_type = composite_index_size(&[
             &file.tables[TableIndex::MethodDef as usize],
@@ -322,9 +397,9 @@ impl File {
         ]);
 
         let resolution_scope = composite_index_size(&[
-            &unused_module,
+            &file.tables[TableIndex::Module as usize],
             &file.tables[TableIndex::ModuleRef as usize],
-            &unused_assembly_ref,
+            &file.tables[TableIndex::AssemblyRef as usize],
             &file.tables[TableIndex::TypeRef as usize],
         ]);
 
@@ -333,7 +408,7 @@ impl File {
             &file.tables[TableIndex::MethodDef as usize],
         ]);
 
-        unused_assembly.set_columns(
+        file.tables[TableIndex::Assembly as usize].set_columns(
             4,
             8,
             4,
@@ -343,7 +418,7 @@ impl File {
         );
         unused_assembly_os.set_columns(4, 4, 4, 0, 0, 0);
         unused_assembly_processor.set_columns(4, 0, 0, 0, 0, 0);
-        unused_assembly_ref.set_columns(
+        file.tables[TableIndex::AssemblyRef as usize].set_columns(
             8,
             4,
             blob_index_size,
@@ -351,9 +426,23 @@ impl File {
             string_index_size,
             blob_index_size,
         );
-        unused_assembly_ref_os.set_columns(4, 4, 4, unused_assembly_ref.index_size(), 0, 0);
-        unused_assembly_ref_processor.set_columns(4, unused_assembly_ref.index_size(), 0, 0, 0, 0);
-        unused_class_layout.set_columns(
+        unused_assembly_ref_os.set_columns(
+            4,
+            4,
+            4,
+            file.tables[TableIndex::AssemblyRef as usize].index_size(),
+            0,
+            0,
+        );
+        unused_assembly_ref_processor.set_columns(
+            4,
+            file.tables[TableIndex::AssemblyRef as usize].index_size(),
+            0,
+            0,
+            0,
+            0,
+        );
+        file.tables[TableIndex::ClassLayout as usize].set_columns(
             2,
             4,
             file.tables[TableIndex::TypeDef as usize].index_size(),
@@ -378,15 +467,22 @@ impl File {
             0,
         );
         unused_decl_security.set_columns(2, has_decl_security, blob_index_size, 0, 0, 0);
-        unused_event_map.set_columns(
+        file.tables[TableIndex::EventMap as usize].set_columns(
             file.tables[TableIndex::TypeDef as usize].index_size(),
-            unused_event.index_size(),
+            file.tables[TableIndex::Event as usize].index_size(),
+            0,
+            0,
+            0,
             0,
+        );
+        file.tables[TableIndex::Event as usize].set_columns(
+            2,
+            string_index_size,
+            type_def_or_ref,
             0,
             0,
             0,
         );
-        unused_event.set_columns(2, string_index_size, type_def_or_ref, 0, 0, 0);
         unused_exported_type.set_columns(
             4,
             4,
@@ -403,7 +499,7 @@ impl File {
             0,
             0,
         );
-        unused_field_layout.set_columns(
+        file.tables[TableIndex::FieldLayout as usize].set_columns(
             4,
             file.tables[TableIndex::Field as usize].index_size(),
             0,
@@ -429,7 +525,7 @@ impl File {
             0,
             0,
         );
-        unused_generic_param_constraint.set_columns(
+        file.tables[TableIndex::GenericParamConstraint as usize].set_columns(
             file.tables[TableIndex::GenericParam as usize].index_size(),
             type_def_or_ref,
             0,
@@ -478,7 +574,7 @@ impl File {
             0,
             0,
         );
-        unused_method_semantics.set_columns(
+        file.tables[TableIndex::MethodSemantics as usize].set_columns(
             2,
             file.tables[TableIndex::MethodDef as usize].index_size(),
             has_semantics,
@@ -487,7 +583,7 @@ impl File {
             0,
         );
         unused_method_spec.set_columns(method_def_or_ref, blob_index_size, 0, 0, 0, 0);
-        unused_module.set_columns(
+        file.tables[TableIndex::Module as usize].set_columns(
             2,
             string_index_size,
             guid_index_size,
@@ -496,7 +592,7 @@ impl File {
             0,
         );
         file.tables[TableIndex::ModuleRef as usize].set_columns(string_index_size, 0, 0, 0, 0, 0);
-        unused_nested_class.set_columns(
+        file.tables[TableIndex::NestedClass as usize].set_columns(
             file.tables[TableIndex::TypeDef as usize].index_size(),
             file.tables[TableIndex::TypeDef as usize].index_size(),
             0,
@@ -505,10 +601,17 @@ impl File {
             0,
         );
         file.tables[TableIndex::Param as usize].set_columns(2, 2, string_index_size, 0, 0, 0);
-        unused_property.set_columns(2, string_index_size, blob_index_size, 0, 0, 0);
-        unused_property_map.set_columns(
+        file.tables[TableIndex::Property as usize].set_columns(
+            2,
+            string_index_size,
+            blob_index_size,
+            0,
+            0,
+            0,
+        );
+        file.tables[TableIndex::PropertyMap as usize].set_columns(
             file.tables[TableIndex::TypeDef as usize].index_size(),
-            unused_property.index_size(),
+            file.tables[TableIndex::Property as usize].index_size(),
             0,
             0,
             0,
@@ -533,7 +636,7 @@ impl File {
         );
         file.tables[TableIndex::TypeSpec as usize].set_columns(blob_index_size, 0, 0, 0, 0, 0);
 
-        unused_module.set_data(&mut view);
+        file.tables[TableIndex::Module as usize].set_data(&mut view);
         file.tables[TableIndex::TypeRef as usize].set_data(&mut view);
         file.tables[TableIndex::TypeDef as usize].set_data(&mut view);
         file.tables[TableIndex::Field as usize].set_data(&mut view);
@@ -545,39 +648,175 @@ impl File {
         file.tables[TableIndex::CustomAttribute as usize].set_data(&mut view);
         unused_field_marshal.set_data(&mut view);
         unused_decl_security.set_data(&mut view);
-        unused_class_layout.set_data(&mut view);
-        unused_field_layout.set_data(&mut view);
+        file.tables[TableIndex::ClassLayout as usize].set_data(&mut view);
+        file.tables[TableIndex::FieldLayout as usize].set_data(&mut view);
         unused_standalone_sig.set_data(&mut view);
-        unused_event_map.set_data(&mut view);
-        unused_event.set_data(&mut view);
-        unused_property_map.set_data(&mut view);
-        unused_property.set_data(&mut view);
-        unused_method_semantics.set_data(&mut view);
+        file.tables[TableIndex::EventMap as usize].set_data(&mut view);
+        file.tables[TableIndex::Event as usize].set_data(&mut view);
+        file.tables[TableIndex::PropertyMap as usize].set_data(&mut view);
+        file.tables[TableIndex::Property as usize].set_data(&mut view);
+        file.tables[TableIndex::MethodSemantics as usize].set_data(&mut view);
         unused_method_impl.set_data(&mut view);
         file.tables[TableIndex::ModuleRef as usize].set_data(&mut view);
         file.tables[TableIndex::TypeSpec as usize].set_data(&mut view);
         file.tables[TableIndex::ImplMap as usize].set_data(&mut view);
         unused_field_rva.set_data(&mut view);
-        unused_assembly.set_data(&mut view);
+        file.tables[TableIndex::Assembly as usize].set_data(&mut view);
         unused_assembly_processor.set_data(&mut view);
         unused_assembly_os.set_data(&mut view);
-        unused_assembly_ref.set_data(&mut view);
+        file.tables[TableIndex::AssemblyRef as usize].set_data(&mut view);
         unused_assembly_ref_processor.set_data(&mut view);
         unused_assembly_ref_os.set_data(&mut view);
         unused_file.set_data(&mut view);
         unused_exported_type.set_data(&mut view);
         unused_manifest_resource.set_data(&mut view);
-        unused_nested_class.set_data(&mut view);
+        file.tables[TableIndex::NestedClass as usize].set_data(&mut view);
         file.tables[TableIndex::GenericParam as usize].set_data(&mut view);
+        unused_method_spec.set_data(&mut view);
+        file.tables[TableIndex::GenericParamConstraint as usize].set_data(&mut view);
+
+        if view > checked.len() {
+            return Err(checked.error("tables extend beyond the end of the file"));
+        }
+
+        Ok(file)
+    }
+
+    /// Parses a Windows Metadata file from its raw bytes
+    ///
+    /// # Panics
+    ///
+    /// Panics if the file is malformed. Use [`File::try_from_bytes`] to handle errors instead.
+    pub fn from_bytes<S: Into<String>>(name: S, bytes: Vec<u8>) -> Self {
+        Self::try_from_bytes(name, bytes).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Reads and parses the Windows Metadata file at the given path
+    pub fn try_new<P: AsRef<std::path::Path>>(filename: P) -> Result<Self, Error> {
+        let name = filename.as_ref().to_string_lossy().into_owned();
+
+        match std::fs::read(filename.as_ref()) {
+            Ok(bytes) => Self::try_from_bytes(name, bytes),
+            Err(error) => Err(Error::Io {
+                file: name,
+                message: error.to_string(),
+            }),
+        }
+    }
+
+    /// Memory-maps and parses the Windows Metadata file at the given path
+    ///
+    /// The file must not be modified while it is mapped.
+    pub fn try_map<P: AsRef<std::path::Path>>(filename: P) -> Result<Self, Error> {
+        let name = filename.as_ref().to_string_lossy().into_owned();
+
+        let io_error = |error: std::io::Error| Error::Io {
+            file: name.clone(),
+            message: error.to_string(),
+        };
+
+        let file = std::fs::File::open(filename.as_ref()).map_err(io_error)?;
+
+        // This is safe as long as the file is not modified while it is mapped, which
+        // is the same assumption the build makes about its inputs.
+        let map = unsafe { memmap2::Mmap::map(&file) }.map_err(io_error)?;
+
+        Self::try_from_storage(name, Bytes::Mapped(map))
+    }
+
+    /// The name used to identify the file, typically its path
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Reads the GUID at the given one-based index into the #GUID heap
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds. Use [`File::try_guid`] to handle errors instead.
+    pub fn guid(&self, index: u32) -> Guid {
+        self.try_guid(index)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Reads the GUID at the given one-based index into the #GUID heap
+    pub fn try_guid(&self, index: u32) -> Result<Guid, Error> {
+        let (start, size) = self.guids;
+
+        if index == 0 || index as u64 * 16 > size as u64 {
+            return Err(Error::invalid_file(
+                &self.name,
+                format!("GUID index {} is out of bounds", index),
+            ));
+        }
+
+        let offset = (start + (index - 1) * 16) as usize;
+        let mut bytes = [0; 16];
+        bytes.copy_from_slice(&self.bytes[offset..offset + 16]);
+        Ok(Guid::from_bytes(bytes))
+    }
 
-        file
+    /// Reads the string at the given offset into the #US heap
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string is malformed. Use [`File::try_user_string`] to handle errors instead.
+    pub fn user_string(&self, offset: u32) -> String {
+        self.try_user_string(offset)
+            .unwrap_or_else(|error| panic!("{}", error))
     }
 
-    pub(crate) fn new<P: AsRef<std::path::Path>>(filename: P) -> Self {
-        let bytes = std::fs::read(filename.as_ref())
-            .unwrap_or_else(|e| panic!("Could not read file {:?}: {:?}", filename.as_ref(), e));
+    /// Reads the string at the given offset into the #US heap
+    pub fn try_user_string(&self, offset: u32) -> Result<String, Error> {
+        let error = |message: &str| {
+            Error::invalid_file(
+                &self.name,
+                format!("user string at offset {} {}", offset, message),
+            )
+        };
+
+        let (start, size) = self.user_strings;
+
+        if offset >= size {
+            return Err(error("is out of bounds"));
+        }
+
+        let heap = &self.bytes[start as usize..(start + size) as usize];
+        let bytes = &heap[offset as usize..];
+
+        // The length is compressed in the same way as a blob's length.
+        let (len, len_bytes) = if bytes[0] & 0x80 == 0 {
+            (bytes[0] as usize, 1)
+        } else if bytes[0] & 0xC0 == 0x80 && bytes.len() >= 2 {
+            ((((bytes[0] & 0x3F) as usize) << 8) | bytes[1] as usize, 2)
+        } else if bytes[0] & 0xE0 == 0xC0 && bytes.len() >= 4 {
+            (
+                (((bytes[0] & 0x1F) as usize) << 24)
+                    | (bytes[1] as usize) << 16
+                    | (bytes[2] as usize) << 8
+                    | bytes[3] as usize,
+                4,
+            )
+        } else {
+            return Err(error("has an invalid length"));
+        };
+
+        let bytes = bytes
+            .get(len_bytes..len_bytes + len)
+            .ok_or_else(|| error("extends beyond the end of the heap"))?;
+
+        // The UTF-16 characters are followed by a single byte flagging special characters.
+        let chars: Vec<u16> = bytes[..len.saturating_sub(1) & !1]
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .collect();
+
+        String::from_utf16(&chars).map_err(|_| error("is not valid UTF-16"))
+    }
 
-        Self::from_bytes(bytes)
+    /// The number of rows in the given table
+    pub fn row_count(&self, table: TableIndex) -> u32 {
+        self.tables[table as usize].row_count
     }
 
     pub(crate) fn type_def_table(&self) -> &TableData {
@@ -585,17 +824,90 @@ impl File {
     }
 }
 
-fn section_from_rva(sections: &[ImageSectionHeader], rva: u32) -> &ImageSectionHeader {
+fn section_from_rva<'a>(
+    checked: &Checked,
+    sections: &'a [ImageSectionHeader],
+    rva: u32,
+) -> Result<&'a ImageSectionHeader, Error> {
     sections
         .iter()
         .find(|&s| {
-            rva >= s.virtual_address && rva < s.virtual_address + s.physical_address_or_virtual_size
+            rva >= s.virtual_address
+                && (rva - s.virtual_address) < s.physical_address_or_virtual_size
         })
-        .expect("Invalid file")
+        .ok_or_else(|| checked.error(format!("no section contains rva 0x{:x}", rva)))
 }
 
 fn offset_from_rva(section: &ImageSectionHeader, rva: u32) -> u32 {
-    rva - section.virtual_address + section.pointer_to_raw_data
+    (rva - section.virtual_address).saturating_add(section.pointer_to_raw_data)
+}
+
+/// A bounds-checked view of a file's bytes used while parsing its headers
+struct Checked<'a> {
+    name: &'a str,
+    bytes: &'a [u8],
+}
+
+impl<'a> Checked<'a> {
+    fn len(&self) -> u32 {
+        self.bytes.len() as u32
+    }
+
+    fn error<M: Into<String>>(&self, message: M) -> Error {
+        Error::invalid_file(self.name, message)
+    }
+
+    fn check<T>(&self, cli_offset: u32, size: u64) -> Result<(), Error> {
+        if cli_offset as u64 + size > self.bytes.len() as u64 {
+            return Err(self.error(format!(
+                "not enough bytes at offset {} to represent {}",
+                cli_offset,
+                std::any::type_name::<T>()
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn view_as<T: Pod>(&self, cli_offset: u32) -> Result<&'a T, Error> {
+        self.check::<T>(cli_offset, sizeof::<T>() as u64)?;
+        self.check_alignment::<T>(cli_offset)?;
+        Ok(self.bytes.view_as(cli_offset))
+    }
+
+    fn view_as_slice_of<T: Pod>(&self, cli_offset: u32, len: u32) -> Result<&'a [T], Error> {
+        self.check::<T>(cli_offset, sizeof::<T>() as u64 * len as u64)?;
+        self.check_alignment::<T>(cli_offset)?;
+        Ok(self.bytes.view_as_slice_of(cli_offset, len))
+    }
+
+    fn check_alignment<T>(&self, cli_offset: u32) -> Result<(), Error> {
+        let ptr = self.bytes[cli_offset as usize..].as_ptr();
+
+        if ptr.align_offset(std::mem::align_of::<T>()) != 0 {
+            return Err(self.error(format!(
+                "offset {} is not properly aligned to {}",
+                cli_offset,
+                std::any::type_name::<T>()
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn copy_as<T: CopyPod>(&self, cli_offset: u32) -> Result<T, Error> {
+        self.check::<T>(cli_offset, sizeof::<T>() as u64)?;
+        Ok(self.bytes.copy_as(cli_offset))
+    }
+
+    fn view_as_str(&self, cli_offset: u32) -> Result<&'a [u8], Error> {
+        let buffer = self.bytes.get(cli_offset as usize..).unwrap_or_default();
+
+        match buffer.iter().position(|c| *c == b'\0') {
+            Some(index) => Ok(&buffer[..index]),
+            None => Err(self.error(format!("unterminated string at offset {}", cli_offset))),
+        }
+    }
 }
 
 fn sizeof<T>() -> u32 {
@@ -688,15 +1000,6 @@ impl View for [u8] {
             data
         }
     }
-
-    fn view_as_str(&self, cli_offset: u32) -> &[u8] {
-        let buffer = &self[cli_offset as usize..];
-        let index = buffer
-            .iter()
-            .position(|c| *c == b'\0')
-            .expect("Invalid file");
-        &self[cli_offset as usize..cli_offset as usize + index]
-    }
 }
 
 const IMAGE_DOS_SIGNATURE: u16 = 0x5A4D;
@@ -923,4 +1226,27 @@ mod tests {
         let foo = bytes.copy_as::<u16>(0);
         assert_eq!(foo, 0x0301);
     }
+
+    #[test]
+    fn error_on_empty_file() {
+        let error = File::try_from_bytes("empty.winmd", Vec::new())
+            .err()
+            .unwrap();
+        assert_eq!(error.file(), "empty.winmd");
+        assert!(matches!(error, Error::InvalidFile { .. }));
+    }
+
+    #[test]
+    fn error_on_invalid_signature() {
+        let error = File::try_from_bytes("invalid.winmd", vec![0u8; 256])
+            .err()
+            .unwrap();
+        assert_eq!(
+            error,
+            Error::InvalidFile {
+                file: "invalid.winmd".to_string(),
+                message: "file does not appear to be a winmd file".to_string(),
+            }
+        );
+    }
 }
@@ -1,12 +1,26 @@
 //! A Windows Metadata (winmd) parser
+pub mod diff;
+mod error;
 mod file;
+pub mod midl;
+pub mod model;
 pub mod parsed;
+mod query;
+mod to_snake;
 mod traits;
 mod type_reader;
+mod type_reader_builder;
+mod validate;
 mod workspace;
+pub mod writer;
 
+pub use error::Error;
 pub use file::{File, TableIndex};
 pub use parsed::*;
+pub use query::*;
+pub use to_snake::*;
 pub use traits::*;
 pub use type_reader::*;
+pub use type_reader_builder::*;
+pub use validate::validate;
 pub use workspace::*;
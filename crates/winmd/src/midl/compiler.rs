@@ -0,0 +1,1472 @@
+use super::parser::{self, Attribute, Decl, DeclKind, Direction, Expr, Member, MemberKind, Pos};
+use super::METADATA;
+use crate::writer::{self, *};
+use crate::{Error, Guid};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+const FOUNDATION: &str = "Windows.Foundation";
+
+/// Compiles MIDL 3.0 source into a Windows Metadata file, using `name` to identify the
+/// source in errors and as the name of the module
+///
+/// Runtime classes get the default, factory and statics interfaces that MIDL would
+/// synthesize, and interfaces and delegates without a `uuid` attribute get a GUID derived
+/// from their name. Types that aren't declared in the source must be referred to by their
+/// namespace-qualified name and are always encoded as reference types, since there is no
+/// metadata to say otherwise.
+///
+/// Returns an [`Error::InvalidSource`] if the source cannot be parsed or refers to a type
+/// that cannot be found.
+pub fn compile(name: &str, source: &str) -> Result<Vec<u8>, Error> {
+    let decls = parser::parse(name, source)?;
+    let mut compiler = Compiler::new(name);
+    let items = compiler.plan(&decls)?;
+
+    for item in &items {
+        compiler.item(item)?;
+    }
+
+    Ok(compiler.writer.into_bytes())
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Category {
+    Interface,
+    Class,
+    Delegate,
+    Enum,
+    Struct,
+    Attribute,
+    Contract,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Declared {
+    index: u32,
+    category: Category,
+}
+
+/// The interfaces that MIDL synthesizes to hold a runtime class's own members
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Synthesized {
+    Default,
+    Factory,
+    Statics,
+}
+
+/// A type definition to emit, in the order of the `TypeDef` table
+enum Item<'a> {
+    Decl(&'a Decl),
+    Synthesized {
+        class: &'a Decl,
+        name: String,
+        kind: Synthesized,
+    },
+}
+
+/// The namespace and generic parameters that names are resolved against
+#[derive(Copy, Clone)]
+struct Scope<'a> {
+    namespace: &'a str,
+    generics: &'a [String],
+}
+
+/// Where a member is being emitted, which determines its flags
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Owner {
+    Interface,
+    Class,
+}
+
+struct Compiler<'a> {
+    file: &'a str,
+    writer: Writer,
+    /// The types declared in the source by namespace-qualified name, with a generic suffix
+    types: HashMap<String, Declared>,
+    type_refs: HashMap<(String, String), u32>,
+    type_specs: HashMap<Vec<u8>, u32>,
+    /// The constructors of attributes defined elsewhere, by type and signature
+    constructors: HashMap<(String, String, Vec<u8>), u32>,
+    /// The constructors of attribute types declared in the source, by type, along with the
+    /// types of their parameters
+    attribute_types: HashMap<u32, (u32, Vec<TypeSig>)>,
+}
+
+impl<'a> Compiler<'a> {
+    fn new(file: &'a str) -> Self {
+        Self {
+            file,
+            writer: Writer::new(file),
+            types: HashMap::new(),
+            type_refs: HashMap::new(),
+            type_specs: HashMap::new(),
+            constructors: HashMap::new(),
+            attribute_types: HashMap::new(),
+        }
+    }
+
+    fn error<M: Into<String>>(&self, pos: Pos, message: M) -> Error {
+        Error::invalid_source(self.file, pos.line, pos.column, message)
+    }
+
+    /// Orders the type definitions, including synthesized interfaces, so that every type
+    /// declared in the source can be referred to before it is emitted
+    fn plan<'d>(&mut self, decls: &'d [Decl]) -> Result<Vec<Item<'d>>, Error> {
+        let mut items = Vec::new();
+
+        for decl in decls {
+            items.push(Item::Decl(decl));
+
+            if let DeclKind::Class { .. } = decl.kind {
+                for kind in &[
+                    Synthesized::Default,
+                    Synthesized::Factory,
+                    Synthesized::Statics,
+                ] {
+                    if has_synthesized(decl, *kind) {
+                        items.push(Item::Synthesized {
+                            class: decl,
+                            name: synthesized_name(&decl.name, *kind),
+                            kind: *kind,
+                        });
+                    }
+                }
+            }
+        }
+
+        for (position, item) in items.iter().enumerate() {
+            let (decl, name, category) = match item {
+                Item::Decl(decl) => (
+                    *decl,
+                    generic_name(&decl.name, &decl.generics),
+                    decl.category(),
+                ),
+                Item::Synthesized { class, name, .. } => {
+                    (*class, name.clone(), Category::Interface)
+                }
+            };
+
+            let full_name = format!("{}.{}", decl.namespace, name);
+            let declared = Declared {
+                // The first row of the TypeDef table is always `<Module>`.
+                index: position as u32 + 1,
+                category,
+            };
+
+            if self.types.insert(full_name.clone(), declared).is_some() {
+                return Err(self.error(
+                    decl.pos,
+                    format!("`{}` is declared more than once", full_name),
+                ));
+            }
+        }
+
+        Ok(items)
+    }
+
+    fn item(&mut self, item: &Item<'_>) -> Result<(), Error> {
+        match item {
+            Item::Decl(decl) => {
+                let scope = Scope {
+                    namespace: &decl.namespace,
+                    generics: &decl.generics,
+                };
+
+                match &decl.kind {
+                    DeclKind::Interface { requires, members } => {
+                        let index = self.type_def(0x40a1, decl, None, scope)?;
+
+                        for ty in requires {
+                            let interface = self.type_def_or_ref(ty, scope)?;
+                            self.interface_impl(index, interface);
+                        }
+
+                        let attributes = self.with_uuid(decl, &decl.attributes);
+                        self.attributes(HasAttribute::TypeDef(index), &attributes, scope)?;
+
+                        for member in members {
+                            self.member(index, member, Owner::Interface, scope)?;
+                        }
+                    }
+                    DeclKind::Class { .. } => self.class(decl)?,
+                    DeclKind::Delegate {
+                        return_type,
+                        params,
+                    } => {
+                        let extends = self.type_ref("System", "MulticastDelegate");
+                        let index = self.type_def(0x4101, decl, Some(extends), scope)?;
+                        let attributes = self.with_uuid(decl, &decl.attributes);
+                        self.attributes(HasAttribute::TypeDef(index), &attributes, scope)?;
+
+                        let sig =
+                            MethodSig::new(TypeSig::Void, vec![TypeSig::Object, TypeSig::ISize]);
+                        self.writer.method_def(0x1881, ".ctor", &sig);
+                        self.runtime_method();
+                        self.writer.param(0, 1, "object");
+                        self.writer.param(0, 2, "method");
+
+                        let invoke = Member {
+                            attributes: Vec::new(),
+                            is_static: false,
+                            name: "Invoke".to_string(),
+                            kind: MemberKind::Method {
+                                return_type: return_type.clone(),
+                            },
+                            params: params.clone(),
+                            pos: decl.pos,
+                        };
+
+                        self.method(0x01c6, &invoke, scope)?;
+                        self.runtime_method();
+                    }
+                    DeclKind::Enum { members } => self.enum_def(decl, members)?,
+                    DeclKind::Struct { fields } => {
+                        let extends = self.type_ref("System", "ValueType");
+                        let index = self.type_def(0x4109, decl, Some(extends), scope)?;
+                        self.attributes(HasAttribute::TypeDef(index), &decl.attributes, scope)?;
+
+                        for field in fields {
+                            let ty = self.type_sig(&field.ty, scope)?;
+                            self.writer.field(0x0006, &field.name, &ty);
+                        }
+                    }
+                    DeclKind::Attribute { fields } => {
+                        let extends = self.type_ref("System", "Attribute");
+                        let index = self.type_def(0x4101, decl, Some(extends), scope)?;
+                        self.attributes(HasAttribute::TypeDef(index), &decl.attributes, scope)?;
+                        let mut params = Vec::new();
+
+                        for field in fields {
+                            let ty = self.type_sig(&field.ty, scope)?;
+                            self.writer.field(0x0006, &field.name, &ty);
+                            params.push(ty);
+                        }
+
+                        // The constructor takes the fields in order.
+                        let sig = MethodSig::new(TypeSig::Void, params.clone());
+                        let constructor = self.writer.method_def(0x1886, ".ctor", &sig);
+                        self.runtime_method();
+                        self.attribute_types.insert(index, (constructor, params));
+
+                        for (sequence, field) in fields.iter().enumerate() {
+                            self.writer.param(0x0001, sequence as u16 + 1, &field.name);
+                        }
+                    }
+                    DeclKind::Contract => {
+                        let extends = self.type_ref("System", "ValueType");
+                        let index = self.type_def(0x4109, decl, Some(extends), scope)?;
+                        self.attributes(HasAttribute::TypeDef(index), &decl.attributes, scope)?;
+                        let constructor =
+                            self.constructor(METADATA, "ApiContractAttribute", Vec::new());
+                        self.writer
+                            .attribute(HasAttribute::TypeDef(index), constructor, &[], &[]);
+                    }
+                }
+            }
+            Item::Synthesized { class, name, kind } => self.synthesized(class, name, *kind)?,
+        }
+
+        Ok(())
+    }
+
+    /// Adds the type's row, checking that it lands where [`Compiler::plan`] expected
+    fn type_def(
+        &mut self,
+        flags: u32,
+        decl: &Decl,
+        extends: Option<TypeDefOrRef>,
+        scope: Scope<'_>,
+    ) -> Result<u32, Error> {
+        let name = generic_name(&decl.name, &decl.generics);
+        let index = self.writer.type_def(flags, &decl.namespace, &name, extends);
+        debug_assert_eq!(
+            Some(index),
+            self.types
+                .get(&format!("{}.{}", decl.namespace, name))
+                .map(|declared| declared.index)
+        );
+
+        for generic in scope.generics {
+            self.writer
+                .generic_param(TypeOrMethodDef::TypeDef(index), 0, generic);
+        }
+
+        Ok(index)
+    }
+
+    fn class(&mut self, decl: &Decl) -> Result<(), Error> {
+        let (is_static, unsealed, bases, members) = match &decl.kind {
+            DeclKind::Class {
+                is_static,
+                unsealed,
+                bases,
+                members,
+            } => (*is_static, *unsealed, bases, members),
+            _ => unreachable!(),
+        };
+
+        let scope = Scope {
+            namespace: &decl.namespace,
+            generics: &[],
+        };
+
+        let mut bases = bases.iter().peekable();
+        let mut extends = self.type_ref("System", "Object");
+
+        // A runtime class declared in the source may only come first, as the base class.
+        if let Some(base) = bases.peek() {
+            let (def, _) = self.resolve(&base.name, 0, base.pos, scope)?;
+
+            if self.category(def) == Some(Category::Class) {
+                extends = def;
+                bases.next();
+            }
+        }
+
+        let flags = if unsealed { 0x4001 } else { 0x4101 };
+        let index = self.type_def(flags, decl, Some(extends), scope)?;
+        let version = version(decl);
+        let default = self.synthesized_interface(decl, Synthesized::Default);
+        let has_default = default.is_some();
+
+        if let Some((interface, _)) = default {
+            let interface_impl = self.interface_impl(index, TypeDefOrRef::TypeDef(interface));
+            self.default_attribute(interface_impl);
+        }
+
+        for (position, base) in bases.enumerate() {
+            let interface = self.type_def_or_ref(base, scope)?;
+
+            if matches!(self.category(interface), Some(category) if category != Category::Interface)
+            {
+                return Err(self.error(base.pos, format!("`{}` is not an interface", base.name)));
+            }
+
+            let interface_impl = self.interface_impl(index, interface);
+
+            // Without members of its own, a class's first interface is its default.
+            if position == 0 && !has_default {
+                self.default_attribute(interface_impl);
+            }
+        }
+
+        let attributes: Vec<Attribute> = decl
+            .attributes
+            .iter()
+            .filter(|attribute| attribute.name != "default_interface")
+            .cloned()
+            .collect();
+
+        self.attributes(HasAttribute::TypeDef(index), &attributes, scope)?;
+        let class_name = format!("{}.{}", decl.namespace, decl.name);
+        let parent = HasAttribute::TypeDef(index);
+
+        let has_default_constructor = members.iter().any(|member| {
+            matches!(member.kind, MemberKind::Constructor) && member.params.is_empty()
+        });
+
+        if let Some((_, factory)) = self.synthesized_interface(decl, Synthesized::Factory) {
+            if unsealed {
+                let composition = self.type_ref(METADATA, "CompositionType");
+                let system_type = self.type_ref("System", "Type");
+                let constructor = self.constructor(
+                    METADATA,
+                    "ComposableAttribute",
+                    vec![
+                        TypeSig::Class(system_type),
+                        TypeSig::ValueType(composition),
+                        TypeSig::U32,
+                    ],
+                );
+
+                let args = [
+                    writer::AttributeArg::Type(Some(factory)),
+                    writer::AttributeArg::Enum(
+                        format!("{}.CompositionType", METADATA),
+                        Box::new(writer::AttributeArg::I32(2)),
+                    ),
+                    writer::AttributeArg::U32(version),
+                ];
+
+                self.writer.attribute(parent, constructor, &args, &[]);
+            } else {
+                self.type_version_attribute(parent, "ActivatableAttribute", Some(factory), version);
+            }
+        }
+
+        if has_default_constructor && !unsealed {
+            self.type_version_attribute(parent, "ActivatableAttribute", None, version);
+        }
+
+        if let Some((_, statics)) = self.synthesized_interface(decl, Synthesized::Statics) {
+            self.type_version_attribute(parent, "StaticAttribute", Some(statics), version);
+        }
+
+        if is_static && (has_default || members.iter().any(|member| !member.is_static)) {
+            return Err(self.error(
+                decl.pos,
+                format!(
+                    "static runtimeclass `{}` cannot have instance members",
+                    class_name
+                ),
+            ));
+        }
+
+        // The class repeats its members, which are implemented by the runtime.
+        for member in members {
+            self.member(index, member, Owner::Class, scope)?;
+        }
+
+        Ok(())
+    }
+
+    fn synthesized(&mut self, class: &Decl, name: &str, kind: Synthesized) -> Result<(), Error> {
+        let scope = Scope {
+            namespace: &class.namespace,
+            generics: &[],
+        };
+
+        let unsealed = matches!(class.kind, DeclKind::Class { unsealed: true, .. });
+        let members = match &class.kind {
+            DeclKind::Class { members, .. } => members,
+            _ => unreachable!(),
+        };
+
+        let index = self.writer.type_def(0x40a1, &class.namespace, name, None);
+        let parent = HasAttribute::TypeDef(index);
+        let guid = name_guid(&format!("{}.{}", class.namespace, name));
+        self.guid_attribute(parent, guid);
+
+        if class
+            .attributes
+            .iter()
+            .any(|attribute| attribute.name == "version")
+        {
+            let constructor = self.constructor(METADATA, "VersionAttribute", vec![TypeSig::U32]);
+            self.writer.attribute(
+                parent,
+                constructor,
+                &[writer::AttributeArg::U32(version(class))],
+                &[],
+            );
+        }
+
+        let system_type = self.type_ref("System", "Type");
+        let constructor = self.constructor(
+            METADATA,
+            "ExclusiveToAttribute",
+            vec![TypeSig::Class(system_type)],
+        );
+        let class_name = format!("{}.{}", class.namespace, class.name);
+        self.writer.attribute(
+            parent,
+            constructor,
+            &[writer::AttributeArg::Type(Some(class_name.clone()))],
+            &[],
+        );
+
+        let mut factory_methods = 0;
+
+        for member in members {
+            match (kind, &member.kind) {
+                (Synthesized::Default, MemberKind::Constructor)
+                | (Synthesized::Statics, MemberKind::Constructor) => {}
+                (Synthesized::Default, _) if !member.is_static => {
+                    self.member(index, member, Owner::Interface, scope)?;
+                }
+                (Synthesized::Statics, _) if member.is_static => {
+                    let mut member = member.clone();
+                    member.is_static = false;
+                    self.member(index, &member, Owner::Interface, scope)?;
+                }
+                (Synthesized::Factory, MemberKind::Constructor)
+                    if unsealed || !member.params.is_empty() =>
+                {
+                    factory_methods += 1;
+
+                    // `method_name` names the factory method rather than adding an attribute.
+                    let (names, attributes): (Vec<&Attribute>, Vec<&Attribute>) = member
+                        .attributes
+                        .iter()
+                        .partition(|attribute| attribute.name == "method_name");
+
+                    let name = match names.first() {
+                        Some(attribute) => match attribute.args.as_slice() {
+                            [(Expr::String(name), _)] => name.clone(),
+                            _ => {
+                                return Err(
+                                    self.error(attribute.pos, "`method_name` expects a string")
+                                )
+                            }
+                        },
+                        None if factory_methods == 1 => "CreateInstance".to_string(),
+                        None => format!("CreateInstance{}", factory_methods),
+                    };
+
+                    let mut params = member.params.clone();
+
+                    if unsealed {
+                        params.push(parser::Param {
+                            direction: Direction::In,
+                            ty: builtin_ref("Object", member.pos),
+                            name: "baseInterface".to_string(),
+                        });
+
+                        params.push(parser::Param {
+                            direction: Direction::Out,
+                            ty: builtin_ref("Object", member.pos),
+                            name: "innerInterface".to_string(),
+                        });
+                    }
+
+                    let method = Member {
+                        attributes: attributes.into_iter().cloned().collect(),
+                        is_static: false,
+                        name,
+                        kind: MemberKind::Method {
+                            return_type: Some(builtin_ref(&class_name, member.pos)),
+                        },
+                        params,
+                        pos: member.pos,
+                    };
+
+                    self.member(index, &method, Owner::Interface, scope)?;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn enum_def(&mut self, decl: &Decl, members: &[parser::EnumMember]) -> Result<(), Error> {
+        let scope = Scope {
+            namespace: &decl.namespace,
+            generics: &[],
+        };
+
+        let extends = self.type_ref("System", "Enum");
+        let index = self.type_def(0x4101, decl, Some(extends), scope)?;
+        self.attributes(HasAttribute::TypeDef(index), &decl.attributes, scope)?;
+
+        // Flags enums are unsigned, as in MIDL.
+        let flags = decl
+            .attributes
+            .iter()
+            .any(|attribute| attribute.name == "flags");
+        let underlying = if flags { TypeSig::U32 } else { TypeSig::I32 };
+        self.writer.field(0x0606, "value__", &underlying);
+
+        let ty = TypeSig::ValueType(TypeDefOrRef::TypeDef(index));
+        let mut next = 0;
+
+        for member in members {
+            let value = match &member.value {
+                Some((expr, pos)) => self.number(expr, *pos)?,
+                None => next,
+            };
+
+            let constant = if flags {
+                u32::try_from(value).map(ConstantValue::U32).ok()
+            } else {
+                i32::try_from(value).map(ConstantValue::I32).ok()
+            };
+
+            let constant = constant.ok_or_else(|| {
+                let pos = member.value.as_ref().map_or(decl.pos, |(_, pos)| *pos);
+                self.error(
+                    pos,
+                    format!("the value of `{}` is out of range", member.name),
+                )
+            })?;
+
+            let field = self.writer.field(0x8056, &member.name, &ty);
+            self.writer.constant(HasConstant::Field(field), &constant);
+            next = value + 1;
+        }
+
+        Ok(())
+    }
+
+    /// Emits a method, property or event along with its attributes
+    fn member(
+        &mut self,
+        owner: u32,
+        member: &Member,
+        kind: Owner,
+        scope: Scope<'_>,
+    ) -> Result<(), Error> {
+        // Interface members are abstract, while a class's own members are implemented by the runtime.
+        let (flags, impl_flags) = match (kind, member.is_static) {
+            (Owner::Interface, _) => (0x05c6, 0),
+            (Owner::Class, false) => (0x01e6, 3),
+            (Owner::Class, true) => (0x0096, 3),
+        };
+
+        match &member.kind {
+            MemberKind::Constructor => {
+                if kind == Owner::Class {
+                    let params = member
+                        .params
+                        .iter()
+                        .map(|param| self.type_sig(&param.ty, scope))
+                        .collect::<Result<Vec<_>, Error>>()?;
+
+                    self.writer
+                        .method_def(0x1886, ".ctor", &MethodSig::new(TypeSig::Void, params));
+                    self.runtime_method();
+                    self.params(&member.params)?;
+                }
+            }
+            MemberKind::Method { .. } => {
+                let method = self.method(flags, member, scope)?;
+                self.set_impl_flags(impl_flags);
+
+                if kind == Owner::Interface {
+                    self.attributes(HasAttribute::MethodDef(method), &member.attributes, scope)?;
+                }
+            }
+            MemberKind::Property { ty, get, set } => {
+                let sig = self.type_sig(ty, scope)?;
+
+                if self.writer.tables.property_map.last().map(|map| map.parent) != Some(owner) {
+                    let property_list = self.writer.tables.property.len() as u32;
+                    self.writer.tables.property_map.push(writer::PropertyMap {
+                        parent: owner,
+                        property_list,
+                    });
+                }
+
+                let property = self.writer.tables.property.len() as u32;
+                self.writer.tables.property.push(writer::Property {
+                    flags: 0,
+                    name: member.name.clone(),
+                    signature: PropertySig {
+                        has_this: !member.is_static,
+                        ty: ParamSig::new(sig.clone()),
+                        params: Vec::new(),
+                    }
+                    .to_bytes(),
+                });
+
+                let association = HasSemantics::Property(property);
+
+                if *get {
+                    let method = self.accessor(
+                        flags,
+                        &format!("get_{}", member.name),
+                        member.is_static,
+                        sig.clone(),
+                        Vec::new(),
+                    );
+                    self.set_impl_flags(impl_flags);
+                    self.semantics(0x0002, method, association);
+                }
+
+                if *set {
+                    let method = self.accessor(
+                        flags,
+                        &format!("put_{}", member.name),
+                        member.is_static,
+                        TypeSig::Void,
+                        vec![sig],
+                    );
+                    self.set_impl_flags(impl_flags);
+                    self.writer.param(0x0001, 1, "value");
+                    self.semantics(0x0001, method, association);
+                }
+
+                if kind == Owner::Interface {
+                    self.attributes(HasAttribute::Property(property), &member.attributes, scope)?;
+                }
+            }
+            MemberKind::Event { ty } => {
+                let event_type = self.type_def_or_ref(ty, scope)?;
+                let handler = self.type_sig(ty, scope)?;
+                let token = TypeSig::ValueType(self.type_ref(FOUNDATION, "EventRegistrationToken"));
+
+                if self.writer.tables.event_map.last().map(|map| map.parent) != Some(owner) {
+                    let event_list = self.writer.tables.event.len() as u32;
+                    self.writer.tables.event_map.push(writer::EventMap {
+                        parent: owner,
+                        event_list,
+                    });
+                }
+
+                let event = self.writer.tables.event.len() as u32;
+                self.writer.tables.event.push(writer::Event {
+                    flags: 0,
+                    name: member.name.clone(),
+                    event_type,
+                });
+
+                let association = HasSemantics::Event(event);
+
+                let method = self.accessor(
+                    flags,
+                    &format!("add_{}", member.name),
+                    member.is_static,
+                    token.clone(),
+                    vec![handler],
+                );
+                self.set_impl_flags(impl_flags);
+                self.writer.param(0x0001, 1, "handler");
+                self.semantics(0x0008, method, association);
+
+                let method = self.accessor(
+                    flags,
+                    &format!("remove_{}", member.name),
+                    member.is_static,
+                    TypeSig::Void,
+                    vec![token],
+                );
+                self.set_impl_flags(impl_flags);
+                self.writer.param(0x0001, 1, "token");
+                self.semantics(0x0010, method, association);
+
+                if kind == Owner::Interface {
+                    self.attributes(HasAttribute::Event(event), &member.attributes, scope)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Adds a method along with its parameters
+    fn method(&mut self, flags: u16, member: &Member, scope: Scope<'_>) -> Result<u32, Error> {
+        let return_type = match &member.kind {
+            MemberKind::Method {
+                return_type: Some(ty),
+            } => self.type_sig(ty, scope)?,
+            _ => TypeSig::Void,
+        };
+
+        let mut sig = MethodSig::new(return_type, Vec::new());
+
+        if member.is_static {
+            sig.calling_convention = 0;
+        }
+
+        for param in &member.params {
+            let mut param_sig = ParamSig::new(self.type_sig(&param.ty, scope)?);
+            param_sig.by_ref = param.direction == Direction::Out;
+            sig.params.push(param_sig);
+        }
+
+        let method = self.writer.method_def(flags, &member.name, &sig);
+        self.params(&member.params)?;
+        Ok(method)
+    }
+
+    fn accessor(
+        &mut self,
+        flags: u16,
+        name: &str,
+        is_static: bool,
+        return_type: TypeSig,
+        params: Vec<TypeSig>,
+    ) -> u32 {
+        let mut sig = MethodSig::new(return_type, params);
+
+        if is_static {
+            sig.calling_convention = 0;
+        }
+
+        self.writer.method_def(flags | 0x0800, name, &sig)
+    }
+
+    fn params(&mut self, params: &[parser::Param]) -> Result<(), Error> {
+        for (sequence, param) in params.iter().enumerate() {
+            let flags = match param.direction {
+                Direction::In => 0x0001,
+                Direction::Out | Direction::Ref => 0x0002,
+            };
+
+            self.writer.param(flags, sequence as u16 + 1, &param.name);
+        }
+
+        Ok(())
+    }
+
+    fn semantics(&mut self, semantics: u16, method: u32, association: HasSemantics) {
+        self.writer
+            .tables
+            .method_semantics
+            .push(writer::MethodSemantics {
+                semantics,
+                method,
+                association,
+            });
+    }
+
+    /// Marks the most recently added method as implemented by the runtime
+    fn runtime_method(&mut self) {
+        self.set_impl_flags(3);
+    }
+
+    fn set_impl_flags(&mut self, impl_flags: u16) {
+        if let Some(method) = self.writer.tables.method_def.last_mut() {
+            method.impl_flags = impl_flags;
+        }
+    }
+
+    fn interface_impl(&mut self, class: u32, interface: TypeDefOrRef) -> u32 {
+        self.writer
+            .tables
+            .interface_impl
+            .push(writer::InterfaceImpl { class, interface });
+        self.writer.tables.interface_impl.len() as u32 - 1
+    }
+
+    fn default_attribute(&mut self, interface_impl: u32) {
+        let constructor = self.constructor(METADATA, "DefaultAttribute", Vec::new());
+        self.writer.attribute(
+            HasAttribute::InterfaceImpl(interface_impl),
+            constructor,
+            &[],
+            &[],
+        );
+    }
+
+    /// Adds an attribute such as `StaticAttribute` that takes an optional type and a version
+    fn type_version_attribute(
+        &mut self,
+        parent: HasAttribute,
+        name: &str,
+        ty: Option<String>,
+        version: u32,
+    ) {
+        let mut params = Vec::new();
+        let mut args = Vec::new();
+
+        if let Some(ty) = ty {
+            params.push(TypeSig::Class(self.type_ref("System", "Type")));
+            args.push(writer::AttributeArg::Type(Some(ty)));
+        }
+
+        params.push(TypeSig::U32);
+        args.push(writer::AttributeArg::U32(version));
+
+        let constructor = self.constructor(METADATA, name, params);
+        self.writer.attribute(parent, constructor, &args, &[]);
+    }
+
+    fn guid_attribute(&mut self, parent: HasAttribute, guid: Guid) {
+        let mut params = vec![TypeSig::U32, TypeSig::U16, TypeSig::U16];
+        params.resize(11, TypeSig::U8);
+        let constructor = self.constructor(METADATA, "GuidAttribute", params);
+
+        let mut args = vec![
+            writer::AttributeArg::U32(guid.0),
+            writer::AttributeArg::U16(guid.1),
+            writer::AttributeArg::U16(guid.2),
+        ];
+
+        args.extend(guid.3.iter().map(|value| writer::AttributeArg::U8(*value)));
+        self.writer.attribute(parent, constructor, &args, &[]);
+    }
+
+    /// Adds a `uuid` attribute derived from the type's name if it doesn't already have one
+    fn with_uuid(&self, decl: &Decl, attributes: &[Attribute]) -> Vec<Attribute> {
+        let mut attributes = attributes.to_vec();
+
+        if !attributes.iter().any(|attribute| attribute.name == "uuid") {
+            let name = format!(
+                "{}.{}",
+                decl.namespace,
+                generic_name(&decl.name, &decl.generics)
+            );
+
+            attributes.push(Attribute {
+                name: "uuid".to_string(),
+                args: Vec::new(),
+                raw: name_guid(&name).to_string(),
+                pos: decl.pos,
+            });
+        }
+
+        attributes
+    }
+
+    fn attributes(
+        &mut self,
+        parent: HasAttribute,
+        attributes: &[Attribute],
+        scope: Scope<'_>,
+    ) -> Result<(), Error> {
+        for attribute in attributes {
+            match attribute.name.as_str() {
+                "uuid" => {
+                    let guid = parse_guid(&attribute.raw).ok_or_else(|| {
+                        self.error(
+                            attribute.pos,
+                            format!("`{}` is not a valid GUID", attribute.raw),
+                        )
+                    })?;
+
+                    self.guid_attribute(parent, guid);
+                }
+                "version" => {
+                    let version = match attribute.args.as_slice() {
+                        [(expr, pos)] => u32::try_from(self.number(expr, *pos)?).ok(),
+                        _ => None,
+                    };
+
+                    let version = version.ok_or_else(|| {
+                        self.error(attribute.pos, "`version` expects a 32-bit unsigned version")
+                    })?;
+                    let constructor =
+                        self.constructor(METADATA, "VersionAttribute", vec![TypeSig::U32]);
+                    self.writer.attribute(
+                        parent,
+                        constructor,
+                        &[writer::AttributeArg::U32(version)],
+                        &[],
+                    );
+                }
+                "flags" => self.marker_attribute(parent, attribute, "System", "FlagsAttribute")?,
+                "default_overload" => {
+                    self.marker_attribute(parent, attribute, METADATA, "DefaultOverloadAttribute")?
+                }
+                "webhosthidden" => {
+                    self.marker_attribute(parent, attribute, METADATA, "WebHostHiddenAttribute")?
+                }
+                "experimental" => {
+                    self.marker_attribute(parent, attribute, METADATA, "ExperimentalAttribute")?
+                }
+                "method_name" => match attribute.args.as_slice() {
+                    [(Expr::String(name), _)] => {
+                        let constructor =
+                            self.constructor(METADATA, "OverloadAttribute", vec![TypeSig::String]);
+                        self.writer.attribute(
+                            parent,
+                            constructor,
+                            &[writer::AttributeArg::String(Some(name.clone()))],
+                            &[],
+                        );
+                    }
+                    _ => return Err(self.error(attribute.pos, "`method_name` expects a string")),
+                },
+                _ => self.custom_attribute(parent, attribute, scope)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn marker_attribute(
+        &mut self,
+        parent: HasAttribute,
+        attribute: &Attribute,
+        namespace: &str,
+        name: &str,
+    ) -> Result<(), Error> {
+        if !attribute.args.is_empty() {
+            return Err(self.error(
+                attribute.pos,
+                format!("`{}` does not take arguments", attribute.name),
+            ));
+        }
+
+        let constructor = self.constructor(namespace, name, Vec::new());
+        self.writer.attribute(parent, constructor, &[], &[]);
+        Ok(())
+    }
+
+    /// Applies an attribute type declared in the source, whose constructor takes its fields
+    fn custom_attribute(
+        &mut self,
+        parent: HasAttribute,
+        attribute: &Attribute,
+        scope: Scope<'_>,
+    ) -> Result<(), Error> {
+        let name = if attribute.name.ends_with("Attribute") {
+            attribute.name.clone()
+        } else {
+            format!("{}Attribute", attribute.name)
+        };
+
+        let index = match self.resolve(&name, 0, attribute.pos, scope) {
+            Ok((TypeDefOrRef::TypeDef(index), _))
+                if self.category(TypeDefOrRef::TypeDef(index)) == Some(Category::Attribute) =>
+            {
+                index
+            }
+            _ => {
+                return Err(self.error(
+                    attribute.pos,
+                    format!("unsupported attribute `{}`", attribute.name),
+                ))
+            }
+        };
+
+        let (constructor, params) = self.attribute_types.get(&index).cloned().ok_or_else(|| {
+            self.error(
+                attribute.pos,
+                format!("`{}` must be declared before it is used", attribute.name),
+            )
+        })?;
+
+        if attribute.args.len() != params.len() {
+            return Err(self.error(
+                attribute.pos,
+                format!("`{}` expects {} arguments", attribute.name, params.len()),
+            ));
+        }
+
+        let mut args = Vec::new();
+
+        for ((expr, pos), param) in attribute.args.iter().zip(&params) {
+            let arg = match (param, expr) {
+                (TypeSig::Bool, Expr::Bool(value)) => writer::AttributeArg::Bool(*value),
+                (TypeSig::String, Expr::String(value)) => {
+                    writer::AttributeArg::String(Some(value.clone()))
+                }
+                (_, Expr::Number { .. }) => {
+                    let value = self.number(expr, *pos)?;
+                    integer_arg(param, value)
+                        .ok_or_else(|| self.error(*pos, "the value is out of range"))?
+                }
+                _ => {
+                    return Err(self.error(
+                        *pos,
+                        "the value does not match the type of the attribute's field",
+                    ))
+                }
+            };
+
+            args.push(arg);
+        }
+
+        self.writer
+            .attribute(parent, AttributeType::MethodDef(constructor), &args, &[]);
+        Ok(())
+    }
+
+    /// Reads an integer literal
+    fn number(&self, expr: &Expr, pos: Pos) -> Result<i128, Error> {
+        let (text, negative) = match expr {
+            Expr::Number { text, negative } => (text, *negative),
+            _ => return Err(self.error(pos, "expected an integer")),
+        };
+
+        let value = if let Some(hex) = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+            i128::from_str_radix(hex, 16)
+        } else {
+            text.parse::<i128>()
+        };
+
+        let value =
+            value.map_err(|_| self.error(pos, format!("`{}` is not a valid integer", text)))?;
+        Ok(if negative { -value } else { value })
+    }
+
+    /// The constructor of an attribute type that isn't declared in the source
+    fn constructor(&mut self, namespace: &str, name: &str, params: Vec<TypeSig>) -> AttributeType {
+        let sig = MethodSig::new(TypeSig::Void, params);
+        let key = (namespace.to_string(), name.to_string(), sig.to_bytes());
+
+        if let Some(index) = self.constructors.get(&key) {
+            return AttributeType::MemberRef(*index);
+        }
+
+        let parent = match self.type_ref(namespace, name) {
+            TypeDefOrRef::TypeRef(index) => MemberRefParent::TypeRef(index),
+            _ => unreachable!(),
+        };
+
+        let index = self.writer.member_ref(parent, ".ctor", &sig);
+        self.constructors.insert(key, index);
+        AttributeType::MemberRef(index)
+    }
+
+    fn type_ref(&mut self, namespace: &str, name: &str) -> TypeDefOrRef {
+        let key = (namespace.to_string(), name.to_string());
+
+        if let Some(index) = self.type_refs.get(&key) {
+            return TypeDefOrRef::TypeRef(*index);
+        }
+
+        let type_ref = self.writer.type_ref(None, namespace, name);
+
+        if let TypeDefOrRef::TypeRef(index) = type_ref {
+            self.type_refs.insert(key, index);
+        }
+
+        type_ref
+    }
+
+    fn category(&self, def: TypeDefOrRef) -> Option<Category> {
+        match def {
+            TypeDefOrRef::TypeDef(index) => self
+                .types
+                .values()
+                .find(|declared| declared.index == index)
+                .map(|declared| declared.category),
+            _ => None,
+        }
+    }
+
+    /// The index and namespace-qualified name of an interface synthesized for the class
+    fn synthesized_interface(&self, class: &Decl, kind: Synthesized) -> Option<(u32, String)> {
+        if !has_synthesized(class, kind) {
+            return None;
+        }
+
+        let name = format!(
+            "{}.{}",
+            class.namespace,
+            synthesized_name(&class.name, kind)
+        );
+        self.types.get(&name).map(|declared| (declared.index, name))
+    }
+
+    fn type_def_or_ref(
+        &mut self,
+        ty: &parser::TypeRef,
+        scope: Scope<'_>,
+    ) -> Result<TypeDefOrRef, Error> {
+        match self.type_sig(ty, scope)? {
+            TypeSig::ValueType(def) | TypeSig::Class(def) => Ok(def),
+            TypeSig::Object => Ok(self.type_ref("System", "Object")),
+            sig @ TypeSig::GenericInst { .. } => {
+                let signature = sig.to_bytes();
+
+                if let Some(index) = self.type_specs.get(&signature) {
+                    return Ok(TypeDefOrRef::TypeSpec(*index));
+                }
+
+                let index = self.writer.tables.type_spec.len() as u32;
+                self.writer.tables.type_spec.push(writer::TypeSpec {
+                    signature: signature.clone(),
+                });
+
+                self.type_specs.insert(signature, index);
+                Ok(TypeDefOrRef::TypeSpec(index))
+            }
+            _ => Err(self.error(
+                ty.pos,
+                format!("`{}` is not a class, interface or delegate", ty.name),
+            )),
+        }
+    }
+
+    fn type_sig(&mut self, ty: &parser::TypeRef, scope: Scope<'_>) -> Result<TypeSig, Error> {
+        let sig = if ty.args.is_empty() {
+            if let Some(index) = scope
+                .generics
+                .iter()
+                .position(|generic| *generic == ty.name)
+            {
+                TypeSig::TypeGeneric(index as u32)
+            } else if let Some(sig) = self.builtin(&ty.name) {
+                sig
+            } else {
+                match self.resolve(&ty.name, 0, ty.pos, scope)? {
+                    (def, true) => TypeSig::ValueType(def),
+                    (def, false) => TypeSig::Class(def),
+                }
+            }
+        } else {
+            let (def, is_value_type) = self.resolve(&ty.name, ty.args.len(), ty.pos, scope)?;
+
+            let args = ty
+                .args
+                .iter()
+                .map(|arg| self.type_sig(arg, scope))
+                .collect::<Result<Vec<_>, Error>>()?;
+
+            TypeSig::GenericInst {
+                def,
+                is_value_type,
+                args,
+            }
+        };
+
+        if ty.array {
+            Ok(TypeSig::SZArray(Vec::new(), Box::new(sig)))
+        } else {
+            Ok(sig)
+        }
+    }
+
+    fn builtin(&mut self, name: &str) -> Option<TypeSig> {
+        let sig = match name {
+            "Boolean" => TypeSig::Bool,
+            "Char" => TypeSig::Char,
+            "Int8" => TypeSig::I8,
+            "UInt8" => TypeSig::U8,
+            "Int16" => TypeSig::I16,
+            "UInt16" => TypeSig::U16,
+            "Int32" => TypeSig::I32,
+            "UInt32" => TypeSig::U32,
+            "Int64" => TypeSig::I64,
+            "UInt64" => TypeSig::U64,
+            "Single" => TypeSig::F32,
+            "Double" => TypeSig::F64,
+            "String" => TypeSig::String,
+            "Object" => TypeSig::Object,
+            "Guid" => TypeSig::ValueType(self.type_ref("System", "Guid")),
+            _ => return None,
+        };
+
+        Some(sig)
+    }
+
+    /// Finds a type by name, searching the enclosing namespaces from the innermost outwards,
+    /// and returns it along with whether it's a value type
+    fn resolve(
+        &mut self,
+        name: &str,
+        arity: usize,
+        pos: Pos,
+        scope: Scope<'_>,
+    ) -> Result<(TypeDefOrRef, bool), Error> {
+        let name = if arity == 0 {
+            name.to_string()
+        } else {
+            format!("{}`{}", name, arity)
+        };
+
+        let mut namespace = Some(scope.namespace);
+
+        while let Some(current) = namespace {
+            let full_name = if current.is_empty() {
+                name.clone()
+            } else {
+                format!("{}.{}", current, name)
+            };
+
+            if let Some(declared) = self.types.get(&full_name) {
+                let is_value_type = matches!(
+                    declared.category,
+                    Category::Enum | Category::Struct | Category::Contract
+                );
+                return Ok((TypeDefOrRef::TypeDef(declared.index), is_value_type));
+            }
+
+            namespace = match current.rfind('.') {
+                Some(index) => Some(&current[..index]),
+                None if current.is_empty() => None,
+                None => Some(""),
+            };
+        }
+
+        match name.rfind('.') {
+            Some(index) => Ok((self.type_ref(&name[..index], &name[index + 1..]), false)),
+            None => Err(self.error(pos, format!("unknown type `{}`", name))),
+        }
+    }
+}
+
+impl Decl {
+    fn category(&self) -> Category {
+        match self.kind {
+            DeclKind::Interface { .. } => Category::Interface,
+            DeclKind::Class { .. } => Category::Class,
+            DeclKind::Delegate { .. } => Category::Delegate,
+            DeclKind::Enum { .. } => Category::Enum,
+            DeclKind::Struct { .. } => Category::Struct,
+            DeclKind::Attribute { .. } => Category::Attribute,
+            DeclKind::Contract => Category::Contract,
+        }
+    }
+}
+
+fn has_synthesized(class: &Decl, kind: Synthesized) -> bool {
+    let (unsealed, members) = match &class.kind {
+        DeclKind::Class {
+            unsealed, members, ..
+        } => (*unsealed, members),
+        _ => return false,
+    };
+
+    members.iter().any(|member| match kind {
+        Synthesized::Default => {
+            !member.is_static && !matches!(member.kind, MemberKind::Constructor)
+        }
+        Synthesized::Factory => {
+            matches!(member.kind, MemberKind::Constructor)
+                && (unsealed || !member.params.is_empty())
+        }
+        Synthesized::Statics => member.is_static,
+    })
+}
+
+fn synthesized_name(class: &str, kind: Synthesized) -> String {
+    match kind {
+        Synthesized::Default => format!("I{}", class),
+        Synthesized::Factory => format!("I{}Factory", class),
+        Synthesized::Statics => format!("I{}Statics", class),
+    }
+}
+
+/// The metadata name of a type, which ends with the number of generic parameters if it has any
+fn generic_name(name: &str, generics: &[String]) -> String {
+    if generics.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}`{}", name, generics.len())
+    }
+}
+
+fn builtin_ref(name: &str, pos: Pos) -> parser::TypeRef {
+    parser::TypeRef {
+        name: name.to_string(),
+        args: Vec::new(),
+        array: false,
+        pos,
+    }
+}
+
+/// The value of the class's `version` attribute, which is also used for the attributes that
+/// MIDL synthesizes
+fn version(decl: &Decl) -> u32 {
+    decl.attributes
+        .iter()
+        .find(|attribute| attribute.name == "version")
+        .and_then(|attribute| match attribute.args.as_slice() {
+            [(
+                Expr::Number {
+                    text,
+                    negative: false,
+                },
+                _,
+            )] => text.parse().ok(),
+            _ => None,
+        })
+        .unwrap_or(1)
+}
+
+/// Converts an integer literal to an attribute argument of the given type, if it fits
+fn integer_arg(ty: &TypeSig, value: i128) -> Option<writer::AttributeArg> {
+    Some(match ty {
+        TypeSig::I8 => writer::AttributeArg::I8(i8::try_from(value).ok()?),
+        TypeSig::U8 => writer::AttributeArg::U8(u8::try_from(value).ok()?),
+        TypeSig::I16 => writer::AttributeArg::I16(i16::try_from(value).ok()?),
+        TypeSig::U16 => writer::AttributeArg::U16(u16::try_from(value).ok()?),
+        TypeSig::I32 => writer::AttributeArg::I32(i32::try_from(value).ok()?),
+        TypeSig::U32 => writer::AttributeArg::U32(u32::try_from(value).ok()?),
+        TypeSig::I64 => writer::AttributeArg::I64(i64::try_from(value).ok()?),
+        TypeSig::U64 => writer::AttributeArg::U64(u64::try_from(value).ok()?),
+        _ => return None,
+    })
+}
+
+/// Parses a GUID written as `01234567-89ab-cdef-0123-456789abcdef`
+fn parse_guid(text: &str) -> Option<Guid> {
+    let text = text.trim_matches(|c| c == '{' || c == '}' || c == '"');
+    let parts: Vec<&str> = text.split('-').collect();
+
+    if parts.iter().map(|part| part.len()).collect::<Vec<_>>() != [8, 4, 4, 4, 12] {
+        return None;
+    }
+
+    let tail = format!("{}{}", parts[3], parts[4]);
+    let mut data4 = [0; 8];
+
+    for (index, value) in data4.iter_mut().enumerate() {
+        *value = u8::from_str_radix(tail.get(index * 2..index * 2 + 2)?, 16).ok()?;
+    }
+
+    Some(Guid(
+        u32::from_str_radix(parts[0], 16).ok()?,
+        u16::from_str_radix(parts[1], 16).ok()?,
+        u16::from_str_radix(parts[2], 16).ok()?,
+        data4,
+    ))
+}
+
+/// A version 5 (SHA-1, name-based) UUID for a type that doesn't have a `uuid` attribute
+fn name_guid(name: &str) -> Guid {
+    // The namespace that the Windows Runtime uses for parameterized interface IDs
+    const NAMESPACE: [u8; 16] = [
+        0x11, 0xf4, 0x7a, 0xd5, 0x7b, 0x73, 0x42, 0xc0, 0xab, 0xae, 0x87, 0x8b, 0x1e, 0x16, 0xad,
+        0xee,
+    ];
+
+    let mut data = NAMESPACE.to_vec();
+    data.extend_from_slice(name.as_bytes());
+    let hash = sha1(&data);
+
+    let mut data4 = [0; 8];
+    data4.copy_from_slice(&hash[8..16]);
+    data4[0] = (data4[0] & 0x3f) | 0x80;
+
+    Guid(
+        u32::from_be_bytes([hash[0], hash[1], hash[2], hash[3]]),
+        u16::from_be_bytes([hash[4], hash[5]]),
+        (u16::from_be_bytes([hash[6], hash[7]]) & 0x0fff) | 0x5000,
+        data4,
+    )
+}
+
+fn sha1(data: &[u8]) -> [u8; 20] {
+    let mut state: [u32; 5] = [
+        0x6745_2301,
+        0xefcd_ab89,
+        0x98ba_dcfe,
+        0x1032_5476,
+        0xc3d2_e1f0,
+    ];
+
+    let mut message = data.to_vec();
+    message.push(0x80);
+
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+
+    message.extend_from_slice(&((data.len() as u64) * 8).to_be_bytes());
+
+    for chunk in message.chunks(64) {
+        let mut words = [0u32; 80];
+
+        for (index, word) in chunk.chunks(4).enumerate() {
+            words[index] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+
+        for index in 16..80 {
+            words[index] =
+                (words[index - 3] ^ words[index - 8] ^ words[index - 14] ^ words[index - 16])
+                    .rotate_left(1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e] = state;
+
+        for (index, word) in words.iter().enumerate() {
+            let (f, k) = match index {
+                0..=19 => ((b & c) | (!b & d), 0x5a82_7999),
+                20..=39 => (b ^ c ^ d, 0x6ed9_eba1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8f1b_bcdc),
+                _ => (b ^ c ^ d, 0xca62_c1d6),
+            };
+
+            let temp = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(*word);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+        }
+
+        for (value, add) in state.iter_mut().zip([a, b, c, d, e].iter()) {
+            *value = value.wrapping_add(*add);
+        }
+    }
+
+    let mut hash = [0; 20];
+
+    for (index, value) in state.iter().enumerate() {
+        hash[index * 4..index * 4 + 4].copy_from_slice(&value.to_be_bytes());
+    }
+
+    hash
+}
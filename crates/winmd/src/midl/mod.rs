@@ -0,0 +1,19 @@
+//! Converts between metadata and MIDL 3.0
+//!
+//! [`namespace`] and [`type_def`] render metadata types as MIDL 3.0. The output is meant for
+//! reviewing an API rather than for compiling, so members are listed in metadata order and
+//! every attribute is kept, including those that MIDL would normally synthesize such as
+//! `uuid` and `exclusiveto`.
+//!
+//! [`compile`] goes the other way, turning a subset of MIDL 3.0 into a metadata file without
+//! the Windows-only MIDL tooling. It understands namespaces, interfaces, runtime classes,
+//! delegates, enums, structs, attributes and generics, which is enough to define test types
+//! inline.
+mod compiler;
+mod parser;
+mod printer;
+
+pub use compiler::compile;
+pub use printer::{namespace, type_def};
+
+const METADATA: &str = "Windows.Foundation.Metadata";
@@ -0,0 +1,883 @@
+use crate::Error;
+
+/// The one-based line and column of a token
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Pos {
+    pub line: u32,
+    pub column: u32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Ident(String),
+    /// A decimal or hexadecimal literal, kept as written until its type is known
+    Number(String),
+    String(String),
+    Punct(char),
+    End,
+}
+
+struct Lexed {
+    token: Token,
+    pos: Pos,
+    /// The byte offsets of the token within the source
+    start: usize,
+    end: usize,
+}
+
+/// A reference to a type, such as `Windows.Foundation.IReference<Int32>[]`
+#[derive(Clone, Debug)]
+pub struct TypeRef {
+    pub name: String,
+    pub args: Vec<TypeRef>,
+    pub array: bool,
+    pub pos: Pos,
+}
+
+/// A literal passed to an attribute or assigned to an enum member
+#[derive(Clone, Debug)]
+pub enum Expr {
+    Number { text: String, negative: bool },
+    String(String),
+    Bool(bool),
+}
+
+#[derive(Clone, Debug)]
+pub struct Attribute {
+    pub name: String,
+    pub args: Vec<(Expr, Pos)>,
+    /// The raw text between the parentheses, which is how `uuid` takes its value
+    pub raw: String,
+    pub pos: Pos,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    In,
+    Out,
+    /// A caller-allocated array that the callee fills in
+    Ref,
+}
+
+#[derive(Clone, Debug)]
+pub struct Param {
+    pub direction: Direction,
+    pub ty: TypeRef,
+    pub name: String,
+}
+
+#[derive(Clone, Debug)]
+pub enum MemberKind {
+    Constructor,
+    Method { return_type: Option<TypeRef> },
+    Property { ty: TypeRef, get: bool, set: bool },
+    Event { ty: TypeRef },
+}
+
+#[derive(Clone, Debug)]
+pub struct Member {
+    pub attributes: Vec<Attribute>,
+    pub is_static: bool,
+    pub name: String,
+    pub kind: MemberKind,
+    pub params: Vec<Param>,
+    pub pos: Pos,
+}
+
+#[derive(Clone, Debug)]
+pub struct Field {
+    pub ty: TypeRef,
+    pub name: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct EnumMember {
+    pub name: String,
+    pub value: Option<(Expr, Pos)>,
+}
+
+#[derive(Clone, Debug)]
+pub enum DeclKind {
+    Interface {
+        requires: Vec<TypeRef>,
+        members: Vec<Member>,
+    },
+    Class {
+        is_static: bool,
+        unsealed: bool,
+        bases: Vec<TypeRef>,
+        members: Vec<Member>,
+    },
+    Delegate {
+        return_type: Option<TypeRef>,
+        params: Vec<Param>,
+    },
+    Enum {
+        members: Vec<EnumMember>,
+    },
+    Struct {
+        fields: Vec<Field>,
+    },
+    Attribute {
+        fields: Vec<Field>,
+    },
+    Contract,
+}
+
+#[derive(Clone, Debug)]
+pub struct Decl {
+    pub attributes: Vec<Attribute>,
+    pub namespace: String,
+    pub name: String,
+    pub generics: Vec<String>,
+    pub kind: DeclKind,
+    pub pos: Pos,
+}
+
+/// Parses MIDL source into a flat list of type declarations
+pub fn parse(file: &str, source: &str) -> Result<Vec<Decl>, Error> {
+    let mut parser = Parser {
+        file,
+        source,
+        tokens: lex(file, source)?,
+        index: 0,
+    };
+
+    let mut decls = Vec::new();
+    parser.items("", &mut decls)?;
+
+    match parser.peek() {
+        Token::End => Ok(decls),
+        _ => Err(parser.unexpected()),
+    }
+}
+
+fn lex(file: &str, source: &str) -> Result<Vec<Lexed>, Error> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+    let mut line = 1;
+    let mut line_start = 0;
+
+    while let Some(&(start, c)) = chars.peek() {
+        let pos = Pos {
+            line,
+            column: source[line_start..start].chars().count() as u32 + 1,
+        };
+
+        if c == '\n' {
+            chars.next();
+            line += 1;
+            line_start = start + 1;
+            continue;
+        }
+
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        let rest = &source[start..];
+
+        // Comments and preprocessor directives are skipped.
+        if rest.starts_with("//") || (c == '#' && source[line_start..start].trim().is_empty()) {
+            while let Some(&(_, c)) = chars.peek() {
+                if c == '\n' {
+                    break;
+                }
+
+                chars.next();
+            }
+
+            continue;
+        }
+
+        if rest.starts_with("/*") {
+            let len = rest.find("*/").ok_or_else(|| {
+                Error::invalid_source(file, pos.line, pos.column, "unterminated comment")
+            })? + 2;
+
+            for (offset, c) in rest[..len].char_indices() {
+                chars.next();
+
+                if c == '\n' {
+                    line += 1;
+                    line_start = start + offset + 1;
+                }
+            }
+
+            continue;
+        }
+
+        let token = if c.is_ascii_alphanumeric() || c == '_' {
+            let mut end = start;
+
+            while let Some(&(index, c)) = chars.peek() {
+                if !c.is_ascii_alphanumeric() && c != '_' {
+                    break;
+                }
+
+                end = index + c.len_utf8();
+                chars.next();
+            }
+
+            let text = source[start..end].to_string();
+
+            if c.is_ascii_digit() {
+                Token::Number(text)
+            } else {
+                Token::Ident(text)
+            }
+        } else if c == '"' {
+            chars.next();
+            let mut value = String::new();
+
+            loop {
+                match chars.next() {
+                    Some((_, '"')) => break,
+                    Some((_, '\\')) => match chars.next() {
+                        Some((_, 'n')) => value.push('\n'),
+                        Some((_, 't')) => value.push('\t'),
+                        Some((_, c)) => value.push(c),
+                        None => break,
+                    },
+                    Some((_, '\n')) | None => {
+                        return Err(Error::invalid_source(
+                            file,
+                            pos.line,
+                            pos.column,
+                            "unterminated string",
+                        ))
+                    }
+                    Some((_, c)) => value.push(c),
+                }
+            }
+
+            Token::String(value)
+        } else {
+            chars.next();
+            Token::Punct(c)
+        };
+
+        let end = chars.peek().map_or(source.len(), |&(index, _)| index);
+        tokens.push(Lexed {
+            token,
+            pos,
+            start,
+            end,
+        });
+    }
+
+    let column = source[line_start..].chars().count() as u32 + 1;
+    tokens.push(Lexed {
+        token: Token::End,
+        pos: Pos { line, column },
+        start: source.len(),
+        end: source.len(),
+    });
+
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    file: &'a str,
+    source: &'a str,
+    tokens: Vec<Lexed>,
+    index: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error<M: Into<String>>(&self, pos: Pos, message: M) -> Error {
+        Error::invalid_source(self.file, pos.line, pos.column, message)
+    }
+
+    fn unexpected(&self) -> Error {
+        let message = match self.peek() {
+            Token::Ident(text) | Token::Number(text) => format!("unexpected `{}`", text),
+            Token::String(text) => format!("unexpected string {:?}", text),
+            Token::Punct(c) => format!("unexpected `{}`", c),
+            Token::End => "unexpected end of file".to_string(),
+        };
+
+        self.error(self.pos(), message)
+    }
+
+    fn peek(&self) -> &Token {
+        self.peek_at(0)
+    }
+
+    fn peek_at(&self, offset: usize) -> &Token {
+        let index = (self.index + offset).min(self.tokens.len() - 1);
+        &self.tokens[index].token
+    }
+
+    fn pos(&self) -> Pos {
+        self.tokens[self.index].pos
+    }
+
+    fn next(&mut self) -> &Lexed {
+        let index = self.index;
+
+        if index < self.tokens.len() - 1 {
+            self.index += 1;
+        }
+
+        &self.tokens[index]
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Token::Ident(text) if text == keyword)
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let found = self.is_keyword(keyword);
+
+        if found {
+            self.next();
+        }
+
+        found
+    }
+
+    fn eat_punct(&mut self, c: char) -> bool {
+        let found = *self.peek() == Token::Punct(c);
+
+        if found {
+            self.next();
+        }
+
+        found
+    }
+
+    fn expect_punct(&mut self, c: char) -> Result<(), Error> {
+        if self.eat_punct(c) {
+            Ok(())
+        } else {
+            Err(self.error(
+                self.pos(),
+                format!("expected `{}` but found {}", c, self.describe()),
+            ))
+        }
+    }
+
+    fn expect_ident(&mut self) -> Result<String, Error> {
+        match self.peek().clone() {
+            Token::Ident(text) => {
+                self.next();
+                Ok(text)
+            }
+            _ => Err(self.error(
+                self.pos(),
+                format!("expected a name but found {}", self.describe()),
+            )),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self.peek() {
+            Token::Ident(text) | Token::Number(text) => format!("`{}`", text),
+            Token::String(text) => format!("{:?}", text),
+            Token::Punct(c) => format!("`{}`", c),
+            Token::End => "the end of the file".to_string(),
+        }
+    }
+
+    /// Reads a name that may be qualified with dots, such as `Windows.Foundation.IStringable`
+    fn dotted_name(&mut self) -> Result<String, Error> {
+        let mut name = self.expect_ident()?;
+
+        while self.eat_punct('.') {
+            name.push('.');
+            name.push_str(&self.expect_ident()?);
+        }
+
+        Ok(name)
+    }
+
+    /// Parses imports, namespaces and type declarations until a closing brace or the end
+    fn items(&mut self, namespace: &str, decls: &mut Vec<Decl>) -> Result<(), Error> {
+        loop {
+            match self.peek() {
+                Token::End | Token::Punct('}') => return Ok(()),
+                _ => {}
+            }
+
+            if self.eat_keyword("import") {
+                match self.next().token {
+                    Token::String(_) => {}
+                    _ => return Err(self.error(self.pos(), "expected a file name to import")),
+                }
+
+                self.expect_punct(';')?;
+            } else if self.eat_keyword("namespace") {
+                let name = self.dotted_name()?;
+
+                let name = if namespace.is_empty() {
+                    name
+                } else {
+                    format!("{}.{}", namespace, name)
+                };
+
+                self.expect_punct('{')?;
+                self.items(&name, decls)?;
+                self.expect_punct('}')?;
+                self.eat_punct(';');
+            } else {
+                let pos = self.pos();
+                let decl = self.decl(namespace)?;
+
+                if namespace.is_empty() {
+                    return Err(self.error(
+                        pos,
+                        format!("`{}` must be declared within a namespace", decl.name),
+                    ));
+                }
+
+                decls.push(decl);
+            }
+        }
+    }
+
+    fn attributes(&mut self) -> Result<Vec<Attribute>, Error> {
+        let mut attributes = Vec::new();
+
+        while self.eat_punct('[') {
+            loop {
+                let pos = self.pos();
+                let name = self.expect_ident()?;
+                let mut args = Vec::new();
+                let mut raw = String::new();
+
+                if *self.peek() == Token::Punct('(') {
+                    let start = self.next().end;
+
+                    if *self.peek() != Token::Punct(')') {
+                        if name == "uuid" {
+                            // A GUID isn't made of tokens, so its value is taken from the source.
+                            while !matches!(self.peek(), Token::Punct(')') | Token::End) {
+                                self.next();
+                            }
+                        } else {
+                            loop {
+                                let pos = self.pos();
+                                args.push((self.expr()?, pos));
+
+                                if !self.eat_punct(',') {
+                                    break;
+                                }
+                            }
+                        }
+                    }
+
+                    raw = self.source[start..self.tokens[self.index].start]
+                        .trim()
+                        .to_string();
+
+                    self.expect_punct(')')?;
+                }
+
+                attributes.push(Attribute {
+                    name,
+                    args,
+                    raw,
+                    pos,
+                });
+
+                if !self.eat_punct(',') {
+                    break;
+                }
+            }
+
+            self.expect_punct(']')?;
+        }
+
+        Ok(attributes)
+    }
+
+    fn expr(&mut self) -> Result<Expr, Error> {
+        let negative = self.eat_punct('-');
+
+        match self.peek().clone() {
+            Token::Number(text) => {
+                self.next();
+                Ok(Expr::Number { text, negative })
+            }
+            Token::String(text) if !negative => {
+                self.next();
+                Ok(Expr::String(text))
+            }
+            Token::Ident(text) if !negative && (text == "true" || text == "false") => {
+                self.next();
+                Ok(Expr::Bool(text == "true"))
+            }
+            _ => Err(self.error(
+                self.pos(),
+                format!("expected a value but found {}", self.describe()),
+            )),
+        }
+    }
+
+    fn decl(&mut self, namespace: &str) -> Result<Decl, Error> {
+        let attributes = self.attributes()?;
+        let pos = self.pos();
+
+        let mut is_static = false;
+        let mut unsealed = false;
+
+        loop {
+            if self.eat_keyword("static") {
+                is_static = true;
+            } else if self.eat_keyword("unsealed") {
+                unsealed = true;
+            } else {
+                break;
+            }
+        }
+
+        let keyword = self.expect_ident()?;
+
+        if (is_static || unsealed) && keyword != "runtimeclass" {
+            return Err(self.error(pos, "only a runtimeclass may be static or unsealed"));
+        }
+
+        let (name, generics, kind) = match keyword.as_str() {
+            "interface" => {
+                let name = self.expect_ident()?;
+                let generics = self.generics()?;
+                let mut requires = Vec::new();
+
+                if self.eat_keyword("requires") {
+                    requires = self.type_list()?;
+                }
+
+                let members = self.members(None)?;
+                (name, generics, DeclKind::Interface { requires, members })
+            }
+            "runtimeclass" => {
+                let name = self.expect_ident()?;
+                let mut bases = Vec::new();
+
+                if self.eat_punct(':') {
+                    bases = self.type_list()?;
+                }
+
+                let members = if *self.peek() == Token::Punct('{') {
+                    self.members(Some(&name))?
+                } else {
+                    Vec::new()
+                };
+
+                (
+                    name,
+                    Vec::new(),
+                    DeclKind::Class {
+                        is_static,
+                        unsealed,
+                        bases,
+                        members,
+                    },
+                )
+            }
+            "delegate" => {
+                let return_type = self.return_type()?;
+                let name = self.expect_ident()?;
+                let generics = self.generics()?;
+                let params = self.params()?;
+
+                (
+                    name,
+                    generics,
+                    DeclKind::Delegate {
+                        return_type,
+                        params,
+                    },
+                )
+            }
+            "enum" => {
+                let name = self.expect_ident()?;
+                self.expect_punct('{')?;
+                let mut members = Vec::new();
+
+                while !self.eat_punct('}') {
+                    let name = self.expect_ident()?;
+                    let mut value = None;
+
+                    if self.eat_punct('=') {
+                        let pos = self.pos();
+                        value = Some((self.expr()?, pos));
+                    }
+
+                    members.push(EnumMember { name, value });
+
+                    if !self.eat_punct(',') {
+                        self.expect_punct('}')?;
+                        break;
+                    }
+                }
+
+                (name, Vec::new(), DeclKind::Enum { members })
+            }
+            "struct" => {
+                let name = self.expect_ident()?;
+                let fields = self.fields()?;
+                (name, Vec::new(), DeclKind::Struct { fields })
+            }
+            "attribute" => {
+                let name = self.expect_ident()?;
+                let fields = self.fields()?;
+                (name, Vec::new(), DeclKind::Attribute { fields })
+            }
+            "apicontract" => {
+                let name = self.expect_ident()?;
+                self.expect_punct('{')?;
+                self.expect_punct('}')?;
+                (name, Vec::new(), DeclKind::Contract)
+            }
+            _ => {
+                return Err(self.error(
+                    pos,
+                    format!("expected a type declaration but found `{}`", keyword),
+                ))
+            }
+        };
+
+        self.eat_punct(';');
+
+        Ok(Decl {
+            attributes,
+            namespace: namespace.to_string(),
+            name,
+            generics,
+            kind,
+            pos,
+        })
+    }
+
+    fn generics(&mut self) -> Result<Vec<String>, Error> {
+        let mut generics = Vec::new();
+
+        if self.eat_punct('<') {
+            loop {
+                generics.push(self.expect_ident()?);
+
+                if !self.eat_punct(',') {
+                    break;
+                }
+            }
+
+            self.expect_punct('>')?;
+        }
+
+        Ok(generics)
+    }
+
+    fn type_list(&mut self) -> Result<Vec<TypeRef>, Error> {
+        let mut types = vec![self.type_ref()?];
+
+        while self.eat_punct(',') {
+            types.push(self.type_ref()?);
+        }
+
+        Ok(types)
+    }
+
+    fn type_ref(&mut self) -> Result<TypeRef, Error> {
+        let pos = self.pos();
+        let name = self.dotted_name()?;
+        let mut args = Vec::new();
+
+        if self.eat_punct('<') {
+            args = self.type_list()?;
+            self.expect_punct('>')?;
+        }
+
+        let array = *self.peek() == Token::Punct('[') && *self.peek_at(1) == Token::Punct(']');
+
+        if array {
+            self.next();
+            self.next();
+        }
+
+        Ok(TypeRef {
+            name,
+            args,
+            array,
+            pos,
+        })
+    }
+
+    /// Reads a return type, where `void` is `None`
+    fn return_type(&mut self) -> Result<Option<TypeRef>, Error> {
+        if self.eat_keyword("void") {
+            Ok(None)
+        } else {
+            Ok(Some(self.type_ref()?))
+        }
+    }
+
+    fn params(&mut self) -> Result<Vec<Param>, Error> {
+        self.expect_punct('(')?;
+        let mut params = Vec::new();
+
+        if !self.eat_punct(')') {
+            loop {
+                let direction = if self.eat_keyword("out") {
+                    Direction::Out
+                } else if self.eat_keyword("ref") {
+                    Direction::Ref
+                } else {
+                    Direction::In
+                };
+
+                let ty = self.type_ref()?;
+
+                if direction == Direction::Ref && !ty.array {
+                    return Err(self.error(ty.pos, "only an array may be passed by `ref`"));
+                }
+
+                let name = self.expect_ident()?;
+                params.push(Param {
+                    direction,
+                    ty,
+                    name,
+                });
+
+                if !self.eat_punct(',') {
+                    break;
+                }
+            }
+
+            self.expect_punct(')')?;
+        }
+
+        Ok(params)
+    }
+
+    fn fields(&mut self) -> Result<Vec<Field>, Error> {
+        self.expect_punct('{')?;
+        let mut fields = Vec::new();
+
+        while !self.eat_punct('}') {
+            let ty = self.type_ref()?;
+            let name = self.expect_ident()?;
+            self.expect_punct(';')?;
+            fields.push(Field { ty, name });
+        }
+
+        Ok(fields)
+    }
+
+    /// Parses the members of an interface or, given its name, a runtime class
+    fn members(&mut self, class: Option<&str>) -> Result<Vec<Member>, Error> {
+        self.expect_punct('{')?;
+        let mut members = Vec::new();
+
+        while !self.eat_punct('}') {
+            let attributes = self.attributes()?;
+            let pos = self.pos();
+            let is_static = self.eat_keyword("static");
+
+            if is_static && class.is_none() {
+                return Err(self.error(pos, "interface members cannot be static"));
+            }
+
+            if self.eat_keyword("event") {
+                let ty = self.type_ref()?;
+                let name = self.expect_ident()?;
+                self.expect_punct(';')?;
+
+                members.push(Member {
+                    attributes,
+                    is_static,
+                    name,
+                    kind: MemberKind::Event { ty },
+                    params: Vec::new(),
+                    pos,
+                });
+
+                continue;
+            }
+
+            let is_constructor = matches!(
+                (self.peek(), self.peek_at(1)),
+                (Token::Ident(name), Token::Punct('(')) if Some(name.as_str()) == class
+            );
+
+            if is_constructor {
+                if is_static {
+                    return Err(self.error(pos, "constructors cannot be static"));
+                }
+
+                let name = self.expect_ident()?;
+                let params = self.params()?;
+                self.expect_punct(';')?;
+
+                members.push(Member {
+                    attributes,
+                    is_static,
+                    name,
+                    kind: MemberKind::Constructor,
+                    params,
+                    pos,
+                });
+
+                continue;
+            }
+
+            let return_type = self.return_type()?;
+            let name = self.expect_ident()?;
+
+            if *self.peek() == Token::Punct('(') {
+                let params = self.params()?;
+                self.expect_punct(';')?;
+
+                members.push(Member {
+                    attributes,
+                    is_static,
+                    name,
+                    kind: MemberKind::Method { return_type },
+                    params,
+                    pos,
+                });
+
+                continue;
+            }
+
+            let ty = return_type.ok_or_else(|| self.error(pos, "a property cannot be void"))?;
+            let (mut get, mut set) = (true, true);
+
+            if self.eat_punct('{') {
+                get = false;
+                set = false;
+
+                while !self.eat_punct('}') {
+                    if self.eat_keyword("get") {
+                        get = true;
+                    } else if self.eat_keyword("set") {
+                        set = true;
+                    } else {
+                        return Err(self.unexpected());
+                    }
+
+                    self.expect_punct(';')?;
+                }
+
+                if !get {
+                    return Err(self.error(pos, "a property must have a getter"));
+                }
+            }
+
+            self.expect_punct(';')?;
+
+            members.push(Member {
+                attributes,
+                is_static,
+                name,
+                kind: MemberKind::Property { ty, get, set },
+                params: Vec::new(),
+                pos,
+            });
+        }
+
+        Ok(members)
+    }
+}
@@ -0,0 +1,579 @@
+use super::*;
+use crate::*;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+/// Renders every type definition in a namespace as MIDL 3.0
+///
+/// # Panics
+///
+/// Panics if the namespace does not exist or if any of its types are malformed.
+pub fn namespace(reader: &TypeReader, namespace: &str) -> String {
+    let mut printer = Printer::new(namespace);
+    printer.line(&format!("namespace {}", namespace));
+    printer.line("{");
+    printer.indent += 1;
+
+    let mut first = true;
+
+    for ty in reader.namespace_types(namespace) {
+        if let Type::TypeDef(def) = ty {
+            if !first {
+                printer.out.push('\n');
+            }
+
+            first = false;
+            printer.type_def(&def);
+        }
+    }
+
+    printer.indent -= 1;
+    printer.line("}");
+    printer.out
+}
+
+/// Renders a type definition as MIDL 3.0, naming other types relative to its namespace
+///
+/// # Panics
+///
+/// Panics if the type is malformed.
+pub fn type_def(def: &TypeDef<'_>) -> String {
+    let mut printer = Printer::new(def.name().0);
+    printer.type_def(def);
+    printer.out
+}
+
+struct Printer<'a> {
+    /// The namespace that type names are relative to
+    namespace: &'a str,
+    out: String,
+    indent: usize,
+}
+
+impl<'a> Printer<'a> {
+    fn new(namespace: &'a str) -> Self {
+        Self {
+            namespace,
+            out: String::new(),
+            indent: 0,
+        }
+    }
+
+    fn line(&mut self, text: &str) {
+        for _ in 0..self.indent {
+            self.out.push_str("    ");
+        }
+
+        self.out.push_str(text);
+        self.out.push('\n');
+    }
+
+    fn type_def(&mut self, def: &TypeDef<'_>) {
+        let generics: Vec<&str> = def.generics().map(|param| param.name()).collect();
+        self.attributes(def.attributes());
+
+        match def.category() {
+            TypeCategory::Interface => self.interface(def, &generics),
+            TypeCategory::Class => self.class(def),
+            TypeCategory::Enum => self.enum_def(def),
+            TypeCategory::Struct => self.fields("struct", def),
+            TypeCategory::Attribute => self.fields("attribute", def),
+            TypeCategory::Delegate => self.delegate(def, &generics),
+            TypeCategory::Contract => {
+                self.line(&format!("apicontract {}", def.name().1));
+                self.line("{");
+                self.line("};");
+            }
+        }
+    }
+
+    fn interface(&mut self, def: &TypeDef<'_>, generics: &[&str]) {
+        let mut header = format!("interface {}", generic_name(def.name().1, generics));
+
+        let required: Vec<String> = def
+            .interfaces()
+            .map(|interface| self.type_def_or_ref(&interface.interface(), generics))
+            .collect();
+
+        if !required.is_empty() {
+            write!(header, " requires {}", required.join(", ")).unwrap();
+        }
+
+        self.line(&header);
+        self.line("{");
+        self.indent += 1;
+        self.members(def, generics);
+        self.indent -= 1;
+        self.line("}");
+    }
+
+    fn class(&mut self, def: &TypeDef<'_>) {
+        let mut bases = Vec::new();
+
+        if def.reader.u32(def.row, 3) != 0 {
+            let extends = self.type_def_or_ref(&def.extends(), &[]);
+
+            if extends != "Object" {
+                bases.push(extends);
+            }
+        }
+
+        let mut instance = false;
+
+        for interface in def.interfaces() {
+            let name = interface.interface();
+
+            // Interfaces that are exclusive to the class appear as the class's own members.
+            if !self.is_exclusive_to(&name, def) {
+                let mut base = self.type_def_or_ref(&name, &[]);
+
+                if interface.is_default() {
+                    base.insert_str(0, "[default] ");
+                }
+
+                bases.push(base);
+            }
+
+            instance = true;
+        }
+
+        instance |= def.methods().any(|method| !method.flags().is_static());
+
+        let mut header = if !instance {
+            "static runtimeclass".to_string()
+        } else if def.flags().sealed() {
+            "runtimeclass".to_string()
+        } else {
+            "unsealed runtimeclass".to_string()
+        };
+
+        write!(header, " {}", def.name().1).unwrap();
+
+        if !bases.is_empty() {
+            write!(header, " : {}", bases.join(", ")).unwrap();
+        }
+
+        self.line(&header);
+        self.line("{");
+        self.indent += 1;
+        self.members(def, &[]);
+        self.indent -= 1;
+        self.line("}");
+    }
+
+    fn is_exclusive_to(&self, interface: &TypeDefOrRef<'_>, class: &TypeDef<'_>) -> bool {
+        let interface = match interface {
+            TypeDefOrRef::TypeDef(def) => *def,
+            TypeDefOrRef::TypeRef(def) => match class.reader.find_type_def(def.name()) {
+                Some(def) => def,
+                None => return false,
+            },
+            TypeDefOrRef::TypeSpec(_) => return false,
+        };
+
+        let exclusive = interface.attributes().any(|attribute| {
+            attribute.name() == (METADATA, "ExclusiveToAttribute")
+                && matches!(
+                    attribute.try_args().as_deref(),
+                    Ok([(_, AttributeArg::TypeDef(def))]) if def == class
+                )
+        });
+
+        exclusive
+    }
+
+    /// Prints the methods of an interface or class in metadata order, printing each property
+    /// and event in place of its first accessor
+    fn members(&mut self, def: &TypeDef<'_>, generics: &[&str]) {
+        let mut printed = BTreeSet::new();
+
+        for method in def.methods() {
+            let prefix = if method.flags().is_static() {
+                "static "
+            } else {
+                ""
+            };
+
+            match method.semantics().map(|semantics| semantics.association()) {
+                Some(HasSemantics::Property(property)) => {
+                    if printed.insert(property.row) {
+                        self.attributes(property.attributes());
+                        let ty = self.param_type(&property.signature().ty, generics);
+
+                        let accessors = match (property.getter(), property.setter()) {
+                            (Some(_), None) => "{ get; }",
+                            (None, Some(_)) => "{ set; }",
+                            _ => "",
+                        };
+
+                        self.line(&format!(
+                            "{}{} {}{};",
+                            prefix,
+                            ty,
+                            property.name(),
+                            accessors
+                        ));
+                    }
+                }
+                Some(HasSemantics::Event(event)) => {
+                    if printed.insert(event.row) {
+                        self.attributes(event.attributes());
+                        let ty = self.type_def_or_ref(&event.event_type(), generics);
+                        self.line(&format!("{}event {} {};", prefix, ty, event.name()));
+                    }
+                }
+                None => {
+                    self.attributes(method.attributes());
+
+                    let name = if method.name() == ".ctor" {
+                        def.name().1.to_string()
+                    } else {
+                        let sig = method.signature();
+                        format!(
+                            "{} {}",
+                            self.param_type(&sig.return_type, generics),
+                            method.name()
+                        )
+                    };
+
+                    self.line(&format!(
+                        "{}{}({});",
+                        prefix,
+                        name,
+                        self.params(&method, generics)
+                    ));
+                }
+            }
+        }
+    }
+
+    fn delegate(&mut self, def: &TypeDef<'_>, generics: &[&str]) {
+        let invoke = def
+            .methods()
+            .find(|method| method.name() == "Invoke")
+            .unwrap_or_else(|| panic!("Delegate {:?} has no Invoke method", def.name()));
+
+        let sig = invoke.signature();
+
+        self.line(&format!(
+            "delegate {} {}({});",
+            self.param_type(&sig.return_type, generics),
+            generic_name(def.name().1, generics),
+            self.params(&invoke, generics)
+        ));
+    }
+
+    fn enum_def(&mut self, def: &TypeDef<'_>) {
+        self.line(&format!("enum {}", def.name().1));
+        self.line("{");
+        self.indent += 1;
+
+        for field in def.fields() {
+            if let Some(constant) = field.constant() {
+                self.line(&format!("{} = {},", field.name(), constant_text(&constant)));
+            }
+        }
+
+        self.indent -= 1;
+        self.line("};");
+    }
+
+    fn fields(&mut self, keyword: &str, def: &TypeDef<'_>) {
+        self.line(&format!("{} {}", keyword, def.name().1));
+        self.line("{");
+        self.indent += 1;
+
+        for field in def.fields() {
+            let ty = self.param_type(&field.signature(), &[]);
+            self.line(&format!("{} {};", ty, field.name()));
+        }
+
+        self.indent -= 1;
+        self.line("};");
+    }
+
+    fn attributes<'b, I: Iterator<Item = Attribute<'b>>>(&mut self, attributes: I) {
+        for attribute in attributes {
+            let (namespace, name) = attribute.name();
+
+            if (namespace, name) == (METADATA, "ApiContractAttribute") {
+                continue;
+            }
+
+            let name = name.trim_end_matches("Attribute");
+
+            let name = match (namespace, name) {
+                (METADATA, "Guid") => "uuid".to_string(),
+                (METADATA, "MarshalingBehavior") => "marshaling_behavior".to_string(),
+                (METADATA, "Overload") => "method_name".to_string(),
+                (METADATA, "DefaultOverload") => "default_overload".to_string(),
+                (METADATA, _) | ("System", _) => name.to_lowercase(),
+                _ => self.relative_name((namespace, name)),
+            };
+
+            let args = match attribute.try_args() {
+                Ok(args) if name == "uuid" => match Guid::from_args(&args) {
+                    Some(guid) => guid.to_string(),
+                    None => "/* arguments could not be decoded */".to_string(),
+                },
+                Ok(args) => {
+                    let params = constructor_params(&attribute);
+
+                    args.iter()
+                        .enumerate()
+                        .map(|(index, (name, arg))| {
+                            let value = match (arg, params.get(index)) {
+                                (
+                                    AttributeArg::I32(value),
+                                    Some(TypeSig::ValueType(TypeDefOrRef::TypeRef(ty))),
+                                ) if name.is_empty() => metadata_enum(ty.name(), *value)
+                                    .unwrap_or_else(|| value.to_string()),
+                                _ => self.arg(arg),
+                            };
+
+                            if name.is_empty() {
+                                value
+                            } else {
+                                format!("{} = {}", name, value)
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                }
+                Err(_) => "/* arguments could not be decoded */".to_string(),
+            };
+
+            if args.is_empty() {
+                self.line(&format!("[{}]", name));
+            } else {
+                self.line(&format!("[{}({})]", name, args));
+            }
+        }
+    }
+
+    fn arg(&self, arg: &AttributeArg<'_>) -> String {
+        match arg {
+            AttributeArg::Null => "null".to_string(),
+            AttributeArg::Bool(value) => value.to_string(),
+            AttributeArg::Char(value) => format!("{:?}", value),
+            AttributeArg::I8(value) => value.to_string(),
+            AttributeArg::U8(value) => value.to_string(),
+            AttributeArg::I16(value) => value.to_string(),
+            AttributeArg::U16(value) => value.to_string(),
+            AttributeArg::I32(value) => value.to_string(),
+            AttributeArg::U32(value) => value.to_string(),
+            AttributeArg::I64(value) => value.to_string(),
+            AttributeArg::U64(value) => value.to_string(),
+            AttributeArg::F32(value) => value.to_string(),
+            AttributeArg::F64(value) => value.to_string(),
+            AttributeArg::String(value) => format!("{:?}", value),
+            AttributeArg::TypeDef(def) => self.relative_name(def.name()),
+            AttributeArg::Array(values) => {
+                let values: Vec<String> = values.iter().map(|value| self.arg(value)).collect();
+                format!("{{ {} }}", values.join(", "))
+            }
+            AttributeArg::Enum(def, value) => {
+                let value = self.arg(value);
+
+                let member = def.fields().find(|field| {
+                    field.constant().map(|constant| constant_text(&constant)) == Some(value.clone())
+                });
+
+                // MIDL names the members of its own metadata enums in lowercase.
+                match member {
+                    Some(member) if def.name().0 == METADATA => member.name().to_lowercase(),
+                    Some(member) => {
+                        format!("{}.{}", self.relative_name(def.name()), member.name())
+                    }
+                    None => value,
+                }
+            }
+            AttributeArg::Boxed(value) => self.arg(value),
+        }
+    }
+
+    fn params(&self, method: &MethodDef<'_>, generics: &[&str]) -> String {
+        let sig = method.signature();
+        let mut params = Vec::new();
+
+        for param in method.params() {
+            // The return value's parameter, if present, has a sequence of zero.
+            let ty = match (param.sequence() as usize)
+                .checked_sub(1)
+                .and_then(|index| sig.params.get(index))
+            {
+                Some(ty) => ty,
+                None => continue,
+            };
+
+            // An output array that isn't passed by reference is filled in by the callee.
+            let direction = match (param.flags().output(), ty.by_ref) {
+                (true, true) => "out ",
+                (true, false) => "ref ",
+                _ => "",
+            };
+
+            params.push(format!(
+                "{}{} {}",
+                direction,
+                self.type_name(&ty.ty, generics),
+                param.name()
+            ));
+        }
+
+        params.join(", ")
+    }
+
+    fn param_type(&self, param: &ParamSig<'_>, generics: &[&str]) -> String {
+        self.type_name(&param.ty, generics)
+    }
+
+    fn type_name(&self, sig: &TypeSig<'_>, generics: &[&str]) -> String {
+        match sig {
+            TypeSig::Void => "void".to_string(),
+            TypeSig::Bool => "Boolean".to_string(),
+            TypeSig::Char => "Char".to_string(),
+            TypeSig::I8 => "Int8".to_string(),
+            TypeSig::U8 => "UInt8".to_string(),
+            TypeSig::I16 => "Int16".to_string(),
+            TypeSig::U16 => "UInt16".to_string(),
+            TypeSig::I32 => "Int32".to_string(),
+            TypeSig::U32 => "UInt32".to_string(),
+            TypeSig::I64 => "Int64".to_string(),
+            TypeSig::U64 => "UInt64".to_string(),
+            TypeSig::F32 => "Single".to_string(),
+            TypeSig::F64 => "Double".to_string(),
+            TypeSig::ISize => "IntPtr".to_string(),
+            TypeSig::USize => "UIntPtr".to_string(),
+            TypeSig::String => "String".to_string(),
+            TypeSig::Object => "Object".to_string(),
+            TypeSig::TypedByRef => "TypedReference".to_string(),
+            TypeSig::ValueType(def) | TypeSig::Class(def) => self.type_def_or_ref(def, generics),
+            TypeSig::TypeGeneric(index) => generics
+                .get(*index as usize)
+                .map_or_else(|| format!("T{}", index), |name| name.to_string()),
+            TypeSig::MethodGeneric(index) => format!("M{}", index),
+            TypeSig::GenericInst { def, args, .. } => {
+                let args: Vec<String> = args
+                    .iter()
+                    .map(|arg| self.type_name(arg, generics))
+                    .collect();
+
+                let name = self.type_def_or_ref(def, generics);
+                format!("{}<{}>", name, args.join(", "))
+            }
+            TypeSig::Ptr(_, ty) => format!("{}*", self.type_name(ty, generics)),
+            TypeSig::SZArray(_, ty) => format!("{}[]", self.type_name(ty, generics)),
+            TypeSig::Array(ty, shape) => format!(
+                "{}[{}]",
+                self.type_name(ty, generics),
+                ",".repeat(shape.rank.saturating_sub(1) as usize)
+            ),
+            TypeSig::FnPtr(_) => "IntPtr".to_string(),
+        }
+    }
+
+    fn type_def_or_ref(&self, def: &TypeDefOrRef<'_>, generics: &[&str]) -> String {
+        match def {
+            TypeDefOrRef::TypeSpec(spec) => self.type_name(&spec.signature(), generics),
+            _ => {
+                let (namespace, name) = def.name();
+                let name = name.split('`').next().unwrap_or_default();
+                self.relative_name((namespace, name))
+            }
+        }
+    }
+
+    /// The name of a type, which is only qualified if it's in a different namespace
+    fn relative_name(&self, (namespace, name): (&str, &str)) -> String {
+        match (namespace, name) {
+            ("System", "Guid") => "Guid".to_string(),
+            ("System", "Object") => "Object".to_string(),
+            _ if namespace == self.namespace || namespace.is_empty() => name.to_string(),
+            _ => format!("{}.{}", namespace, name),
+        }
+    }
+}
+
+/// The name of a generic type definition along with its parameters, such as `IMap<K, V>`
+fn generic_name(name: &str, generics: &[&str]) -> String {
+    let name = name.split('`').next().unwrap_or_default();
+
+    if generics.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}<{}>", name, generics.join(", "))
+    }
+}
+
+/// The types of an attribute constructor's parameters
+fn constructor_params<'a>(attribute: &Attribute<'a>) -> Vec<TypeSig<'a>> {
+    let sig = match attribute.constructor() {
+        AttributeType::MethodDef(method) => method.try_signature().ok(),
+        AttributeType::MemberRef(method) => match method.try_signature() {
+            Ok(Signature::Method(sig)) => Some(sig),
+            _ => None,
+        },
+    };
+
+    sig.map(|sig| sig.params.into_iter().map(|param| param.ty).collect())
+        .unwrap_or_default()
+}
+
+/// Names a value of one of the metadata enums used by MIDL's own attributes, which are read as
+/// plain `Int32` values when the file defining them wasn't loaded
+fn metadata_enum((namespace, name): (&str, &str), value: i32) -> Option<String> {
+    if namespace != METADATA {
+        return None;
+    }
+
+    let member = match (name, value) {
+        ("ThreadingModel", 1) => "sta",
+        ("ThreadingModel", 2) => "mta",
+        ("ThreadingModel", 3) => "both",
+        ("MarshalingType", 1) => "none",
+        ("MarshalingType", 2) => "agile",
+        ("MarshalingType", 3) => "standard",
+        ("CompositionType", 1) => "protected",
+        ("CompositionType", 2) => "public",
+        ("AttributeTargets", 1) => "delegate",
+        ("AttributeTargets", 2) => "enum",
+        ("AttributeTargets", 4) => "event",
+        ("AttributeTargets", 8) => "field",
+        ("AttributeTargets", 16) => "interface",
+        ("AttributeTargets", 64) => "method",
+        ("AttributeTargets", 128) => "parameter",
+        ("AttributeTargets", 256) => "property",
+        ("AttributeTargets", 512) => "runtimeclass",
+        ("AttributeTargets", 1024) => "struct",
+        ("AttributeTargets", 2048) => "interfaceimpl",
+        ("AttributeTargets", 8192) => "apicontract",
+        _ => return None,
+    };
+
+    Some(member.to_string())
+}
+
+fn constant_text(constant: &Constant<'_>) -> String {
+    let mut value = constant.value();
+
+    match constant.value_type() {
+        ElementType::Bool => (value.read_u8() != 0).to_string(),
+        ElementType::Char => format!(
+            "{:?}",
+            std::char::from_u32(value.read_u16() as u32).unwrap_or('\u{fffd}')
+        ),
+        ElementType::I8 => value.read_i8().to_string(),
+        ElementType::U8 => value.read_u8().to_string(),
+        ElementType::I16 => value.read_i16().to_string(),
+        ElementType::U16 => value.read_u16().to_string(),
+        ElementType::I32 => value.read_i32().to_string(),
+        ElementType::U32 => value.read_u32().to_string(),
+        ElementType::I64 => value.read_i64().to_string(),
+        ElementType::U64 => value.read_u64().to_string(),
+        ElementType::F32 => value.read_f32().to_string(),
+        ElementType::F64 => value.read_f64().to_string(),
+        ElementType::String => format!("{:?}", value.read_utf16()),
+        _ => "null".to_string(),
+    }
+}
@@ -0,0 +1,642 @@
+//! An owned copy of the metadata that can outlive the [`TypeReader`] it was read from
+//!
+//! The row handles such as [`TypeDef`] borrow from a [`TypeReader`], which makes them
+//! unsuitable for handing to other tools. [`Metadata::new`] copies every namespace
+//! into plain values instead, and with the `serde` feature enabled the model
+//! implements `Serialize` and `Deserialize`.
+//!
+//! # Schema
+//!
+//! The serialized form is versioned by [`SCHEMA_VERSION`], which is written to the
+//! `version` field of [`Metadata`] and is incremented whenever a field is removed or
+//! changes meaning. Fields may be added without changing the version.
+//!
+//! - Structs serialize as objects whose keys are the field names shown here.
+//! - Flags are the raw bit masks from the metadata tables.
+//! - GUIDs are strings of the form `00000000-0000-0000-0000-000000000000`.
+//! - [`TypeCategory`] values are lowercase strings such as `"interface"`.
+//! - A [`Type`] is an object whose `kind` key names the variant in snake case and whose
+//!   remaining keys are the variant's fields, such as `{"kind":"i32"}` or
+//!   `{"kind":"named","namespace":"Test","name":"Point","value_type":true}`.
+//! - A [`Value`] is an object whose `kind` key names the variant in snake case and whose
+//!   `value` key holds its contents, such as `{"kind":"u32","value":5}`. Floating point
+//!   values that are not finite have no JSON representation and are written as `null`.
+//!
+//! A struct with one field, as JSON:
+//!
+//! ```text
+//! {
+//!   "version": 1,
+//!   "namespaces": [{
+//!     "name": "Test",
+//!     "types": [{
+//!       "name": "Point",
+//!       "category": "struct",
+//!       "flags": 16649,
+//!       "guid": null,
+//!       "extends": {
+//!         "kind": "named",
+//!         "namespace": "System",
+//!         "name": "ValueType",
+//!         "value_type": false
+//!       },
+//!       "generics": [],
+//!       "interfaces": [],
+//!       "attributes": [],
+//!       "fields": [{
+//!         "name": "X",
+//!         "flags": 6,
+//!         "ty": {"kind": "i32"},
+//!         "constant": null,
+//!         "attributes": []
+//!       }],
+//!       "methods": [],
+//!       "nested_types": []
+//!     }]
+//!   }]
+//! }
+//! ```
+use crate::*;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The version of the serialized schema, see the [module documentation](self)
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Every namespace known to a [`TypeReader`]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Metadata {
+    /// The [`SCHEMA_VERSION`] the model was written with
+    pub version: u32,
+    pub namespaces: Vec<Namespace>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Namespace {
+    pub name: String,
+    /// The types in the namespace by name, including classes such as `Apis` that hold
+    /// the namespace's functions and constants
+    pub types: Vec<TypeDefinition>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TypeDefinition {
+    pub name: String,
+    pub category: TypeCategory,
+    pub flags: u32,
+    pub guid: Option<String>,
+    /// The base type, which interfaces and types such as `<Module>` do not have
+    pub extends: Option<Type>,
+    pub generics: Vec<GenericParameter>,
+    pub interfaces: Vec<Interface>,
+    pub attributes: Vec<Attribute>,
+    pub fields: Vec<FieldDefinition>,
+    pub methods: Vec<MethodDefinition>,
+    pub nested_types: Vec<TypeDefinition>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GenericParameter {
+    pub name: String,
+    pub flags: u32,
+    pub constraints: Vec<Type>,
+}
+
+/// An interface required or implemented by a type
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Interface {
+    pub ty: Type,
+    pub attributes: Vec<Attribute>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FieldDefinition {
+    pub name: String,
+    pub flags: u32,
+    pub ty: Type,
+    pub constant: Option<Value>,
+    pub attributes: Vec<Attribute>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MethodDefinition {
+    pub name: String,
+    pub flags: u32,
+    pub return_type: Type,
+    pub params: Vec<Parameter>,
+    pub attributes: Vec<Attribute>,
+    /// The module and entry point of a function imported from a DLL
+    pub import: Option<Import>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Parameter {
+    /// The parameter's name, which is empty if the metadata does not give one
+    pub name: String,
+    pub flags: u32,
+    pub ty: Type,
+    pub attributes: Vec<Attribute>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Import {
+    pub module: String,
+    pub name: String,
+    pub flags: u32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Attribute {
+    pub ty: TypeName,
+    /// The fixed arguments, which have no name, followed by the named arguments
+    ///
+    /// This is `None` if the arguments cannot be decoded, which happens when they refer
+    /// to an enum whose definition was not read.
+    pub args: Option<Vec<AttributeArgument>>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AttributeArgument {
+    pub name: Option<String>,
+    pub value: Value,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TypeName {
+    pub namespace: String,
+    pub name: String,
+}
+
+/// A type within a signature
+///
+/// Custom modifiers such as `IsConst` are left out.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(tag = "kind", rename_all = "snake_case")
+)]
+pub enum Type {
+    Void,
+    Bool,
+    Char,
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    I64,
+    U64,
+    F32,
+    F64,
+    #[cfg_attr(feature = "serde", serde(rename = "isize"))]
+    ISize,
+    #[cfg_attr(feature = "serde", serde(rename = "usize"))]
+    USize,
+    String,
+    Object,
+    TypedByRef,
+    /// A type definition or reference, where `value_type` distinguishes structs and enums
+    /// from classes, interfaces and delegates
+    Named {
+        namespace: String,
+        name: String,
+        value_type: bool,
+    },
+    /// An instance of a generic type
+    Generic {
+        namespace: String,
+        name: String,
+        value_type: bool,
+        args: Vec<Type>,
+    },
+    /// A generic parameter of the enclosing type, by index
+    TypeParameter {
+        index: u32,
+    },
+    /// A generic parameter of the enclosing method, by index
+    MethodParameter {
+        index: u32,
+    },
+    Pointer {
+        element: Box<Type>,
+    },
+    /// A managed reference, as used by `out` parameters
+    Reference {
+        element: Box<Type>,
+    },
+    /// A single-dimensional array with a lower bound of zero
+    Array {
+        element: Box<Type>,
+    },
+    /// A general array, where `sizes` and `lower_bounds` may cover fewer dimensions than `rank`
+    MultiArray {
+        element: Box<Type>,
+        rank: u32,
+        sizes: Vec<u32>,
+        lower_bounds: Vec<i32>,
+    },
+    Function {
+        signature: Box<Signature>,
+    },
+}
+
+/// The signature of a function pointer
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Signature {
+    pub return_type: Type,
+    pub params: Vec<Type>,
+}
+
+/// The value of a constant or attribute argument
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(tag = "kind", content = "value", rename_all = "snake_case")
+)]
+pub enum Value {
+    /// A null string, type, array or reference
+    Null,
+    Bool(bool),
+    Char(char),
+    I8(i8),
+    U8(u8),
+    I16(i16),
+    U16(u16),
+    I32(i32),
+    U32(u32),
+    I64(i64),
+    U64(u64),
+    F32(f32),
+    F64(f64),
+    String(String),
+    Type(TypeName),
+    Array(Vec<Value>),
+    /// An enum value along with the enum's type
+    Enum {
+        ty: TypeName,
+        value: Box<Value>,
+    },
+    /// A value passed as `System.Object`
+    Boxed(Box<Value>),
+}
+
+impl Metadata {
+    /// Copies every namespace known to the reader
+    ///
+    /// Returns an [`Error`] if a signature or constant is malformed.
+    pub fn new(reader: &TypeReader) -> Result<Self, Error> {
+        let mut namespaces = Vec::new();
+
+        for name in reader.namespaces() {
+            let mut types = Vec::new();
+
+            for ty in reader.namespace_types(name) {
+                if let crate::Type::TypeDef(def) = ty {
+                    types.push(TypeDefinition::new(&def)?);
+                }
+            }
+
+            namespaces.push(Namespace {
+                name: name.to_string(),
+                types,
+            });
+        }
+
+        Ok(Self {
+            version: SCHEMA_VERSION,
+            namespaces,
+        })
+    }
+}
+
+impl TypeDefinition {
+    /// Copies the type along with its members and nested types
+    ///
+    /// Returns an [`Error`] if a signature or constant is malformed.
+    pub fn new(def: &TypeDef<'_>) -> Result<Self, Error> {
+        let extends = if def.reader.u32(def.row, 3) == 0 {
+            None
+        } else {
+            Some(Type::from_def(&def.extends(), false)?)
+        };
+
+        let mut generics = Vec::new();
+
+        for param in def.generics() {
+            let mut constraints = Vec::new();
+
+            for constraint in param.constraints() {
+                constraints.push(Type::from_def(&constraint.constraint(), false)?);
+            }
+
+            generics.push(GenericParameter {
+                name: param.name().to_string(),
+                flags: param.flags().0,
+                constraints,
+            });
+        }
+
+        let mut interfaces = Vec::new();
+
+        for interface in def.interfaces() {
+            interfaces.push(Interface {
+                ty: Type::from_def(&interface.interface(), false)?,
+                attributes: attributes(interface.attributes()),
+            });
+        }
+
+        let mut fields = Vec::new();
+
+        for field in def.fields() {
+            fields.push(FieldDefinition {
+                name: field.name().to_string(),
+                flags: field.flags().0,
+                ty: Type::from_param(&field.try_signature()?)?,
+                constant: field
+                    .constant()
+                    .map(|constant| constant_value(&constant))
+                    .transpose()?,
+                attributes: attributes(field.attributes()),
+            });
+        }
+
+        let mut methods = Vec::new();
+
+        for method in def.methods() {
+            methods.push(MethodDefinition::new(&method)?);
+        }
+
+        let mut nested_types = Vec::new();
+
+        for nested in def.nested_types() {
+            nested_types.push(Self::new(&nested)?);
+        }
+
+        Ok(Self {
+            name: def.name().1.to_string(),
+            category: def.category(),
+            flags: def.flags().0,
+            guid: def.guid().map(|guid| guid.to_string()),
+            extends,
+            generics,
+            interfaces,
+            attributes: attributes(def.attributes()),
+            fields,
+            methods,
+            nested_types,
+        })
+    }
+}
+
+impl MethodDefinition {
+    /// Copies the method, pairing each parameter in its signature with its `Param` row
+    ///
+    /// Returns an [`Error`] if the signature is malformed.
+    pub fn new(method: &MethodDef<'_>) -> Result<Self, Error> {
+        let sig = method.try_signature()?;
+        let rows: Vec<Param<'_>> = method.params().collect();
+        let mut params = Vec::new();
+
+        for (index, ty) in sig.params.iter().enumerate() {
+            // The return value's parameter, if present, has a sequence of zero.
+            let row = rows
+                .iter()
+                .find(|param| param.sequence() as usize == index + 1);
+
+            params.push(Parameter {
+                name: row.map_or("", |param| param.name()).to_string(),
+                flags: row.map_or(0, |param| param.flags().0),
+                ty: Type::from_param(ty)?,
+                attributes: row.map_or_else(Vec::new, |param| attributes(param.attributes())),
+            });
+        }
+
+        Ok(Self {
+            name: method.name().to_string(),
+            flags: method.flags().0,
+            return_type: Type::from_param(&sig.return_type)?,
+            params,
+            attributes: attributes(method.attributes()),
+            import: method.impl_map().map(|import| Import {
+                module: import.scope().name().to_string(),
+                name: import.import_name().to_string(),
+                flags: import.reader.u32(import.row, 0),
+            }),
+        })
+    }
+}
+
+impl Type {
+    /// Copies a type from a signature
+    ///
+    /// Returns an [`Error`] if the signature of a `TypeSpec` it refers to is malformed.
+    pub fn from_sig(sig: &TypeSig<'_>) -> Result<Self, Error> {
+        Ok(match sig {
+            TypeSig::Void => Self::Void,
+            TypeSig::Bool => Self::Bool,
+            TypeSig::Char => Self::Char,
+            TypeSig::I8 => Self::I8,
+            TypeSig::U8 => Self::U8,
+            TypeSig::I16 => Self::I16,
+            TypeSig::U16 => Self::U16,
+            TypeSig::I32 => Self::I32,
+            TypeSig::U32 => Self::U32,
+            TypeSig::I64 => Self::I64,
+            TypeSig::U64 => Self::U64,
+            TypeSig::F32 => Self::F32,
+            TypeSig::F64 => Self::F64,
+            TypeSig::ISize => Self::ISize,
+            TypeSig::USize => Self::USize,
+            TypeSig::String => Self::String,
+            TypeSig::Object => Self::Object,
+            TypeSig::TypedByRef => Self::TypedByRef,
+            TypeSig::ValueType(def) => Self::from_def(def, true)?,
+            TypeSig::Class(def) => Self::from_def(def, false)?,
+            TypeSig::TypeGeneric(index) => Self::TypeParameter { index: *index },
+            TypeSig::MethodGeneric(index) => Self::MethodParameter { index: *index },
+            TypeSig::GenericInst {
+                def,
+                is_value_type,
+                args,
+            } => {
+                let name = TypeName::from_def(def)?;
+                let mut types = Vec::new();
+
+                for arg in args {
+                    types.push(Self::from_sig(arg)?);
+                }
+
+                Self::Generic {
+                    namespace: name.namespace,
+                    name: name.name,
+                    value_type: *is_value_type,
+                    args: types,
+                }
+            }
+            TypeSig::Ptr(_, ty) => Self::Pointer {
+                element: Box::new(Self::from_sig(ty)?),
+            },
+            TypeSig::SZArray(_, ty) => Self::Array {
+                element: Box::new(Self::from_sig(ty)?),
+            },
+            TypeSig::Array(ty, shape) => Self::MultiArray {
+                element: Box::new(Self::from_sig(ty)?),
+                rank: shape.rank,
+                sizes: shape.sizes.clone(),
+                lower_bounds: shape.lower_bounds.clone(),
+            },
+            TypeSig::FnPtr(sig) => {
+                let mut params = Vec::new();
+
+                for param in &sig.params {
+                    params.push(Self::from_param(param)?);
+                }
+
+                Self::Function {
+                    signature: Box::new(Signature {
+                        return_type: Self::from_param(&sig.return_type)?,
+                        params,
+                    }),
+                }
+            }
+        })
+    }
+
+    /// Copies the type of a parameter, return value or field, wrapping it in a
+    /// [`Type::Reference`] if it is passed by reference
+    pub fn from_param(param: &ParamSig<'_>) -> Result<Self, Error> {
+        let ty = Self::from_sig(&param.ty)?;
+
+        Ok(if param.by_ref {
+            Self::Reference {
+                element: Box::new(ty),
+            }
+        } else {
+            ty
+        })
+    }
+
+    /// Copies a type definition or reference, decoding the signature of a `TypeSpec`
+    fn from_def(def: &TypeDefOrRef<'_>, value_type: bool) -> Result<Self, Error> {
+        match def {
+            TypeDefOrRef::TypeSpec(spec) => Self::from_sig(&spec.try_signature()?),
+            _ => {
+                let (namespace, name) = def.name();
+
+                Ok(Self::Named {
+                    namespace: namespace.to_string(),
+                    name: name.to_string(),
+                    value_type,
+                })
+            }
+        }
+    }
+}
+
+impl TypeName {
+    fn from_def(def: &TypeDefOrRef<'_>) -> Result<Self, Error> {
+        match def {
+            TypeDefOrRef::TypeSpec(spec) => Err(spec
+                .reader
+                .row_error(spec.row, "a generic type must be a TypeDef or TypeRef")),
+            _ => Ok(def.name().into()),
+        }
+    }
+}
+
+impl From<(&str, &str)> for TypeName {
+    fn from((namespace, name): (&str, &str)) -> Self {
+        Self {
+            namespace: namespace.to_string(),
+            name: name.to_string(),
+        }
+    }
+}
+
+impl From<&AttributeArg<'_>> for Value {
+    fn from(arg: &AttributeArg<'_>) -> Self {
+        match arg {
+            AttributeArg::Null => Self::Null,
+            AttributeArg::Bool(value) => Self::Bool(*value),
+            AttributeArg::Char(value) => Self::Char(*value),
+            AttributeArg::I8(value) => Self::I8(*value),
+            AttributeArg::U8(value) => Self::U8(*value),
+            AttributeArg::I16(value) => Self::I16(*value),
+            AttributeArg::U16(value) => Self::U16(*value),
+            AttributeArg::I32(value) => Self::I32(*value),
+            AttributeArg::U32(value) => Self::U32(*value),
+            AttributeArg::I64(value) => Self::I64(*value),
+            AttributeArg::U64(value) => Self::U64(*value),
+            AttributeArg::F32(value) => Self::F32(*value),
+            AttributeArg::F64(value) => Self::F64(*value),
+            AttributeArg::String(value) => Self::String(value.clone()),
+            AttributeArg::TypeDef(def) => Self::Type(def.name().into()),
+            AttributeArg::Array(values) => Self::Array(values.iter().map(Into::into).collect()),
+            AttributeArg::Enum(def, value) => Self::Enum {
+                ty: def.name().into(),
+                value: Box::new(value.as_ref().into()),
+            },
+            AttributeArg::Boxed(value) => Self::Boxed(Box::new(value.as_ref().into())),
+        }
+    }
+}
+
+fn attributes<'a, I: Iterator<Item = crate::Attribute<'a>>>(attributes: I) -> Vec<Attribute> {
+    attributes
+        .map(|attribute| Attribute {
+            ty: attribute.name().into(),
+            args: attribute.try_args().ok().map(|args| {
+                args.iter()
+                    .map(|(name, value)| AttributeArgument {
+                        name: if name.is_empty() {
+                            None
+                        } else {
+                            Some(name.clone())
+                        },
+                        value: value.into(),
+                    })
+                    .collect()
+            }),
+        })
+        .collect()
+}
+
+fn constant_value(constant: &Constant<'_>) -> Result<Value, Error> {
+    let mut value = constant.value();
+
+    Ok(match constant.value_type() {
+        ElementType::Bool => Value::Bool(value.try_read_u8()? != 0),
+        ElementType::Char => {
+            Value::Char(std::char::from_u32(value.try_read_u16()? as u32).unwrap_or('\u{fffd}'))
+        }
+        ElementType::I8 => Value::I8(value.try_read_i8()?),
+        ElementType::U8 => Value::U8(value.try_read_u8()?),
+        ElementType::I16 => Value::I16(value.try_read_i16()?),
+        ElementType::U16 => Value::U16(value.try_read_u16()?),
+        ElementType::I32 => Value::I32(value.try_read_i32()?),
+        ElementType::U32 => Value::U32(value.try_read_u32()?),
+        ElementType::I64 => Value::I64(value.try_read_i64()?),
+        ElementType::U64 => Value::U64(value.try_read_u64()?),
+        ElementType::F32 => Value::F32(value.try_read_f32()?),
+        ElementType::F64 => Value::F64(value.try_read_f64()?),
+        ElementType::String => Value::String(value.read_utf16()),
+        _ => Value::Null,
+    })
+}
@@ -0,0 +1,90 @@
+use super::*;
+use crate::TypeReader;
+
+#[derive(Copy, Clone)]
+pub struct Assembly<'a> {
+    pub reader: &'a TypeReader,
+    pub row: Row,
+}
+
+/// The four-part version of an assembly
+#[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Version {
+    pub major: u16,
+    pub minor: u16,
+    pub build: u16,
+    pub revision: u16,
+}
+
+impl Version {
+    pub(crate) fn from_u64(value: u64) -> Self {
+        Self {
+            major: value as u16,
+            minor: (value >> 16) as u16,
+            build: (value >> 32) as u16,
+            revision: (value >> 48) as u16,
+        }
+    }
+}
+
+impl std::fmt::Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}.{}.{}.{}",
+            self.major, self.minor, self.build, self.revision
+        )
+    }
+}
+
+impl<'a> Assembly<'a> {
+    pub fn hash_algorithm(&self) -> u32 {
+        self.reader.u32(self.row, 0)
+    }
+
+    pub fn version(&self) -> Version {
+        Version::from_u64(self.reader.u64(self.row, 1))
+    }
+
+    pub fn flags(&self) -> AssemblyFlags {
+        AssemblyFlags(self.reader.u32(self.row, 2))
+    }
+
+    pub fn public_key(&self) -> &'a [u8] {
+        self.reader.blob_bytes(self.row, 3)
+    }
+
+    pub fn name(&self) -> &'a str {
+        self.reader.str(self.row, 4)
+    }
+
+    pub fn culture(&self) -> &'a str {
+        self.reader.str(self.row, 5)
+    }
+}
+
+impl std::fmt::Debug for Assembly<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Assembly").field("row", &self.row).finish()
+    }
+}
+
+impl PartialEq for Assembly<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.row == other.row
+    }
+}
+
+impl Eq for Assembly<'_> {}
+
+impl Ord for Assembly<'_> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.row.cmp(&other.row)
+    }
+}
+
+impl PartialOrd for Assembly<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
@@ -0,0 +1,63 @@
+use super::*;
+use crate::TypeReader;
+
+#[derive(Copy, Clone)]
+pub struct AssemblyRef<'a> {
+    pub reader: &'a TypeReader,
+    pub row: Row,
+}
+
+impl<'a> AssemblyRef<'a> {
+    pub fn version(&self) -> Version {
+        Version::from_u64(self.reader.u64(self.row, 0))
+    }
+
+    pub fn flags(&self) -> AssemblyFlags {
+        AssemblyFlags(self.reader.u32(self.row, 1))
+    }
+
+    /// The full public key or its token, depending on [`AssemblyFlags::public_key`]
+    pub fn public_key_or_token(&self) -> &'a [u8] {
+        self.reader.blob_bytes(self.row, 2)
+    }
+
+    pub fn name(&self) -> &'a str {
+        self.reader.str(self.row, 3)
+    }
+
+    pub fn culture(&self) -> &'a str {
+        self.reader.str(self.row, 4)
+    }
+
+    pub fn hash_value(&self) -> &'a [u8] {
+        self.reader.blob_bytes(self.row, 5)
+    }
+}
+
+impl std::fmt::Debug for AssemblyRef<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AssemblyRef")
+            .field("row", &self.row)
+            .finish()
+    }
+}
+
+impl PartialEq for AssemblyRef<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.row == other.row
+    }
+}
+
+impl Eq for AssemblyRef<'_> {}
+
+impl Ord for AssemblyRef<'_> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.row.cmp(&other.row)
+    }
+}
+
+impl PartialOrd for AssemblyRef<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
@@ -1,158 +1,471 @@
 use super::*;
-use crate::TypeReader;
+use crate::{Error, TypeReader};
+
+/// How deeply array and boxed arguments may nest before a blob is considered malformed
+const MAX_ARG_DEPTH: usize = 8;
 
 #[derive(Copy, Clone)]
-pub struct Attribute {
-    pub reader: &'static TypeReader,
+pub struct Attribute<'a> {
+    pub reader: &'a TypeReader,
     pub row: Row,
 }
 
-impl Attribute {
-    pub fn constructor(&self) -> AttributeType {
+impl<'a> Attribute<'a> {
+    pub fn constructor(&self) -> AttributeType<'a> {
         self.reader.decode(self.row, 1)
     }
 
-    pub fn name(&self) -> (&'static str, &'static str) {
-        if let AttributeType::MemberRef(method) = self.constructor() {
-            return method.parent().name();
+    pub fn name(&self) -> (&'a str, &'a str) {
+        match self.constructor() {
+            AttributeType::MethodDef(method) => method.parent().name(),
+            AttributeType::MemberRef(method) => method.parent().name(),
         }
+    }
 
-        panic!("Attribute.name");
+    /// Decodes the attribute's fixed and named arguments
+    ///
+    /// # Panics
+    ///
+    /// Panics if the attribute blob is malformed. Use [`Attribute::try_args`] to handle errors instead.
+    pub fn args(&self) -> Vec<(String, AttributeArg<'a>)> {
+        self.try_args().unwrap_or_else(|error| panic!("{}", error))
     }
 
-    pub fn args(&self) -> Vec<(String, AttributeArg)> {
+    /// Decodes the attribute's fixed and named arguments
+    pub fn try_args(&self) -> Result<Vec<(String, AttributeArg<'a>)>, Error> {
         let (mut sig, mut values) = match self.constructor() {
             AttributeType::MethodDef(method) => (
-                self.reader.blob(method.row, 4),
-                self.reader.blob(self.row, 2),
+                self.reader.try_blob(method.row, 4)?,
+                self.reader.try_blob(self.row, 2)?,
             ),
             AttributeType::MemberRef(method) => (
-                self.reader.blob(method.row, 2),
-                self.reader.blob(self.row, 2),
+                self.reader.try_blob(method.row, 2)?,
+                self.reader.try_blob(self.row, 2)?,
             ),
         };
 
-        let prolog = values.read_u16();
-        debug_assert!(prolog == 0x0001, "CustomAttribute Prolog must be 0x0001"); // Required by spec.
-
-        let _this_and_gen_param_count = sig.read_unsigned();
-        let fixed_arg_count = sig.read_unsigned();
-        let _ret_type = sig.read_unsigned();
-
-        let mut args: Vec<(String, AttributeArg)> = Vec::with_capacity(fixed_arg_count as usize);
-
-        for _ in 0..fixed_arg_count {
-            let arg = match ElementType::from_blob(&mut sig) {
-                ElementType::I8 => AttributeArg::I8(values.read_i8()),
-                ElementType::U8 => AttributeArg::U8(values.read_u8()),
-                ElementType::I16 => AttributeArg::I16(values.read_i16()),
-                ElementType::U16 => AttributeArg::U16(values.read_u16()),
-                ElementType::I32 => AttributeArg::I32(values.read_i32()),
-                ElementType::U32 => AttributeArg::U32(values.read_u32()),
-                ElementType::I64 => AttributeArg::I64(values.read_i64()),
-                ElementType::U64 => AttributeArg::U64(values.read_u64()),
-                ElementType::String => AttributeArg::String(values.read_str().to_string()),
-                ElementType::Struct(type_def_or_ref) | ElementType::Class(type_def_or_ref) => {
-                    let (namespace, type_name) = match type_def_or_ref {
-                        TypeDefOrRef::TypeDef(type_def) => type_def.name(),
-                        TypeDefOrRef::TypeRef(type_ref) => type_ref.name(),
-                        _ => panic!("Expected a TypeDef or TypeRef"),
-                    };
-
-                    if namespace == "System" && type_name == "Type" {
-                        let name = values.read_str();
-                        let index = name.rfind('.').unwrap();
-                        AttributeArg::TypeDef(
-                            self.reader
-                                .expect_type_def((&name[0..index], &name[index + 1..])),
-                        )
-                    } else {
-                        let def = match type_def_or_ref {
-                            TypeDefOrRef::TypeRef(value) => {
-                                self.reader.expect_type_def(value.name())
-                            }
-                            TypeDefOrRef::TypeDef(value) => value,
-                            TypeDefOrRef::TypeSpec(_) => panic!("Unsupported underlying type"),
-                        };
-
-                        let underlying_type = def.underlying_type();
-                        read_enum(&underlying_type, &mut values)
-                    }
-                }
-                _ => panic!("Unexpected fixed attribute argument type"),
-            };
+        // Required by spec.
+        if values.try_read_u16()? != 0x0001 {
+            return Err(values.error("CustomAttribute Prolog must be 0x0001"));
+        }
 
-            args.push((String::new(), arg));
+        let sig = MethodSig::try_from_blob(&mut sig)?;
+        let mut args = Vec::with_capacity(sig.params.len());
+
+        for param in &sig.params {
+            let arg_type = self.try_fixed_arg_type(&param.ty, &values, 0)?;
+            args.push((String::new(), self.try_read_arg(&arg_type, &mut values, 0)?));
         }
 
-        let named_arg_count = values.read_u16();
+        let named_arg_count = values.try_read_u16()?;
         args.reserve(named_arg_count as usize);
 
         for _ in 0..named_arg_count {
-            let id = values.read_u8();
-            debug_assert!(
-                id == 0x53 || id == 0x54,
-                "A NamedArg must start with an id of 0x53 (Field) or 0x54 (Property)"
-            );
-            let arg_type = values.read_u8();
-            let name = values.read_str().to_string();
-            let arg = match arg_type {
-                0x02 => AttributeArg::Bool(values.read_u8() != 0),
-                0x08 => AttributeArg::I32(values.read_i32()),
-                0x0E => AttributeArg::String(values.read_str().to_string()),
-                0x50 => {
-                    let name = values.read_str();
-                    let index = name.rfind('.').unwrap();
-                    AttributeArg::TypeDef(
-                        self.reader
-                            .expect_type_def((&name[0..index], &name[index + 1..])),
-                    )
-                }
-                _ => panic!("Unexpected named attribute argument type"),
-            };
-            args.push((name, arg));
+            let id = values.try_read_u8()?;
+
+            if id != 0x53 && id != 0x54 {
+                return Err(values
+                    .error("A NamedArg must start with an id of 0x53 (Field) or 0x54 (Property)"));
+            }
+
+            let arg_type = self.try_named_arg_type(&mut values, 0)?;
+
+            let name = try_read_ser_string(&mut values)?
+                .ok_or_else(|| values.error("A NamedArg must have a name"))?
+                .to_string();
+
+            args.push((name, self.try_read_arg(&arg_type, &mut values, 0)?));
         }
 
-        args
+        Ok(args)
+    }
+
+    /// Maps the type of a constructor parameter to the encoding of its fixed argument
+    fn try_fixed_arg_type(
+        &self,
+        sig: &TypeSig<'a>,
+        values: &Blob<'a>,
+        depth: usize,
+    ) -> Result<ArgType<'a>, Error> {
+        check_depth(values, depth)?;
+
+        let arg_type = match sig {
+            TypeSig::Bool => ArgType::Bool,
+            TypeSig::Char => ArgType::Char,
+            TypeSig::I8 => ArgType::I8,
+            TypeSig::U8 => ArgType::U8,
+            TypeSig::I16 => ArgType::I16,
+            TypeSig::U16 => ArgType::U16,
+            TypeSig::I32 => ArgType::I32,
+            TypeSig::U32 => ArgType::U32,
+            TypeSig::I64 => ArgType::I64,
+            TypeSig::U64 => ArgType::U64,
+            TypeSig::F32 => ArgType::F32,
+            TypeSig::F64 => ArgType::F64,
+            TypeSig::String => ArgType::String,
+            TypeSig::Object => ArgType::Boxed,
+            TypeSig::SZArray(_, element) => ArgType::Array(Box::new(self.try_fixed_arg_type(
+                element,
+                values,
+                depth + 1,
+            )?)),
+            TypeSig::ValueType(type_def_or_ref) | TypeSig::Class(type_def_or_ref) => {
+                let def = match type_def_or_ref {
+                    TypeDefOrRef::TypeDef(value) => *value,
+                    TypeDefOrRef::TypeRef(value) => match value.name() {
+                        ("System", "Type") => return Ok(ArgType::Type),
+                        ("System", "Object") => return Ok(ArgType::Boxed),
+                        // The enum may live in metadata that isn't loaded, such as the
+                        // `Windows.Foundation.Metadata` attributes' own enums, so fall back to
+                        // the `Int32` underlying type that all WinRT enums share.
+                        name => match self.reader.find_type_def(name) {
+                            Some(def) => def,
+                            None => return Ok(ArgType::I32),
+                        },
+                    },
+                    TypeDefOrRef::TypeSpec(_) => {
+                        return Err(values.error("unsupported fixed attribute argument type"))
+                    }
+                };
+
+                if def.category() != TypeCategory::Enum {
+                    return Err(values.error(format!(
+                        "fixed attribute argument type `{}.{}` is not an enum",
+                        def.name().0,
+                        def.name().1
+                    )));
+                }
+
+                ArgType::Enum(def)
+            }
+            _ => return Err(values.error("unexpected fixed attribute argument type")),
+        };
+
+        Ok(arg_type)
     }
+
+    /// Reads the `FieldOrPropType` encoding of a named or boxed argument
+    fn try_named_arg_type(
+        &self,
+        values: &mut Blob<'a>,
+        depth: usize,
+    ) -> Result<ArgType<'a>, Error> {
+        check_depth(values, depth)?;
+
+        let arg_type = match values.try_read_u8()? {
+            0x02 => ArgType::Bool,
+            0x03 => ArgType::Char,
+            0x04 => ArgType::I8,
+            0x05 => ArgType::U8,
+            0x06 => ArgType::I16,
+            0x07 => ArgType::U16,
+            0x08 => ArgType::I32,
+            0x09 => ArgType::U32,
+            0x0a => ArgType::I64,
+            0x0b => ArgType::U64,
+            0x0c => ArgType::F32,
+            0x0d => ArgType::F64,
+            0x0e => ArgType::String,
+            0x1d => ArgType::Array(Box::new(self.try_named_arg_type(values, depth + 1)?)),
+            0x50 => ArgType::Type,
+            0x51 => ArgType::Boxed,
+            0x55 => {
+                let name = try_read_ser_string(values)?
+                    .ok_or_else(|| values.error("An enum argument must have a type name"))?;
+
+                // As with fixed arguments, an enum that can't be found is read as `Int32`.
+                match self.try_find_type_def(values, name) {
+                    Ok(def) => ArgType::Enum(def),
+                    Err(_) => ArgType::I32,
+                }
+            }
+            code => {
+                return Err(values.error(format!(
+                    "unexpected named attribute argument type 0x{:x}",
+                    code
+                )))
+            }
+        };
+
+        Ok(arg_type)
+    }
+
+    fn try_read_arg(
+        &self,
+        arg_type: &ArgType<'a>,
+        values: &mut Blob<'a>,
+        depth: usize,
+    ) -> Result<AttributeArg<'a>, Error> {
+        check_depth(values, depth)?;
+
+        let arg = match arg_type {
+            ArgType::Bool => AttributeArg::Bool(values.try_read_u8()? != 0),
+            ArgType::Char => {
+                let value = values.try_read_u16()?;
+
+                AttributeArg::Char(
+                    std::char::from_u32(value as u32).ok_or_else(|| {
+                        values.error(format!("invalid char argument 0x{:x}", value))
+                    })?,
+                )
+            }
+            ArgType::I8 => AttributeArg::I8(values.try_read_i8()?),
+            ArgType::U8 => AttributeArg::U8(values.try_read_u8()?),
+            ArgType::I16 => AttributeArg::I16(values.try_read_i16()?),
+            ArgType::U16 => AttributeArg::U16(values.try_read_u16()?),
+            ArgType::I32 => AttributeArg::I32(values.try_read_i32()?),
+            ArgType::U32 => AttributeArg::U32(values.try_read_u32()?),
+            ArgType::I64 => AttributeArg::I64(values.try_read_i64()?),
+            ArgType::U64 => AttributeArg::U64(values.try_read_u64()?),
+            ArgType::F32 => AttributeArg::F32(values.try_read_f32()?),
+            ArgType::F64 => AttributeArg::F64(values.try_read_f64()?),
+            ArgType::String => match try_read_ser_string(values)? {
+                Some(value) => AttributeArg::String(value.to_string()),
+                None => AttributeArg::Null,
+            },
+            ArgType::Type => match try_read_ser_string(values)? {
+                Some(name) => AttributeArg::TypeDef(self.try_find_type_def(values, name)?),
+                None => AttributeArg::Null,
+            },
+            ArgType::Boxed => {
+                let arg_type = self.try_named_arg_type(values, depth + 1)?;
+                AttributeArg::Boxed(Box::new(self.try_read_arg(&arg_type, values, depth + 1)?))
+            }
+            ArgType::Enum(def) => {
+                let value = read_enum(&def.underlying_type(), values)?;
+                AttributeArg::Enum(*def, Box::new(value))
+            }
+            ArgType::Array(element) => {
+                let len = values.try_read_u32()?;
+
+                // A length of 0xFFFFFFFF denotes a null array.
+                if len == 0xFFFF_FFFF {
+                    AttributeArg::Null
+                } else {
+                    let mut array = Vec::new();
+
+                    for _ in 0..len {
+                        array.push(self.try_read_arg(element, values, depth + 1)?);
+                    }
+
+                    AttributeArg::Array(array)
+                }
+            }
+        };
+
+        Ok(arg)
+    }
+
+    /// Finds a type by its serialized name, ignoring any assembly qualification
+    fn try_find_type_def(&self, values: &Blob<'a>, name: &str) -> Result<TypeDef<'a>, Error> {
+        let name = name.split(',').next().unwrap_or_default().trim();
+
+        let index = name
+            .rfind('.')
+            .ok_or_else(|| values.error(format!("invalid type name `{}`", name)))?;
+
+        self.reader
+            .find_type_def((&name[0..index], &name[index + 1..]))
+            .ok_or_else(|| values.error(format!("could not find type def `{}`", name)))
+    }
+}
+
+/// The encoding of an attribute argument's value
+enum ArgType<'a> {
+    Bool,
+    Char,
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    I64,
+    U64,
+    F32,
+    F64,
+    String,
+    Type,
+    Boxed,
+    Enum(TypeDef<'a>),
+    Array(Box<ArgType<'a>>),
 }
 
-fn read_enum(element_type: &ElementType, blob: &mut Blob) -> AttributeArg {
+fn check_depth(values: &Blob, depth: usize) -> Result<(), Error> {
+    if depth > MAX_ARG_DEPTH {
+        Err(values.error("attribute argument is nested too deeply"))
+    } else {
+        Ok(())
+    }
+}
+
+/// Reads a `SerString`, which is `None` when the string is null
+fn try_read_ser_string<'a>(blob: &mut Blob<'a>) -> Result<Option<&'a str>, Error> {
+    if blob.try_read_expected_u8(0xFF)? {
+        Ok(None)
+    } else {
+        Ok(Some(blob.try_read_str()?))
+    }
+}
+
+fn read_enum<'a>(
+    element_type: &ElementType<'a>,
+    blob: &mut Blob<'a>,
+) -> Result<AttributeArg<'a>, Error> {
     match element_type {
-        ElementType::I8 => AttributeArg::I8(blob.read_i8()),
-        ElementType::U8 => AttributeArg::U8(blob.read_u8()),
-        ElementType::I16 => AttributeArg::I16(blob.read_i16()),
-        ElementType::U16 => AttributeArg::U16(blob.read_u16()),
-        ElementType::I32 => AttributeArg::I32(blob.read_i32()),
-        ElementType::U32 => AttributeArg::U32(blob.read_u32()),
-        ElementType::I64 => AttributeArg::I64(blob.read_i64()),
-        ElementType::U64 => AttributeArg::U64(blob.read_u64()),
-        _ => panic!("Invalid underlying enum type encountered!"),
+        ElementType::I8 => Ok(AttributeArg::I8(blob.try_read_i8()?)),
+        ElementType::U8 => Ok(AttributeArg::U8(blob.try_read_u8()?)),
+        ElementType::I16 => Ok(AttributeArg::I16(blob.try_read_i16()?)),
+        ElementType::U16 => Ok(AttributeArg::U16(blob.try_read_u16()?)),
+        ElementType::I32 => Ok(AttributeArg::I32(blob.try_read_i32()?)),
+        ElementType::U32 => Ok(AttributeArg::U32(blob.try_read_u32()?)),
+        ElementType::I64 => Ok(AttributeArg::I64(blob.try_read_i64()?)),
+        ElementType::U64 => Ok(AttributeArg::U64(blob.try_read_u64()?)),
+        _ => Err(blob.error("invalid underlying enum type encountered")),
     }
 }
 
-impl std::fmt::Debug for Attribute {
+impl std::fmt::Debug for Attribute<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Attribute").field("row", &self.row).finish()
     }
 }
 
-impl PartialEq for Attribute {
+impl PartialEq for Attribute<'_> {
     fn eq(&self, other: &Self) -> bool {
         self.row == other.row
     }
 }
 
-impl Eq for Attribute {}
+impl Eq for Attribute<'_> {}
 
-impl Ord for Attribute {
+impl Ord for Attribute<'_> {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         self.row.cmp(&other.row)
     }
 }
 
-impl PartialOrd for Attribute {
+impl PartialOrd for Attribute<'_> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TableIndex;
+
+    fn read_named(bytes: &[u8]) -> Result<AttributeArg<'static>, Error> {
+        let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("../../.windows/winmd/TestComponent.winmd");
+
+        let mut file = std::fs::read(path).unwrap();
+        let offset = file.len();
+        file.extend_from_slice(bytes);
+
+        let reader = TypeReader::builder()
+            .bytes("TestComponent.winmd", file)
+            .exclude_default(true)
+            .build()
+            .unwrap();
+        let reader: &'static TypeReader = Box::leak(Box::new(reader));
+
+        let mut blob = Blob {
+            reader,
+            file_index: 0,
+            row: Row::new(0, TableIndex::CustomAttribute, 0),
+            offset,
+            size: bytes.len(),
+        };
+
+        let attribute = Attribute {
+            reader,
+            row: Row::new(0, TableIndex::CustomAttribute, 0),
+        };
+
+        let arg_type = attribute.try_named_arg_type(&mut blob, 0)?;
+        attribute.try_read_arg(&arg_type, &mut blob, 0)
+    }
+
+    #[test]
+    fn primitives() {
+        assert_eq!(read_named(&[0x02, 0x01]).unwrap(), AttributeArg::Bool(true));
+        assert_eq!(
+            read_named(&[0x03, 0x41, 0x00]).unwrap(),
+            AttributeArg::Char('A')
+        );
+        assert_eq!(
+            read_named(&[0x0c, 0x00, 0x00, 0x80, 0x3f]).unwrap(),
+            AttributeArg::F32(1.0)
+        );
+        assert_eq!(
+            read_named(&[0x0d, 0, 0, 0, 0, 0, 0, 0xf0, 0xbf]).unwrap(),
+            AttributeArg::F64(-1.0)
+        );
+    }
+
+    #[test]
+    fn strings() {
+        assert_eq!(
+            read_named(&[0x0e, 0x02, b'h', b'i']).unwrap(),
+            AttributeArg::String("hi".to_string())
+        );
+        assert_eq!(read_named(&[0x0e, 0xff]).unwrap(), AttributeArg::Null);
+        assert_eq!(read_named(&[0x50, 0xff]).unwrap(), AttributeArg::Null);
+    }
+
+    #[test]
+    fn types() {
+        let mut bytes = vec![0x50, 0x18];
+        bytes.extend_from_slice(b"TestComponent.Composable");
+
+        match read_named(&bytes).unwrap() {
+            AttributeArg::TypeDef(def) => assert_eq!(def.name(), ("TestComponent", "Composable")),
+            arg => panic!("expected a type def but found {:?}", arg),
+        }
+    }
+
+    #[test]
+    fn arrays() {
+        assert_eq!(
+            read_named(&[0x1d, 0x05, 0x02, 0x00, 0x00, 0x00, 0x07, 0x09]).unwrap(),
+            AttributeArg::Array(vec![AttributeArg::U8(7), AttributeArg::U8(9)])
+        );
+        assert_eq!(
+            read_named(&[0x1d, 0x08, 0xff, 0xff, 0xff, 0xff]).unwrap(),
+            AttributeArg::Null
+        );
+    }
+
+    #[test]
+    fn boxed() {
+        assert_eq!(
+            read_named(&[0x51, 0x06, 0xfe, 0xff]).unwrap(),
+            AttributeArg::Boxed(Box::new(AttributeArg::I16(-2)))
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        // Unpaired surrogate
+        assert!(read_named(&[0x03, 0x00, 0xd8]).is_err());
+        // Truncated array
+        assert!(read_named(&[0x1d, 0x08, 0x02, 0x00, 0x00, 0x00, 0x01]).is_err());
+        // Unknown type code
+        assert!(read_named(&[0x42]).is_err());
+        // Arrays and boxes nested without end
+        assert!(read_named(&[0x1d; 64]).is_err());
+        assert!(read_named(&[0x51; 64]).is_err());
+    }
+
+    #[test]
+    fn unknown_enum() {
+        // An enum that isn't loaded is read as its `Int32` underlying type.
+        assert_eq!(
+            read_named(&[0x55, 0x05, b'A', b'.', b'B', b'C', b'D', 0x2a, 0x00, 0x00, 0x00])
+                .unwrap(),
+            AttributeArg::I32(42)
+        );
+    }
+}
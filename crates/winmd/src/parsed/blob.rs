@@ -2,40 +2,149 @@ use crate::*;
 
 use std::convert::TryInto;
 
-pub struct Blob {
-    pub reader: &'static TypeReader,
+macro_rules! read_primitive {
+    ($try_name:ident, $name:ident, $t:ty) => {
+        pub fn $try_name(&mut self) -> Result<$t, Error> {
+            const SIZE: usize = std::mem::size_of::<$t>();
+            let value = <$t>::from_le_bytes(self.try_bytes(SIZE)?.try_into().unwrap());
+            self.offset += SIZE;
+            Ok(value)
+        }
+
+        pub fn $name(&mut self) -> $t {
+            self.$try_name().unwrap_or_else(|error| panic!("{}", error))
+        }
+    };
+}
+
+pub struct Blob<'a> {
+    pub reader: &'a TypeReader,
     pub file_index: u16,
+    /// The row that the blob was read from
+    pub row: Row,
     pub offset: usize,
     pub size: usize,
 }
 
-impl Blob {
-    fn bytes(&self) -> &'static [u8] {
+impl<'a> Blob<'a> {
+    fn bytes(&self) -> &'a [u8] {
         &self.reader.files[self.file_index as usize].bytes[self.offset..]
     }
 
-    pub fn peek_unsigned(&self) -> (u32, usize) {
+    fn try_bytes(&self, len: usize) -> Result<&'a [u8], Error> {
         let bytes = self.bytes();
 
+        if bytes.len() < len {
+            Err(self.error(format!("unexpected end of blob at offset {}", self.offset)))
+        } else {
+            Ok(&bytes[..len])
+        }
+    }
+
+    /// Creates an [`Error`] describing a malformed value in this blob
+    pub fn error<M: Into<String>>(&self, message: M) -> Error {
+        self.reader.row_error(self.row, message)
+    }
+
+    pub fn peek_unsigned(&self) -> (u32, usize) {
+        self.try_peek_unsigned()
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_peek_unsigned(&self) -> Result<(u32, usize), Error> {
+        let bytes = self.try_bytes(1)?;
+
         if bytes[0] & 0x80 == 0 {
-            (bytes[0] as u32, 1)
+            Ok((bytes[0] as u32, 1))
         } else if bytes[0] & 0xC0 == 0x80 {
-            ((((bytes[0] & 0x3F) as u32) << 8) | bytes[1] as u32, 2)
+            let bytes = self.try_bytes(2)?;
+            Ok(((((bytes[0] & 0x3F) as u32) << 8) | bytes[1] as u32, 2))
         } else {
-            (
+            let bytes = self.try_bytes(4)?;
+            Ok((
                 (((bytes[0] & 0x1F) as u32) << 24)
                     | (bytes[1] as u32) << 16
                     | (bytes[2] as u32) << 8
                     | bytes[3] as u32,
                 4,
-            )
+            ))
         }
     }
 
     pub fn read_unsigned(&mut self) -> u32 {
-        let (value, offset) = self.peek_unsigned();
+        self.try_read_unsigned()
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_read_unsigned(&mut self) -> Result<u32, Error> {
+        let (value, offset) = self.try_peek_unsigned()?;
+        self.offset += offset;
+        Ok(value)
+    }
+
+    /// Reads a compressed signed integer
+    pub fn try_read_signed(&mut self) -> Result<i32, Error> {
+        let (value, offset) = self.try_peek_unsigned()?;
         self.offset += offset;
-        value
+
+        // The sign bit is rotated into the least significant bit.
+        let bits = match offset {
+            1 => 6,
+            2 => 13,
+            _ => 28,
+        };
+
+        if value & 1 == 0 {
+            Ok((value >> 1) as i32)
+        } else {
+            Ok((value >> 1) as i32 - (1 << bits))
+        }
+    }
+
+    /// Reads a `TypeDefOrRef` coded index, checking that it refers to a valid row
+    pub fn try_read_type_def_or_ref(&mut self) -> Result<TypeDefOrRef<'a>, Error> {
+        let code = self.try_read_unsigned()?;
+
+        let table = match code & 3 {
+            0 => TableIndex::TypeDef,
+            1 => TableIndex::TypeRef,
+            2 => TableIndex::TypeSpec,
+            _ => return Err(self.error(format!("invalid TypeDefOrRef code 0x{:x}", code))),
+        };
+
+        let row_count =
+            self.reader.files[self.file_index as usize].tables[table as usize].row_count;
+
+        if code >> 2 == 0 || code >> 2 > row_count {
+            return Err(self.error(format!(
+                "TypeDefOrRef code 0x{:x} refers to a missing {:?} row",
+                code, table
+            )));
+        }
+
+        Ok(TypeDefOrRef::decode(self.reader, code, self.file_index))
+    }
+
+    pub fn try_read_expected(&mut self, expected: u32) -> Result<bool, Error> {
+        let (value, offset) = self.try_peek_unsigned()?;
+
+        if value == expected {
+            self.offset += offset;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Consumes the next byte if it has the expected value, without decoding it as a
+    /// compressed integer
+    pub fn try_read_expected_u8(&mut self, expected: u8) -> Result<bool, Error> {
+        if self.try_bytes(1)?[0] == expected {
+            self.offset += 1;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
     }
 
     pub fn read_expected(&mut self, expected: u32) -> bool {
@@ -48,7 +157,7 @@ impl Blob {
         }
     }
 
-    pub fn read_modifiers(&mut self) -> Vec<TypeDefOrRef> {
+    pub fn read_modifiers(&mut self) -> Vec<TypeDefOrRef<'a>> {
         let mut mods = vec![];
 
         loop {
@@ -68,13 +177,18 @@ impl Blob {
         mods
     }
 
-    pub fn read_str(&mut self) -> &'static str {
-        let len = self.read_unsigned() as usize;
+    pub fn read_str(&mut self) -> &'a str {
+        self.try_read_str()
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_read_str(&mut self) -> Result<&'a str, Error> {
+        let len = self.try_read_unsigned()? as usize;
+        let bytes = self.try_bytes(len)?;
+        let value = std::str::from_utf8(bytes)
+            .map_err(|_| self.error(format!("string at offset {} is not utf-8", self.offset)))?;
         self.offset += len;
-        std::str::from_utf8(
-            &self.reader.files[self.file_index as usize].bytes[self.offset - len..self.offset],
-        )
-        .unwrap()
+        Ok(value)
     }
 
     pub fn read_utf16(&self) -> String {
@@ -97,63 +211,14 @@ impl Blob {
         }
     }
 
-    pub fn read_i8(&mut self) -> i8 {
-        let value = i8::from_le_bytes(self.bytes()[..1].try_into().unwrap());
-        self.offset += 1;
-        value
-    }
-
-    pub fn read_u8(&mut self) -> u8 {
-        let value = u8::from_le_bytes(self.bytes()[..1].try_into().unwrap());
-        self.offset += 1;
-        value
-    }
-
-    pub fn read_i16(&mut self) -> i16 {
-        let value = i16::from_le_bytes(self.bytes()[..2].try_into().unwrap());
-        self.offset += 2;
-        value
-    }
-
-    pub fn read_u16(&mut self) -> u16 {
-        let value = u16::from_le_bytes(self.bytes()[..2].try_into().unwrap());
-        self.offset += 2;
-        value
-    }
-
-    pub fn read_i32(&mut self) -> i32 {
-        let value = i32::from_le_bytes(self.bytes()[..4].try_into().unwrap());
-        self.offset += 4;
-        value
-    }
-
-    pub fn read_u32(&mut self) -> u32 {
-        let value = u32::from_le_bytes(self.bytes()[..4].try_into().unwrap());
-        self.offset += 4;
-        value
-    }
-
-    pub fn read_i64(&mut self) -> i64 {
-        let value = i64::from_le_bytes(self.bytes()[..8].try_into().unwrap());
-        self.offset += 8;
-        value
-    }
-
-    pub fn read_u64(&mut self) -> u64 {
-        let value = u64::from_le_bytes(self.bytes()[..8].try_into().unwrap());
-        self.offset += 8;
-        value
-    }
-
-    pub fn read_f32(&mut self) -> f32 {
-        let value = f32::from_le_bytes(self.bytes()[..4].try_into().unwrap());
-        self.offset += 4;
-        value
-    }
-
-    pub fn read_f64(&mut self) -> f64 {
-        let value = f64::from_le_bytes(self.bytes()[..8].try_into().unwrap());
-        self.offset += 8;
-        value
-    }
+    read_primitive!(try_read_i8, read_i8, i8);
+    read_primitive!(try_read_u8, read_u8, u8);
+    read_primitive!(try_read_i16, read_i16, i16);
+    read_primitive!(try_read_u16, read_u16, u16);
+    read_primitive!(try_read_i32, read_i32, i32);
+    read_primitive!(try_read_u32, read_u32, u32);
+    read_primitive!(try_read_i64, read_i64, i64);
+    read_primitive!(try_read_u64, read_u64, u64);
+    read_primitive!(try_read_f32, read_f32, f32);
+    read_primitive!(try_read_f64, read_f64, f64);
 }
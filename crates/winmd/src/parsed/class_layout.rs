@@ -0,0 +1,59 @@
+use super::*;
+use crate::{TableIndex, TypeReader};
+
+#[derive(Copy, Clone)]
+pub struct ClassLayout<'a> {
+    pub reader: &'a TypeReader,
+    pub row: Row,
+}
+
+impl<'a> ClassLayout<'a> {
+    /// The alignment of the type's fields in bytes, or zero if the default alignment applies
+    pub fn packing_size(&self) -> u32 {
+        self.reader.u32(self.row, 0)
+    }
+
+    /// The size of the type in bytes, or zero if the size is computed from its fields
+    pub fn class_size(&self) -> u32 {
+        self.reader.u32(self.row, 1)
+    }
+
+    pub fn parent(&self) -> TypeDef<'a> {
+        TypeDef {
+            reader: self.reader,
+            row: Row::new(
+                self.reader.u32(self.row, 2) - 1,
+                TableIndex::TypeDef,
+                self.row.file_index,
+            ),
+        }
+    }
+}
+
+impl std::fmt::Debug for ClassLayout<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClassLayout")
+            .field("row", &self.row)
+            .finish()
+    }
+}
+
+impl PartialEq for ClassLayout<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.row == other.row
+    }
+}
+
+impl Eq for ClassLayout<'_> {}
+
+impl Ord for ClassLayout<'_> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.row.cmp(&other.row)
+    }
+}
+
+impl PartialOrd for ClassLayout<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
@@ -4,20 +4,20 @@ use crate::{traits::Decode, TableIndex, TypeReader};
 use windows_winmd_macros::type_code;
 
 #[type_code(2)]
-pub enum TypeDefOrRef {
+pub enum TypeDefOrRef<'a> {
     TypeDef,
     TypeRef,
     TypeSpec,
 }
 
 #[type_code(1)]
-pub enum TypeOrMethodDef {
+pub enum TypeOrMethodDef<'a> {
     TypeDef,
     MethodDef,
 }
 
 #[type_code(5)]
-pub enum HasAttribute {
+pub enum HasAttribute<'a> {
     MethodDef,
     Field,
     TypeRef,
@@ -25,12 +25,15 @@ pub enum HasAttribute {
     Param,
     InterfaceImpl,
     MemberRef,
+    Property = 9,
+    Event,
     TypeSpec = 13,
     GenericParam = 19,
+    GenericParamConstraint,
 }
 
 #[type_code(3)]
-pub enum MemberRefParent {
+pub enum MemberRefParent<'a> {
     TypeDef,
     TypeRef,
     MethodDef = 3,
@@ -38,25 +41,40 @@ pub enum MemberRefParent {
 }
 
 #[type_code(2)]
-pub enum HasConstant {
+pub enum HasConstant<'a> {
     Field,
     Param,
+    Property,
 }
 
 #[type_code(3)]
-pub enum AttributeType {
+pub enum AttributeType<'a> {
     MethodDef = 2,
     MemberRef,
 }
 
 #[type_code(1)]
-pub enum MemberForwarded {
+pub enum HasSemantics<'a> {
+    Event,
+    Property,
+}
+
+#[type_code(2)]
+pub enum ResolutionScope<'a> {
+    Module,
+    ModuleRef,
+    AssemblyRef,
+    TypeRef,
+}
+
+#[type_code(1)]
+pub enum MemberForwarded<'a> {
     Field,
     MethodDef,
 }
 
-impl TypeDefOrRef {
-    pub fn name(&self) -> (&'static str, &'static str) {
+impl<'a> TypeDefOrRef<'a> {
+    pub fn name(&self) -> (&'a str, &'a str) {
         match self {
             Self::TypeDef(value) => value.name(),
             Self::TypeRef(value) => value.name(),
@@ -64,7 +82,7 @@ impl TypeDefOrRef {
         }
     }
 
-    pub fn resolve(&self) -> TypeDef {
+    pub fn resolve(&self) -> TypeDef<'a> {
         match self {
             Self::TypeDef(value) => *value,
             Self::TypeRef(value) => value.resolve(),
@@ -73,8 +91,8 @@ impl TypeDefOrRef {
     }
 }
 
-impl MemberRefParent {
-    pub fn name(&self) -> (&'static str, &'static str) {
+impl<'a> MemberRefParent<'a> {
+    pub fn name(&self) -> (&'a str, &'a str) {
         match self {
             Self::TypeDef(value) => value.name(),
             Self::TypeRef(value) => value.name(),
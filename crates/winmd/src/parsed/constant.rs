@@ -2,42 +2,42 @@ use super::*;
 use crate::TypeReader;
 
 #[derive(Copy, Clone)]
-pub struct Constant {
-    pub reader: &'static TypeReader,
+pub struct Constant<'a> {
+    pub reader: &'a TypeReader,
     pub row: Row,
 }
 
-impl Constant {
-    pub fn value_type(&self) -> ElementType {
+impl<'a> Constant<'a> {
+    pub fn value_type(&self) -> ElementType<'a> {
         ElementType::from_code(self.reader.u32(self.row, 0))
     }
 
-    pub fn value(&self) -> Blob {
+    pub fn value(&self) -> Blob<'a> {
         self.reader.blob(self.row, 2)
     }
 }
 
-impl std::fmt::Debug for Constant {
+impl std::fmt::Debug for Constant<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Constant").field("row", &self.row).finish()
     }
 }
 
-impl PartialEq for Constant {
+impl PartialEq for Constant<'_> {
     fn eq(&self, other: &Self) -> bool {
         self.row == other.row
     }
 }
 
-impl Eq for Constant {}
+impl Eq for Constant<'_> {}
 
-impl Ord for Constant {
+impl Ord for Constant<'_> {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         self.row.cmp(&other.row)
     }
 }
 
-impl PartialOrd for Constant {
+impl PartialOrd for Constant<'_> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.cmp(other))
     }
@@ -1,8 +1,8 @@
 use super::*;
-use crate::traits::Decode;
+use crate::Error;
 
 #[derive(Debug)]
-pub enum ElementType {
+pub enum ElementType<'a> {
     Void,
     Bool,
     Char,
@@ -19,64 +19,59 @@ pub enum ElementType {
     ISize,
     USize,
     String,
-    Struct(TypeDefOrRef),
-    Class(TypeDefOrRef),
+    Struct(TypeDefOrRef<'a>),
+    Class(TypeDefOrRef<'a>),
 }
 
-impl ElementType {
-    pub fn from_blob(blob: &mut Blob) -> ElementType {
-        let code = blob.read_unsigned();
-        match code {
-            0x01 => ElementType::Void,
-            0x02 => ElementType::Bool,
-            0x03 => ElementType::Char,
-            0x04 => ElementType::I8,
-            0x05 => ElementType::U8,
-            0x06 => ElementType::I16,
-            0x07 => ElementType::U16,
-            0x08 => ElementType::I32,
-            0x09 => ElementType::U32,
-            0x0a => ElementType::I64,
-            0x0b => ElementType::U64,
-            0x0c => ElementType::F32,
-            0x0d => ElementType::F64,
-            0x18 => ElementType::ISize,
-            0x19 => ElementType::USize,
-            0x0e => ElementType::String,
-            0x11 => ElementType::Struct(TypeDefOrRef::decode(
-                blob.reader,
-                blob.read_unsigned(),
-                blob.file_index,
-            )),
-            0x12 => ElementType::Class(TypeDefOrRef::decode(
-                blob.reader,
-                blob.read_unsigned(),
-                blob.file_index,
-            )),
+impl<'a> ElementType<'a> {
+    /// Reads an element type from a signature blob
+    ///
+    /// # Panics
+    ///
+    /// Panics if the blob is malformed. Use [`ElementType::try_from_blob`] to handle errors instead.
+    pub fn from_blob(blob: &mut Blob<'a>) -> ElementType<'a> {
+        Self::try_from_blob(blob).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Reads an element type from a signature blob
+    pub fn try_from_blob(blob: &mut Blob<'a>) -> Result<ElementType<'a>, Error> {
+        let code = blob.try_read_unsigned()?;
+
+        if let Some(element_type) = Self::from_primitive_code(code) {
+            return Ok(element_type);
+        }
 
-            unknown_type => panic!(format!("Unexpected ElementType: {:x}", unknown_type)),
+        match code {
+            0x11 => Ok(ElementType::Struct(blob.try_read_type_def_or_ref()?)),
+            0x12 => Ok(ElementType::Class(blob.try_read_type_def_or_ref()?)),
+            unknown_type => Err(blob.error(format!("unexpected ElementType: {:x}", unknown_type))),
         }
     }
 
-    pub fn from_code(code: u32) -> ElementType {
+    pub fn from_code(code: u32) -> ElementType<'a> {
+        Self::from_primitive_code(code)
+            .unwrap_or_else(|| panic!("Unexpected ElementType: {:x}", code))
+    }
+
+    fn from_primitive_code(code: u32) -> Option<ElementType<'a>> {
         match code {
-            0x01 => ElementType::Void,
-            0x02 => ElementType::Bool,
-            0x03 => ElementType::Char,
-            0x04 => ElementType::I8,
-            0x05 => ElementType::U8,
-            0x06 => ElementType::I16,
-            0x07 => ElementType::U16,
-            0x08 => ElementType::I32,
-            0x09 => ElementType::U32,
-            0x0a => ElementType::I64,
-            0x0b => ElementType::U64,
-            0x0c => ElementType::F32,
-            0x0d => ElementType::F64,
-            0x18 => ElementType::ISize,
-            0x19 => ElementType::USize,
-            0x0e => ElementType::String,
-            unknown_code => panic!(format!("Unexpected ElementType: {:x}", unknown_code)),
+            0x01 => Some(ElementType::Void),
+            0x02 => Some(ElementType::Bool),
+            0x03 => Some(ElementType::Char),
+            0x04 => Some(ElementType::I8),
+            0x05 => Some(ElementType::U8),
+            0x06 => Some(ElementType::I16),
+            0x07 => Some(ElementType::U16),
+            0x08 => Some(ElementType::I32),
+            0x09 => Some(ElementType::U32),
+            0x0a => Some(ElementType::I64),
+            0x0b => Some(ElementType::U64),
+            0x0c => Some(ElementType::F32),
+            0x0d => Some(ElementType::F64),
+            0x18 => Some(ElementType::ISize),
+            0x19 => Some(ElementType::USize),
+            0x0e => Some(ElementType::String),
+            _ => None,
         }
     }
 }
@@ -0,0 +1,92 @@
+use super::*;
+use crate::{TableIndex, TypeReader};
+
+#[derive(Copy, Clone)]
+pub struct Event<'a> {
+    pub reader: &'a TypeReader,
+    pub row: Row,
+}
+
+impl<'a> Event<'a> {
+    pub fn flags(&self) -> EventFlags {
+        EventFlags(self.reader.u32(self.row, 0))
+    }
+
+    pub fn name(&self) -> &'a str {
+        self.reader.str(self.row, 1)
+    }
+
+    pub fn event_type(&self) -> TypeDefOrRef<'a> {
+        self.reader.decode(self.row, 2)
+    }
+
+    pub fn semantics(&self) -> impl Iterator<Item = MethodSemantics<'a>> + '_ {
+        self.reader
+            .equal_range(
+                self.row.file_index,
+                TableIndex::MethodSemantics,
+                2,
+                HasSemantics::Event(*self).encode(),
+            )
+            .map(move |row| MethodSemantics {
+                reader: self.reader,
+                row,
+            })
+    }
+
+    pub fn adder(&self) -> Option<MethodDef<'a>> {
+        self.semantics()
+            .find(|semantics| semantics.flags().add_on())
+            .map(|semantics| semantics.method())
+    }
+
+    pub fn remover(&self) -> Option<MethodDef<'a>> {
+        self.semantics()
+            .find(|semantics| semantics.flags().remove_on())
+            .map(|semantics| semantics.method())
+    }
+
+    pub fn attributes(&self) -> impl Iterator<Item = Attribute<'a>> + '_ {
+        self.reader
+            .equal_range(
+                self.row.file_index,
+                TableIndex::CustomAttribute,
+                0,
+                HasAttribute::Event(*self).encode(),
+            )
+            .map(move |row| Attribute {
+                reader: self.reader,
+                row,
+            })
+    }
+
+    pub fn has_attribute(&self, name: (&str, &str)) -> bool {
+        self.attributes().any(|attribute| attribute.name() == name)
+    }
+}
+
+impl std::fmt::Debug for Event<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Event").field("row", &self.row).finish()
+    }
+}
+
+impl PartialEq for Event<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.row == other.row
+    }
+}
+
+impl Eq for Event<'_> {}
+
+impl Ord for Event<'_> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.row.cmp(&other.row)
+    }
+}
+
+impl PartialOrd for Event<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
@@ -1,26 +1,35 @@
 use super::*;
-use crate::{TableIndex, TypeReader};
+use crate::{Error, TableIndex, TypeReader};
 
 #[derive(Copy, Clone)]
-pub struct Field {
-    pub reader: &'static TypeReader,
+pub struct Field<'a> {
+    pub reader: &'a TypeReader,
     pub row: Row,
 }
 
-impl Field {
-    pub fn name(&self) -> &'static str {
+impl<'a> Field<'a> {
+    pub fn name(&self) -> &'a str {
         self.reader.str(self.row, 1)
     }
 
-    pub fn sig(&self) -> Blob {
+    pub fn sig(&self) -> Blob<'a> {
         self.reader.blob(self.row, 2)
     }
 
+    pub fn signature(&self) -> ParamSig<'a> {
+        self.try_signature()
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_signature(&self) -> Result<ParamSig<'a>, Error> {
+        ParamSig::try_from_field_blob(&mut self.reader.try_blob(self.row, 2)?)
+    }
+
     pub fn flags(&self) -> FieldFlags {
         FieldFlags(self.reader.u32(self.row, 0))
     }
 
-    pub fn constant(&self) -> Option<Constant> {
+    pub fn constant(&self) -> Option<Constant<'a>> {
         self.reader
             .equal_range(
                 self.row.file_index,
@@ -34,29 +43,90 @@ impl Field {
             })
             .next()
     }
+
+    pub fn attributes(&self) -> impl Iterator<Item = Attribute<'a>> + '_ {
+        self.reader
+            .equal_range(
+                self.row.file_index,
+                TableIndex::CustomAttribute,
+                0,
+                HasAttribute::Field(*self).encode(),
+            )
+            .map(move |row| Attribute {
+                reader: self.reader,
+                row,
+            })
+    }
+
+    pub fn has_attribute(&self, name: (&str, &str)) -> bool {
+        self.attributes().any(|attribute| attribute.name() == name)
+    }
+
+    /// The type definition that owns this field
+    pub fn parent(&self) -> TypeDef<'a> {
+        let row = self.reader.upper_bound(
+            self.row.file_index,
+            TableIndex::TypeDef,
+            4,
+            self.row.index + 1,
+        );
+
+        TypeDef {
+            reader: self.reader,
+            row: Row::new(row - 1, TableIndex::TypeDef, self.row.file_index),
+        }
+    }
+
+    pub fn impl_map(&self) -> Option<ImplMap<'a>> {
+        self.reader
+            .equal_range(
+                self.row.file_index,
+                TableIndex::ImplMap,
+                1,
+                MemberForwarded::Field(*self).encode(),
+            )
+            .map(move |row| ImplMap {
+                reader: self.reader,
+                row,
+            })
+            .next()
+    }
+
+    /// The field's byte offset within a type with explicit layout
+    pub fn offset(&self) -> Option<u32> {
+        self.reader
+            .equal_range(
+                self.row.file_index,
+                TableIndex::FieldLayout,
+                1,
+                self.row.index + 1,
+            )
+            .map(|row| self.reader.u32(row, 0))
+            .next()
+    }
 }
 
-impl std::fmt::Debug for Field {
+impl std::fmt::Debug for Field<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Field").field("row", &self.row).finish()
     }
 }
 
-impl PartialEq for Field {
+impl PartialEq for Field<'_> {
     fn eq(&self, other: &Self) -> bool {
         self.row == other.row
     }
 }
 
-impl Eq for Field {}
+impl Eq for Field<'_> {}
 
-impl Ord for Field {
+impl Ord for Field<'_> {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         self.row.cmp(&other.row)
     }
 }
 
-impl PartialOrd for Field {
+impl PartialOrd for Field<'_> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.cmp(other))
     }
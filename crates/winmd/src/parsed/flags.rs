@@ -9,10 +9,19 @@ pub struct ParamFlags(pub u32);
 #[derive(Default)]
 pub struct FieldFlags(pub u32);
 
+pub struct PropertyFlags(pub u32);
+pub struct EventFlags(pub u32);
+pub struct MethodSemanticsFlags(pub u32);
+pub struct AssemblyFlags(pub u32);
+pub struct GenericParamFlags(pub u32);
+
 impl MethodFlags {
     pub fn special(&self) -> bool {
         self.0 & 0b1000_0000_0000 != 0
     }
+    pub fn is_static(&self) -> bool {
+        self.0 & 0b1_0000 != 0
+    }
 }
 
 impl TypeFlags {
@@ -22,6 +31,18 @@ impl TypeFlags {
     pub fn interface(&self) -> bool {
         self.0 & 0b10_0000 != 0
     }
+    pub fn sealed(&self) -> bool {
+        self.0 & 0b1_0000_0000 != 0
+    }
+    pub fn nested(&self) -> bool {
+        self.0 & 0b111 > 1
+    }
+    pub fn sequential_layout(&self) -> bool {
+        self.0 & 0b1_1000 == 0b1000
+    }
+    pub fn explicit_layout(&self) -> bool {
+        self.0 & 0b1_1000 == 0b1_0000
+    }
 }
 
 impl ParamFlags {
@@ -46,7 +67,79 @@ impl FieldFlags {
     }
 }
 
-#[derive(PartialEq)]
+impl PropertyFlags {
+    pub fn special(&self) -> bool {
+        self.0 & 0x0200 != 0
+    }
+    pub fn has_default(&self) -> bool {
+        self.0 & 0x1000 != 0
+    }
+}
+
+impl EventFlags {
+    pub fn special(&self) -> bool {
+        self.0 & 0x0200 != 0
+    }
+}
+
+impl AssemblyFlags {
+    /// Whether the assembly reference holds the full public key rather than its token
+    pub fn public_key(&self) -> bool {
+        self.0 & 0x0001 != 0
+    }
+
+    pub fn windows_runtime(&self) -> bool {
+        self.0 & 0x0e00 == 0x0200
+    }
+}
+
+impl GenericParamFlags {
+    /// Whether the parameter is covariant (`out T`)
+    pub fn covariant(&self) -> bool {
+        self.0 & 0x0003 == 0x0001
+    }
+    /// Whether the parameter is contravariant (`in T`)
+    pub fn contravariant(&self) -> bool {
+        self.0 & 0x0003 == 0x0002
+    }
+    pub fn reference_type(&self) -> bool {
+        self.0 & 0x0004 != 0
+    }
+    pub fn value_type(&self) -> bool {
+        self.0 & 0x0008 != 0
+    }
+    pub fn default_constructor(&self) -> bool {
+        self.0 & 0x0010 != 0
+    }
+}
+
+impl MethodSemanticsFlags {
+    pub fn setter(&self) -> bool {
+        self.0 & 0x0001 != 0
+    }
+    pub fn getter(&self) -> bool {
+        self.0 & 0x0002 != 0
+    }
+    pub fn other(&self) -> bool {
+        self.0 & 0x0004 != 0
+    }
+    pub fn add_on(&self) -> bool {
+        self.0 & 0x0008 != 0
+    }
+    pub fn remove_on(&self) -> bool {
+        self.0 & 0x0010 != 0
+    }
+    pub fn fire(&self) -> bool {
+        self.0 & 0x0020 != 0
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
 pub enum TypeCategory {
     Interface,
     Class,
@@ -77,8 +170,10 @@ pub enum MethodCategory {
     Remove,
 }
 
-#[derive(Debug)]
-pub enum AttributeArg {
+#[derive(Clone, Debug, PartialEq)]
+pub enum AttributeArg<'a> {
+    /// A null string, type or array
+    Null,
     Bool(bool),
     Char(char),
     I8(i8),
@@ -92,5 +187,10 @@ pub enum AttributeArg {
     F32(f32),
     F64(f64),
     String(String),
-    TypeDef(TypeDef),
+    TypeDef(TypeDef<'a>),
+    Array(Vec<AttributeArg<'a>>),
+    /// An enum value along with the enum's type
+    Enum(TypeDef<'a>, Box<AttributeArg<'a>>),
+    /// A value passed as `System.Object`, which carries its own type
+    Boxed(Box<AttributeArg<'a>>),
 }
@@ -1,19 +1,61 @@
 use super::*;
-use crate::TypeReader;
+use crate::{TableIndex, TypeReader};
 
 #[derive(Copy, Clone)]
-pub struct GenericParam {
-    pub reader: &'static TypeReader,
+pub struct GenericParam<'a> {
+    pub reader: &'a TypeReader,
     pub row: Row,
 }
 
-impl GenericParam {
-    pub fn name<'a>(&self) -> &'a str {
+impl<'a> GenericParam<'a> {
+    /// The zero-based position of the parameter within its owner's generic parameter list
+    pub fn number(&self) -> u16 {
+        self.reader.u32(self.row, 0) as u16
+    }
+
+    pub fn flags(&self) -> GenericParamFlags {
+        GenericParamFlags(self.reader.u32(self.row, 1))
+    }
+
+    /// The type or method that declares the parameter
+    pub fn owner(&self) -> TypeOrMethodDef<'a> {
+        self.reader.decode(self.row, 2)
+    }
+
+    pub fn name(&self) -> &'a str {
         self.reader.str(self.row, 3)
     }
+
+    pub fn constraints(&self) -> impl Iterator<Item = GenericParamConstraint<'a>> + '_ {
+        self.reader
+            .equal_range(
+                self.row.file_index,
+                TableIndex::GenericParamConstraint,
+                0,
+                self.row.index + 1,
+            )
+            .map(move |row| GenericParamConstraint {
+                reader: self.reader,
+                row,
+            })
+    }
+
+    pub fn attributes(&self) -> impl Iterator<Item = Attribute<'a>> + '_ {
+        self.reader
+            .equal_range(
+                self.row.file_index,
+                TableIndex::CustomAttribute,
+                0,
+                HasAttribute::GenericParam(*self).encode(),
+            )
+            .map(move |row| Attribute {
+                reader: self.reader,
+                row,
+            })
+    }
 }
 
-impl std::fmt::Debug for GenericParam {
+impl std::fmt::Debug for GenericParam<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("GenericParam")
             .field("row", &self.row)
@@ -21,21 +63,21 @@ impl std::fmt::Debug for GenericParam {
     }
 }
 
-impl PartialEq for GenericParam {
+impl PartialEq for GenericParam<'_> {
     fn eq(&self, other: &Self) -> bool {
         self.row == other.row
     }
 }
 
-impl Eq for GenericParam {}
+impl Eq for GenericParam<'_> {}
 
-impl Ord for GenericParam {
+impl Ord for GenericParam<'_> {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         self.row.cmp(&other.row)
     }
 }
 
-impl PartialOrd for GenericParam {
+impl PartialOrd for GenericParam<'_> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.cmp(other))
     }
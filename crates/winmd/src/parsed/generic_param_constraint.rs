@@ -0,0 +1,69 @@
+use super::*;
+use crate::{TableIndex, TypeReader};
+
+#[derive(Copy, Clone)]
+pub struct GenericParamConstraint<'a> {
+    pub reader: &'a TypeReader,
+    pub row: Row,
+}
+
+impl<'a> GenericParamConstraint<'a> {
+    /// The generic parameter that is constrained
+    pub fn owner(&self) -> GenericParam<'a> {
+        GenericParam {
+            reader: self.reader,
+            row: Row::new(
+                self.reader.u32(self.row, 0) - 1,
+                TableIndex::GenericParam,
+                self.row.file_index,
+            ),
+        }
+    }
+
+    /// The class or interface that the parameter is constrained to
+    pub fn constraint(&self) -> TypeDefOrRef<'a> {
+        self.reader.decode(self.row, 1)
+    }
+
+    pub fn attributes(&self) -> impl Iterator<Item = Attribute<'a>> + '_ {
+        self.reader
+            .equal_range(
+                self.row.file_index,
+                TableIndex::CustomAttribute,
+                0,
+                HasAttribute::GenericParamConstraint(*self).encode(),
+            )
+            .map(move |row| Attribute {
+                reader: self.reader,
+                row,
+            })
+    }
+}
+
+impl std::fmt::Debug for GenericParamConstraint<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GenericParamConstraint")
+            .field("row", &self.row)
+            .finish()
+    }
+}
+
+impl PartialEq for GenericParamConstraint<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.row == other.row
+    }
+}
+
+impl Eq for GenericParamConstraint<'_> {}
+
+impl Ord for GenericParamConstraint<'_> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.row.cmp(&other.row)
+    }
+}
+
+impl PartialOrd for GenericParamConstraint<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
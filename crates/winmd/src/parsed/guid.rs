@@ -0,0 +1,80 @@
+use super::*;
+
+/// A GUID read from the #GUID heap
+#[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Guid(pub u32, pub u16, pub u16, pub [u8; 8]);
+
+impl Guid {
+    /// Creates a GUID from its little-endian binary representation
+    pub fn from_bytes(bytes: [u8; 16]) -> Self {
+        let mut tail = [0; 8];
+        tail.copy_from_slice(&bytes[8..]);
+
+        Self(
+            u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+            u16::from_le_bytes([bytes[4], bytes[5]]),
+            u16::from_le_bytes([bytes[6], bytes[7]]),
+            tail,
+        )
+    }
+
+    /// The little-endian binary representation of the GUID
+    pub fn to_bytes(&self) -> [u8; 16] {
+        let mut bytes = [0; 16];
+        bytes[..4].copy_from_slice(&self.0.to_le_bytes());
+        bytes[4..6].copy_from_slice(&self.1.to_le_bytes());
+        bytes[6..8].copy_from_slice(&self.2.to_le_bytes());
+        bytes[8..].copy_from_slice(&self.3);
+        bytes
+    }
+
+    /// Reads a GUID from the eleven arguments of a `GuidAttribute`
+    pub(crate) fn from_args(args: &[(String, AttributeArg<'_>)]) -> Option<Self> {
+        let mut args = args.iter().map(|(_, arg)| arg);
+
+        let data1 = match args.next()? {
+            AttributeArg::U32(value) => *value,
+            _ => return None,
+        };
+
+        let mut data = [0u16; 2];
+
+        for value in &mut data {
+            *value = match args.next()? {
+                AttributeArg::U16(arg) => *arg,
+                _ => return None,
+            };
+        }
+
+        let mut data4 = [0u8; 8];
+
+        for value in &mut data4 {
+            *value = match args.next()? {
+                AttributeArg::U8(arg) => *arg,
+                _ => return None,
+            };
+        }
+
+        Some(Self(data1, data[0], data[1], data4))
+    }
+}
+
+impl std::fmt::Display for Guid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:08x}-{:04x}-{:04x}-{:02x}{:02x}-{:02x}{:02x}{:02x}{:02x}{:02x}{:02x}",
+            self.0,
+            self.1,
+            self.2,
+            self.3[0],
+            self.3[1],
+            self.3[2],
+            self.3[3],
+            self.3[4],
+            self.3[5],
+            self.3[6],
+            self.3[7]
+        )
+    }
+}
@@ -2,13 +2,23 @@ use super::*;
 use crate::{TableIndex, TypeReader};
 
 #[derive(Copy, Clone)]
-pub struct ImplMap {
-    pub reader: &'static TypeReader,
+pub struct ImplMap<'a> {
+    pub reader: &'a TypeReader,
     pub row: Row,
 }
 
-impl ImplMap {
-    pub fn scope(&self) -> ModuleRef {
+impl<'a> ImplMap<'a> {
+    /// The function or variable that is imported
+    pub fn member(&self) -> MemberForwarded<'a> {
+        self.reader.decode(self.row, 1)
+    }
+
+    /// The name of the function or variable within the module
+    pub fn import_name(&self) -> &'a str {
+        self.reader.str(self.row, 2)
+    }
+
+    pub fn scope(&self) -> ModuleRef<'a> {
         let index = self.reader.u32(self.row, 3) - 1;
         let row = Row::new(index, TableIndex::ModuleRef, self.row.file_index);
 
@@ -19,27 +29,27 @@ impl ImplMap {
     }
 }
 
-impl std::fmt::Debug for ImplMap {
+impl std::fmt::Debug for ImplMap<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ImplMap").field("row", &self.row).finish()
     }
 }
 
-impl PartialEq for ImplMap {
+impl PartialEq for ImplMap<'_> {
     fn eq(&self, other: &Self) -> bool {
         self.row == other.row
     }
 }
 
-impl Eq for ImplMap {}
+impl Eq for ImplMap<'_> {}
 
-impl Ord for ImplMap {
+impl Ord for ImplMap<'_> {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         self.row.cmp(&other.row)
     }
 }
 
-impl PartialOrd for ImplMap {
+impl PartialOrd for ImplMap<'_> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.cmp(other))
     }
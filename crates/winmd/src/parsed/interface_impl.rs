@@ -2,17 +2,17 @@ use super::*;
 use crate::{TableIndex, TypeReader};
 
 #[derive(Copy, Clone)]
-pub struct InterfaceImpl {
-    pub reader: &'static TypeReader,
+pub struct InterfaceImpl<'a> {
+    pub reader: &'a TypeReader,
     pub row: Row,
 }
 
-impl InterfaceImpl {
-    pub fn interface(&self) -> TypeDefOrRef {
+impl<'a> InterfaceImpl<'a> {
+    pub fn interface(&self) -> TypeDefOrRef<'a> {
         self.reader.decode(self.row, 1)
     }
 
-    pub fn attributes(&self) -> impl Iterator<Item = Attribute> + '_ {
+    pub fn attributes(&self) -> impl Iterator<Item = Attribute<'a>> + '_ {
         self.reader
             .equal_range(
                 self.row.file_index,
@@ -35,7 +35,7 @@ impl InterfaceImpl {
     }
 }
 
-impl std::fmt::Debug for InterfaceImpl {
+impl std::fmt::Debug for InterfaceImpl<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("InterfaceImpl")
             .field("row", &self.row)
@@ -43,21 +43,21 @@ impl std::fmt::Debug for InterfaceImpl {
     }
 }
 
-impl PartialEq for InterfaceImpl {
+impl PartialEq for InterfaceImpl<'_> {
     fn eq(&self, other: &Self) -> bool {
         self.row == other.row
     }
 }
 
-impl Eq for InterfaceImpl {}
+impl Eq for InterfaceImpl<'_> {}
 
-impl Ord for InterfaceImpl {
+impl Ord for InterfaceImpl<'_> {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         self.row.cmp(&other.row)
     }
 }
 
-impl PartialOrd for InterfaceImpl {
+impl PartialOrd for InterfaceImpl<'_> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.cmp(other))
     }
@@ -1,43 +1,52 @@
 use super::*;
-use crate::TypeReader;
+use crate::{Error, TypeReader};
 
 #[derive(Copy, Clone)]
-pub struct MemberRef {
-    pub reader: &'static TypeReader,
+pub struct MemberRef<'a> {
+    pub reader: &'a TypeReader,
     pub row: Row,
 }
 
-impl MemberRef {
-    pub fn parent(&self) -> MemberRefParent {
+impl<'a> MemberRef<'a> {
+    pub fn parent(&self) -> MemberRefParent<'a> {
         self.reader.decode(self.row, 0)
     }
 
     pub fn name(&self) -> &str {
         self.reader.str(self.row, 1)
     }
+
+    pub fn signature(&self) -> Signature<'a> {
+        self.try_signature()
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_signature(&self) -> Result<Signature<'a>, Error> {
+        Signature::try_from_blob(&mut self.reader.try_blob(self.row, 2)?)
+    }
 }
 
-impl std::fmt::Debug for MemberRef {
+impl std::fmt::Debug for MemberRef<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("MemberRef").field("row", &self.row).finish()
     }
 }
 
-impl PartialEq for MemberRef {
+impl PartialEq for MemberRef<'_> {
     fn eq(&self, other: &Self) -> bool {
         self.row == other.row
     }
 }
 
-impl Eq for MemberRef {}
+impl Eq for MemberRef<'_> {}
 
-impl Ord for MemberRef {
+impl Ord for MemberRef<'_> {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         self.row.cmp(&other.row)
     }
 }
 
-impl PartialOrd for MemberRef {
+impl PartialOrd for MemberRef<'_> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.cmp(other))
     }
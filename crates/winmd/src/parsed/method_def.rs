@@ -1,18 +1,18 @@
 use super::*;
-use crate::{TableIndex, TypeReader};
+use crate::{Error, TableIndex, TypeReader};
 
 #[derive(Copy, Clone)]
-pub struct MethodDef {
-    pub reader: &'static TypeReader,
+pub struct MethodDef<'a> {
+    pub reader: &'a TypeReader,
     pub row: Row,
 }
 
-impl MethodDef {
+impl<'a> MethodDef<'a> {
     pub fn flags(&self) -> MethodFlags {
         MethodFlags(self.reader.u32(self.row, 2))
     }
 
-    pub fn params(&self) -> impl Iterator<Item = Param> + '_ {
+    pub fn params(&self) -> impl Iterator<Item = Param<'a>> + '_ {
         self.reader
             .list(self.row, TableIndex::Param, 5)
             .map(move |row| Param {
@@ -21,36 +21,64 @@ impl MethodDef {
             })
     }
 
-    pub fn name(&self) -> &'static str {
+    pub fn name(&self) -> &'a str {
         self.reader.str(self.row, 3)
     }
 
-    pub fn sig(&self) -> Blob {
+    pub fn sig(&self) -> Blob<'a> {
         self.reader.blob(self.row, 4)
     }
 
+    pub fn signature(&self) -> MethodSig<'a> {
+        self.try_signature()
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_signature(&self) -> Result<MethodSig<'a>, Error> {
+        MethodSig::try_from_blob(&mut self.reader.try_blob(self.row, 4)?)
+    }
+
+    /// The type definition that owns this method
+    pub fn parent(&self) -> TypeDef<'a> {
+        let row = self.reader.upper_bound(
+            self.row.file_index,
+            TableIndex::TypeDef,
+            5,
+            self.row.index + 1,
+        );
+
+        TypeDef {
+            reader: self.reader,
+            row: Row::new(row - 1, TableIndex::TypeDef, self.row.file_index),
+        }
+    }
+
+    /// The property or event accessor semantics of this method, if any
+    pub fn semantics(&self) -> Option<MethodSemantics<'a>> {
+        self.reader
+            .method_semantics(self.row)
+            .map(|row| MethodSemantics {
+                reader: self.reader,
+                row,
+            })
+    }
+
     pub fn category(&self) -> MethodCategory {
-        if self.flags().special() {
-            let name = self.name();
-
-            if name.starts_with("get") {
-                MethodCategory::Get
-            } else if name.starts_with("put") {
-                MethodCategory::Set
-            } else if name.starts_with("add") {
-                MethodCategory::Add
-            } else if name.starts_with("remove") {
-                MethodCategory::Remove
-            } else {
-                // A delegate's 'Invoke' method is "special" but lacks a preamble.
-                MethodCategory::Normal
-            }
-        } else {
-            MethodCategory::Normal
+        if !self.flags().special() {
+            return MethodCategory::Normal;
+        }
+
+        // A delegate's 'Invoke' method is "special" but has no semantics.
+        match self.semantics().map(|semantics| semantics.flags()) {
+            Some(flags) if flags.getter() => MethodCategory::Get,
+            Some(flags) if flags.setter() => MethodCategory::Set,
+            Some(flags) if flags.add_on() => MethodCategory::Add,
+            Some(flags) if flags.remove_on() => MethodCategory::Remove,
+            _ => MethodCategory::Normal,
         }
     }
 
-    pub fn attributes(&self) -> impl Iterator<Item = Attribute> + '_ {
+    pub fn attributes(&self) -> impl Iterator<Item = Attribute<'a>> + '_ {
         self.reader
             .equal_range(
                 self.row.file_index,
@@ -64,7 +92,11 @@ impl MethodDef {
             })
     }
 
-    pub fn impl_map(&self) -> Option<ImplMap> {
+    pub fn has_attribute(&self, name: (&str, &str)) -> bool {
+        self.attributes().any(|attribute| attribute.name() == name)
+    }
+
+    pub fn impl_map(&self) -> Option<ImplMap<'a>> {
         self.reader
             .equal_range(
                 self.row.file_index,
@@ -80,27 +112,27 @@ impl MethodDef {
     }
 }
 
-impl std::fmt::Debug for MethodDef {
+impl std::fmt::Debug for MethodDef<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("MethodDef").field("row", &self.row).finish()
     }
 }
 
-impl PartialEq for MethodDef {
+impl PartialEq for MethodDef<'_> {
     fn eq(&self, other: &Self) -> bool {
         self.row == other.row
     }
 }
 
-impl Eq for MethodDef {}
+impl Eq for MethodDef<'_> {}
 
-impl Ord for MethodDef {
+impl Ord for MethodDef<'_> {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         self.row.cmp(&other.row)
     }
 }
 
-impl PartialOrd for MethodDef {
+impl PartialOrd for MethodDef<'_> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.cmp(other))
     }
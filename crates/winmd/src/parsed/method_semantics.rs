@@ -0,0 +1,57 @@
+use super::*;
+use crate::{TableIndex, TypeReader};
+
+#[derive(Copy, Clone)]
+pub struct MethodSemantics<'a> {
+    pub reader: &'a TypeReader,
+    pub row: Row,
+}
+
+impl<'a> MethodSemantics<'a> {
+    pub fn flags(&self) -> MethodSemanticsFlags {
+        MethodSemanticsFlags(self.reader.u32(self.row, 0))
+    }
+
+    pub fn method(&self) -> MethodDef<'a> {
+        MethodDef {
+            reader: self.reader,
+            row: Row::new(
+                self.reader.u32(self.row, 1) - 1,
+                TableIndex::MethodDef,
+                self.row.file_index,
+            ),
+        }
+    }
+
+    pub fn association(&self) -> HasSemantics<'a> {
+        self.reader.decode(self.row, 2)
+    }
+}
+
+impl std::fmt::Debug for MethodSemantics<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MethodSemantics")
+            .field("row", &self.row)
+            .finish()
+    }
+}
+
+impl PartialEq for MethodSemantics<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.row == other.row
+    }
+}
+
+impl Eq for MethodSemantics<'_> {}
+
+impl Ord for MethodSemantics<'_> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.row.cmp(&other.row)
+    }
+}
+
+impl PartialOrd for MethodSemantics<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
@@ -1,38 +1,58 @@
 //! Parsed elements of a winmd file
+mod assembly;
+mod assembly_ref;
 mod attribute;
 mod blob;
+mod class_layout;
 mod codes;
 mod constant;
 mod element_type;
+mod event;
 mod field;
 mod flags;
 mod generic_param;
+mod generic_param_constraint;
+mod guid;
 mod impl_map;
 mod interface_impl;
 mod member_ref;
 mod method_def;
+mod method_semantics;
+mod module;
 mod module_ref;
 mod param;
+mod property;
 mod row;
+mod signature;
 mod type_def;
 mod type_ref;
 mod type_spec;
 
+pub use assembly::*;
+pub use assembly_ref::*;
 pub use attribute::*;
 pub use blob::*;
+pub use class_layout::*;
 pub use codes::*;
 pub use constant::*;
 pub use element_type::*;
+pub use event::*;
 pub use field::*;
 pub use flags::*;
 pub use generic_param::*;
+pub use generic_param_constraint::*;
+pub use guid::*;
 pub use impl_map::*;
 pub use interface_impl::*;
 pub use member_ref::*;
 pub use method_def::*;
+pub use method_semantics::*;
+pub use module::*;
 pub use module_ref::*;
 pub use param::*;
+pub use property::*;
 pub use row::*;
+pub use signature::*;
 pub use type_def::*;
 pub use type_ref::*;
 pub use type_spec::*;
@@ -0,0 +1,79 @@
+use super::*;
+use crate::{File, TableIndex, TypeReader};
+
+#[derive(Copy, Clone)]
+pub struct Module<'a> {
+    pub reader: &'a TypeReader,
+    pub row: Row,
+}
+
+impl<'a> Module<'a> {
+    pub fn generation(&self) -> u16 {
+        self.reader.u32(self.row, 0) as u16
+    }
+
+    pub fn name(&self) -> &'a str {
+        self.reader.str(self.row, 1)
+    }
+
+    /// The module version identifier, which distinguishes between two versions of the same module
+    pub fn mvid(&self) -> Guid {
+        self.file().guid(self.reader.u32(self.row, 2))
+    }
+
+    /// The file that contains this module
+    pub fn file(&self) -> &'a File {
+        &self.reader.files[self.row.file_index as usize]
+    }
+
+    /// The assembly defined by the module's file, if any
+    pub fn assembly(&self) -> Option<Assembly<'a>> {
+        self.rows(TableIndex::Assembly)
+            .next()
+            .map(move |row| Assembly {
+                reader: self.reader,
+                row,
+            })
+    }
+
+    /// The assemblies that the module's file refers to
+    pub fn assembly_refs(&self) -> impl Iterator<Item = AssemblyRef<'a>> + '_ {
+        self.rows(TableIndex::AssemblyRef)
+            .map(move |row| AssemblyRef {
+                reader: self.reader,
+                row,
+            })
+    }
+
+    fn rows(&self, table: TableIndex) -> impl Iterator<Item = Row> {
+        let file_index = self.row.file_index;
+        let row_count = self.file().tables[table as usize].row_count;
+        (0..row_count).map(move |row| Row::new(row, table, file_index))
+    }
+}
+
+impl std::fmt::Debug for Module<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Module").field("row", &self.row).finish()
+    }
+}
+
+impl PartialEq for Module<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.row == other.row
+    }
+}
+
+impl Eq for Module<'_> {}
+
+impl Ord for Module<'_> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.row.cmp(&other.row)
+    }
+}
+
+impl PartialOrd for Module<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
@@ -2,38 +2,38 @@ use super::*;
 use crate::TypeReader;
 
 #[derive(Copy, Clone)]
-pub struct ModuleRef {
-    pub reader: &'static TypeReader,
+pub struct ModuleRef<'a> {
+    pub reader: &'a TypeReader,
     pub row: Row,
 }
 
-impl ModuleRef {
-    pub fn name(&self) -> &'static str {
+impl<'a> ModuleRef<'a> {
+    pub fn name(&self) -> &'a str {
         self.reader.str(self.row, 0)
     }
 }
 
-impl std::fmt::Debug for ModuleRef {
+impl std::fmt::Debug for ModuleRef<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ModuleRef").field("row", &self.row).finish()
     }
 }
 
-impl PartialEq for ModuleRef {
+impl PartialEq for ModuleRef<'_> {
     fn eq(&self, other: &Self) -> bool {
         self.row == other.row
     }
 }
 
-impl Eq for ModuleRef {}
+impl Eq for ModuleRef<'_> {}
 
-impl Ord for ModuleRef {
+impl Ord for ModuleRef<'_> {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         self.row.cmp(&other.row)
     }
 }
 
-impl PartialOrd for ModuleRef {
+impl PartialOrd for ModuleRef<'_> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.cmp(other))
     }
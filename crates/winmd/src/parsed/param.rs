@@ -2,12 +2,12 @@ use super::*;
 use crate::{TableIndex, TypeReader};
 
 #[derive(Copy, Clone)]
-pub struct Param {
-    pub reader: &'static TypeReader,
+pub struct Param<'a> {
+    pub reader: &'a TypeReader,
     pub row: Row,
 }
 
-impl Param {
+impl<'a> Param<'a> {
     pub fn flags(&self) -> ParamFlags {
         ParamFlags(self.reader.u32(self.row, 0))
     }
@@ -16,11 +16,11 @@ impl Param {
         self.reader.u32(self.row, 1)
     }
 
-    pub fn name(&self) -> &'static str {
+    pub fn name(&self) -> &'a str {
         self.reader.str(self.row, 2)
     }
 
-    pub fn attributes(&self) -> impl Iterator<Item = Attribute> + '_ {
+    pub fn attributes(&self) -> impl Iterator<Item = Attribute<'a>> + '_ {
         self.reader
             .equal_range(
                 self.row.file_index,
@@ -39,7 +39,7 @@ impl Param {
     }
 }
 
-impl std::fmt::Debug for Param {
+impl std::fmt::Debug for Param<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Param")
             .field("name", &self.name().to_string())
@@ -48,21 +48,21 @@ impl std::fmt::Debug for Param {
     }
 }
 
-impl PartialEq for Param {
+impl PartialEq for Param<'_> {
     fn eq(&self, other: &Self) -> bool {
         self.row == other.row
     }
 }
 
-impl Eq for Param {}
+impl Eq for Param<'_> {}
 
-impl Ord for Param {
+impl Ord for Param<'_> {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         self.row.cmp(&other.row)
     }
 }
 
-impl PartialOrd for Param {
+impl PartialOrd for Param<'_> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.cmp(other))
     }
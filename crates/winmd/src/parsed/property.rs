@@ -0,0 +1,101 @@
+use super::*;
+use crate::{Error, TableIndex, TypeReader};
+
+#[derive(Copy, Clone)]
+pub struct Property<'a> {
+    pub reader: &'a TypeReader,
+    pub row: Row,
+}
+
+impl<'a> Property<'a> {
+    pub fn flags(&self) -> PropertyFlags {
+        PropertyFlags(self.reader.u32(self.row, 0))
+    }
+
+    pub fn name(&self) -> &'a str {
+        self.reader.str(self.row, 1)
+    }
+
+    pub fn sig(&self) -> Blob<'a> {
+        self.reader.blob(self.row, 2)
+    }
+
+    pub fn signature(&self) -> PropertySig<'a> {
+        self.try_signature()
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_signature(&self) -> Result<PropertySig<'a>, Error> {
+        PropertySig::try_from_blob(&mut self.reader.try_blob(self.row, 2)?)
+    }
+
+    pub fn semantics(&self) -> impl Iterator<Item = MethodSemantics<'a>> + '_ {
+        self.reader
+            .equal_range(
+                self.row.file_index,
+                TableIndex::MethodSemantics,
+                2,
+                HasSemantics::Property(*self).encode(),
+            )
+            .map(move |row| MethodSemantics {
+                reader: self.reader,
+                row,
+            })
+    }
+
+    pub fn getter(&self) -> Option<MethodDef<'a>> {
+        self.semantics()
+            .find(|semantics| semantics.flags().getter())
+            .map(|semantics| semantics.method())
+    }
+
+    pub fn setter(&self) -> Option<MethodDef<'a>> {
+        self.semantics()
+            .find(|semantics| semantics.flags().setter())
+            .map(|semantics| semantics.method())
+    }
+
+    pub fn attributes(&self) -> impl Iterator<Item = Attribute<'a>> + '_ {
+        self.reader
+            .equal_range(
+                self.row.file_index,
+                TableIndex::CustomAttribute,
+                0,
+                HasAttribute::Property(*self).encode(),
+            )
+            .map(move |row| Attribute {
+                reader: self.reader,
+                row,
+            })
+    }
+
+    pub fn has_attribute(&self, name: (&str, &str)) -> bool {
+        self.attributes().any(|attribute| attribute.name() == name)
+    }
+}
+
+impl std::fmt::Debug for Property<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Property").field("row", &self.row).finish()
+    }
+}
+
+impl PartialEq for Property<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.row == other.row
+    }
+}
+
+impl Eq for Property<'_> {}
+
+impl Ord for Property<'_> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.row.cmp(&other.row)
+    }
+}
+
+impl PartialOrd for Property<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
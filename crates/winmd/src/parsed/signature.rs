@@ -0,0 +1,546 @@
+use super::*;
+use crate::Error;
+
+/// A custom modifier (`modreq` or `modopt`) applied to a type within a signature
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Modifier<'a> {
+    /// Whether the modifier is required (`modreq`) rather than optional (`modopt`)
+    pub required: bool,
+    pub def: TypeDefOrRef<'a>,
+}
+
+/// The rank and bounds of a general array
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ArrayShape {
+    pub rank: u32,
+    /// The sizes of the leading dimensions, which may be fewer than the rank
+    pub sizes: Vec<u32>,
+    /// The lower bounds of the leading dimensions, which may be fewer than the rank
+    pub lower_bounds: Vec<i32>,
+}
+
+/// A type encoded within a signature
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TypeSig<'a> {
+    Void,
+    Bool,
+    Char,
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    I64,
+    U64,
+    F32,
+    F64,
+    ISize,
+    USize,
+    String,
+    Object,
+    TypedByRef,
+    ValueType(TypeDefOrRef<'a>),
+    Class(TypeDefOrRef<'a>),
+    /// A generic parameter of the enclosing type, by index
+    TypeGeneric(u32),
+    /// A generic parameter of the enclosing method, by index
+    MethodGeneric(u32),
+    GenericInst {
+        def: TypeDefOrRef<'a>,
+        is_value_type: bool,
+        args: Vec<TypeSig<'a>>,
+    },
+    Ptr(Vec<Modifier<'a>>, Box<TypeSig<'a>>),
+    SZArray(Vec<Modifier<'a>>, Box<TypeSig<'a>>),
+    Array(Box<TypeSig<'a>>, ArrayShape),
+    FnPtr(Box<MethodSig<'a>>),
+}
+
+/// The type of a parameter, return value, field or property along with its custom modifiers
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParamSig<'a> {
+    pub modifiers: Vec<Modifier<'a>>,
+    pub by_ref: bool,
+    pub ty: TypeSig<'a>,
+}
+
+/// The signature of a method definition, method reference or function pointer
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MethodSig<'a> {
+    pub calling_convention: u8,
+    pub generic_count: u32,
+    pub return_type: ParamSig<'a>,
+    pub params: Vec<ParamSig<'a>>,
+    /// The index of the first variable argument, if the signature has a sentinel
+    pub sentinel: Option<usize>,
+}
+
+/// The signature of a property
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PropertySig<'a> {
+    pub has_this: bool,
+    pub ty: ParamSig<'a>,
+    pub params: Vec<ParamSig<'a>>,
+}
+
+/// The type of a local variable
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LocalSig<'a> {
+    pub pinned: bool,
+    pub param: ParamSig<'a>,
+}
+
+/// Any signature stored in the #Blob heap
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Signature<'a> {
+    Method(MethodSig<'a>),
+    Field(ParamSig<'a>),
+    Property(PropertySig<'a>),
+    Local(Vec<LocalSig<'a>>),
+}
+
+const FIELD: u32 = 0x06;
+const LOCAL: u32 = 0x07;
+const PROPERTY: u32 = 0x08;
+const GENERIC: u32 = 0x10;
+const HAS_THIS: u32 = 0x20;
+const EXPLICIT_THIS: u32 = 0x40;
+const VARARG: u32 = 0x05;
+const SENTINEL: u32 = 0x41;
+const PINNED: u32 = 0x45;
+const BY_REF: u32 = 0x10;
+const MOD_REQ: u32 = 0x1f;
+const MOD_OPT: u32 = 0x20;
+
+impl<'a> Signature<'a> {
+    /// Reads a signature of any kind from a blob
+    ///
+    /// # Panics
+    ///
+    /// Panics if the blob is malformed. Use [`Signature::try_from_blob`] to handle errors instead.
+    pub fn from_blob(blob: &mut Blob<'a>) -> Self {
+        Self::try_from_blob(blob).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Reads a signature of any kind from a blob
+    pub fn try_from_blob(blob: &mut Blob<'a>) -> Result<Self, Error> {
+        let (kind, _) = blob.try_peek_unsigned()?;
+
+        match kind & 0x0f {
+            FIELD => Ok(Self::Field(ParamSig::try_from_field_blob(blob)?)),
+            LOCAL => {
+                blob.try_read_unsigned()?;
+                let count = blob.try_read_unsigned()?;
+                let mut locals = Vec::new();
+
+                for _ in 0..count {
+                    locals.push(LocalSig::try_from_blob(blob)?);
+                }
+
+                Ok(Self::Local(locals))
+            }
+            PROPERTY => Ok(Self::Property(PropertySig::try_from_blob(blob)?)),
+            _ => Ok(Self::Method(MethodSig::try_from_blob(blob)?)),
+        }
+    }
+}
+
+impl<'a> MethodSig<'a> {
+    /// Reads a method signature from a blob
+    pub fn try_from_blob(blob: &mut Blob<'a>) -> Result<Self, Error> {
+        let calling_convention = blob.try_read_unsigned()?;
+
+        if calling_convention & 0x0f > VARARG {
+            return Err(blob.error(format!(
+                "invalid method calling convention 0x{:x}",
+                calling_convention
+            )));
+        }
+
+        let generic_count = if calling_convention & GENERIC != 0 {
+            blob.try_read_unsigned()?
+        } else {
+            0
+        };
+
+        let param_count = blob.try_read_unsigned()?;
+        let return_type = ParamSig::try_from_blob(blob)?;
+        let mut params = Vec::new();
+        let mut sentinel = None;
+
+        for _ in 0..param_count {
+            if blob.try_read_expected(SENTINEL)? {
+                sentinel = Some(params.len());
+            }
+
+            params.push(ParamSig::try_from_blob(blob)?);
+        }
+
+        Ok(Self {
+            calling_convention: calling_convention as u8,
+            generic_count,
+            return_type,
+            params,
+            sentinel,
+        })
+    }
+
+    pub fn has_this(&self) -> bool {
+        self.calling_convention as u32 & HAS_THIS != 0
+    }
+
+    pub fn explicit_this(&self) -> bool {
+        self.calling_convention as u32 & EXPLICIT_THIS != 0
+    }
+
+    pub fn is_vararg(&self) -> bool {
+        self.calling_convention as u32 & 0x0f == VARARG
+    }
+}
+
+impl<'a> PropertySig<'a> {
+    /// Reads a property signature from a blob
+    pub fn try_from_blob(blob: &mut Blob<'a>) -> Result<Self, Error> {
+        let kind = blob.try_read_unsigned()?;
+
+        if kind & 0x0f != PROPERTY {
+            return Err(blob.error(format!("invalid property signature 0x{:x}", kind)));
+        }
+
+        let param_count = blob.try_read_unsigned()?;
+        let ty = ParamSig::try_from_blob(blob)?;
+        let mut params = Vec::new();
+
+        for _ in 0..param_count {
+            params.push(ParamSig::try_from_blob(blob)?);
+        }
+
+        Ok(Self {
+            has_this: kind & HAS_THIS != 0,
+            ty,
+            params,
+        })
+    }
+}
+
+impl<'a> LocalSig<'a> {
+    fn try_from_blob(blob: &mut Blob<'a>) -> Result<Self, Error> {
+        let mut pinned = false;
+        let mut modifiers = Vec::new();
+
+        loop {
+            if blob.try_read_expected(PINNED)? {
+                pinned = true;
+            } else if let Some(modifier) = Modifier::try_from_blob(blob)? {
+                modifiers.push(modifier);
+            } else {
+                break;
+            }
+        }
+
+        let mut param = ParamSig::try_from_blob(blob)?;
+        modifiers.append(&mut param.modifiers);
+        param.modifiers = modifiers;
+
+        Ok(Self { pinned, param })
+    }
+}
+
+impl<'a> ParamSig<'a> {
+    /// Reads the type of a parameter or return value, including any custom modifiers
+    pub fn try_from_blob(blob: &mut Blob<'a>) -> Result<Self, Error> {
+        let mut modifiers = Modifier::try_read_all(blob)?;
+        let by_ref = blob.try_read_expected(BY_REF)?;
+
+        // Some metadata writers place custom modifiers after `BYREF` rather than before it.
+        modifiers.append(&mut Modifier::try_read_all(blob)?);
+
+        let ty = TypeSig::try_from_blob(blob)?;
+
+        Ok(Self {
+            modifiers,
+            by_ref,
+            ty,
+        })
+    }
+
+    /// Reads a field signature from a blob
+    pub fn try_from_field_blob(blob: &mut Blob<'a>) -> Result<Self, Error> {
+        let kind = blob.try_read_unsigned()?;
+
+        if kind & 0x0f != FIELD {
+            return Err(blob.error(format!("invalid field signature 0x{:x}", kind)));
+        }
+
+        Self::try_from_blob(blob)
+    }
+
+    /// Returns whether the type has a custom modifier with the given name
+    pub fn has_modifier(&self, name: (&str, &str)) -> bool {
+        self.modifiers
+            .iter()
+            .any(|modifier| modifier.def.name() == name)
+    }
+}
+
+impl<'a> Modifier<'a> {
+    fn try_from_blob(blob: &mut Blob<'a>) -> Result<Option<Self>, Error> {
+        let (code, _) = blob.try_peek_unsigned()?;
+
+        if code != MOD_REQ && code != MOD_OPT {
+            return Ok(None);
+        }
+
+        blob.try_read_unsigned()?;
+
+        Ok(Some(Self {
+            required: code == MOD_REQ,
+            def: blob.try_read_type_def_or_ref()?,
+        }))
+    }
+
+    fn try_read_all(blob: &mut Blob<'a>) -> Result<Vec<Self>, Error> {
+        let mut modifiers = Vec::new();
+
+        while let Some(modifier) = Self::try_from_blob(blob)? {
+            modifiers.push(modifier);
+        }
+
+        Ok(modifiers)
+    }
+}
+
+impl<'a> TypeSig<'a> {
+    /// Reads a type from a signature blob
+    ///
+    /// # Panics
+    ///
+    /// Panics if the blob is malformed. Use [`TypeSig::try_from_blob`] to handle errors instead.
+    pub fn from_blob(blob: &mut Blob<'a>) -> Self {
+        Self::try_from_blob(blob).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Reads a type from a signature blob
+    pub fn try_from_blob(blob: &mut Blob<'a>) -> Result<Self, Error> {
+        let code = blob.try_read_unsigned()?;
+
+        let sig = match code {
+            0x01 => Self::Void,
+            0x02 => Self::Bool,
+            0x03 => Self::Char,
+            0x04 => Self::I8,
+            0x05 => Self::U8,
+            0x06 => Self::I16,
+            0x07 => Self::U16,
+            0x08 => Self::I32,
+            0x09 => Self::U32,
+            0x0a => Self::I64,
+            0x0b => Self::U64,
+            0x0c => Self::F32,
+            0x0d => Self::F64,
+            0x0e => Self::String,
+            0x0f => {
+                let modifiers = Modifier::try_read_all(blob)?;
+                Self::Ptr(modifiers, Box::new(Self::try_from_blob(blob)?))
+            }
+            0x11 => Self::ValueType(blob.try_read_type_def_or_ref()?),
+            0x12 => Self::Class(blob.try_read_type_def_or_ref()?),
+            0x13 => Self::TypeGeneric(blob.try_read_unsigned()?),
+            0x14 => {
+                let element = Self::try_from_blob(blob)?;
+                let rank = blob.try_read_unsigned()?;
+                let mut shape = ArrayShape {
+                    rank,
+                    ..Default::default()
+                };
+
+                for _ in 0..blob.try_read_unsigned()? {
+                    shape.sizes.push(blob.try_read_unsigned()?);
+                }
+
+                for _ in 0..blob.try_read_unsigned()? {
+                    shape.lower_bounds.push(blob.try_read_signed()?);
+                }
+
+                if shape.sizes.len() > rank as usize || shape.lower_bounds.len() > rank as usize {
+                    return Err(blob.error(format!("array bounds exceed its rank of {}", rank)));
+                }
+
+                Self::Array(Box::new(element), shape)
+            }
+            0x15 => {
+                let is_value_type = match blob.try_read_unsigned()? {
+                    0x11 => true,
+                    0x12 => false,
+                    code => {
+                        return Err(
+                            blob.error(format!("invalid generic instance type 0x{:x}", code))
+                        )
+                    }
+                };
+
+                let def = blob.try_read_type_def_or_ref()?;
+                let mut args = Vec::new();
+
+                for _ in 0..blob.try_read_unsigned()? {
+                    args.push(Self::try_from_blob(blob)?);
+                }
+
+                Self::GenericInst {
+                    def,
+                    is_value_type,
+                    args,
+                }
+            }
+            0x16 => Self::TypedByRef,
+            0x18 => Self::ISize,
+            0x19 => Self::USize,
+            0x1b => Self::FnPtr(Box::new(MethodSig::try_from_blob(blob)?)),
+            0x1c => Self::Object,
+            0x1d => {
+                let modifiers = Modifier::try_read_all(blob)?;
+                Self::SZArray(modifiers, Box::new(Self::try_from_blob(blob)?))
+            }
+            0x1e => Self::MethodGeneric(blob.try_read_unsigned()?),
+            code => return Err(blob.error(format!("unexpected element type 0x{:x}", code))),
+        };
+
+        Ok(sig)
+    }
+
+    /// The equivalent [`ElementType`], if the type is a primitive or a named type
+    pub fn element_type(&self) -> Option<ElementType<'a>> {
+        match self {
+            Self::Void => Some(ElementType::Void),
+            Self::Bool => Some(ElementType::Bool),
+            Self::Char => Some(ElementType::Char),
+            Self::I8 => Some(ElementType::I8),
+            Self::U8 => Some(ElementType::U8),
+            Self::I16 => Some(ElementType::I16),
+            Self::U16 => Some(ElementType::U16),
+            Self::I32 => Some(ElementType::I32),
+            Self::U32 => Some(ElementType::U32),
+            Self::I64 => Some(ElementType::I64),
+            Self::U64 => Some(ElementType::U64),
+            Self::F32 => Some(ElementType::F32),
+            Self::F64 => Some(ElementType::F64),
+            Self::ISize => Some(ElementType::ISize),
+            Self::USize => Some(ElementType::USize),
+            Self::String => Some(ElementType::String),
+            Self::ValueType(def) => Some(ElementType::Struct(*def)),
+            Self::Class(def) => Some(ElementType::Class(*def)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{TableIndex, TypeReader};
+
+    fn blob(bytes: &[u8]) -> Blob<'static> {
+        let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("../../.windows/winmd/TestComponent.winmd");
+
+        let mut file = std::fs::read(path).unwrap();
+        let offset = file.len();
+        file.extend_from_slice(bytes);
+
+        let reader = TypeReader::builder()
+            .bytes("TestComponent.winmd", file)
+            .exclude_default(true)
+            .build()
+            .unwrap();
+
+        Blob {
+            reader: Box::leak(Box::new(reader)),
+            file_index: 0,
+            row: Row::new(0, TableIndex::TypeSpec, 0),
+            offset,
+            size: bytes.len(),
+        }
+    }
+
+    #[test]
+    fn general_array() {
+        // int32[1...3, -3...]
+        let sig =
+            TypeSig::try_from_blob(&mut blob(&[0x14, 0x08, 0x02, 0x01, 0x03, 0x02, 0x02, 0x7B]))
+                .unwrap();
+
+        assert_eq!(
+            sig,
+            TypeSig::Array(
+                Box::new(TypeSig::I32),
+                ArrayShape {
+                    rank: 2,
+                    sizes: vec![3],
+                    lower_bounds: vec![1, -3],
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn pointers_and_modifiers() {
+        // modreq(TypeRef 1) uint8** with a modopt on the inner pointer
+        let mut blob = blob(&[0x1f, 0x05, 0x0f, 0x20, 0x05, 0x0f, 0x05]);
+        let sig = ParamSig::try_from_blob(&mut blob).unwrap();
+
+        assert_eq!(sig.modifiers.len(), 1);
+        assert!(sig.modifiers[0].required);
+
+        match sig.ty {
+            TypeSig::Ptr(outer, inner) => {
+                assert!(outer.len() == 1 && !outer[0].required);
+                assert_eq!(*inner, TypeSig::Ptr(vec![], Box::new(TypeSig::U8)));
+            }
+            _ => panic!("expected a pointer"),
+        }
+    }
+
+    #[test]
+    fn function_pointer() {
+        // void (*)(int32, ...; float64)
+        let sig =
+            TypeSig::try_from_blob(&mut blob(&[0x1b, 0x05, 0x02, 0x01, 0x08, 0x41, 0x0d])).unwrap();
+
+        match sig {
+            TypeSig::FnPtr(method) => {
+                assert!(method.is_vararg());
+                assert!(!method.has_this());
+                assert_eq!(method.return_type.ty, TypeSig::Void);
+                assert_eq!(method.params.len(), 2);
+                assert_eq!(method.sentinel, Some(1));
+            }
+            _ => panic!("expected a function pointer"),
+        }
+    }
+
+    #[test]
+    fn local_variables() {
+        let sig =
+            Signature::try_from_blob(&mut blob(&[0x07, 0x02, 0x45, 0x10, 0x08, 0x0e])).unwrap();
+
+        match sig {
+            Signature::Local(locals) => {
+                assert!(locals[0].pinned && locals[0].param.by_ref);
+                assert_eq!(locals[0].param.ty, TypeSig::I32);
+                assert!(!locals[1].pinned);
+                assert_eq!(locals[1].param.ty, TypeSig::String);
+            }
+            _ => panic!("expected local variables"),
+        }
+    }
+
+    #[test]
+    fn malformed() {
+        assert!(TypeSig::try_from_blob(&mut blob(&[0x17])).is_err());
+        assert!(TypeSig::try_from_blob(&mut blob(&[0x15, 0x08])).is_err());
+        assert!(ParamSig::try_from_field_blob(&mut blob(&[0x20])).is_err());
+        assert!(MethodSig::try_from_blob(&mut blob(&[0x0a])).is_err());
+        assert!(TypeSig::try_from_blob(&mut blob(&[0x14, 0x08, 0x01, 0x02, 0x01, 0x01])).is_err());
+    }
+}
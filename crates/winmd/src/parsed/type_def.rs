@@ -1,26 +1,31 @@
 use super::*;
-use crate::{TableIndex, TypeReader};
+use crate::{File, TableIndex, TypeReader};
 
 #[derive(Copy, Clone)]
-pub struct TypeDef {
-    pub reader: &'static TypeReader,
+pub struct TypeDef<'a> {
+    pub reader: &'a TypeReader,
     pub row: Row,
 }
 
-impl TypeDef {
+impl<'a> TypeDef<'a> {
     pub fn flags(&self) -> TypeFlags {
         TypeFlags(self.reader.u32(self.row, 0))
     }
 
-    pub fn name(&self) -> (&'static str, &'static str) {
+    pub fn name(&self) -> (&'a str, &'a str) {
         (self.reader.str(self.row, 2), self.reader.str(self.row, 1))
     }
 
-    pub fn extends(&self) -> TypeDefOrRef {
+    /// The file that defines this type
+    pub fn file(&self) -> &'a File {
+        &self.reader.files[self.row.file_index as usize]
+    }
+
+    pub fn extends(&self) -> TypeDefOrRef<'a> {
         self.reader.decode(self.row, 3)
     }
 
-    pub fn fields(&self) -> impl Iterator<Item = Field> + '_ {
+    pub fn fields(&self) -> impl Iterator<Item = Field<'a>> + '_ {
         self.reader
             .list(self.row, TableIndex::Field, 4)
             .map(move |row| Field {
@@ -29,7 +34,7 @@ impl TypeDef {
             })
     }
 
-    pub fn methods(&self) -> impl Iterator<Item = MethodDef> + '_ {
+    pub fn methods(&self) -> impl Iterator<Item = MethodDef<'a>> + '_ {
         self.reader
             .list(self.row, TableIndex::MethodDef, 5)
             .map(move |row| MethodDef {
@@ -38,7 +43,37 @@ impl TypeDef {
             })
     }
 
-    pub fn generics(&self) -> impl Iterator<Item = GenericParam> + '_ {
+    pub fn properties(&self) -> impl Iterator<Item = Property<'a>> + '_ {
+        self.reader
+            .equal_range(
+                self.row.file_index,
+                TableIndex::PropertyMap,
+                0,
+                self.row.index + 1,
+            )
+            .flat_map(move |map| self.reader.list(map, TableIndex::Property, 1))
+            .map(move |row| Property {
+                reader: self.reader,
+                row,
+            })
+    }
+
+    pub fn events(&self) -> impl Iterator<Item = Event<'a>> + '_ {
+        self.reader
+            .equal_range(
+                self.row.file_index,
+                TableIndex::EventMap,
+                0,
+                self.row.index + 1,
+            )
+            .flat_map(move |map| self.reader.list(map, TableIndex::Event, 1))
+            .map(move |row| Event {
+                reader: self.reader,
+                row,
+            })
+    }
+
+    pub fn generics(&self) -> impl Iterator<Item = GenericParam<'a>> + '_ {
         self.reader
             .equal_range(
                 self.row.file_index,
@@ -52,7 +87,12 @@ impl TypeDef {
             })
     }
 
-    pub fn interfaces(&self) -> impl Iterator<Item = InterfaceImpl> + '_ {
+    /// The number of generic parameters declared by the type
+    pub fn generic_count(&self) -> usize {
+        self.generics().count()
+    }
+
+    pub fn interfaces(&self) -> impl Iterator<Item = InterfaceImpl<'a>> + '_ {
         self.reader
             .equal_range(
                 self.row.file_index,
@@ -66,7 +106,51 @@ impl TypeDef {
             })
     }
 
-    pub fn attributes(&self) -> impl Iterator<Item = Attribute> + '_ {
+    pub fn nested_types(&self) -> impl Iterator<Item = TypeDef<'a>> + '_ {
+        self.reader
+            .nested_types(self.row)
+            .iter()
+            .map(move |row| TypeDef {
+                reader: self.reader,
+                row: *row,
+            })
+    }
+
+    pub fn enclosing_type(&self) -> Option<TypeDef<'a>> {
+        self.reader
+            .equal_range(
+                self.row.file_index,
+                TableIndex::NestedClass,
+                0,
+                self.row.index + 1,
+            )
+            .next()
+            .map(|row| TypeDef {
+                reader: self.reader,
+                row: Row::new(
+                    self.reader.u32(row, 1) - 1,
+                    TableIndex::TypeDef,
+                    self.row.file_index,
+                ),
+            })
+    }
+
+    pub fn class_layout(&self) -> Option<ClassLayout<'a>> {
+        self.reader
+            .equal_range(
+                self.row.file_index,
+                TableIndex::ClassLayout,
+                2,
+                self.row.index + 1,
+            )
+            .map(move |row| ClassLayout {
+                reader: self.reader,
+                row,
+            })
+            .next()
+    }
+
+    pub fn attributes(&self) -> impl Iterator<Item = Attribute<'a>> + '_ {
         self.reader
             .equal_range(
                 self.row.file_index,
@@ -84,13 +168,25 @@ impl TypeDef {
         self.attributes().any(|attribute| attribute.name() == name)
     }
 
+    /// The GUID from the type's `GuidAttribute`, if it has one
+    pub fn guid(&self) -> Option<Guid> {
+        let attribute = self
+            .attributes()
+            .find(|attribute| attribute.name().1 == "GuidAttribute")?;
+
+        Guid::from_args(&attribute.try_args().ok()?)
+    }
+
     pub fn is_winrt(&self) -> bool {
         self.flags().windows_runtime()
     }
 
+    /// The type's category, where types such as `<Module>` that extend nothing are classes
     pub fn category(&self) -> TypeCategory {
         if self.flags().interface() {
             TypeCategory::Interface
+        } else if self.reader.u32(self.row, 3) == 0 {
+            TypeCategory::Class
         } else {
             match self.extends().name() {
                 ("System", "Enum") => TypeCategory::Enum,
@@ -108,19 +204,12 @@ impl TypeDef {
         }
     }
 
-    pub fn underlying_type(&self) -> ElementType {
-        for field in self.fields() {
+    pub fn underlying_type(&self) -> ElementType<'a> {
+        if let Some(field) = self.fields().next() {
             if let Some(constant) = field.constant() {
                 return constant.value_type();
-            } else {
-                let blob = &mut field.sig();
-                blob.read_unsigned();
-                blob.read_modifiers();
-
-                blob.read_expected(0x1D);
-                blob.read_modifiers();
-
-                return ElementType::from_blob(blob);
+            } else if let Some(element_type) = field.signature().ty.element_type() {
+                return element_type;
             }
         }
 
@@ -128,27 +217,27 @@ impl TypeDef {
     }
 }
 
-impl std::fmt::Debug for TypeDef {
+impl std::fmt::Debug for TypeDef<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("TypeDef").field("row", &self.row).finish()
     }
 }
 
-impl PartialEq for TypeDef {
+impl PartialEq for TypeDef<'_> {
     fn eq(&self, other: &Self) -> bool {
         self.row == other.row
     }
 }
 
-impl Eq for TypeDef {}
+impl Eq for TypeDef<'_> {}
 
-impl Ord for TypeDef {
+impl Ord for TypeDef<'_> {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         self.row.cmp(&other.row)
     }
 }
 
-impl PartialOrd for TypeDef {
+impl PartialOrd for TypeDef<'_> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.cmp(other))
     }
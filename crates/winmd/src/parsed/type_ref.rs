@@ -2,42 +2,79 @@ use super::*;
 use crate::TypeReader;
 
 #[derive(Copy, Clone)]
-pub struct TypeRef {
-    pub reader: &'static TypeReader,
+pub struct TypeRef<'a> {
+    pub reader: &'a TypeReader,
     pub row: Row,
 }
 
-impl TypeRef {
-    pub fn name(&self) -> (&'static str, &'static str) {
+impl<'a> TypeRef<'a> {
+    pub fn name(&self) -> (&'a str, &'a str) {
         (self.reader.str(self.row, 2), self.reader.str(self.row, 1))
     }
 
-    pub fn resolve(&self) -> TypeDef {
-        self.reader.expect_type_def(self.name())
+    /// The scope in which the referenced type is defined, if any
+    ///
+    /// A reference without a scope refers to an exported type.
+    pub fn scope(&self) -> Option<ResolutionScope<'a>> {
+        if self.reader.u32(self.row, 0) >> 2 == 0 {
+            None
+        } else {
+            Some(self.reader.decode(self.row, 0))
+        }
+    }
+
+    /// The type that encloses this type, if the reference is to a nested type
+    pub fn enclosing_type(&self) -> Option<TypeRef<'a>> {
+        match self.scope() {
+            Some(ResolutionScope::TypeRef(enclosing)) => Some(enclosing),
+            _ => None,
+        }
+    }
+
+    pub fn resolve(&self) -> TypeDef<'a> {
+        match self.enclosing_type() {
+            Some(enclosing) => {
+                let enclosing = enclosing.resolve();
+                let name = self.name().1;
+
+                let nested = enclosing
+                    .nested_types()
+                    .find(|nested| nested.name().1 == name);
+
+                nested.unwrap_or_else(|| {
+                    panic!(
+                        "Could not find nested type `{}` in {:?}",
+                        name,
+                        enclosing.name()
+                    )
+                })
+            }
+            None => self.reader.expect_type_def(self.name()),
+        }
     }
 }
 
-impl std::fmt::Debug for TypeRef {
+impl std::fmt::Debug for TypeRef<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("TypeRef").field("row", &self.row).finish()
     }
 }
 
-impl PartialEq for TypeRef {
+impl PartialEq for TypeRef<'_> {
     fn eq(&self, other: &Self) -> bool {
         self.row == other.row
     }
 }
 
-impl Eq for TypeRef {}
+impl Eq for TypeRef<'_> {}
 
-impl Ord for TypeRef {
+impl Ord for TypeRef<'_> {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         self.row.cmp(&other.row)
     }
 }
 
-impl PartialOrd for TypeRef {
+impl PartialOrd for TypeRef<'_> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.cmp(other))
     }
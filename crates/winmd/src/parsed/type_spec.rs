@@ -1,39 +1,48 @@
 use super::*;
-use crate::TypeReader;
+use crate::{Error, TypeReader};
 
 #[derive(Copy, Clone)]
-pub struct TypeSpec {
-    pub reader: &'static TypeReader,
+pub struct TypeSpec<'a> {
+    pub reader: &'a TypeReader,
     pub row: Row,
 }
 
-impl TypeSpec {
-    pub fn sig(&self) -> Blob {
+impl<'a> TypeSpec<'a> {
+    pub fn sig(&self) -> Blob<'a> {
         self.reader.blob(self.row, 0)
     }
+
+    pub fn signature(&self) -> TypeSig<'a> {
+        self.try_signature()
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_signature(&self) -> Result<TypeSig<'a>, Error> {
+        TypeSig::try_from_blob(&mut self.reader.try_blob(self.row, 0)?)
+    }
 }
 
-impl std::fmt::Debug for TypeSpec {
+impl std::fmt::Debug for TypeSpec<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("TypeSpec").field("row", &self.row).finish()
     }
 }
 
-impl PartialEq for TypeSpec {
+impl PartialEq for TypeSpec<'_> {
     fn eq(&self, other: &Self) -> bool {
         self.row == other.row
     }
 }
 
-impl Eq for TypeSpec {}
+impl Eq for TypeSpec<'_> {}
 
-impl Ord for TypeSpec {
+impl Ord for TypeSpec<'_> {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         self.row.cmp(&other.row)
     }
 }
 
-impl PartialOrd for TypeSpec {
+impl PartialOrd for TypeSpec<'_> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.cmp(other))
     }
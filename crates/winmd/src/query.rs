@@ -0,0 +1,305 @@
+use crate::*;
+
+/// Finds the types, methods and fields known to a [`TypeReader`] that match a set of filters
+///
+/// Every filter that has been set must match. The namespace, category and type flags
+/// filters select types, and methods and fields are only considered if the type that
+/// owns them is selected. The remaining filters apply to whichever item is returned.
+///
+/// Attributes, imports and nested type lookups use the binary search of the sorted
+/// metadata tables, so filtering costs a lookup per candidate rather than a scan of
+/// the tables. Types are visited by namespace and then by name, with nested types
+/// following the type that encloses them.
+#[derive(Clone)]
+pub struct Query<'a> {
+    reader: &'a TypeReader,
+    namespace: Option<String>,
+    category: Option<TypeCategory>,
+    type_flags: u32,
+    method_flags: u32,
+    field_flags: u32,
+    attributes: Vec<(String, String)>,
+    module: Option<String>,
+    references: Vec<(String, String)>,
+}
+
+impl<'a> Query<'a> {
+    pub(crate) fn new(reader: &'a TypeReader) -> Self {
+        Self {
+            reader,
+            namespace: None,
+            category: None,
+            type_flags: 0,
+            method_flags: 0,
+            field_flags: 0,
+            attributes: Vec::new(),
+            module: None,
+            references: Vec::new(),
+        }
+    }
+
+    /// Only considers types in the given namespace, which must match exactly
+    pub fn namespace(mut self, namespace: &str) -> Self {
+        self.namespace = Some(namespace.to_string());
+        self
+    }
+
+    /// Only considers types of the given category
+    pub fn category(mut self, category: TypeCategory) -> Self {
+        self.category = Some(category);
+        self
+    }
+
+    /// Only considers types whose flags include every bit of `flags`
+    pub fn type_flags(mut self, flags: u32) -> Self {
+        self.type_flags |= flags;
+        self
+    }
+
+    /// Only matches methods whose flags include every bit of `flags`
+    pub fn method_flags(mut self, flags: u32) -> Self {
+        self.method_flags |= flags;
+        self
+    }
+
+    /// Only matches fields whose flags include every bit of `flags`
+    pub fn field_flags(mut self, flags: u32) -> Self {
+        self.field_flags |= flags;
+        self
+    }
+
+    /// Only matches items that carry the given attribute, such as
+    /// `("Windows.Foundation.Metadata", "DeprecatedAttribute")`
+    ///
+    /// May be called more than once to require several attributes.
+    pub fn attribute(mut self, (namespace, name): (&str, &str)) -> Self {
+        self.attributes
+            .push((namespace.to_string(), name.to_string()));
+        self
+    }
+
+    /// Only matches functions and variables imported from the given module, or the
+    /// types that declare them
+    ///
+    /// Module names are compared ignoring case and any `.dll` extension, so
+    /// `d3d12.dll` matches an import from `D3D12`.
+    pub fn module(mut self, module: &str) -> Self {
+        self.module = Some(module_name(module));
+        self
+    }
+
+    /// Only matches items whose signatures refer to the given type, including within
+    /// generic arguments, pointers, arrays and function pointers
+    ///
+    /// A method matches if its return type or any parameter refers to the type, and any
+    /// other type matches if its base type, interfaces, fields or methods do. May be
+    /// called more than once to require several types.
+    pub fn references(mut self, (namespace, name): (&str, &str)) -> Self {
+        self.references
+            .push((namespace.to_string(), name.to_string()));
+        self
+    }
+
+    /// The matching types, including nested types
+    ///
+    /// # Panics
+    ///
+    /// Panics if a signature that must be checked is malformed.
+    pub fn types(&self) -> impl Iterator<Item = TypeDef<'a>> + '_ {
+        self.candidates()
+            .into_iter()
+            .filter(move |def| self.matches_type(def))
+    }
+
+    /// The matching methods, including the functions of a namespace
+    ///
+    /// # Panics
+    ///
+    /// Panics if a signature that must be checked is malformed.
+    pub fn methods(&self) -> impl Iterator<Item = MethodDef<'a>> + '_ {
+        self.candidates()
+            .into_iter()
+            .flat_map(|def| def.methods().collect::<Vec<_>>())
+            .filter(move |method| self.matches_method(method))
+    }
+
+    /// The matching fields, including the constants of a namespace
+    ///
+    /// # Panics
+    ///
+    /// Panics if a signature that must be checked is malformed.
+    pub fn fields(&self) -> impl Iterator<Item = Field<'a>> + '_ {
+        self.candidates()
+            .into_iter()
+            .flat_map(|def| def.fields().collect::<Vec<_>>())
+            .filter(move |field| self.matches_field(field))
+    }
+
+    /// The types selected by the namespace, category and type flags filters
+    fn candidates(&self) -> Vec<TypeDef<'a>> {
+        fn push<'a>(query: &Query<'a>, types: &mut Vec<TypeDef<'a>>, def: TypeDef<'a>) {
+            let category = match query.category {
+                Some(category) => category == def.category(),
+                None => true,
+            };
+
+            if category && def.flags().0 & query.type_flags == query.type_flags {
+                types.push(def);
+            }
+
+            for nested in def.nested_types() {
+                push(query, types, nested);
+            }
+        }
+
+        let mut types = Vec::new();
+
+        for namespace in self.reader.namespaces() {
+            if let Some(filter) = &self.namespace {
+                if filter != namespace {
+                    continue;
+                }
+            }
+
+            for ty in self.reader.namespace_types(namespace) {
+                if let Type::TypeDef(def) = ty {
+                    push(self, &mut types, def);
+                }
+            }
+        }
+
+        types
+    }
+
+    fn matches_type(&self, def: &TypeDef<'a>) -> bool {
+        if !self.matches_attributes(def.attributes()) {
+            return false;
+        }
+
+        if self.module.is_some()
+            && !def
+                .methods()
+                .any(|method| self.matches_module(method.impl_map()))
+            && !def
+                .fields()
+                .any(|field| self.matches_module(field.impl_map()))
+        {
+            return false;
+        }
+
+        self.references.iter().all(|(namespace, name)| {
+            let name = (namespace.as_str(), name.as_str());
+
+            // A type is not considered to refer to itself, as an enum's values do.
+            if def.name() == name {
+                return false;
+            }
+
+            // Interfaces and classes such as `<Module>` have no base type.
+            (def.reader.u32(def.row, 3) != 0 && def_refers_to(&def.extends(), name))
+                || def
+                    .interfaces()
+                    .any(|interface| def_refers_to(&interface.interface(), name))
+                || def
+                    .fields()
+                    .any(|field| refers_to(&field.signature().ty, name))
+                || def
+                    .methods()
+                    .any(|method| method_refers_to(&method.signature(), name))
+        })
+    }
+
+    fn matches_method(&self, method: &MethodDef<'a>) -> bool {
+        if method.flags().0 & self.method_flags != self.method_flags
+            || !self.matches_attributes(method.attributes())
+            || !self.matches_module(method.impl_map())
+        {
+            return false;
+        }
+
+        if self.references.is_empty() {
+            return true;
+        }
+
+        let signature = method.signature();
+
+        self.references.iter().all(|(namespace, name)| {
+            method_refers_to(&signature, (namespace.as_str(), name.as_str()))
+        })
+    }
+
+    fn matches_field(&self, field: &Field<'a>) -> bool {
+        if field.flags().0 & self.field_flags != self.field_flags
+            || !self.matches_attributes(field.attributes())
+            || !self.matches_module(field.impl_map())
+        {
+            return false;
+        }
+
+        if self.references.is_empty() {
+            return true;
+        }
+
+        let signature = field.signature();
+
+        self.references
+            .iter()
+            .all(|(namespace, name)| refers_to(&signature.ty, (namespace.as_str(), name.as_str())))
+    }
+
+    fn matches_attributes<I: Iterator<Item = Attribute<'a>>>(&self, attributes: I) -> bool {
+        if self.attributes.is_empty() {
+            return true;
+        }
+
+        let names: Vec<(&str, &str)> = attributes.map(|attribute| attribute.name()).collect();
+
+        self.attributes
+            .iter()
+            .all(|(namespace, name)| names.contains(&(namespace.as_str(), name.as_str())))
+    }
+
+    fn matches_module(&self, impl_map: Option<ImplMap<'a>>) -> bool {
+        match (&self.module, impl_map) {
+            (None, _) => true,
+            (Some(module), Some(impl_map)) => *module == module_name(impl_map.scope().name()),
+            (Some(_), None) => false,
+        }
+    }
+}
+
+/// The name of a module for comparison, without case or a `.dll` extension
+fn module_name(name: &str) -> String {
+    let name = name.to_lowercase();
+
+    match name.strip_suffix(".dll") {
+        Some(name) => name.to_string(),
+        None => name,
+    }
+}
+
+fn refers_to(sig: &TypeSig<'_>, name: (&str, &str)) -> bool {
+    match sig {
+        TypeSig::ValueType(def) | TypeSig::Class(def) => def_refers_to(def, name),
+        TypeSig::GenericInst { def, args, .. } => {
+            def_refers_to(def, name) || args.iter().any(|arg| refers_to(arg, name))
+        }
+        TypeSig::Ptr(_, ty) | TypeSig::SZArray(_, ty) | TypeSig::Array(ty, _) => {
+            refers_to(ty, name)
+        }
+        TypeSig::FnPtr(sig) => method_refers_to(sig, name),
+        _ => false,
+    }
+}
+
+fn def_refers_to(def: &TypeDefOrRef<'_>, name: (&str, &str)) -> bool {
+    match def {
+        TypeDefOrRef::TypeSpec(spec) => refers_to(&spec.signature(), name),
+        _ => def.name() == name,
+    }
+}
+
+fn method_refers_to(sig: &MethodSig<'_>, name: (&str, &str)) -> bool {
+    refers_to(&sig.return_type.ty, name)
+        || sig.params.iter().any(|param| refers_to(&param.ty, name))
+}
@@ -0,0 +1,77 @@
+/// Change a CamelCase name to snake case the way the code generator names modules,
+/// fields and methods.
+pub fn to_snake(camel: &str) -> String {
+    // Add any manual fixups here, anything that isn't handle automatically by the algorithm below.
+    if camel == "WinRT" {
+        return "winrt".to_string();
+    }
+
+    let mut snake = String::with_capacity(camel.len());
+    let mut since_last_underscore = 0;
+    let mut chars = camel.chars();
+
+    // first character as lowercased
+    if let Some(first) = chars.next() {
+        for c in first.to_lowercase() {
+            since_last_underscore += 1;
+            snake.push(c);
+        }
+    }
+
+    // zip together iterator of previous characters and next characters
+    for (previous, next) in camel.chars().zip(camel.chars().skip(2)) {
+        // safe to unwrap since the iterator of next chars produced something
+        let current = chars.next().unwrap();
+
+        // If the current character isn't uppercase we can just push it and move on
+        if !current.is_uppercase() {
+            since_last_underscore += 1;
+            snake.push(current);
+            continue;
+        }
+
+        if previous.is_lowercase() || next.is_lowercase() && since_last_underscore > 1 {
+            since_last_underscore = 0;
+
+            if previous != '_' {
+                snake.push('_');
+            }
+        }
+
+        for c in current.to_lowercase() {
+            since_last_underscore += 1;
+
+            snake.push(c);
+        }
+    }
+
+    if let Some(last) = chars.next() {
+        snake.extend(last.to_lowercase());
+    }
+
+    snake
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_snake_works() {
+        assert_eq!(to_snake("Windows"), "windows".to_owned());
+        assert_eq!(to_snake("ApplicationModel"), "application_model".to_owned());
+        assert_eq!(to_snake("UIProgramming"), "ui_programming".to_owned());
+        assert_eq!(
+            to_snake("CreateUInt8Array"),
+            "create_uint8_array".to_owned()
+        );
+        assert_eq!(to_snake("appointmentId"), "appointment_id".to_owned());
+        assert_eq!(
+            to_snake("CreateField_Default"),
+            "create_field_default".to_owned()
+        );
+        assert_eq!(to_snake("a"), "a".to_owned());
+        assert_eq!(to_snake(""), "".to_owned());
+        assert!(to_snake("WinRT") == "winrt");
+    }
+}
@@ -31,9 +31,8 @@ pub(crate) trait View {
     fn view_as<T: Pod>(&self, cli_offset: u32) -> &T;
     fn view_as_slice_of<T: Pod>(&self, cli_offset: u32, len: u32) -> &[T];
     fn copy_as<T: Copy + CopyPod>(&self, cli_offset: u32) -> T;
-    fn view_as_str(&self, cli_offset: u32) -> &[u8];
 }
 
-pub trait Decode {
-    fn decode(reader: &'static TypeReader, code: u32, file: u16) -> Self;
+pub trait Decode<'a> {
+    fn decode(reader: &'a TypeReader, code: u32, file: u16) -> Self;
 }
@@ -1,21 +1,48 @@
 use crate::*;
+use once_cell::sync::OnceCell;
 use std::cmp::Ordering;
 use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 /// A reader of type information from Windows Metadata
 pub struct TypeReader {
-    // TODO: fields should be private
     /// The parsed Windows metadata files the [`TypeReader`] has access to
-    pub files: Vec<File>,
-    /// Types known to this [`TypeReader`]
+    pub(crate) files: Vec<File>,
+    /// Types known to this [`TypeReader`], keyed by namespace
     ///
-    /// This is a mapping between namespace names and the types inside
-    /// that namespace. The keys are the namespace and the values is a mapping
-    /// of type names to type definitions
-    types: BTreeMap<String, BTreeMap<String, TypeRow>>,
-    // TODO: store Row objects and turn them into TypeDef on request.
-    // When turning into TypeDef they add the &'static TypeReader
+    /// The keys borrow from the bytes of `files`, which stay at a fixed address
+    /// and are never modified for as long as the [`TypeReader`] is alive.
+    types: BTreeMap<&'static str, Namespace>,
+    /// A mapping of enclosing types to the types nested inside them
+    nested: BTreeMap<Row, Vec<Row>>,
+    /// Pairs of type definitions with the same name, where the first is used and the
+    /// second is ignored
+    duplicates: Vec<(Row, Row)>,
+    /// The `MethodSemantics` rows of each file ordered by their Method column, which are
+    /// only sorted when a method's semantics are first queried
+    semantics: Vec<OnceCell<Vec<u32>>>,
+}
+
+/// A type definition that is ignored because an earlier file defines a type with the same name
+#[derive(Copy, Clone, Debug)]
+pub struct DuplicateType<'a> {
+    /// The definition that the [`TypeReader`] uses
+    pub used: TypeDef<'a>,
+    /// The definition that is ignored
+    pub ignored: TypeDef<'a>,
+}
+
+/// The types within a single namespace
+#[derive(Default)]
+struct Namespace {
+    /// Type definitions keyed by name, which are indexed up front
+    types: BTreeMap<&'static str, TypeRow>,
+    /// Classes such as `Apis` whose functions and constants are exposed directly
+    /// within the namespace
+    apis: Vec<Row>,
+    /// The type definitions along with the members of `apis`, which are only
+    /// indexed when the namespace is first queried
+    all: OnceCell<BTreeMap<&'static str, TypeRow>>,
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
@@ -26,14 +53,14 @@ enum TypeRow {
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
-pub enum Type {
-    TypeDef(TypeDef),
-    MethodDef((TypeDef, MethodDef)),
-    Field((TypeDef, Field)),
+pub enum Type<'a> {
+    TypeDef(TypeDef<'a>),
+    MethodDef((TypeDef<'a>, MethodDef<'a>)),
+    Field((TypeDef<'a>, Field<'a>)),
 }
 
-impl Type {
-    fn new(reader: &'static TypeReader, row: TypeRow) -> Self {
+impl<'a> Type<'a> {
+    fn new(reader: &'a TypeReader, row: TypeRow) -> Self {
         match row {
             TypeRow::TypeDef(def) => Type::TypeDef(TypeDef { reader, row: def }),
             TypeRow::MethodDef((def, method)) => Type::MethodDef((
@@ -51,146 +78,201 @@ impl Type {
 }
 
 impl TypeReader {
+    /// The shared reader used by code generation, which reads the files found by
+    /// [`winmd_search_paths`] or else the default metadata
+    ///
+    /// Set the `WINDOWS_WINMD_VALIDATE` environment variable to [`validate`] the files first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the files cannot be read or are malformed.
     pub fn get() -> &'static Self {
+        Self::get_with(&[])
+    }
+
+    /// The shared reader used by code generation, searching `search_paths` for files before
+    /// the places that [`TypeReader::get`] searches
+    ///
+    /// The reader is created by the first call to either function, so `search_paths` has no
+    /// effect once it exists.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the files cannot be read or are malformed.
+    pub fn get_with(search_paths: &[PathBuf]) -> &'static Self {
         use std::{mem::MaybeUninit, sync::Once};
         static ONCE: Once = Once::new();
         static mut VALUE: MaybeUninit<TypeReader> = MaybeUninit::uninit();
 
         ONCE.call_once(|| {
+            let paths = find_winmd_files(&winmd_search_paths(search_paths));
+
+            let reader = Self::builder()
+                .exclude_default(!paths.is_empty())
+                .paths(paths)
+                .memory_map(true)
+                .validate(std::env::var_os("WINDOWS_WINMD_VALIDATE").is_some())
+                .build()
+                .unwrap_or_else(|error| panic!("{}", error));
+
             // This is safe because `Once` provides thread-safe one-time initialization
-            unsafe { VALUE = MaybeUninit::new(Self::from_iter(winmd_paths())) }
+            unsafe { VALUE = MaybeUninit::new(reader) }
         });
 
         // This is safe because `call_once` has already been called.
         unsafe { &*VALUE.as_ptr() }
     }
 
-    /// Insert WinRT metadata at the given paths
-    ///
-    /// # Panics
+    /// Returns a [`TypeReaderBuilder`] for reading a chosen set of Windows Metadata files
+    pub fn builder() -> TypeReaderBuilder {
+        TypeReaderBuilder::default()
+    }
+
+    /// Reads the Windows Metadata files at the given paths, falling back to the
+    /// default metadata if no paths are provided
     ///
-    /// This function panics if the if the files where the windows metadata are stored cannot be read.
-    fn from_iter<I: IntoIterator<Item = PathBuf>>(files: I) -> Self {
-        let mut files: Vec<File> = files.into_iter().map(|file| File::new(file)).collect();
+    /// Returns an [`Error`] if any of the files cannot be read or are malformed.
+    pub fn try_from_paths<I: IntoIterator<Item = PathBuf>>(paths: I) -> Result<Self, Error> {
+        let paths: Vec<PathBuf> = paths.into_iter().collect();
+
+        Self::builder()
+            .exclude_default(!paths.is_empty())
+            .paths(paths)
+            .build()
+    }
 
-        if files.is_empty() {
-            files.push(File::from_bytes(
-                include_bytes!("../default/Windows.Win32.winmd").to_vec(),
-            ));
-            files.push(File::from_bytes(
-                include_bytes!("../default/Windows.WinRT.winmd").to_vec(),
-            ));
-        }
+    /// Indexes the types in the given files, which have already been parsed
+    ///
+    /// Only type definitions are indexed here. The functions and constants of a
+    /// namespace are indexed the first time the namespace is queried.
+    pub(crate) fn try_from_files(files: Vec<File>) -> Result<Self, Error> {
+        let semantics = files.iter().map(|_| OnceCell::new()).collect();
 
-        let reader = Self {
+        let mut reader = Self {
             files,
             types: BTreeMap::default(),
+            nested: BTreeMap::default(),
+            duplicates: Vec::new(),
+            semantics,
         };
 
-        let mut types = BTreeMap::<String, BTreeMap<String, TypeRow>>::default();
+        let mut types = BTreeMap::<&'static str, Namespace>::default();
+        let mut nested = BTreeMap::<Row, Vec<Row>>::default();
+        let mut duplicates = Vec::new();
 
         for (index, file) in reader.files.iter().enumerate() {
             let row_count = file.type_def_table().row_count;
 
-            for row in 0..row_count {
-                let def = Row::new(row, TableIndex::TypeDef, index as u16);
-                let namespace = reader.str(def, 2).to_string();
-                let name = reader.str(def, 1).to_string();
+            for row in 0..file.tables[TableIndex::NestedClass as usize].row_count {
+                let row = Row::new(row, TableIndex::NestedClass, index as u16);
+                let inner = reader.u32(row, 0);
+                let outer = reader.u32(row, 1);
+
+                if inner == 0 || inner > row_count || outer == 0 || outer > row_count {
+                    return Err(reader.row_error(row, "refers to an invalid TypeDef"));
+                }
 
-                types
-                    .entry(namespace.to_string())
+                nested
+                    .entry(Row::new(outer - 1, TableIndex::TypeDef, index as u16))
                     .or_default()
-                    .entry(name.to_string())
-                    .or_insert(TypeRow::TypeDef(def));
+                    .push(Row::new(inner - 1, TableIndex::TypeDef, index as u16));
+            }
 
+            for row in 0..row_count {
+                let def = Row::new(row, TableIndex::TypeDef, index as u16);
                 let flags = TypeFlags(reader.u32(def, 0));
 
-                if flags.interface() || flags.windows_runtime() {
+                // Nested types are only reachable through their enclosing type.
+                if flags.nested() {
                     continue;
                 }
 
-                let extends = reader.u32(def, 3);
+                let namespace = reader.try_static_str(def, 2)?;
+                let name = reader.try_static_str(def, 1)?;
+                let entry = types.entry(namespace).or_default();
+
+                if !is_excluded_type((namespace, name)) {
+                    match entry.types.entry(name).or_insert(TypeRow::TypeDef(def)) {
+                        TypeRow::TypeDef(used) if *used != def => duplicates.push((*used, def)),
+                        _ => {}
+                    }
+                }
 
-                if extends == 0 {
+                if flags.interface() || flags.windows_runtime() {
                     continue;
                 }
 
-                let extends = Row::new((extends >> 2) - 1, TableIndex::TypeRef, index as u16);
+                let extends = reader.u32(def, 3);
 
-                if (reader.str(extends, 2), reader.str(extends, 1)) != ("System", "Object") {
+                // Only a `TypeRef` can refer to `System.Object`.
+                if extends & 3 != 1 {
                     continue;
                 }
 
-                for field in reader.list(def, TableIndex::Field, 4) {
-                    let name = reader.str(field, 1);
-
-                    types
-                        .entry(namespace.to_string())
-                        .or_default()
-                        .entry(name.to_string())
-                        .or_insert(TypeRow::Field((def, field)));
+                if extends >> 2 == 0
+                    || extends >> 2 > file.tables[TableIndex::TypeRef as usize].row_count
+                {
+                    return Err(reader.row_error(def, "extends an invalid TypeRef"));
                 }
 
-                for method in reader.list(def, TableIndex::MethodDef, 5) {
-                    let name = reader.str(method, 3);
+                let extends = Row::new((extends >> 2) - 1, TableIndex::TypeRef, index as u16);
 
-                    types
-                        .entry(namespace.to_string())
-                        .or_default()
-                        .entry(name.to_string())
-                        .or_insert(TypeRow::MethodDef((def, method)));
+                if (reader.try_str(extends, 2)?, reader.try_str(extends, 1)?)
+                    != ("System", "Object")
+                {
+                    continue;
                 }
-            }
-        }
 
-        fn remove_excluded_type(
-            types: &mut BTreeMap<String, BTreeMap<String, TypeRow>>,
-            (namespace, type_name): (&str, &str),
-        ) {
-            if let Some(value) = types.get_mut(namespace) {
-                value.remove(type_name);
+                // Check the member lists now so that indexing them later cannot fail.
+                let _ = reader.try_list(def, TableIndex::Field, 4)?;
+                let _ = reader.try_list(def, TableIndex::MethodDef, 5)?;
+                entry.apis.push(def);
             }
         }
 
-        remove_excluded_type(&mut types, ("Windows.Foundation", "HResult"));
-        remove_excluded_type(&mut types, ("Windows.Win32.Com", "IUnknown"));
-        remove_excluded_type(&mut types, ("Windows.Win32.SystemServices", "BOOL"));
-        remove_excluded_type(&mut types, ("Windows.Win32.Direct2D", "D2D_MATRIX_3X2_F"));
-
-        remove_excluded_type(
-            &mut types,
-            ("Windows.Win32.SystemServices", "LARGE_INTEGER"),
-        );
-
-        remove_excluded_type(
-            &mut types,
-            ("Windows.Win32.SystemServices", "ULARGE_INTEGER"),
-        );
-
-        // TODO: map BSTR
+        reader.types = types;
+        reader.nested = nested;
+        reader.duplicates = duplicates;
+        Ok(reader)
+    }
 
-        // TODO: remove once this is fixed: https://github.com/microsoft/win32metadata/issues/30
-        remove_excluded_type(
-            &mut types,
-            ("Windows.Win32", "CFunctionDiscoveryNotificationWrapper"),
-        );
+    /// The parsed Windows metadata files the [`TypeReader`] has access to
+    pub fn files(&self) -> &[File] {
+        &self.files
+    }
 
-        Self {
-            files: reader.files,
-            types,
-        }
+    /// Type definitions that share a name with a type from an earlier file
+    ///
+    /// When several files define the same type, the definition from the earliest file
+    /// is used. Files passed explicitly are read in the order given, followed by any
+    /// in-memory buffers, then MIDL sources and finally the default metadata, so user
+    /// files take precedence over the defaults. The shared reader reads files in the order that
+    /// [`find_winmd_files`] finds them.
+    pub fn duplicate_types(&self) -> impl Iterator<Item = DuplicateType<'_>> + '_ {
+        self.duplicates
+            .iter()
+            .map(move |(used, ignored)| DuplicateType {
+                used: TypeDef {
+                    reader: self,
+                    row: *used,
+                },
+                ignored: TypeDef {
+                    reader: self,
+                    row: *ignored,
+                },
+            })
     }
 
-    pub fn find_lowercase_namespace(&'static self, lowercase: &str) -> Option<&'static str> {
+    pub fn find_lowercase_namespace(&self, lowercase: &str) -> Option<&str> {
         self.types
             .keys()
             .find(|namespace| namespace.to_lowercase() == lowercase)
-            .map(|namespace| namespace.as_str())
+            .copied()
     }
 
     /// Get all the namespace names that the [`TypeReader`] knows about
-    pub fn namespaces(&self) -> impl Iterator<Item = &String> {
-        self.types.keys()
+    pub fn namespaces(&self) -> impl Iterator<Item = &str> {
+        self.types.keys().copied()
     }
 
     /// Get all type definitions ([`TypeDef`]s) for a given namespace
@@ -198,15 +280,15 @@ impl TypeReader {
     /// # Panics
     ///
     /// Panics if the namespace does not exist
-    pub fn namespace_types(&'static self, namespace: &str) -> impl Iterator<Item = Type> + '_ {
-        self.types[namespace]
+    pub fn namespace_types(&self, namespace: &str) -> impl Iterator<Item = Type<'_>> + '_ {
+        self.namespace_index(&self.types[namespace])
             .values()
             .map(move |row| Type::new(self, *row))
     }
 
-    pub fn expect_type(&'static self, (namespace, type_name): (&str, &str)) -> Type {
+    pub fn expect_type(&self, (namespace, type_name): (&str, &str)) -> Type<'_> {
         if let Some(types) = self.types.get(namespace) {
-            if let Some(row) = types.get(type_name) {
+            if let Some(row) = self.namespace_index(types).get(type_name) {
                 return Type::new(self, *row);
             }
         }
@@ -214,17 +296,86 @@ impl TypeReader {
         panic!("Could not find type `{}.{}`", namespace, type_name);
     }
 
-    pub fn expect_type_def(&'static self, (namespace, type_name): (&str, &str)) -> TypeDef {
+    pub fn expect_type_def(&self, (namespace, type_name): (&str, &str)) -> TypeDef<'_> {
+        self.find_type_def((namespace, type_name))
+            .unwrap_or_else(|| panic!("Could not find type def `{}.{}`", namespace, type_name))
+    }
+
+    /// Returns a [`Query`] for finding the types, methods and fields that match a set of filters
+    pub fn query(&self) -> Query<'_> {
+        Query::new(self)
+    }
+
+    /// Finds the type definition ([`TypeDef`]) with the given namespace and name, if any
+    pub fn find_type_def(&self, (namespace, type_name): (&str, &str)) -> Option<TypeDef<'_>> {
         if let Some(types) = self.types.get(namespace) {
-            if let Some(TypeRow::TypeDef(row)) = types.get(type_name) {
-                return TypeDef {
+            if let Some(TypeRow::TypeDef(row)) = types.types.get(type_name) {
+                return Some(TypeDef {
                     reader: self,
                     row: *row,
-                };
+                });
             }
         }
 
-        panic!("Could not find type def `{}.{}`", namespace, type_name);
+        None
+    }
+
+    /// Returns the full index of a namespace, indexing its functions and constants
+    /// if this is the first time the namespace has been queried
+    fn namespace_index<'a>(&self, namespace: &'a Namespace) -> &'a BTreeMap<&'static str, TypeRow> {
+        namespace.all.get_or_init(|| {
+            let mut all = namespace.types.clone();
+
+            for def in &namespace.apis {
+                let type_namespace = self.static_str(*def, 2);
+
+                for field in self.list(*def, TableIndex::Field, 4) {
+                    let name = self.static_str(field, 1);
+
+                    if !is_excluded_type((type_namespace, name)) {
+                        all.entry(name).or_insert(TypeRow::Field((*def, field)));
+                    }
+                }
+
+                for method in self.list(*def, TableIndex::MethodDef, 5) {
+                    let name = self.static_str(method, 3);
+
+                    if !is_excluded_type((type_namespace, name)) {
+                        all.entry(name)
+                            .or_insert(TypeRow::MethodDef((*def, method)));
+                    }
+                }
+            }
+
+            all
+        })
+    }
+
+    /// Read a string that is used as a key in the index
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string is malformed.
+    fn static_str(&self, row: Row, column: u32) -> &'static str {
+        self.try_static_str(row, column)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Read a string that is used as a key in the index
+    ///
+    /// The string borrows from the bytes of `files`, which outlive the index since both
+    /// are owned by the [`TypeReader`]. The `'static` lifetime must not escape the index.
+    fn try_static_str(&self, row: Row, column: u32) -> Result<&'static str, Error> {
+        let value = self.try_str(row, column)?;
+
+        // This is safe because the bytes of each file stay at a fixed address and are
+        // neither modified nor dropped before the index that refers to them.
+        Ok(unsafe { &*(value as *const str) })
+    }
+
+    /// The `TypeDef` rows of the types nested directly within the given `TypeDef` row
+    pub(crate) fn nested_types(&self, enclosing: Row) -> &[Row] {
+        self.nested.get(&enclosing).map_or(&[], |nested| nested)
     }
 
     /// Read a [`u32`] value from a specific [`Row`] and column
@@ -240,19 +391,48 @@ impl TypeReader {
         }
     }
 
+    /// Read a [`u64`] value from a specific [`Row`] and column
+    pub fn u64(&self, row: Row, column: u32) -> u64 {
+        let file = &self.files[row.file_index as usize];
+        let table = &file.tables[row.table_index as usize];
+        let offset = table.data + row.index * table.row_size + table.columns[column as usize].0;
+        match table.columns[column as usize].1 {
+            1 => file.bytes.copy_as::<u8>(offset) as u64,
+            2 => file.bytes.copy_as::<u16>(offset) as u64,
+            4 => file.bytes.copy_as::<u32>(offset) as u64,
+            _ => file.bytes.copy_as::<u64>(offset),
+        }
+    }
+
     /// Read a [`&str`] value from a specific [`Row`] and column
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string is malformed. Use [`TypeReader::try_str`] to handle errors instead.
     pub fn str(&self, row: Row, column: u32) -> &str {
+        self.try_str(row, column)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Read a [`&str`] value from a specific [`Row`] and column
+    pub fn try_str(&self, row: Row, column: u32) -> Result<&str, Error> {
         let file = &self.files[row.file_index as usize];
-        let offset = (file.strings + self.u32(row, column)) as usize;
-        let last = file.bytes[offset..]
-            .iter()
-            .position(|c| *c == b'\0')
-            .unwrap();
-        std::str::from_utf8(&file.bytes[offset..offset + last]).unwrap()
+        let offset = file.strings.0 as usize + self.u32(row, column) as usize;
+
+        let bytes = file.bytes.get(offset..).ok_or_else(|| {
+            self.row_error(row, format!("string offset {} is out of bounds", offset))
+        })?;
+
+        let last = bytes.iter().position(|c| *c == b'\0').ok_or_else(|| {
+            self.row_error(row, format!("unterminated string at offset {}", offset))
+        })?;
+
+        std::str::from_utf8(&bytes[..last])
+            .map_err(|_| self.row_error(row, format!("string at offset {} is not utf-8", offset)))
     }
 
     /// Read a `T: Decode` value from a specific [`Row`] and column
-    pub(crate) fn decode<T: Decode>(&'static self, row: Row, column: u32) -> T {
+    pub(crate) fn decode<'a, T: Decode<'a>>(&'a self, row: Row, column: u32) -> T {
         T::decode(self, self.u32(row, column), row.file_index)
     }
 
@@ -262,39 +442,128 @@ impl TypeReader {
         table: TableIndex,
         column: u32,
     ) -> impl Iterator<Item = Row> {
+        self.try_list(row, table, column)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub(crate) fn try_list(
+        &self,
+        row: Row,
+        table: TableIndex,
+        column: u32,
+    ) -> Result<impl Iterator<Item = Row>, Error> {
         let file = &self.files[row.file_index as usize];
-        let first = self.u32(row, column) - 1;
+        let row_count = file.tables[table as usize].row_count;
+        let first = self.u32(row, column);
 
         let last = if row.index + 1 < file.tables[row.table_index as usize].row_count {
-            self.u32(row.next(), column) - 1
+            self.u32(row.next(), column)
         } else {
-            file.tables[table as usize].row_count
+            row_count + 1
         };
 
-        (first..last).map(move |value| Row::new(value, table, row.file_index))
+        if first == 0 || first > last || last > row_count + 1 {
+            return Err(
+                self.row_error(row, format!("invalid {:?} list {}..{}", table, first, last))
+            );
+        }
+
+        Ok((first - 1..last - 1).map(move |value| Row::new(value, table, row.file_index)))
+    }
+
+    /// Read a blob for a given row and column
+    ///
+    /// # Panics
+    ///
+    /// Panics if the blob is malformed. Use [`TypeReader::try_blob`] to handle errors instead.
+    pub fn blob(&self, row: Row, column: u32) -> Blob<'_> {
+        self.try_blob(row, column)
+            .unwrap_or_else(|error| panic!("{}", error))
     }
 
     /// Read a blob for a given row and column
-    pub fn blob(&'static self, row: Row, column: u32) -> Blob {
+    pub fn try_blob(&self, row: Row, column: u32) -> Result<Blob<'_>, Error> {
         let file = &self.files[row.file_index as usize];
-        let offset = (file.blobs + self.u32(row, column)) as usize;
-        let initial_byte = file.bytes[offset];
+        let offset = file.blobs.0 as usize + self.u32(row, column) as usize;
+
+        let initial_byte = *file.bytes.get(offset).ok_or_else(|| {
+            self.row_error(row, format!("blob offset {} is out of bounds", offset))
+        })?;
+
         let (blob_size, blob_size_bytes) = match initial_byte >> 5 {
             0..=3 => (initial_byte & 0x7f, 1),
             4..=5 => (initial_byte & 0x3f, 2),
             6 => (initial_byte & 0x1f, 4),
-            _ => panic!("Invalid blob size"),
+            _ => return Err(self.row_error(row, format!("invalid blob size at offset {}", offset))),
         };
+
+        let size_bytes = file
+            .bytes
+            .get(offset + 1..offset + blob_size_bytes)
+            .ok_or_else(|| {
+                self.row_error(row, format!("blob offset {} is out of bounds", offset))
+            })?;
+
         let mut blob_size = blob_size as usize;
-        for byte in &file.bytes[offset + 1..offset + blob_size_bytes] {
+        for byte in size_bytes {
             blob_size = blob_size.checked_shl(8).unwrap_or(0) + (*byte as usize);
         }
-        Blob {
+
+        if offset + blob_size_bytes + blob_size > file.bytes.len() {
+            return Err(self.row_error(
+                row,
+                format!(
+                    "blob at offset {} extends beyond the end of the file",
+                    offset
+                ),
+            ));
+        }
+
+        Ok(Blob {
             reader: self,
             file_index: row.file_index,
+            row,
             offset: offset + blob_size_bytes,
             size: blob_size,
-        }
+        })
+    }
+
+    /// Read the raw bytes of a blob for a given row and column
+    ///
+    /// # Panics
+    ///
+    /// Panics if the blob is malformed. Use [`TypeReader::try_blob_bytes`] to handle errors instead.
+    pub fn blob_bytes(&self, row: Row, column: u32) -> &[u8] {
+        self.try_blob_bytes(row, column)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Read the raw bytes of a blob for a given row and column
+    pub fn try_blob_bytes(&self, row: Row, column: u32) -> Result<&[u8], Error> {
+        let blob = self.try_blob(row, column)?;
+        Ok(&self.files[row.file_index as usize].bytes[blob.offset..blob.offset + blob.size])
+    }
+
+    /// Every module known to the [`TypeReader`], one for each file
+    pub fn modules(&self) -> impl Iterator<Item = Module<'_>> + '_ {
+        self.files
+            .iter()
+            .enumerate()
+            .filter(|(_, file)| file.tables[TableIndex::Module as usize].row_count > 0)
+            .map(move |(index, _)| Module {
+                reader: self,
+                row: Row::new(0, TableIndex::Module, index as u16),
+            })
+    }
+
+    /// Creates an [`Error`] describing a malformed value in the given [`Row`]
+    pub(crate) fn row_error<M: Into<String>>(&self, row: Row, message: M) -> Error {
+        Error::invalid_row(
+            &self.files[row.file_index as usize].name,
+            row.table_index,
+            row.index,
+            message,
+        )
     }
 
     pub(crate) fn equal_range(
@@ -316,6 +585,41 @@ impl TypeReader {
         (first..last).map(move |row| Row::new(row, table, file))
     }
 
+    /// The `MethodSemantics` row whose Method column refers to the given method, if any
+    ///
+    /// ECMA-335 sorts the table by its Association column, so the rows are ordered by
+    /// method once per file and then searched.
+    pub(crate) fn method_semantics(&self, method: Row) -> Option<Row> {
+        let file = method.file_index;
+        let row = |index| Row::new(index, TableIndex::MethodSemantics, file);
+
+        let rows = self.semantics[file as usize].get_or_init(|| {
+            let row_count =
+                self.files[file as usize].tables[TableIndex::MethodSemantics as usize].row_count;
+
+            let mut rows: Vec<u32> = (0..row_count).collect();
+            rows.sort_by_key(|index| self.u32(row(*index), 1));
+            rows
+        });
+
+        rows.binary_search_by_key(&(method.index + 1), |index| self.u32(row(*index), 1))
+            .ok()
+            .map(|position| row(rows[position]))
+    }
+
+    /// Returns the index of the first row whose value in the given sorted column is
+    /// greater than `value`
+    pub(crate) fn upper_bound(&self, file: u16, table: TableIndex, column: u32, value: u32) -> u32 {
+        self.upper_bound_of(
+            table,
+            file,
+            0,
+            self.files[file as usize].tables[table as usize].row_count,
+            column,
+            value,
+        )
+    }
+
     fn lower_bound_of(
         &self,
         table: TableIndex,
@@ -401,27 +705,18 @@ impl TypeReader {
     }
 }
 
-fn winmd_paths() -> Vec<std::path::PathBuf> {
-    let mut windows_path = workspace_windows_dir();
-    windows_path.push("winmd");
-
-    let mut paths = vec![];
-    push_winmd_paths(windows_path, &mut paths);
-    paths
-}
-
-fn push_winmd_paths(dir: std::path::PathBuf, paths: &mut Vec<std::path::PathBuf>) {
-    if let Ok(files) = std::fs::read_dir(dir) {
-        for file in files.filter_map(|file| file.ok()) {
-            if let Ok(file_type) = file.file_type() {
-                if file_type.is_file() {
-                    let path = file.path();
-                    if let Some("winmd") = path.extension().and_then(|extension| extension.to_str())
-                    {
-                        paths.push(file.path());
-                    }
-                }
-            }
-        }
-    }
+/// Types that are hidden from the index because they are mapped to built-in types
+pub(crate) fn is_excluded_type(name: (&str, &str)) -> bool {
+    matches!(
+        name,
+        ("Windows.Foundation", "HResult")
+            | ("Windows.Win32.Com", "IUnknown")
+            | ("Windows.Win32.SystemServices", "BOOL")
+            | ("Windows.Win32.Direct2D", "D2D_MATRIX_3X2_F")
+            | ("Windows.Win32.SystemServices", "LARGE_INTEGER")
+            | ("Windows.Win32.SystemServices", "ULARGE_INTEGER")
+            // TODO: map BSTR
+            // TODO: remove once this is fixed: https://github.com/microsoft/win32metadata/issues/30
+            | ("Windows.Win32", "CFunctionDiscoveryNotificationWrapper")
+    )
 }
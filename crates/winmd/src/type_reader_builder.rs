@@ -0,0 +1,118 @@
+use crate::*;
+use std::path::PathBuf;
+
+/// Configures the Windows Metadata files read by a [`TypeReader`]
+///
+/// Files are read in the order they were added: paths first, then in-memory buffers,
+/// then MIDL sources, then the default metadata unless it is excluded. When several
+/// files define the same type, the earliest file takes precedence and the rest are
+/// reported by [`TypeReader::duplicate_types`].
+#[derive(Default)]
+pub struct TypeReaderBuilder {
+    paths: Vec<PathBuf>,
+    buffers: Vec<(String, Vec<u8>)>,
+    sources: Vec<(String, String)>,
+    exclude_default: bool,
+    memory_map: bool,
+    validate: bool,
+}
+
+impl TypeReaderBuilder {
+    /// Adds the Windows Metadata file at the given path
+    pub fn path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.paths.push(path.into());
+        self
+    }
+
+    /// Adds the Windows Metadata files at the given paths
+    pub fn paths<I: IntoIterator<Item = PathBuf>>(mut self, paths: I) -> Self {
+        self.paths.extend(paths);
+        self
+    }
+
+    /// Adds a Windows Metadata file that is already in memory, using `name` to identify
+    /// the file in errors
+    pub fn bytes<S: Into<String>>(mut self, name: S, bytes: Vec<u8>) -> Self {
+        self.buffers.push((name.into(), bytes));
+        self
+    }
+
+    /// Adds the metadata compiled from MIDL 3.0 source, using `name` to identify the source
+    /// in errors
+    ///
+    /// See [`midl::compile`] for the subset of MIDL that is understood.
+    pub fn midl<N: Into<String>, S: Into<String>>(mut self, name: N, source: S) -> Self {
+        self.sources.push((name.into(), source.into()));
+        self
+    }
+
+    /// Whether to leave out the default metadata that ships with this crate
+    pub fn exclude_default(mut self, exclude: bool) -> Self {
+        self.exclude_default = exclude;
+        self
+    }
+
+    /// Whether to memory-map files read from paths rather than reading them into memory
+    ///
+    /// The files must not be modified while the [`TypeReader`] is alive.
+    pub fn memory_map(mut self, memory_map: bool) -> Self {
+        self.memory_map = memory_map;
+        self
+    }
+
+    /// Whether to check that the files are well-formed before returning the [`TypeReader`]
+    ///
+    /// See [`validate`] for the checks, the first violation of which is returned by
+    /// [`TypeReaderBuilder::build`].
+    pub fn validate(mut self, validate: bool) -> Self {
+        self.validate = validate;
+        self
+    }
+
+    /// Reads and indexes the configured files
+    ///
+    /// Returns an [`Error`] if any of the files cannot be read or are malformed.
+    pub fn build(self) -> Result<TypeReader, Error> {
+        let load = if self.memory_map {
+            File::try_map::<PathBuf>
+        } else {
+            File::try_new::<PathBuf>
+        };
+
+        let mut files = self
+            .paths
+            .into_iter()
+            .map(load)
+            .collect::<Result<Vec<File>, Error>>()?;
+
+        for (name, bytes) in self.buffers {
+            files.push(File::try_from_bytes(name, bytes)?);
+        }
+
+        for (name, source) in self.sources {
+            let bytes = midl::compile(&name, &source)?;
+            files.push(File::try_from_bytes(name, bytes)?);
+        }
+
+        if !self.exclude_default {
+            files.push(File::try_from_bytes(
+                "Windows.Win32.winmd",
+                include_bytes!("../default/Windows.Win32.winmd").to_vec(),
+            )?);
+            files.push(File::try_from_bytes(
+                "Windows.WinRT.winmd",
+                include_bytes!("../default/Windows.WinRT.winmd").to_vec(),
+            )?);
+        }
+
+        let reader = TypeReader::try_from_files(files)?;
+
+        if self.validate {
+            if let Some(error) = validate(&reader).into_iter().next() {
+                return Err(error);
+            }
+        }
+
+        Ok(reader)
+    }
+}
@@ -0,0 +1,712 @@
+use crate::*;
+
+/// What a table column refers to
+#[derive(Copy, Clone)]
+enum Column {
+    /// An offset into the #Strings heap
+    String,
+    /// An offset into the #Blob heap
+    Blob,
+    /// A one-based index into the #GUID heap, where zero is null
+    Guid,
+    /// A one-based row index into a single table
+    Index(TableIndex),
+    /// A coded index into one of several tables, which may be null if `optional`
+    ///
+    /// Tags that refer to tables this crate doesn't read are `None`.
+    Coded {
+        bits: u32,
+        tables: &'static [Option<TableIndex>],
+        optional: bool,
+    },
+    /// The first row of a run of rows owned by this row, which must not decrease
+    List(TableIndex),
+}
+
+const TYPE_DEF_OR_REF: &[Option<TableIndex>] = &[
+    Some(TableIndex::TypeDef),
+    Some(TableIndex::TypeRef),
+    Some(TableIndex::TypeSpec),
+];
+
+const HAS_CONSTANT: &[Option<TableIndex>] = &[
+    Some(TableIndex::Field),
+    Some(TableIndex::Param),
+    Some(TableIndex::Property),
+];
+
+const HAS_CUSTOM_ATTRIBUTE: &[Option<TableIndex>] = &[
+    Some(TableIndex::MethodDef),
+    Some(TableIndex::Field),
+    Some(TableIndex::TypeRef),
+    Some(TableIndex::TypeDef),
+    Some(TableIndex::Param),
+    Some(TableIndex::InterfaceImpl),
+    Some(TableIndex::MemberRef),
+    Some(TableIndex::Module),
+    None,
+    Some(TableIndex::Property),
+    Some(TableIndex::Event),
+    None,
+    Some(TableIndex::ModuleRef),
+    Some(TableIndex::TypeSpec),
+    Some(TableIndex::Assembly),
+    Some(TableIndex::AssemblyRef),
+    None,
+    None,
+    None,
+    Some(TableIndex::GenericParam),
+    Some(TableIndex::GenericParamConstraint),
+    None,
+];
+
+const MEMBER_REF_PARENT: &[Option<TableIndex>] = &[
+    Some(TableIndex::TypeDef),
+    Some(TableIndex::TypeRef),
+    Some(TableIndex::ModuleRef),
+    Some(TableIndex::MethodDef),
+    Some(TableIndex::TypeSpec),
+];
+
+const HAS_SEMANTICS: &[Option<TableIndex>] = &[Some(TableIndex::Event), Some(TableIndex::Property)];
+
+const MEMBER_FORWARDED: &[Option<TableIndex>] =
+    &[Some(TableIndex::Field), Some(TableIndex::MethodDef)];
+
+const CUSTOM_ATTRIBUTE_TYPE: &[Option<TableIndex>] = &[
+    None,
+    None,
+    Some(TableIndex::MethodDef),
+    Some(TableIndex::MemberRef),
+    None,
+];
+
+const RESOLUTION_SCOPE: &[Option<TableIndex>] = &[
+    Some(TableIndex::Module),
+    Some(TableIndex::ModuleRef),
+    Some(TableIndex::AssemblyRef),
+    Some(TableIndex::TypeRef),
+];
+
+const TYPE_OR_METHOD_DEF: &[Option<TableIndex>] =
+    &[Some(TableIndex::TypeDef), Some(TableIndex::MethodDef)];
+
+const fn coded(bits: u32, tables: &'static [Option<TableIndex>]) -> Column {
+    Column::Coded {
+        bits,
+        tables,
+        optional: false,
+    }
+}
+
+const fn optional(bits: u32, tables: &'static [Option<TableIndex>]) -> Column {
+    Column::Coded {
+        bits,
+        tables,
+        optional: true,
+    }
+}
+
+/// The columns of a table that refer to heaps or other tables, along with the column the
+/// table is sorted by, if any
+struct Schema {
+    table: TableIndex,
+    columns: &'static [(u32, Column)],
+    sorted_by: Option<u32>,
+}
+
+const SCHEMAS: &[Schema] = &[
+    Schema {
+        table: TableIndex::Module,
+        columns: &[
+            (1, Column::String),
+            (2, Column::Guid),
+            (3, Column::Guid),
+            (4, Column::Guid),
+        ],
+        sorted_by: None,
+    },
+    Schema {
+        table: TableIndex::TypeRef,
+        columns: &[
+            (0, optional(2, RESOLUTION_SCOPE)),
+            (1, Column::String),
+            (2, Column::String),
+        ],
+        sorted_by: None,
+    },
+    Schema {
+        table: TableIndex::TypeDef,
+        columns: &[
+            (1, Column::String),
+            (2, Column::String),
+            (3, optional(2, TYPE_DEF_OR_REF)),
+            (4, Column::List(TableIndex::Field)),
+            (5, Column::List(TableIndex::MethodDef)),
+        ],
+        sorted_by: None,
+    },
+    Schema {
+        table: TableIndex::Field,
+        columns: &[(1, Column::String), (2, Column::Blob)],
+        sorted_by: None,
+    },
+    Schema {
+        table: TableIndex::MethodDef,
+        columns: &[
+            (3, Column::String),
+            (4, Column::Blob),
+            (5, Column::List(TableIndex::Param)),
+        ],
+        sorted_by: None,
+    },
+    Schema {
+        table: TableIndex::Param,
+        columns: &[(2, Column::String)],
+        sorted_by: None,
+    },
+    Schema {
+        table: TableIndex::InterfaceImpl,
+        columns: &[
+            (0, Column::Index(TableIndex::TypeDef)),
+            (1, coded(2, TYPE_DEF_OR_REF)),
+        ],
+        sorted_by: Some(0),
+    },
+    Schema {
+        table: TableIndex::MemberRef,
+        columns: &[
+            (0, coded(3, MEMBER_REF_PARENT)),
+            (1, Column::String),
+            (2, Column::Blob),
+        ],
+        sorted_by: None,
+    },
+    Schema {
+        table: TableIndex::Constant,
+        columns: &[(1, coded(2, HAS_CONSTANT)), (2, Column::Blob)],
+        sorted_by: Some(1),
+    },
+    Schema {
+        table: TableIndex::CustomAttribute,
+        columns: &[
+            (0, coded(5, HAS_CUSTOM_ATTRIBUTE)),
+            (1, coded(3, CUSTOM_ATTRIBUTE_TYPE)),
+            (2, Column::Blob),
+        ],
+        sorted_by: Some(0),
+    },
+    Schema {
+        table: TableIndex::ClassLayout,
+        columns: &[(2, Column::Index(TableIndex::TypeDef))],
+        sorted_by: Some(2),
+    },
+    Schema {
+        table: TableIndex::FieldLayout,
+        columns: &[(1, Column::Index(TableIndex::Field))],
+        sorted_by: Some(1),
+    },
+    Schema {
+        table: TableIndex::EventMap,
+        columns: &[
+            (0, Column::Index(TableIndex::TypeDef)),
+            (1, Column::List(TableIndex::Event)),
+        ],
+        sorted_by: None,
+    },
+    Schema {
+        table: TableIndex::Event,
+        columns: &[(1, Column::String), (2, coded(2, TYPE_DEF_OR_REF))],
+        sorted_by: None,
+    },
+    Schema {
+        table: TableIndex::PropertyMap,
+        columns: &[
+            (0, Column::Index(TableIndex::TypeDef)),
+            (1, Column::List(TableIndex::Property)),
+        ],
+        sorted_by: None,
+    },
+    Schema {
+        table: TableIndex::Property,
+        columns: &[(1, Column::String), (2, Column::Blob)],
+        sorted_by: None,
+    },
+    Schema {
+        table: TableIndex::MethodSemantics,
+        columns: &[
+            (1, Column::Index(TableIndex::MethodDef)),
+            (2, coded(1, HAS_SEMANTICS)),
+        ],
+        sorted_by: Some(2),
+    },
+    Schema {
+        table: TableIndex::ModuleRef,
+        columns: &[(0, Column::String)],
+        sorted_by: None,
+    },
+    Schema {
+        table: TableIndex::TypeSpec,
+        columns: &[(0, Column::Blob)],
+        sorted_by: None,
+    },
+    Schema {
+        table: TableIndex::ImplMap,
+        columns: &[
+            (1, coded(1, MEMBER_FORWARDED)),
+            (2, Column::String),
+            (3, Column::Index(TableIndex::ModuleRef)),
+        ],
+        sorted_by: Some(1),
+    },
+    Schema {
+        table: TableIndex::Assembly,
+        columns: &[(3, Column::Blob), (4, Column::String), (5, Column::String)],
+        sorted_by: None,
+    },
+    Schema {
+        table: TableIndex::AssemblyRef,
+        columns: &[
+            (2, Column::Blob),
+            (3, Column::String),
+            (4, Column::String),
+            (5, Column::Blob),
+        ],
+        sorted_by: None,
+    },
+    Schema {
+        table: TableIndex::NestedClass,
+        columns: &[
+            (0, Column::Index(TableIndex::TypeDef)),
+            (1, Column::Index(TableIndex::TypeDef)),
+        ],
+        sorted_by: Some(0),
+    },
+    Schema {
+        table: TableIndex::GenericParam,
+        columns: &[(2, coded(1, TYPE_OR_METHOD_DEF)), (3, Column::String)],
+        sorted_by: Some(2),
+    },
+    Schema {
+        table: TableIndex::GenericParamConstraint,
+        columns: &[
+            (0, Column::Index(TableIndex::GenericParam)),
+            (1, coded(2, TYPE_DEF_OR_REF)),
+        ],
+        sorted_by: Some(0),
+    },
+];
+
+/// Checks that the files read by a [`TypeReader`] are well-formed, returning every violation
+///
+/// The [`TypeReader`] relies on invariants from ECMA-335 that are too costly to check as
+/// it reads, so a malformed file can produce wrong results or panics far from the cause.
+/// This checks that:
+///
+/// * string, blob and GUID offsets fall within their heaps, and blobs fit within the #Blob heap
+/// * strings are terminated and are valid UTF-8
+/// * simple and coded indices refer to existing rows of the tables they may refer to
+/// * list columns, such as a type's fields and methods, never decrease and stay in range
+/// * tables that are searched by a column are sorted by that column
+/// * type references resolve to a type definition known to the [`TypeReader`]
+///
+/// Violations are reported as [`Error::InvalidRow`] with the file, table and row. Type
+/// references are only resolved in files without other violations, and references to
+/// types in the `System` namespaces are assumed to resolve since the runtime provides them.
+pub fn validate(reader: &TypeReader) -> Vec<Error> {
+    let mut errors = Vec::new();
+
+    for (file_index, file) in reader.files().iter().enumerate() {
+        let validator = Validator {
+            reader,
+            file,
+            file_index: file_index as u16,
+        };
+
+        let count = errors.len();
+
+        for schema in SCHEMAS {
+            validator.table(schema, &mut errors);
+        }
+
+        if errors.len() == count {
+            validator.type_refs(&mut errors);
+        }
+    }
+
+    errors
+}
+
+struct Validator<'a> {
+    reader: &'a TypeReader,
+    file: &'a File,
+    file_index: u16,
+}
+
+impl<'a> Validator<'a> {
+    fn row(&self, table: TableIndex, index: u32) -> Row {
+        Row::new(index, table, self.file_index)
+    }
+
+    fn error<M: Into<String>>(&self, row: Row, message: M) -> Error {
+        Error::invalid_row(&self.file.name, row.table_index, row.index, message)
+    }
+
+    fn table(&self, schema: &Schema, errors: &mut Vec<Error>) {
+        let row_count = self.file.tables[schema.table as usize].row_count;
+
+        for index in 0..row_count {
+            let row = self.row(schema.table, index);
+
+            for (column, kind) in schema.columns {
+                if let Err(error) = self.column(row, *column, *kind) {
+                    errors.push(error);
+                }
+            }
+
+            if let Some(column) = schema.sorted_by {
+                if index > 0
+                    && self.reader.u32(row, column)
+                        < self.reader.u32(self.row(schema.table, index - 1), column)
+                {
+                    errors.push(self.error(
+                        row,
+                        format!(
+                            "is out of order, the table must be sorted by column {}",
+                            column
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+
+    fn column(&self, row: Row, column: u32, kind: Column) -> Result<(), Error> {
+        let value = self.reader.u32(row, column);
+
+        match kind {
+            Column::String => self.string(row, column, value),
+            Column::Blob => self.blob(row, column, value),
+            Column::Guid => {
+                if value as u64 * 16 > self.file.guids.1 as u64 {
+                    Err(self.error(
+                        row,
+                        format!(
+                            "column {} refers to GUID {} beyond the end of the #GUID heap",
+                            column, value
+                        ),
+                    ))
+                } else {
+                    Ok(())
+                }
+            }
+            Column::Index(table) => self.index(row, column, table, value),
+            Column::Coded {
+                bits,
+                tables,
+                optional,
+            } => {
+                if optional && value >> bits == 0 {
+                    return Ok(());
+                }
+
+                let tag = value & ((1 << bits) - 1);
+
+                match tables.get(tag as usize) {
+                    Some(Some(table)) => self.index(row, column, *table, value >> bits),
+                    Some(None) => Err(self.error(
+                        row,
+                        format!("column {} refers to an unsupported table", column),
+                    )),
+                    None => Err(self.error(
+                        row,
+                        format!("column {} has an invalid coded index tag {}", column, tag),
+                    )),
+                }
+            }
+            Column::List(table) => {
+                let last = self.file.tables[table as usize].row_count + 1;
+
+                if value == 0 || value > last {
+                    return Err(self.error(
+                        row,
+                        format!(
+                            "column {} starts a {:?} list at {} but the table has {} rows",
+                            column,
+                            table,
+                            value,
+                            last - 1
+                        ),
+                    ));
+                }
+
+                if row.index > 0 {
+                    let previous = self
+                        .reader
+                        .u32(self.row(row.table_index, row.index - 1), column);
+
+                    if value < previous {
+                        return Err(self.error(
+                            row,
+                            format!(
+                                "column {} starts a {:?} list at {} before the previous row's list at {}",
+                                column, table, value, previous
+                            ),
+                        ));
+                    }
+                }
+
+                Ok(())
+            }
+        }
+    }
+
+    fn index(&self, row: Row, column: u32, table: TableIndex, value: u32) -> Result<(), Error> {
+        let row_count = self.file.tables[table as usize].row_count;
+
+        if value == 0 || value > row_count {
+            Err(self.error(
+                row,
+                format!(
+                    "column {} refers to {:?} row {} but the table has {} rows",
+                    column, table, value, row_count
+                ),
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn string(&self, row: Row, column: u32, offset: u32) -> Result<(), Error> {
+        let (start, size) = self.file.strings;
+
+        if offset >= size {
+            return Err(self.error(
+                row,
+                format!(
+                    "column {} refers to string offset {} beyond the end of the #Strings heap",
+                    column, offset
+                ),
+            ));
+        }
+
+        let heap = &self.file.bytes[(start + offset) as usize..(start + size) as usize];
+
+        match heap.iter().position(|c| *c == b'\0') {
+            Some(last) if std::str::from_utf8(&heap[..last]).is_ok() => Ok(()),
+            Some(_) => Err(self.error(
+                row,
+                format!(
+                    "column {} refers to a string at offset {} that is not UTF-8",
+                    column, offset
+                ),
+            )),
+            None => Err(self.error(
+                row,
+                format!(
+                    "column {} refers to an unterminated string at offset {}",
+                    column, offset
+                ),
+            )),
+        }
+    }
+
+    fn blob(&self, row: Row, column: u32, offset: u32) -> Result<(), Error> {
+        let (start, size) = self.file.blobs;
+        let out_of_bounds = || {
+            self.error(
+                row,
+                format!(
+                    "column {} refers to a blob at offset {} that extends beyond the end of the #Blob heap",
+                    column, offset
+                ),
+            )
+        };
+
+        if offset >= size {
+            return Err(out_of_bounds());
+        }
+
+        let heap = &self.file.bytes[(start + offset) as usize..(start + size) as usize];
+
+        let (blob_size, header_size) = match heap[0] >> 5 {
+            0..=3 => (heap[0] as usize & 0x7f, 1),
+            4..=5 => (heap[0] as usize & 0x3f, 2),
+            6 => (heap[0] as usize & 0x1f, 4),
+            _ => {
+                return Err(self.error(
+                    row,
+                    format!(
+                        "column {} refers to a blob at offset {} with an invalid size",
+                        column, offset
+                    ),
+                ))
+            }
+        };
+
+        let blob_size = heap
+            .get(1..header_size)
+            .ok_or_else(out_of_bounds)?
+            .iter()
+            .fold(blob_size, |size, byte| (size << 8) + *byte as usize);
+
+        if header_size + blob_size > heap.len() {
+            Err(out_of_bounds())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn type_refs(&self, errors: &mut Vec<Error>) {
+        let row_count = self.file.tables[TableIndex::TypeRef as usize].row_count;
+
+        for index in 0..row_count {
+            let type_ref = TypeRef {
+                reader: self.reader,
+                row: self.row(TableIndex::TypeRef, index),
+            };
+
+            if self.resolve(type_ref, row_count).is_err() {
+                let message = match type_ref.name() {
+                    (_, name) if type_ref.enclosing_type().is_some() => {
+                        format!("refers to nested type `{}` which is not defined", name)
+                    }
+                    (namespace, name) => {
+                        format!("refers to `{}.{}` which is not defined", namespace, name)
+                    }
+                };
+
+                errors.push(self.error(type_ref.row, message));
+            }
+        }
+    }
+
+    /// Resolves the reference, where references to runtime and built-in types resolve to
+    /// `None`, following at most `depth` enclosing references so that a cycle of nested
+    /// references cannot recurse forever
+    fn resolve(&self, type_ref: TypeRef<'a>, depth: u32) -> Result<Option<TypeDef<'a>>, ()> {
+        match type_ref.enclosing_type() {
+            Some(_) if depth == 0 => Err(()),
+            Some(enclosing) => match self.resolve(enclosing, depth - 1)? {
+                Some(def) => {
+                    let name = type_ref.name().1;
+
+                    def.nested_types()
+                        .find(|nested| nested.name().1 == name)
+                        .map(Some)
+                        .ok_or(())
+                }
+                None => Ok(None),
+            },
+            None => {
+                let name = type_ref.name();
+
+                if name.0 == "System" || name.0.starts_with("System.") || is_excluded_type(name) {
+                    Ok(None)
+                } else {
+                    self.reader.find_type_def(name).map(Some).ok_or(())
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer::{self, Writer};
+
+    /// A file with two custom attributes, which are sorted by their parent
+    fn bytes() -> Vec<u8> {
+        let mut writer = Writer::new("Test.winmd");
+        let object = writer.type_ref(None, "System", "Object");
+        let first = writer.type_def(0x4101, "Test", "First", Some(object));
+        writer.field(0x16, "Value", &writer::TypeSig::I32);
+        let second = writer.type_def(0x4101, "Test", "Second", Some(object));
+
+        let constructor = writer.member_ref(
+            writer::MemberRefParent::TypeDef(first),
+            ".ctor",
+            &writer::MethodSig::new(writer::TypeSig::Void, Vec::new()),
+        );
+
+        for parent in &[first, second] {
+            writer.attribute(
+                writer::HasAttribute::TypeDef(*parent),
+                writer::AttributeType::MemberRef(constructor),
+                &[],
+                &[],
+            );
+        }
+
+        writer.into_bytes()
+    }
+
+    /// Rewrites the file's bytes and validates the result
+    fn validate_with<F: FnOnce(&File, &mut Vec<u8>)>(change: F) -> Vec<Error> {
+        let mut bytes = bytes();
+        let file = File::try_from_bytes("Test.winmd", bytes.clone()).unwrap();
+        change(&file, &mut bytes);
+
+        let file = File::try_from_bytes("Test.winmd", bytes).unwrap();
+        validate(&TypeReader::try_from_files(vec![file]).unwrap())
+    }
+
+    /// The offset of a column within the file's bytes
+    fn column(file: &File, table: TableIndex, row: u32, column: usize) -> usize {
+        let table = &file.tables[table as usize];
+        (table.data + row * table.row_size + table.columns[column].0) as usize
+    }
+
+    #[test]
+    fn valid() {
+        assert!(validate_with(|_, _| {}).is_empty());
+    }
+
+    #[test]
+    fn unsorted() {
+        let errors = validate_with(|file, bytes| {
+            let row_size = file.tables[TableIndex::CustomAttribute as usize].row_size as usize;
+            let first = column(file, TableIndex::CustomAttribute, 0, 0);
+            let (rows, second) = bytes[first..].split_at_mut(row_size);
+            rows.swap_with_slice(&mut second[..row_size]);
+        });
+
+        assert_eq!(
+            errors,
+            [Error::invalid_row(
+                "Test.winmd",
+                TableIndex::CustomAttribute,
+                1,
+                "is out of order, the table must be sorted by column 0"
+            )]
+        );
+    }
+
+    #[test]
+    fn heap_offsets() {
+        let (mut strings, mut blob) = (0, 0);
+
+        let errors = validate_with(|file, bytes| {
+            strings = file.strings.1;
+            let name = column(file, TableIndex::Field, 0, 1);
+            bytes[name..name + 2].copy_from_slice(&(strings as u16).to_le_bytes());
+
+            // Point the signature at the heap's last byte, claiming a five byte blob.
+            blob = file.blobs.1 - 1;
+            bytes[(file.blobs.0 + blob) as usize] = 5;
+            let signature = column(file, TableIndex::Field, 0, 2);
+            bytes[signature..signature + 2].copy_from_slice(&(blob as u16).to_le_bytes());
+        });
+
+        let messages: Vec<String> = errors.iter().map(ToString::to_string).collect();
+
+        assert_eq!(
+            messages,
+            [
+                format!("Invalid file `Test.winmd`: Field row 0: column 1 refers to string offset {} beyond the end of the #Strings heap", strings),
+                format!("Invalid file `Test.winmd`: Field row 0: column 2 refers to a blob at offset {} that extends beyond the end of the #Blob heap", blob),
+            ]
+        );
+    }
+}
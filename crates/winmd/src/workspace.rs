@@ -1,35 +1,121 @@
+use std::path::{Path, PathBuf};
+
+/// The environment variable listing extra places to look for Windows Metadata files
+///
+/// Entries are separated the same way as `PATH`, and relative entries are resolved
+/// against the root of the workspace.
+pub const WINMD_PATH_VAR: &str = "WINDOWS_WINMD_PATH";
+
 /// Returns the build's `.windows` directory in the root of the workspace as a `PathBuf`.
-pub fn workspace_windows_dir() -> std::path::PathBuf {
+pub fn workspace_windows_dir() -> PathBuf {
     let mut path = workspace_dir();
     path.push(".windows");
     path
 }
 
-fn workspace_dir() -> std::path::PathBuf {
+/// The places to look for Windows Metadata files, in order of precedence
+///
+/// These are the given `search_paths`, then the entries of the [`WINMD_PATH_VAR`]
+/// environment variable and finally the workspace's `.windows/winmd` directory. Each
+/// may name either a file or a directory.
+pub fn winmd_search_paths(search_paths: &[PathBuf]) -> Vec<PathBuf> {
+    let mut paths = search_paths.to_vec();
+
+    if let Some(value) = std::env::var_os(WINMD_PATH_VAR) {
+        for path in std::env::split_paths(&value) {
+            if path.as_os_str().is_empty() {
+                continue;
+            }
+
+            if path.is_relative() {
+                paths.push(workspace_dir().join(path));
+            } else {
+                paths.push(path);
+            }
+        }
+    }
+
+    let mut workspace = workspace_windows_dir();
+    workspace.push("winmd");
+    paths.push(workspace);
+    paths
+}
+
+/// Finds the Windows Metadata (`.winmd`) files at the given paths
+///
+/// A path naming a file is used as is, whatever its extension, while a directory is
+/// searched recursively in order of path. Directories reached through symbolic links
+/// are not searched, and paths that do not exist are skipped. A file that is found
+/// more than once is only returned the first time.
+pub fn find_winmd_files(search_paths: &[PathBuf]) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+
+    for path in search_paths {
+        if path.is_file() {
+            push_file(path.clone(), &mut files);
+        } else {
+            push_dir(path, &mut files);
+        }
+    }
+
+    files
+}
+
+fn push_dir(dir: &Path, files: &mut Vec<PathBuf>) {
+    let mut entries: Vec<std::fs::DirEntry> = match std::fs::read_dir(dir) {
+        Ok(entries) => entries.filter_map(|entry| entry.ok()).collect(),
+        Err(_) => return,
+    };
+
+    // Directory order is not deterministic so sort to give files a stable precedence.
+    entries.sort_by_key(|entry| entry.path());
+
+    for entry in entries {
+        let path = entry.path();
+
+        let file_type = match entry.file_type() {
+            Ok(file_type) => file_type,
+            Err(_) => continue,
+        };
+
+        if file_type.is_dir() {
+            push_dir(&path, files);
+        } else if path.extension().and_then(|extension| extension.to_str()) == Some("winmd")
+            && path.is_file()
+        {
+            push_file(path, files);
+        }
+    }
+}
+
+fn push_file(path: PathBuf, files: &mut Vec<PathBuf>) {
+    let key = path.canonicalize().unwrap_or_else(|_| path.clone());
+
+    if !files
+        .iter()
+        .any(|file| file.canonicalize().unwrap_or_else(|_| file.clone()) == key)
+    {
+        files.push(path);
+    }
+}
+
+fn workspace_dir() -> PathBuf {
     use std::{mem::MaybeUninit, sync::Once};
     static ONCE: Once = Once::new();
-    static mut VALUE: MaybeUninit<std::path::PathBuf> = MaybeUninit::uninit();
+    static mut VALUE: MaybeUninit<PathBuf> = MaybeUninit::uninit();
 
     ONCE.call_once(|| {
         let output = std::process::Command::new(env!("CARGO"))
             .arg("metadata")
             .arg("--format-version=1")
+            .arg("--no-deps")
             .output()
             .expect("Failed to run `cargo metadata`");
 
-        const JSON_KEY: &str = r#""workspace_root":"#;
         let json = String::from_utf8(output.stdout).expect("Cargo metadata is not utf-8");
-        let beginning_index = json
-            .find(JSON_KEY)
-            .expect("Cargo metadata did not contain `workspace_root` key.")
-            + JSON_KEY.len()
-            + 1;
-
-        let ending_index = json[beginning_index..]
-            .find("\"")
-            .expect("Cargo metadata ended before closing '\"' in `workspace_root` value");
 
-        let workspace_root = &json[beginning_index..beginning_index + ending_index];
+        let workspace_root = json_string(&json, "workspace_root")
+            .expect("Cargo metadata did not contain a `workspace_root` string");
 
         // This is safe because `Once` provides thread-safe one-time initialization
         unsafe { VALUE = MaybeUninit::new(workspace_root.into()) }
@@ -38,3 +124,65 @@ fn workspace_dir() -> std::path::PathBuf {
     // This is safe because `call_once` has already been called.
     unsafe { (*VALUE.as_ptr()).clone() }
 }
+
+/// Finds the first string value with the given key in a JSON document, decoding its escapes
+fn json_string(json: &str, key: &str) -> Option<String> {
+    let start = json.find(&format!("\"{}\":", key))? + key.len() + 3;
+    let mut chars = json[start..].trim_start().strip_prefix('"')?.chars();
+    let mut value = String::new();
+
+    loop {
+        match chars.next()? {
+            '"' => return Some(value),
+            '\\' => match chars.next()? {
+                'n' => value.push('\n'),
+                't' => value.push('\t'),
+                'r' => value.push('\r'),
+                'b' => value.push('\u{8}'),
+                'f' => value.push('\u{c}'),
+                'u' => {
+                    let code: String = chars.by_ref().take(4).collect();
+                    let mut code = u32::from_str_radix(&code, 16).ok()?;
+
+                    // Characters outside the basic multilingual plane are escaped as a
+                    // surrogate pair.
+                    if (0xD800..0xDC00).contains(&code) {
+                        let low: String = chars.by_ref().skip(2).take(4).collect();
+                        let low = u32::from_str_radix(&low, 16).ok()?;
+                        code = 0x10000 + ((code - 0xD800) << 10) + (low.checked_sub(0xDC00)?);
+                    }
+
+                    value.push(std::char::from_u32(code)?);
+                }
+                c => value.push(c),
+            },
+            c => value.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn workspace_root() {
+        assert_eq!(
+            json_string(
+                r#"{"packages":[],"workspace_root":"C:\\src\\my \"repo\"\u00e9\ud83d\ude00","x":1}"#,
+                "workspace_root"
+            )
+            .as_deref(),
+            Some("C:\\src\\my \"repo\"\u{e9}\u{1f600}")
+        );
+
+        assert_eq!(
+            json_string(r#"{"workspace_root": "/src"}"#, "workspace_root").as_deref(),
+            Some("/src")
+        );
+        assert_eq!(
+            json_string(r#"{"target_directory":"/src"}"#, "workspace_root"),
+            None
+        );
+    }
+}
@@ -0,0 +1,244 @@
+use super::*;
+
+/// The encoded type of an attribute argument
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ArgType {
+    Bool,
+    Char,
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    I64,
+    U64,
+    F32,
+    F64,
+    String,
+    Type,
+    /// A value passed as `System.Object`
+    Boxed,
+    /// An enum, by its namespace-qualified name
+    Enum(String),
+    Array(Box<ArgType>),
+}
+
+/// The value of an attribute argument
+#[derive(Clone, Debug, PartialEq)]
+pub enum AttributeArg {
+    Bool(bool),
+    Char(char),
+    I8(i8),
+    U8(u8),
+    I16(i16),
+    U16(u16),
+    I32(i32),
+    U32(u32),
+    I64(i64),
+    U64(u64),
+    F32(f32),
+    F64(f64),
+    String(Option<String>),
+    /// A type, by its namespace-qualified name
+    Type(Option<String>),
+    /// An enum value along with the enum's namespace-qualified name
+    Enum(String, Box<AttributeArg>),
+    /// An array along with the type of its elements
+    Array(ArgType, Option<Vec<AttributeArg>>),
+    /// A value passed as `System.Object`, which carries its own type
+    Boxed(Box<AttributeArg>),
+}
+
+/// A named argument, which sets a field or property of the attribute
+#[derive(Clone, Debug, PartialEq)]
+pub struct NamedArg {
+    /// Whether the argument sets a property rather than a field
+    pub property: bool,
+    pub name: String,
+    pub value: AttributeArg,
+}
+
+impl ArgType {
+    fn encode(&self, buffer: &mut Vec<u8>) {
+        match self {
+            Self::Bool => buffer.push(0x02),
+            Self::Char => buffer.push(0x03),
+            Self::I8 => buffer.push(0x04),
+            Self::U8 => buffer.push(0x05),
+            Self::I16 => buffer.push(0x06),
+            Self::U16 => buffer.push(0x07),
+            Self::I32 => buffer.push(0x08),
+            Self::U32 => buffer.push(0x09),
+            Self::I64 => buffer.push(0x0a),
+            Self::U64 => buffer.push(0x0b),
+            Self::F32 => buffer.push(0x0c),
+            Self::F64 => buffer.push(0x0d),
+            Self::String => buffer.push(0x0e),
+            Self::Array(element) => {
+                buffer.push(0x1d);
+                element.encode(buffer);
+            }
+            Self::Type => buffer.push(0x50),
+            Self::Boxed => buffer.push(0x51),
+            Self::Enum(name) => {
+                buffer.push(0x55);
+                write_ser_string(buffer, Some(name));
+            }
+        }
+    }
+}
+
+impl AttributeArg {
+    /// The encoded type of the argument
+    pub fn arg_type(&self) -> ArgType {
+        match self {
+            Self::Bool(_) => ArgType::Bool,
+            Self::Char(_) => ArgType::Char,
+            Self::I8(_) => ArgType::I8,
+            Self::U8(_) => ArgType::U8,
+            Self::I16(_) => ArgType::I16,
+            Self::U16(_) => ArgType::U16,
+            Self::I32(_) => ArgType::I32,
+            Self::U32(_) => ArgType::U32,
+            Self::I64(_) => ArgType::I64,
+            Self::U64(_) => ArgType::U64,
+            Self::F32(_) => ArgType::F32,
+            Self::F64(_) => ArgType::F64,
+            Self::String(_) => ArgType::String,
+            Self::Type(_) => ArgType::Type,
+            Self::Enum(name, _) => ArgType::Enum(name.clone()),
+            Self::Array(element, _) => ArgType::Array(Box::new(element.clone())),
+            Self::Boxed(_) => ArgType::Boxed,
+        }
+    }
+
+    fn encode(&self, buffer: &mut Vec<u8>) {
+        match self {
+            Self::Bool(value) => buffer.push(*value as u8),
+            Self::Char(value) => {
+                let mut units = [0; 2];
+
+                // Characters outside the basic multilingual plane cannot be represented.
+                buffer.extend_from_slice(&value.encode_utf16(&mut units)[0].to_le_bytes())
+            }
+            Self::I8(value) => buffer.extend_from_slice(&value.to_le_bytes()),
+            Self::U8(value) => buffer.extend_from_slice(&value.to_le_bytes()),
+            Self::I16(value) => buffer.extend_from_slice(&value.to_le_bytes()),
+            Self::U16(value) => buffer.extend_from_slice(&value.to_le_bytes()),
+            Self::I32(value) => buffer.extend_from_slice(&value.to_le_bytes()),
+            Self::U32(value) => buffer.extend_from_slice(&value.to_le_bytes()),
+            Self::I64(value) => buffer.extend_from_slice(&value.to_le_bytes()),
+            Self::U64(value) => buffer.extend_from_slice(&value.to_le_bytes()),
+            Self::F32(value) => buffer.extend_from_slice(&value.to_le_bytes()),
+            Self::F64(value) => buffer.extend_from_slice(&value.to_le_bytes()),
+            Self::String(value) | Self::Type(value) => write_ser_string(buffer, value.as_deref()),
+            Self::Enum(_, value) => value.encode(buffer),
+            Self::Array(_, None) => buffer.extend_from_slice(&0xFFFF_FFFFu32.to_le_bytes()),
+            Self::Array(_, Some(values)) => {
+                buffer.extend_from_slice(&(values.len() as u32).to_le_bytes());
+
+                for value in values {
+                    value.encode(buffer);
+                }
+            }
+            Self::Boxed(value) => {
+                value.arg_type().encode(buffer);
+                value.encode(buffer);
+            }
+        }
+    }
+}
+
+/// Encodes the value blob of a custom attribute from its fixed and named arguments
+///
+/// The fixed arguments must match the parameters of the attribute's constructor. Arguments
+/// whose parameter type is `System.Object` must be [`AttributeArg::Boxed`].
+pub fn attribute_blob(args: &[AttributeArg], named_args: &[NamedArg]) -> Vec<u8> {
+    // The prolog required by the spec
+    let mut buffer = vec![0x01, 0x00];
+
+    for arg in args {
+        arg.encode(&mut buffer);
+    }
+
+    buffer.extend_from_slice(&(named_args.len() as u16).to_le_bytes());
+
+    for arg in named_args {
+        buffer.push(if arg.property { 0x54 } else { 0x53 });
+        arg.value.arg_type().encode(&mut buffer);
+        write_ser_string(&mut buffer, Some(&arg.name));
+        arg.value.encode(&mut buffer);
+    }
+
+    buffer
+}
+
+/// The value of a constant field, parameter or property
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConstantValue {
+    Bool(bool),
+    Char(char),
+    I8(i8),
+    U8(u8),
+    I16(i16),
+    U16(u16),
+    I32(i32),
+    U32(u32),
+    I64(i64),
+    U64(u64),
+    F32(f32),
+    F64(f64),
+    String(String),
+    /// A null reference
+    Null,
+}
+
+impl ConstantValue {
+    /// The element type stored in the `Constant` table's `Type` column
+    pub fn element_type(&self) -> u8 {
+        match self {
+            Self::Bool(_) => 0x02,
+            Self::Char(_) => 0x03,
+            Self::I8(_) => 0x04,
+            Self::U8(_) => 0x05,
+            Self::I16(_) => 0x06,
+            Self::U16(_) => 0x07,
+            Self::I32(_) => 0x08,
+            Self::U32(_) => 0x09,
+            Self::I64(_) => 0x0a,
+            Self::U64(_) => 0x0b,
+            Self::F32(_) => 0x0c,
+            Self::F64(_) => 0x0d,
+            Self::String(_) => 0x0e,
+            Self::Null => 0x12,
+        }
+    }
+
+    /// Encodes the value blob stored in the `Constant` table's `Value` column
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            Self::Bool(value) => vec![*value as u8],
+            Self::Char(value) => {
+                let mut units = [0; 2];
+                value.encode_utf16(&mut units)[0].to_le_bytes().to_vec()
+            }
+            Self::I8(value) => value.to_le_bytes().to_vec(),
+            Self::U8(value) => value.to_le_bytes().to_vec(),
+            Self::I16(value) => value.to_le_bytes().to_vec(),
+            Self::U16(value) => value.to_le_bytes().to_vec(),
+            Self::I32(value) => value.to_le_bytes().to_vec(),
+            Self::U32(value) => value.to_le_bytes().to_vec(),
+            Self::I64(value) => value.to_le_bytes().to_vec(),
+            Self::U64(value) => value.to_le_bytes().to_vec(),
+            Self::F32(value) => value.to_le_bytes().to_vec(),
+            Self::F64(value) => value.to_le_bytes().to_vec(),
+            Self::String(value) => value
+                .encode_utf16()
+                .flat_map(|c| c.to_le_bytes().to_vec())
+                .collect(),
+            // A null reference is stored as a four-byte zero.
+            Self::Null => vec![0; 4],
+        }
+    }
+}
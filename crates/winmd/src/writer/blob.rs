@@ -0,0 +1,90 @@
+/// Writes an unsigned integer using the compressed encoding of ECMA-335 II.23.2
+///
+/// # Panics
+///
+/// Panics if the value is too large to be compressed.
+pub(crate) fn write_unsigned(buffer: &mut Vec<u8>, value: u32) {
+    if value < 0x80 {
+        buffer.push(value as u8);
+    } else if value < 0x4000 {
+        buffer.extend_from_slice(&(0x8000 | value as u16).to_be_bytes());
+    } else if value < 0x2000_0000 {
+        buffer.extend_from_slice(&(0xC000_0000 | value).to_be_bytes());
+    } else {
+        panic!("{} is too large to compress", value);
+    }
+}
+
+/// Writes a signed integer using the compressed encoding of ECMA-335 II.23.2
+///
+/// # Panics
+///
+/// Panics if the value is too large to be compressed.
+pub(crate) fn write_signed(buffer: &mut Vec<u8>, value: i32) {
+    // The sign bit is rotated into the least significant bit of the smallest encoding
+    // that can hold the value.
+    let rotate = |bits: u32| ((value as u32) << 1 & ((1 << (bits + 1)) - 1)) | (value < 0) as u32;
+
+    if (-(1 << 6)..(1 << 6)).contains(&value) {
+        buffer.push(rotate(6) as u8);
+    } else if (-(1 << 13)..(1 << 13)).contains(&value) {
+        buffer.extend_from_slice(&(0x8000 | rotate(13) as u16).to_be_bytes());
+    } else if (-(1 << 28)..(1 << 28)).contains(&value) {
+        buffer.extend_from_slice(&(0xC000_0000 | rotate(28)).to_be_bytes());
+    } else {
+        panic!("{} is too large to compress", value);
+    }
+}
+
+/// Writes a `SerString`, which is a compressed length followed by UTF-8 bytes or `0xFF` for null
+pub(crate) fn write_ser_string(buffer: &mut Vec<u8>, value: Option<&str>) {
+    match value {
+        Some(value) => {
+            write_unsigned(buffer, value.len() as u32);
+            buffer.extend_from_slice(value.as_bytes());
+        }
+        None => buffer.push(0xFF),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unsigned(value: u32) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        write_unsigned(&mut buffer, value);
+        buffer
+    }
+
+    fn signed(value: i32) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        write_signed(&mut buffer, value);
+        buffer
+    }
+
+    #[test]
+    fn compressed_unsigned() {
+        // Examples from ECMA-335 II.23.2
+        assert_eq!(unsigned(0x03), [0x03]);
+        assert_eq!(unsigned(0x7F), [0x7F]);
+        assert_eq!(unsigned(0x80), [0x80, 0x80]);
+        assert_eq!(unsigned(0x2E57), [0xAE, 0x57]);
+        assert_eq!(unsigned(0x3FFF), [0xBF, 0xFF]);
+        assert_eq!(unsigned(0x4000), [0xC0, 0x00, 0x40, 0x00]);
+        assert_eq!(unsigned(0x1FFF_FFFF), [0xDF, 0xFF, 0xFF, 0xFF]);
+    }
+
+    #[test]
+    fn compressed_signed() {
+        // Examples from ECMA-335 II.23.2
+        assert_eq!(signed(3), [0x06]);
+        assert_eq!(signed(-3), [0x7B]);
+        assert_eq!(signed(64), [0x80, 0x80]);
+        assert_eq!(signed(-64), [0x01]);
+        assert_eq!(signed(8192), [0xC0, 0x00, 0x40, 0x00]);
+        assert_eq!(signed(-8192), [0x80, 0x01]);
+        assert_eq!(signed(268_435_455), [0xDF, 0xFF, 0xFF, 0xFE]);
+        assert_eq!(signed(-268_435_456), [0xC0, 0x00, 0x00, 0x01]);
+    }
+}
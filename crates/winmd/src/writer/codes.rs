@@ -0,0 +1,108 @@
+use crate::TableIndex;
+
+/// Declares a coded index whose variants hold zero-based row indices
+macro_rules! coded_index {
+    ($name:ident, $bits:literal, { $($table:ident = $tag:literal,)* }) => {
+        #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub enum $name {
+            $($table(u32),)*
+        }
+
+        impl $name {
+            /// Encodes the coded index as it is stored in a table column or signature
+            pub fn encode(&self) -> u32 {
+                match self {
+                    $(Self::$table(row) => ((row + 1) << $bits) | $tag,)*
+                }
+            }
+
+            /// Decodes a coded index, returning `None` for a null index or an unsupported table
+            pub fn decode(code: u32) -> Option<Self> {
+                let row = (code >> $bits).checked_sub(1)?;
+
+                match code & ((1 << $bits) - 1) {
+                    $($tag => Some(Self::$table(row)),)*
+                    _ => None,
+                }
+            }
+
+            pub(crate) fn size(row_counts: &[u32]) -> u32 {
+                let small = [$(TableIndex::$table,)*]
+                    .iter()
+                    .all(|table| (row_counts[*table as usize] as u64) < (1u64 << (16 - $bits)));
+
+                if small {
+                    2
+                } else {
+                    4
+                }
+            }
+        }
+    };
+}
+
+coded_index!(TypeDefOrRef, 2, {
+    TypeDef = 0,
+    TypeRef = 1,
+    TypeSpec = 2,
+});
+
+coded_index!(TypeOrMethodDef, 1, {
+    TypeDef = 0,
+    MethodDef = 1,
+});
+
+coded_index!(HasAttribute, 5, {
+    MethodDef = 0,
+    Field = 1,
+    TypeRef = 2,
+    TypeDef = 3,
+    Param = 4,
+    InterfaceImpl = 5,
+    MemberRef = 6,
+    Module = 7,
+    Property = 9,
+    Event = 10,
+    ModuleRef = 12,
+    TypeSpec = 13,
+    Assembly = 14,
+    AssemblyRef = 15,
+    GenericParam = 19,
+    GenericParamConstraint = 20,
+});
+
+coded_index!(MemberRefParent, 3, {
+    TypeDef = 0,
+    TypeRef = 1,
+    ModuleRef = 2,
+    MethodDef = 3,
+    TypeSpec = 4,
+});
+
+coded_index!(HasConstant, 2, {
+    Field = 0,
+    Param = 1,
+    Property = 2,
+});
+
+coded_index!(AttributeType, 3, {
+    MethodDef = 2,
+    MemberRef = 3,
+});
+
+coded_index!(HasSemantics, 1, {
+    Event = 0,
+    Property = 1,
+});
+
+coded_index!(ResolutionScope, 2, {
+    Module = 0,
+    ModuleRef = 1,
+    AssemblyRef = 2,
+    TypeRef = 3,
+});
+
+coded_index!(MemberForwarded, 1, {
+    Field = 0,
+    MethodDef = 1,
+});
@@ -0,0 +1,107 @@
+use super::*;
+use crate::Guid;
+
+use std::collections::HashMap;
+
+/// The #Strings, #Blob and #GUID heaps, which are built as the tables are written
+pub(crate) struct Heaps {
+    pub strings: Vec<u8>,
+    pub blobs: Vec<u8>,
+    pub guids: Vec<u8>,
+    string_offsets: HashMap<String, u32>,
+    blob_offsets: HashMap<Vec<u8>, u32>,
+    guid_indexes: HashMap<Guid, u32>,
+}
+
+impl Heaps {
+    pub fn new() -> Self {
+        // Offset zero of the #Strings and #Blob heaps is always the empty value.
+        Self {
+            strings: vec![0],
+            blobs: vec![0],
+            guids: Vec::new(),
+            string_offsets: HashMap::new(),
+            blob_offsets: HashMap::new(),
+            guid_indexes: HashMap::new(),
+        }
+    }
+
+    /// Adds a string to the #Strings heap, returning its offset
+    pub fn string(&mut self, value: &str) -> u32 {
+        if value.is_empty() {
+            return 0;
+        }
+
+        if let Some(offset) = self.string_offsets.get(value) {
+            return *offset;
+        }
+
+        let offset = self.strings.len() as u32;
+        self.strings.extend_from_slice(value.as_bytes());
+        self.strings.push(0);
+        self.string_offsets.insert(value.to_string(), offset);
+        offset
+    }
+
+    /// Adds a blob to the #Blob heap, returning its offset
+    pub fn blob(&mut self, value: &[u8]) -> u32 {
+        if value.is_empty() {
+            return 0;
+        }
+
+        if let Some(offset) = self.blob_offsets.get(value) {
+            return *offset;
+        }
+
+        let offset = self.blobs.len() as u32;
+        write_unsigned(&mut self.blobs, value.len() as u32);
+        self.blobs.extend_from_slice(value);
+        self.blob_offsets.insert(value.to_vec(), offset);
+        offset
+    }
+
+    /// Adds a GUID to the #GUID heap, returning its one-based index
+    pub fn guid(&mut self, value: Option<Guid>) -> u32 {
+        let value = match value {
+            Some(value) => value,
+            None => return 0,
+        };
+
+        if let Some(index) = self.guid_indexes.get(&value) {
+            return *index;
+        }
+
+        self.guids.extend_from_slice(&value.to_bytes());
+        let index = (self.guids.len() / 16) as u32;
+        self.guid_indexes.insert(value, index);
+        index
+    }
+}
+
+/// Adds a string to a #US heap, returning its offset
+pub(crate) fn user_string(heap: &mut Vec<u8>, value: &str) -> u32 {
+    if heap.is_empty() {
+        heap.push(0);
+    }
+
+    if value.is_empty() {
+        return 0;
+    }
+
+    let offset = heap.len() as u32;
+    let chars: Vec<u16> = value.encode_utf16().collect();
+
+    // The trailing byte flags strings that contain characters needing special handling.
+    let special = chars
+        .iter()
+        .any(|c| *c >= 0x80 || matches!(*c, 0x01..=0x08 | 0x0e..=0x1f | 0x27 | 0x2d | 0x7f));
+
+    write_unsigned(heap, chars.len() as u32 * 2 + 1);
+
+    for c in chars {
+        heap.extend_from_slice(&c.to_le_bytes());
+    }
+
+    heap.push(special as u8);
+    offset
+}
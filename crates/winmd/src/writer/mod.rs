@@ -0,0 +1,320 @@
+//! A Windows Metadata (winmd) writer
+//!
+//! A [`Writer`] holds the rows of each metadata table. Writing the file builds the heaps,
+//! sorts the tables and wraps the metadata in the PE/CLI headers that [`File`](crate::File)
+//! expects, so that the output can be read back with a [`TypeReader`](crate::TypeReader).
+mod attribute;
+mod blob;
+mod codes;
+mod heaps;
+mod pe;
+mod signature;
+mod tables;
+
+pub use attribute::*;
+pub(crate) use blob::*;
+pub use codes::*;
+pub(crate) use heaps::*;
+pub use signature::*;
+pub use tables::*;
+
+use crate::{Error, TypeReader};
+
+/// Builds a Windows Metadata file
+pub struct Writer {
+    pub tables: Tables,
+    /// The metadata version string, such as `WindowsRuntime 1.4`
+    pub version: String,
+    /// The raw #US heap, which is only referenced by IL and so is never rebuilt
+    user_strings: Vec<u8>,
+}
+
+impl Writer {
+    /// Creates a writer for a module with the given name
+    ///
+    /// The `Module` table and the `<Module>` type, which is always the first row of the
+    /// `TypeDef` table, are added automatically.
+    pub fn new(name: &str) -> Self {
+        let mut tables = Tables::default();
+
+        tables.module.push(Module {
+            name: name.to_string(),
+            ..Default::default()
+        });
+
+        tables.type_def.push(TypeDef {
+            flags: 0,
+            name: "<Module>".to_string(),
+            namespace: String::new(),
+            extends: None,
+            field_list: 0,
+            method_list: 0,
+        });
+
+        Self {
+            tables,
+            version: "WindowsRuntime 1.4".to_string(),
+            user_strings: Vec::new(),
+        }
+    }
+
+    /// Creates a writer holding the rows of one of the reader's files
+    ///
+    /// Only the tables that the [`TypeReader`] understands are copied.
+    pub fn try_from_file(reader: &TypeReader, file: u16) -> Result<Self, Error> {
+        let tables = Tables::try_read(reader, file)?;
+        let source = &reader.files[file as usize];
+        let (start, size) = source.user_strings;
+
+        Ok(Self {
+            tables,
+            version: "WindowsRuntime 1.4".to_string(),
+            user_strings: source.bytes[start as usize..(start + size) as usize].to_vec(),
+        })
+    }
+
+    /// Adds a string to the #US heap, returning its offset
+    pub fn user_string(&mut self, value: &str) -> u32 {
+        user_string(&mut self.user_strings, value)
+    }
+
+    /// Adds a `TypeRef` row
+    pub fn type_ref(
+        &mut self,
+        scope: Option<ResolutionScope>,
+        namespace: &str,
+        name: &str,
+    ) -> TypeDefOrRef {
+        self.tables.type_ref.push(TypeRef {
+            scope,
+            name: name.to_string(),
+            namespace: namespace.to_string(),
+        });
+
+        TypeDefOrRef::TypeRef(self.tables.type_ref.len() as u32 - 1)
+    }
+
+    /// Adds a `TypeDef` row, which owns the fields and methods added after it
+    pub fn type_def(
+        &mut self,
+        flags: u32,
+        namespace: &str,
+        name: &str,
+        extends: Option<TypeDefOrRef>,
+    ) -> u32 {
+        self.tables.type_def.push(TypeDef {
+            flags,
+            name: name.to_string(),
+            namespace: namespace.to_string(),
+            extends,
+            field_list: self.tables.field.len() as u32,
+            method_list: self.tables.method_def.len() as u32,
+        });
+
+        self.tables.type_def.len() as u32 - 1
+    }
+
+    /// Adds a `Field` row to the most recently added type
+    pub fn field(&mut self, flags: u16, name: &str, ty: &TypeSig) -> u32 {
+        self.tables.field.push(Field {
+            flags,
+            name: name.to_string(),
+            signature: ParamSig::new(ty.clone()).to_field_bytes(),
+        });
+
+        self.tables.field.len() as u32 - 1
+    }
+
+    /// Adds a `MethodDef` row to the most recently added type, which owns the parameters added after it
+    pub fn method_def(&mut self, flags: u16, name: &str, sig: &MethodSig) -> u32 {
+        self.tables.method_def.push(MethodDef {
+            rva: 0,
+            impl_flags: 0,
+            flags,
+            name: name.to_string(),
+            signature: sig.to_bytes(),
+            param_list: self.tables.param.len() as u32,
+        });
+
+        self.tables.method_def.len() as u32 - 1
+    }
+
+    /// Adds a `Param` row to the most recently added method
+    pub fn param(&mut self, flags: u16, sequence: u16, name: &str) -> u32 {
+        self.tables.param.push(Param {
+            flags,
+            sequence,
+            name: name.to_string(),
+        });
+
+        self.tables.param.len() as u32 - 1
+    }
+
+    /// Adds a `MemberRef` row referring to a method
+    pub fn member_ref(&mut self, parent: MemberRefParent, name: &str, sig: &MethodSig) -> u32 {
+        self.tables.member_ref.push(MemberRef {
+            parent,
+            name: name.to_string(),
+            signature: sig.to_bytes(),
+        });
+
+        self.tables.member_ref.len() as u32 - 1
+    }
+
+    /// Adds a `GenericParam` row, numbered after the owner's existing parameters
+    pub fn generic_param(&mut self, owner: TypeOrMethodDef, flags: u16, name: &str) -> u32 {
+        let number = self
+            .tables
+            .generic_param
+            .iter()
+            .filter(|param| param.owner == owner)
+            .count() as u16;
+
+        self.tables.generic_param.push(GenericParam {
+            number,
+            flags,
+            owner,
+            name: name.to_string(),
+        });
+
+        self.tables.generic_param.len() as u32 - 1
+    }
+
+    /// Adds a `GenericParamConstraint` row constraining the given generic parameter
+    pub fn generic_param_constraint(&mut self, owner: u32, constraint: TypeDefOrRef) {
+        self.tables
+            .generic_param_constraint
+            .push(GenericParamConstraint { owner, constraint });
+    }
+
+    /// Adds a `Constant` row holding the value of a field, parameter or property
+    pub fn constant(&mut self, parent: HasConstant, value: &ConstantValue) {
+        self.tables.constant.push(Constant {
+            element_type: value.element_type(),
+            parent,
+            value: value.to_bytes(),
+        });
+    }
+
+    /// Adds a `CustomAttribute` row
+    pub fn attribute(
+        &mut self,
+        parent: HasAttribute,
+        constructor: AttributeType,
+        args: &[AttributeArg],
+        named_args: &[NamedArg],
+    ) {
+        self.tables.custom_attribute.push(CustomAttribute {
+            parent,
+            constructor,
+            value: attribute_blob(args, named_args),
+        });
+    }
+
+    /// Writes the metadata file to a buffer
+    pub fn into_bytes(mut self) -> Vec<u8> {
+        self.tables.sort();
+
+        let mut heaps = Heaps::new();
+        let tables = self.tables.columns(&mut heaps);
+        let row_counts = self.tables.row_counts();
+
+        let string_size = heap_index_size(&heaps.strings);
+        let guid_size = heap_index_size(&heaps.guids);
+        let blob_size = heap_index_size(&heaps.blobs);
+
+        let mut stream = Vec::new();
+        pe::push_u32(&mut stream, 0); // reserved
+        stream.push(2); // major_version
+        stream.push(0); // minor_version
+        stream.push(
+            (string_size == 4) as u8
+                | ((guid_size == 4) as u8) << 1
+                | ((blob_size == 4) as u8) << 2,
+        );
+        stream.push(1); // reserved
+
+        let valid = tables
+            .iter()
+            .filter(|(_, rows)| !rows.is_empty())
+            .fold(0u64, |valid, (number, _)| valid | 1 << number);
+
+        // InterfaceImpl, Constant, CustomAttribute, ClassLayout, FieldLayout, MethodSemantics,
+        // ImplMap, NestedClass, GenericParam and GenericParamConstraint
+        let sorted: u64 = [0x09, 0x0b, 0x0c, 0x0f, 0x10, 0x18, 0x1c, 0x29, 0x2a, 0x2c]
+            .iter()
+            .fold(0, |sorted, number| sorted | 1 << number);
+
+        stream.extend_from_slice(&valid.to_le_bytes());
+        stream.extend_from_slice(&sorted.to_le_bytes());
+
+        for (_, rows) in &tables {
+            if !rows.is_empty() {
+                pe::push_u32(&mut stream, rows.len() as u32);
+            }
+        }
+
+        for (_, rows) in &tables {
+            for columns in rows {
+                for column in columns {
+                    let (size, value) = match column {
+                        Column::U16(value) => (2, *value as u64),
+                        Column::U32(value) => (4, *value as u64),
+                        Column::U64(value) => (8, *value),
+                        Column::String(value) => (string_size, *value as u64),
+                        Column::Blob(value) => (blob_size, *value as u64),
+                        Column::Guid(value) => (guid_size, *value as u64),
+                        Column::Index(table, value) => {
+                            let size = if row_counts[*table as usize] < (1 << 16) {
+                                2
+                            } else {
+                                4
+                            };
+
+                            (size, *value as u64)
+                        }
+                        Column::Coded(size, value) => (size(&row_counts), *value as u64),
+                    };
+
+                    stream.extend_from_slice(&value.to_le_bytes()[..size as usize]);
+                }
+            }
+        }
+
+        // Offset zero of the #US heap is always the empty string.
+        let mut user_strings = self.user_strings;
+
+        if user_strings.is_empty() {
+            user_strings.push(0);
+        }
+
+        let metadata = pe::metadata(
+            &self.version,
+            &[
+                ("#~", &stream),
+                ("#Strings", &heaps.strings),
+                ("#US", &user_strings),
+                ("#GUID", &heaps.guids),
+                ("#Blob", &heaps.blobs),
+            ],
+        );
+
+        pe::image(&metadata)
+    }
+
+    /// Writes the metadata file to the given path
+    pub fn write<P: AsRef<std::path::Path>>(self, path: P) -> Result<(), Error> {
+        std::fs::write(path.as_ref(), self.into_bytes()).map_err(|error| Error::Io {
+            file: path.as_ref().to_string_lossy().into_owned(),
+            message: error.to_string(),
+        })
+    }
+}
+
+fn heap_index_size(heap: &[u8]) -> u32 {
+    if heap.len() < (1 << 16) {
+        2
+    } else {
+        4
+    }
+}
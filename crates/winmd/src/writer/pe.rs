@@ -0,0 +1,156 @@
+const FILE_ALIGNMENT: u32 = 0x200;
+const SECTION_ALIGNMENT: u32 = 0x2000;
+const NT_HEADER_OFFSET: u32 = 0x80;
+const CLI_HEADER_SIZE: u32 = 72;
+
+/// Wraps metadata in a PE32 image with a single section holding the CLI header and metadata
+pub(crate) fn image(metadata: &[u8]) -> Vec<u8> {
+    let section_size = CLI_HEADER_SIZE + metadata.len() as u32;
+    let raw_size = align(section_size, FILE_ALIGNMENT);
+    let mut buffer = Vec::with_capacity((FILE_ALIGNMENT + raw_size) as usize);
+
+    // IMAGE_DOS_HEADER, which only needs a signature and the offset of the NT headers
+    push_u16(&mut buffer, 0x5A4D);
+    buffer.resize(0x3C, 0);
+    push_u32(&mut buffer, NT_HEADER_OFFSET);
+    buffer.resize(NT_HEADER_OFFSET as usize, 0);
+
+    // IMAGE_NT_HEADERS32
+    buffer.extend_from_slice(b"PE\0\0");
+
+    // IMAGE_FILE_HEADER
+    push_u16(&mut buffer, 0x014C); // machine: i386
+    push_u16(&mut buffer, 1); // number_of_sections
+    push_u32(&mut buffer, 0); // time_date_stamp
+    push_u32(&mut buffer, 0); // pointer_to_symbol_table
+    push_u32(&mut buffer, 0); // number_of_symbols
+    push_u16(&mut buffer, 0xE0); // size_of_optional_header
+    push_u16(&mut buffer, 0x2102); // characteristics: executable, 32-bit, dll
+
+    // IMAGE_OPTIONAL_HEADER32
+    push_u16(&mut buffer, 0x010B); // magic
+    buffer.push(8); // major_linker_version
+    buffer.push(0); // minor_linker_version
+    push_u32(&mut buffer, raw_size); // size_of_code
+    push_u32(&mut buffer, 0); // size_of_initialized_data
+    push_u32(&mut buffer, 0); // size_of_uninitialized_data
+    push_u32(&mut buffer, 0); // address_of_entry_point
+    push_u32(&mut buffer, SECTION_ALIGNMENT); // base_of_code
+    push_u32(&mut buffer, 0); // base_of_data
+    push_u32(&mut buffer, 0x0040_0000); // image_base
+    push_u32(&mut buffer, SECTION_ALIGNMENT);
+    push_u32(&mut buffer, FILE_ALIGNMENT);
+    push_u16(&mut buffer, 4); // major_operating_system_version
+    push_u16(&mut buffer, 0); // minor_operating_system_version
+    push_u16(&mut buffer, 0); // major_image_version
+    push_u16(&mut buffer, 0); // minor_image_version
+    push_u16(&mut buffer, 4); // major_subsystem_version
+    push_u16(&mut buffer, 0); // minor_subsystem_version
+    push_u32(&mut buffer, 0); // win32_version_value
+    push_u32(
+        &mut buffer,
+        SECTION_ALIGNMENT + align(section_size, SECTION_ALIGNMENT),
+    ); // size_of_image
+    push_u32(&mut buffer, FILE_ALIGNMENT); // size_of_headers
+    push_u32(&mut buffer, 0); // check_sum
+    push_u16(&mut buffer, 3); // subsystem: console
+    push_u16(&mut buffer, 0x8540); // dll_characteristics: dynamic base, nx, no seh, terminal server aware
+    push_u32(&mut buffer, 0x0010_0000); // size_of_stack_reserve
+    push_u32(&mut buffer, 0x1000); // size_of_stack_commit
+    push_u32(&mut buffer, 0x0010_0000); // size_of_heap_reserve
+    push_u32(&mut buffer, 0x1000); // size_of_heap_commit
+    push_u32(&mut buffer, 0); // loader_flags
+    push_u32(&mut buffer, 16); // number_of_rva_and_sizes
+
+    // The data directories, of which only the CLI header is present
+    for index in 0..16 {
+        if index == 14 {
+            push_u32(&mut buffer, SECTION_ALIGNMENT);
+            push_u32(&mut buffer, CLI_HEADER_SIZE);
+        } else {
+            push_u32(&mut buffer, 0);
+            push_u32(&mut buffer, 0);
+        }
+    }
+
+    // IMAGE_SECTION_HEADER
+    buffer.extend_from_slice(b".text\0\0\0");
+    push_u32(&mut buffer, section_size); // virtual_size
+    push_u32(&mut buffer, SECTION_ALIGNMENT); // virtual_address
+    push_u32(&mut buffer, raw_size); // size_of_raw_data
+    push_u32(&mut buffer, FILE_ALIGNMENT); // pointer_to_raw_data
+    push_u32(&mut buffer, 0); // pointer_to_relocations
+    push_u32(&mut buffer, 0); // pointer_to_line_numbers
+    push_u16(&mut buffer, 0); // number_of_relocations
+    push_u16(&mut buffer, 0); // number_of_line_numbers
+    push_u32(&mut buffer, 0x6000_0020); // characteristics: code, execute, read
+    buffer.resize(FILE_ALIGNMENT as usize, 0);
+
+    // IMAGE_COR20_HEADER
+    push_u32(&mut buffer, CLI_HEADER_SIZE); // cb
+    push_u16(&mut buffer, 2); // major_runtime_version
+    push_u16(&mut buffer, 5); // minor_runtime_version
+    push_u32(&mut buffer, SECTION_ALIGNMENT + CLI_HEADER_SIZE); // meta_data.virtual_address
+    push_u32(&mut buffer, metadata.len() as u32); // meta_data.size
+    push_u32(&mut buffer, 1); // flags: IL only
+    push_u32(&mut buffer, 0); // entry_point_token
+    buffer.resize((FILE_ALIGNMENT + CLI_HEADER_SIZE) as usize, 0);
+
+    buffer.extend_from_slice(metadata);
+    buffer.resize((FILE_ALIGNMENT + raw_size) as usize, 0);
+    buffer
+}
+
+/// Builds the metadata root, which is followed by the given streams
+pub(crate) fn metadata(version: &str, streams: &[(&str, &[u8])]) -> Vec<u8> {
+    let mut version = version.as_bytes().to_vec();
+    version.push(0);
+    version.resize(align(version.len() as u32, 4) as usize, 0);
+
+    let mut buffer = Vec::new();
+    push_u32(&mut buffer, 0x424A_5342); // signature
+    push_u16(&mut buffer, 1); // major_version
+    push_u16(&mut buffer, 1); // minor_version
+    push_u32(&mut buffer, 0); // reserved
+    push_u32(&mut buffer, version.len() as u32);
+    buffer.extend_from_slice(&version);
+    push_u16(&mut buffer, 0); // flags
+    push_u16(&mut buffer, streams.len() as u16);
+
+    let headers_size: u32 = streams
+        .iter()
+        .map(|(name, _)| 8 + align(name.len() as u32 + 1, 4))
+        .sum();
+
+    let mut offset = buffer.len() as u32 + headers_size;
+
+    for (name, stream) in streams {
+        let size = align(stream.len() as u32, 4);
+        push_u32(&mut buffer, offset);
+        push_u32(&mut buffer, size);
+        buffer.extend_from_slice(name.as_bytes());
+        let padded = buffer.len() + align(name.len() as u32 + 1, 4) as usize - name.len();
+        buffer.resize(padded, 0);
+        offset += size;
+    }
+
+    for (_, stream) in streams {
+        buffer.extend_from_slice(stream);
+        buffer.resize(align(buffer.len() as u32, 4) as usize, 0);
+    }
+
+    buffer
+}
+
+pub(crate) fn push_u16(buffer: &mut Vec<u8>, value: u16) {
+    buffer.extend_from_slice(&value.to_le_bytes());
+}
+
+pub(crate) fn push_u32(buffer: &mut Vec<u8>, value: u32) {
+    buffer.extend_from_slice(&value.to_le_bytes());
+}
+
+fn align(value: u32, alignment: u32) -> u32 {
+    // Every alignment is a power of two.
+    (value + alignment - 1) & !(alignment - 1)
+}
@@ -0,0 +1,371 @@
+use super::*;
+use crate::{parsed, ArrayShape};
+
+/// A custom modifier (`modreq` or `modopt`) applied to a type within a signature
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Modifier {
+    /// Whether the modifier is required (`modreq`) rather than optional (`modopt`)
+    pub required: bool,
+    pub def: TypeDefOrRef,
+}
+
+/// A type to encode within a signature
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TypeSig {
+    Void,
+    Bool,
+    Char,
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    I64,
+    U64,
+    F32,
+    F64,
+    ISize,
+    USize,
+    String,
+    Object,
+    TypedByRef,
+    ValueType(TypeDefOrRef),
+    Class(TypeDefOrRef),
+    /// A generic parameter of the enclosing type, by index
+    TypeGeneric(u32),
+    /// A generic parameter of the enclosing method, by index
+    MethodGeneric(u32),
+    GenericInst {
+        def: TypeDefOrRef,
+        is_value_type: bool,
+        args: Vec<TypeSig>,
+    },
+    Ptr(Vec<Modifier>, Box<TypeSig>),
+    SZArray(Vec<Modifier>, Box<TypeSig>),
+    Array(Box<TypeSig>, ArrayShape),
+    FnPtr(Box<MethodSig>),
+}
+
+/// The type of a parameter, return value, field or property along with its custom modifiers
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParamSig {
+    pub modifiers: Vec<Modifier>,
+    pub by_ref: bool,
+    pub ty: TypeSig,
+}
+
+/// The signature of a method definition, method reference or function pointer
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MethodSig {
+    pub calling_convention: u8,
+    pub generic_count: u32,
+    pub return_type: ParamSig,
+    pub params: Vec<ParamSig>,
+    /// The index of the first variable argument, if the signature has a sentinel
+    pub sentinel: Option<usize>,
+}
+
+/// The signature of a property
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PropertySig {
+    pub has_this: bool,
+    pub ty: ParamSig,
+    pub params: Vec<ParamSig>,
+}
+
+const FIELD: u8 = 0x06;
+const PROPERTY: u8 = 0x08;
+const GENERIC: u8 = 0x10;
+const HAS_THIS: u8 = 0x20;
+const SENTINEL: u8 = 0x41;
+const BY_REF: u8 = 0x10;
+const MOD_REQ: u8 = 0x1f;
+const MOD_OPT: u8 = 0x20;
+
+impl TypeSig {
+    /// Encodes the type as it appears within a signature blob
+    pub fn encode(&self, buffer: &mut Vec<u8>) {
+        match self {
+            Self::Void => buffer.push(0x01),
+            Self::Bool => buffer.push(0x02),
+            Self::Char => buffer.push(0x03),
+            Self::I8 => buffer.push(0x04),
+            Self::U8 => buffer.push(0x05),
+            Self::I16 => buffer.push(0x06),
+            Self::U16 => buffer.push(0x07),
+            Self::I32 => buffer.push(0x08),
+            Self::U32 => buffer.push(0x09),
+            Self::I64 => buffer.push(0x0a),
+            Self::U64 => buffer.push(0x0b),
+            Self::F32 => buffer.push(0x0c),
+            Self::F64 => buffer.push(0x0d),
+            Self::String => buffer.push(0x0e),
+            Self::Ptr(modifiers, ty) => {
+                buffer.push(0x0f);
+                encode_modifiers(modifiers, buffer);
+                ty.encode(buffer);
+            }
+            Self::ValueType(def) => {
+                buffer.push(0x11);
+                write_unsigned(buffer, def.encode());
+            }
+            Self::Class(def) => {
+                buffer.push(0x12);
+                write_unsigned(buffer, def.encode());
+            }
+            Self::TypeGeneric(index) => {
+                buffer.push(0x13);
+                write_unsigned(buffer, *index);
+            }
+            Self::Array(ty, shape) => {
+                buffer.push(0x14);
+                ty.encode(buffer);
+                write_unsigned(buffer, shape.rank);
+                write_unsigned(buffer, shape.sizes.len() as u32);
+
+                for size in &shape.sizes {
+                    write_unsigned(buffer, *size);
+                }
+
+                write_unsigned(buffer, shape.lower_bounds.len() as u32);
+
+                for bound in &shape.lower_bounds {
+                    write_signed(buffer, *bound);
+                }
+            }
+            Self::GenericInst {
+                def,
+                is_value_type,
+                args,
+            } => {
+                buffer.push(0x15);
+                buffer.push(if *is_value_type { 0x11 } else { 0x12 });
+                write_unsigned(buffer, def.encode());
+                write_unsigned(buffer, args.len() as u32);
+
+                for arg in args {
+                    arg.encode(buffer);
+                }
+            }
+            Self::TypedByRef => buffer.push(0x16),
+            Self::ISize => buffer.push(0x18),
+            Self::USize => buffer.push(0x19),
+            Self::FnPtr(method) => {
+                buffer.push(0x1b);
+                method.encode(buffer);
+            }
+            Self::Object => buffer.push(0x1c),
+            Self::SZArray(modifiers, ty) => {
+                buffer.push(0x1d);
+                encode_modifiers(modifiers, buffer);
+                ty.encode(buffer);
+            }
+            Self::MethodGeneric(index) => {
+                buffer.push(0x1e);
+                write_unsigned(buffer, *index);
+            }
+        }
+    }
+
+    /// Encodes the type as a `TypeSpec` signature blob
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        self.encode(&mut buffer);
+        buffer
+    }
+}
+
+impl ParamSig {
+    /// Creates a parameter of the given type without any custom modifiers
+    pub fn new(ty: TypeSig) -> Self {
+        Self {
+            modifiers: Vec::new(),
+            by_ref: false,
+            ty,
+        }
+    }
+
+    /// Encodes the type of a parameter or return value
+    pub fn encode(&self, buffer: &mut Vec<u8>) {
+        encode_modifiers(&self.modifiers, buffer);
+
+        if self.by_ref {
+            buffer.push(BY_REF);
+        }
+
+        self.ty.encode(buffer);
+    }
+
+    /// Encodes the type as a field signature blob
+    pub fn to_field_bytes(&self) -> Vec<u8> {
+        let mut buffer = vec![FIELD];
+        self.encode(&mut buffer);
+        buffer
+    }
+}
+
+impl MethodSig {
+    /// Creates the signature of an instance method with the given return and parameter types
+    pub fn new(return_type: TypeSig, params: Vec<TypeSig>) -> Self {
+        Self {
+            calling_convention: HAS_THIS,
+            generic_count: 0,
+            return_type: ParamSig::new(return_type),
+            params: params.into_iter().map(ParamSig::new).collect(),
+            sentinel: None,
+        }
+    }
+
+    /// Encodes the method signature
+    pub fn encode(&self, buffer: &mut Vec<u8>) {
+        buffer.push(self.calling_convention);
+
+        if self.calling_convention & GENERIC != 0 {
+            write_unsigned(buffer, self.generic_count);
+        }
+
+        write_unsigned(buffer, self.params.len() as u32);
+        self.return_type.encode(buffer);
+
+        for (index, param) in self.params.iter().enumerate() {
+            if self.sentinel == Some(index) {
+                buffer.push(SENTINEL);
+            }
+
+            param.encode(buffer);
+        }
+    }
+
+    /// Encodes the method signature as a blob
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        self.encode(&mut buffer);
+        buffer
+    }
+}
+
+impl PropertySig {
+    /// Encodes the property signature as a blob
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buffer = vec![if self.has_this {
+            PROPERTY | HAS_THIS
+        } else {
+            PROPERTY
+        }];
+
+        write_unsigned(&mut buffer, self.params.len() as u32);
+        self.ty.encode(&mut buffer);
+
+        for param in &self.params {
+            param.encode(&mut buffer);
+        }
+
+        buffer
+    }
+}
+
+fn encode_modifiers(modifiers: &[Modifier], buffer: &mut Vec<u8>) {
+    for modifier in modifiers {
+        buffer.push(if modifier.required { MOD_REQ } else { MOD_OPT });
+        write_unsigned(buffer, modifier.def.encode());
+    }
+}
+
+impl From<&parsed::TypeDefOrRef<'_>> for TypeDefOrRef {
+    fn from(def: &parsed::TypeDefOrRef<'_>) -> Self {
+        Self::decode(def.encode()).unwrap()
+    }
+}
+
+impl From<&parsed::Modifier<'_>> for Modifier {
+    fn from(modifier: &parsed::Modifier<'_>) -> Self {
+        Self {
+            required: modifier.required,
+            def: (&modifier.def).into(),
+        }
+    }
+}
+
+impl From<&parsed::TypeSig<'_>> for TypeSig {
+    fn from(sig: &parsed::TypeSig<'_>) -> Self {
+        let modifiers =
+            |modifiers: &[parsed::Modifier<'_>]| modifiers.iter().map(Modifier::from).collect();
+
+        match sig {
+            parsed::TypeSig::Void => Self::Void,
+            parsed::TypeSig::Bool => Self::Bool,
+            parsed::TypeSig::Char => Self::Char,
+            parsed::TypeSig::I8 => Self::I8,
+            parsed::TypeSig::U8 => Self::U8,
+            parsed::TypeSig::I16 => Self::I16,
+            parsed::TypeSig::U16 => Self::U16,
+            parsed::TypeSig::I32 => Self::I32,
+            parsed::TypeSig::U32 => Self::U32,
+            parsed::TypeSig::I64 => Self::I64,
+            parsed::TypeSig::U64 => Self::U64,
+            parsed::TypeSig::F32 => Self::F32,
+            parsed::TypeSig::F64 => Self::F64,
+            parsed::TypeSig::ISize => Self::ISize,
+            parsed::TypeSig::USize => Self::USize,
+            parsed::TypeSig::String => Self::String,
+            parsed::TypeSig::Object => Self::Object,
+            parsed::TypeSig::TypedByRef => Self::TypedByRef,
+            parsed::TypeSig::ValueType(def) => Self::ValueType(def.into()),
+            parsed::TypeSig::Class(def) => Self::Class(def.into()),
+            parsed::TypeSig::TypeGeneric(index) => Self::TypeGeneric(*index),
+            parsed::TypeSig::MethodGeneric(index) => Self::MethodGeneric(*index),
+            parsed::TypeSig::GenericInst {
+                def,
+                is_value_type,
+                args,
+            } => Self::GenericInst {
+                def: def.into(),
+                is_value_type: *is_value_type,
+                args: args.iter().map(Self::from).collect(),
+            },
+            parsed::TypeSig::Ptr(mods, ty) => {
+                Self::Ptr(modifiers(mods), Box::new(ty.as_ref().into()))
+            }
+            parsed::TypeSig::SZArray(mods, ty) => {
+                Self::SZArray(modifiers(mods), Box::new(ty.as_ref().into()))
+            }
+            parsed::TypeSig::Array(ty, shape) => {
+                Self::Array(Box::new(ty.as_ref().into()), shape.clone())
+            }
+            parsed::TypeSig::FnPtr(method) => Self::FnPtr(Box::new(method.as_ref().into())),
+        }
+    }
+}
+
+impl From<&parsed::ParamSig<'_>> for ParamSig {
+    fn from(sig: &parsed::ParamSig<'_>) -> Self {
+        Self {
+            modifiers: sig.modifiers.iter().map(Modifier::from).collect(),
+            by_ref: sig.by_ref,
+            ty: (&sig.ty).into(),
+        }
+    }
+}
+
+impl From<&parsed::MethodSig<'_>> for MethodSig {
+    fn from(sig: &parsed::MethodSig<'_>) -> Self {
+        Self {
+            calling_convention: sig.calling_convention,
+            generic_count: sig.generic_count,
+            return_type: (&sig.return_type).into(),
+            params: sig.params.iter().map(ParamSig::from).collect(),
+            sentinel: sig.sentinel,
+        }
+    }
+}
+
+impl From<&parsed::PropertySig<'_>> for PropertySig {
+    fn from(sig: &parsed::PropertySig<'_>) -> Self {
+        Self {
+            has_this: sig.has_this,
+            ty: (&sig.ty).into(),
+            params: sig.params.iter().map(ParamSig::from).collect(),
+        }
+    }
+}
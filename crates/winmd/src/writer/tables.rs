@@ -0,0 +1,847 @@
+use super::*;
+use crate::{Error, Guid, Row, TableIndex, TypeReader, Version};
+
+/// A row of the `Module` table
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Module {
+    pub generation: u16,
+    pub name: String,
+    pub mvid: Guid,
+    pub enc_id: Option<Guid>,
+    pub enc_base_id: Option<Guid>,
+}
+
+/// A row of the `TypeRef` table
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeRef {
+    pub scope: Option<ResolutionScope>,
+    pub name: String,
+    pub namespace: String,
+}
+
+/// A row of the `TypeDef` table
+///
+/// The type owns the fields from `field_list` up to the `field_list` of the next type,
+/// and likewise for its methods.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeDef {
+    pub flags: u32,
+    pub name: String,
+    pub namespace: String,
+    pub extends: Option<TypeDefOrRef>,
+    pub field_list: u32,
+    pub method_list: u32,
+}
+
+/// A row of the `Field` table
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Field {
+    pub flags: u16,
+    pub name: String,
+    pub signature: Vec<u8>,
+}
+
+/// A row of the `MethodDef` table
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MethodDef {
+    pub rva: u32,
+    pub impl_flags: u16,
+    pub flags: u16,
+    pub name: String,
+    pub signature: Vec<u8>,
+    pub param_list: u32,
+}
+
+/// A row of the `Param` table
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Param {
+    pub flags: u16,
+    /// The position of the parameter, where zero refers to the return value
+    pub sequence: u16,
+    pub name: String,
+}
+
+/// A row of the `InterfaceImpl` table
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InterfaceImpl {
+    pub class: u32,
+    pub interface: TypeDefOrRef,
+}
+
+/// A row of the `MemberRef` table
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MemberRef {
+    pub parent: MemberRefParent,
+    pub name: String,
+    pub signature: Vec<u8>,
+}
+
+/// A row of the `Constant` table
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Constant {
+    pub element_type: u8,
+    pub parent: HasConstant,
+    pub value: Vec<u8>,
+}
+
+/// A row of the `CustomAttribute` table
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CustomAttribute {
+    pub parent: HasAttribute,
+    pub constructor: AttributeType,
+    pub value: Vec<u8>,
+}
+
+/// A row of the `ClassLayout` table
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClassLayout {
+    pub packing_size: u16,
+    pub class_size: u32,
+    pub parent: u32,
+}
+
+/// A row of the `FieldLayout` table
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldLayout {
+    pub offset: u32,
+    pub field: u32,
+}
+
+/// A row of the `EventMap` table
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EventMap {
+    pub parent: u32,
+    pub event_list: u32,
+}
+
+/// A row of the `Event` table
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Event {
+    pub flags: u16,
+    pub name: String,
+    pub event_type: TypeDefOrRef,
+}
+
+/// A row of the `PropertyMap` table
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PropertyMap {
+    pub parent: u32,
+    pub property_list: u32,
+}
+
+/// A row of the `Property` table
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Property {
+    pub flags: u16,
+    pub name: String,
+    pub signature: Vec<u8>,
+}
+
+/// A row of the `MethodSemantics` table
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MethodSemantics {
+    pub semantics: u16,
+    pub method: u32,
+    pub association: HasSemantics,
+}
+
+/// A row of the `ModuleRef` table
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ModuleRef {
+    pub name: String,
+}
+
+/// A row of the `TypeSpec` table
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeSpec {
+    pub signature: Vec<u8>,
+}
+
+/// A row of the `ImplMap` table
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImplMap {
+    pub flags: u16,
+    pub member: MemberForwarded,
+    pub import_name: String,
+    pub import_scope: u32,
+}
+
+/// A row of the `Assembly` table
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Assembly {
+    pub hash_algorithm: u32,
+    pub version: Version,
+    pub flags: u32,
+    pub public_key: Vec<u8>,
+    pub name: String,
+    pub culture: String,
+}
+
+/// A row of the `AssemblyRef` table
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AssemblyRef {
+    pub version: Version,
+    pub flags: u32,
+    pub public_key_or_token: Vec<u8>,
+    pub name: String,
+    pub culture: String,
+    pub hash_value: Vec<u8>,
+}
+
+/// A row of the `NestedClass` table
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NestedClass {
+    pub nested: u32,
+    pub enclosing: u32,
+}
+
+/// A row of the `GenericParam` table
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GenericParam {
+    pub number: u16,
+    pub flags: u16,
+    pub owner: TypeOrMethodDef,
+    pub name: String,
+}
+
+/// A row of the `GenericParamConstraint` table
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GenericParamConstraint {
+    pub owner: u32,
+    pub constraint: TypeDefOrRef,
+}
+
+/// The rows of each table, where every reference to another row is a zero-based index
+///
+/// Rows of the `InterfaceImpl` and `GenericParam` tables must be added in the order of their
+/// `class` and `owner` respectively. The other tables that ECMA-335 requires to be sorted are
+/// sorted when the file is written.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Tables {
+    pub module: Vec<Module>,
+    pub type_ref: Vec<TypeRef>,
+    pub type_def: Vec<TypeDef>,
+    pub field: Vec<Field>,
+    pub method_def: Vec<MethodDef>,
+    pub param: Vec<Param>,
+    pub interface_impl: Vec<InterfaceImpl>,
+    pub member_ref: Vec<MemberRef>,
+    pub constant: Vec<Constant>,
+    pub custom_attribute: Vec<CustomAttribute>,
+    pub class_layout: Vec<ClassLayout>,
+    pub field_layout: Vec<FieldLayout>,
+    pub event_map: Vec<EventMap>,
+    pub event: Vec<Event>,
+    pub property_map: Vec<PropertyMap>,
+    pub property: Vec<Property>,
+    pub method_semantics: Vec<MethodSemantics>,
+    pub module_ref: Vec<ModuleRef>,
+    pub type_spec: Vec<TypeSpec>,
+    pub impl_map: Vec<ImplMap>,
+    pub assembly: Vec<Assembly>,
+    pub assembly_ref: Vec<AssemblyRef>,
+    pub nested_class: Vec<NestedClass>,
+    pub generic_param: Vec<GenericParam>,
+    pub generic_param_constraint: Vec<GenericParamConstraint>,
+}
+
+/// A column value that has been added to the heaps but whose width is not yet known
+pub(crate) enum Column {
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    String(u32),
+    Blob(u32),
+    Guid(u32),
+    /// A one-based index into the given table
+    Index(TableIndex, u32),
+    /// An encoded coded index along with the function that computes its width
+    Coded(fn(&[u32]) -> u32, u32),
+}
+
+/// The metadata table number of each table, which determines the order of tables in the file
+pub(crate) const TABLE_NUMBERS: [(TableIndex, u32); 25] = [
+    (TableIndex::Module, 0x00),
+    (TableIndex::TypeRef, 0x01),
+    (TableIndex::TypeDef, 0x02),
+    (TableIndex::Field, 0x04),
+    (TableIndex::MethodDef, 0x06),
+    (TableIndex::Param, 0x08),
+    (TableIndex::InterfaceImpl, 0x09),
+    (TableIndex::MemberRef, 0x0a),
+    (TableIndex::Constant, 0x0b),
+    (TableIndex::CustomAttribute, 0x0c),
+    (TableIndex::ClassLayout, 0x0f),
+    (TableIndex::FieldLayout, 0x10),
+    (TableIndex::EventMap, 0x12),
+    (TableIndex::Event, 0x14),
+    (TableIndex::PropertyMap, 0x15),
+    (TableIndex::Property, 0x17),
+    (TableIndex::MethodSemantics, 0x18),
+    (TableIndex::ModuleRef, 0x1a),
+    (TableIndex::TypeSpec, 0x1b),
+    (TableIndex::ImplMap, 0x1c),
+    (TableIndex::Assembly, 0x20),
+    (TableIndex::AssemblyRef, 0x23),
+    (TableIndex::NestedClass, 0x29),
+    (TableIndex::GenericParam, 0x2a),
+    (TableIndex::GenericParamConstraint, 0x2c),
+];
+
+fn coded<T, F: Fn(&T) -> u32>(value: &T, size: fn(&[u32]) -> u32, encode: F) -> Column {
+    Column::Coded(size, encode(value))
+}
+
+fn optional<T, F: Fn(&T) -> u32>(value: &Option<T>, size: fn(&[u32]) -> u32, encode: F) -> Column {
+    Column::Coded(size, value.as_ref().map_or(0, encode))
+}
+
+fn version(value: &Version) -> Column {
+    Column::U64(
+        value.major as u64
+            | (value.minor as u64) << 16
+            | (value.build as u64) << 32
+            | (value.revision as u64) << 48,
+    )
+}
+
+impl Tables {
+    /// The number of rows in each table, indexed by [`TableIndex`]
+    pub(crate) fn row_counts(&self) -> Vec<u32> {
+        let mut counts = vec![0; TABLE_NUMBERS.len()];
+
+        for (table, _) in TABLE_NUMBERS.iter() {
+            counts[*table as usize] = self.len(*table) as u32;
+        }
+
+        counts
+    }
+
+    fn len(&self, table: TableIndex) -> usize {
+        match table {
+            TableIndex::Module => self.module.len(),
+            TableIndex::TypeRef => self.type_ref.len(),
+            TableIndex::TypeDef => self.type_def.len(),
+            TableIndex::Field => self.field.len(),
+            TableIndex::MethodDef => self.method_def.len(),
+            TableIndex::Param => self.param.len(),
+            TableIndex::InterfaceImpl => self.interface_impl.len(),
+            TableIndex::MemberRef => self.member_ref.len(),
+            TableIndex::Constant => self.constant.len(),
+            TableIndex::CustomAttribute => self.custom_attribute.len(),
+            TableIndex::ClassLayout => self.class_layout.len(),
+            TableIndex::FieldLayout => self.field_layout.len(),
+            TableIndex::EventMap => self.event_map.len(),
+            TableIndex::Event => self.event.len(),
+            TableIndex::PropertyMap => self.property_map.len(),
+            TableIndex::Property => self.property.len(),
+            TableIndex::MethodSemantics => self.method_semantics.len(),
+            TableIndex::ModuleRef => self.module_ref.len(),
+            TableIndex::TypeSpec => self.type_spec.len(),
+            TableIndex::ImplMap => self.impl_map.len(),
+            TableIndex::Assembly => self.assembly.len(),
+            TableIndex::AssemblyRef => self.assembly_ref.len(),
+            TableIndex::NestedClass => self.nested_class.len(),
+            TableIndex::GenericParam => self.generic_param.len(),
+            TableIndex::GenericParamConstraint => self.generic_param_constraint.len(),
+        }
+    }
+
+    /// Sorts the tables that ECMA-335 requires to be sorted by their primary key
+    pub(crate) fn sort(&mut self) {
+        self.constant.sort_by_key(|row| row.parent.encode());
+        self.custom_attribute.sort_by_key(|row| row.parent.encode());
+        self.class_layout.sort_by_key(|row| row.parent);
+        self.field_layout.sort_by_key(|row| row.field);
+        self.method_semantics
+            .sort_by_key(|row| row.association.encode());
+        self.impl_map.sort_by_key(|row| row.member.encode());
+        self.nested_class.sort_by_key(|row| row.nested);
+        self.generic_param_constraint.sort_by_key(|row| row.owner);
+    }
+
+    /// Adds the values of each row to the heaps, returning the columns of each table
+    ///
+    /// The tables are returned in the order they appear in the file, along with their table numbers.
+    pub(crate) fn columns(&self, heaps: &mut Heaps) -> Vec<(u32, Vec<Vec<Column>>)> {
+        let mut tables = Vec::new();
+
+        for (table, number) in TABLE_NUMBERS.iter() {
+            let rows = (0..self.len(*table))
+                .map(|row| self.row_columns(*table, row, heaps))
+                .collect();
+
+            tables.push((*number, rows));
+        }
+
+        tables
+    }
+
+    fn row_columns(&self, table: TableIndex, row: usize, heaps: &mut Heaps) -> Vec<Column> {
+        match table {
+            TableIndex::Module => {
+                let row = &self.module[row];
+                vec![
+                    Column::U16(row.generation),
+                    Column::String(heaps.string(&row.name)),
+                    Column::Guid(heaps.guid(Some(row.mvid))),
+                    Column::Guid(heaps.guid(row.enc_id)),
+                    Column::Guid(heaps.guid(row.enc_base_id)),
+                ]
+            }
+            TableIndex::TypeRef => {
+                let row = &self.type_ref[row];
+                vec![
+                    optional(&row.scope, ResolutionScope::size, ResolutionScope::encode),
+                    Column::String(heaps.string(&row.name)),
+                    Column::String(heaps.string(&row.namespace)),
+                ]
+            }
+            TableIndex::TypeDef => {
+                let row = &self.type_def[row];
+                vec![
+                    Column::U32(row.flags),
+                    Column::String(heaps.string(&row.name)),
+                    Column::String(heaps.string(&row.namespace)),
+                    optional(&row.extends, TypeDefOrRef::size, TypeDefOrRef::encode),
+                    Column::Index(TableIndex::Field, row.field_list + 1),
+                    Column::Index(TableIndex::MethodDef, row.method_list + 1),
+                ]
+            }
+            TableIndex::Field => {
+                let row = &self.field[row];
+                vec![
+                    Column::U16(row.flags),
+                    Column::String(heaps.string(&row.name)),
+                    Column::Blob(heaps.blob(&row.signature)),
+                ]
+            }
+            TableIndex::MethodDef => {
+                let row = &self.method_def[row];
+                vec![
+                    Column::U32(row.rva),
+                    Column::U16(row.impl_flags),
+                    Column::U16(row.flags),
+                    Column::String(heaps.string(&row.name)),
+                    Column::Blob(heaps.blob(&row.signature)),
+                    Column::Index(TableIndex::Param, row.param_list + 1),
+                ]
+            }
+            TableIndex::Param => {
+                let row = &self.param[row];
+                vec![
+                    Column::U16(row.flags),
+                    Column::U16(row.sequence),
+                    Column::String(heaps.string(&row.name)),
+                ]
+            }
+            TableIndex::InterfaceImpl => {
+                let row = &self.interface_impl[row];
+                vec![
+                    Column::Index(TableIndex::TypeDef, row.class + 1),
+                    coded(&row.interface, TypeDefOrRef::size, TypeDefOrRef::encode),
+                ]
+            }
+            TableIndex::MemberRef => {
+                let row = &self.member_ref[row];
+                vec![
+                    coded(&row.parent, MemberRefParent::size, MemberRefParent::encode),
+                    Column::String(heaps.string(&row.name)),
+                    Column::Blob(heaps.blob(&row.signature)),
+                ]
+            }
+            TableIndex::Constant => {
+                let row = &self.constant[row];
+                vec![
+                    // The element type is followed by a padding byte.
+                    Column::U16(row.element_type as u16),
+                    coded(&row.parent, HasConstant::size, HasConstant::encode),
+                    Column::Blob(heaps.blob(&row.value)),
+                ]
+            }
+            TableIndex::CustomAttribute => {
+                let row = &self.custom_attribute[row];
+                vec![
+                    coded(&row.parent, HasAttribute::size, HasAttribute::encode),
+                    coded(&row.constructor, AttributeType::size, AttributeType::encode),
+                    Column::Blob(heaps.blob(&row.value)),
+                ]
+            }
+            TableIndex::ClassLayout => {
+                let row = &self.class_layout[row];
+                vec![
+                    Column::U16(row.packing_size),
+                    Column::U32(row.class_size),
+                    Column::Index(TableIndex::TypeDef, row.parent + 1),
+                ]
+            }
+            TableIndex::FieldLayout => {
+                let row = &self.field_layout[row];
+                vec![
+                    Column::U32(row.offset),
+                    Column::Index(TableIndex::Field, row.field + 1),
+                ]
+            }
+            TableIndex::EventMap => {
+                let row = &self.event_map[row];
+                vec![
+                    Column::Index(TableIndex::TypeDef, row.parent + 1),
+                    Column::Index(TableIndex::Event, row.event_list + 1),
+                ]
+            }
+            TableIndex::Event => {
+                let row = &self.event[row];
+                vec![
+                    Column::U16(row.flags),
+                    Column::String(heaps.string(&row.name)),
+                    coded(&row.event_type, TypeDefOrRef::size, TypeDefOrRef::encode),
+                ]
+            }
+            TableIndex::PropertyMap => {
+                let row = &self.property_map[row];
+                vec![
+                    Column::Index(TableIndex::TypeDef, row.parent + 1),
+                    Column::Index(TableIndex::Property, row.property_list + 1),
+                ]
+            }
+            TableIndex::Property => {
+                let row = &self.property[row];
+                vec![
+                    Column::U16(row.flags),
+                    Column::String(heaps.string(&row.name)),
+                    Column::Blob(heaps.blob(&row.signature)),
+                ]
+            }
+            TableIndex::MethodSemantics => {
+                let row = &self.method_semantics[row];
+                vec![
+                    Column::U16(row.semantics),
+                    Column::Index(TableIndex::MethodDef, row.method + 1),
+                    coded(&row.association, HasSemantics::size, HasSemantics::encode),
+                ]
+            }
+            TableIndex::ModuleRef => {
+                let row = &self.module_ref[row];
+                vec![Column::String(heaps.string(&row.name))]
+            }
+            TableIndex::TypeSpec => {
+                let row = &self.type_spec[row];
+                vec![Column::Blob(heaps.blob(&row.signature))]
+            }
+            TableIndex::ImplMap => {
+                let row = &self.impl_map[row];
+                vec![
+                    Column::U16(row.flags),
+                    coded(&row.member, MemberForwarded::size, MemberForwarded::encode),
+                    Column::String(heaps.string(&row.import_name)),
+                    Column::Index(TableIndex::ModuleRef, row.import_scope + 1),
+                ]
+            }
+            TableIndex::Assembly => {
+                let row = &self.assembly[row];
+                vec![
+                    Column::U32(row.hash_algorithm),
+                    version(&row.version),
+                    Column::U32(row.flags),
+                    Column::Blob(heaps.blob(&row.public_key)),
+                    Column::String(heaps.string(&row.name)),
+                    Column::String(heaps.string(&row.culture)),
+                ]
+            }
+            TableIndex::AssemblyRef => {
+                let row = &self.assembly_ref[row];
+                vec![
+                    version(&row.version),
+                    Column::U32(row.flags),
+                    Column::Blob(heaps.blob(&row.public_key_or_token)),
+                    Column::String(heaps.string(&row.name)),
+                    Column::String(heaps.string(&row.culture)),
+                    Column::Blob(heaps.blob(&row.hash_value)),
+                ]
+            }
+            TableIndex::NestedClass => {
+                let row = &self.nested_class[row];
+                vec![
+                    Column::Index(TableIndex::TypeDef, row.nested + 1),
+                    Column::Index(TableIndex::TypeDef, row.enclosing + 1),
+                ]
+            }
+            TableIndex::GenericParam => {
+                let row = &self.generic_param[row];
+                vec![
+                    Column::U16(row.number),
+                    Column::U16(row.flags),
+                    coded(&row.owner, TypeOrMethodDef::size, TypeOrMethodDef::encode),
+                    Column::String(heaps.string(&row.name)),
+                ]
+            }
+            TableIndex::GenericParamConstraint => {
+                let row = &self.generic_param_constraint[row];
+                vec![
+                    Column::Index(TableIndex::GenericParam, row.owner + 1),
+                    coded(&row.constraint, TypeDefOrRef::size, TypeDefOrRef::encode),
+                ]
+            }
+        }
+    }
+
+    /// Reads the rows of every table that the [`TypeReader`] understands from one of its files
+    pub(crate) fn try_read(reader: &TypeReader, file: u16) -> Result<Self, Error> {
+        let source = Source { reader, file };
+
+        Ok(Self {
+            module: source.rows(TableIndex::Module, |row| {
+                Ok(Module {
+                    generation: reader.u32(row, 0) as u16,
+                    name: source.string(row, 1)?,
+                    mvid: source.guid(row, 2)?.unwrap_or_default(),
+                    enc_id: source.guid(row, 3)?,
+                    enc_base_id: source.guid(row, 4)?,
+                })
+            })?,
+            type_ref: source.rows(TableIndex::TypeRef, |row| {
+                Ok(TypeRef {
+                    scope: source.optional(row, 0, ResolutionScope::decode)?,
+                    name: source.string(row, 1)?,
+                    namespace: source.string(row, 2)?,
+                })
+            })?,
+            type_def: source.rows(TableIndex::TypeDef, |row| {
+                Ok(TypeDef {
+                    flags: reader.u32(row, 0),
+                    name: source.string(row, 1)?,
+                    namespace: source.string(row, 2)?,
+                    extends: source.optional(row, 3, TypeDefOrRef::decode)?,
+                    field_list: source.index(row, 4)?,
+                    method_list: source.index(row, 5)?,
+                })
+            })?,
+            field: source.rows(TableIndex::Field, |row| {
+                Ok(Field {
+                    flags: reader.u32(row, 0) as u16,
+                    name: source.string(row, 1)?,
+                    signature: source.blob(row, 2)?,
+                })
+            })?,
+            method_def: source.rows(TableIndex::MethodDef, |row| {
+                Ok(MethodDef {
+                    rva: reader.u32(row, 0),
+                    impl_flags: reader.u32(row, 1) as u16,
+                    flags: reader.u32(row, 2) as u16,
+                    name: source.string(row, 3)?,
+                    signature: source.blob(row, 4)?,
+                    param_list: source.index(row, 5)?,
+                })
+            })?,
+            param: source.rows(TableIndex::Param, |row| {
+                Ok(Param {
+                    flags: reader.u32(row, 0) as u16,
+                    sequence: reader.u32(row, 1) as u16,
+                    name: source.string(row, 2)?,
+                })
+            })?,
+            interface_impl: source.rows(TableIndex::InterfaceImpl, |row| {
+                Ok(InterfaceImpl {
+                    class: source.index(row, 0)?,
+                    interface: source.coded(row, 1, TypeDefOrRef::decode)?,
+                })
+            })?,
+            member_ref: source.rows(TableIndex::MemberRef, |row| {
+                Ok(MemberRef {
+                    parent: source.coded(row, 0, MemberRefParent::decode)?,
+                    name: source.string(row, 1)?,
+                    signature: source.blob(row, 2)?,
+                })
+            })?,
+            constant: source.rows(TableIndex::Constant, |row| {
+                Ok(Constant {
+                    element_type: reader.u32(row, 0) as u8,
+                    parent: source.coded(row, 1, HasConstant::decode)?,
+                    value: source.blob(row, 2)?,
+                })
+            })?,
+            custom_attribute: source.rows(TableIndex::CustomAttribute, |row| {
+                Ok(CustomAttribute {
+                    parent: source.coded(row, 0, HasAttribute::decode)?,
+                    constructor: source.coded(row, 1, AttributeType::decode)?,
+                    value: source.blob(row, 2)?,
+                })
+            })?,
+            class_layout: source.rows(TableIndex::ClassLayout, |row| {
+                Ok(ClassLayout {
+                    packing_size: reader.u32(row, 0) as u16,
+                    class_size: reader.u32(row, 1),
+                    parent: source.index(row, 2)?,
+                })
+            })?,
+            field_layout: source.rows(TableIndex::FieldLayout, |row| {
+                Ok(FieldLayout {
+                    offset: reader.u32(row, 0),
+                    field: source.index(row, 1)?,
+                })
+            })?,
+            event_map: source.rows(TableIndex::EventMap, |row| {
+                Ok(EventMap {
+                    parent: source.index(row, 0)?,
+                    event_list: source.index(row, 1)?,
+                })
+            })?,
+            event: source.rows(TableIndex::Event, |row| {
+                Ok(Event {
+                    flags: reader.u32(row, 0) as u16,
+                    name: source.string(row, 1)?,
+                    event_type: source.coded(row, 2, TypeDefOrRef::decode)?,
+                })
+            })?,
+            property_map: source.rows(TableIndex::PropertyMap, |row| {
+                Ok(PropertyMap {
+                    parent: source.index(row, 0)?,
+                    property_list: source.index(row, 1)?,
+                })
+            })?,
+            property: source.rows(TableIndex::Property, |row| {
+                Ok(Property {
+                    flags: reader.u32(row, 0) as u16,
+                    name: source.string(row, 1)?,
+                    signature: source.blob(row, 2)?,
+                })
+            })?,
+            method_semantics: source.rows(TableIndex::MethodSemantics, |row| {
+                Ok(MethodSemantics {
+                    semantics: reader.u32(row, 0) as u16,
+                    method: source.index(row, 1)?,
+                    association: source.coded(row, 2, HasSemantics::decode)?,
+                })
+            })?,
+            module_ref: source.rows(TableIndex::ModuleRef, |row| {
+                Ok(ModuleRef {
+                    name: source.string(row, 0)?,
+                })
+            })?,
+            type_spec: source.rows(TableIndex::TypeSpec, |row| {
+                Ok(TypeSpec {
+                    signature: source.blob(row, 0)?,
+                })
+            })?,
+            impl_map: source.rows(TableIndex::ImplMap, |row| {
+                Ok(ImplMap {
+                    flags: reader.u32(row, 0) as u16,
+                    member: source.coded(row, 1, MemberForwarded::decode)?,
+                    import_name: source.string(row, 2)?,
+                    import_scope: source.index(row, 3)?,
+                })
+            })?,
+            assembly: source.rows(TableIndex::Assembly, |row| {
+                Ok(Assembly {
+                    hash_algorithm: reader.u32(row, 0),
+                    version: Version::from_u64(reader.u64(row, 1)),
+                    flags: reader.u32(row, 2),
+                    public_key: source.blob(row, 3)?,
+                    name: source.string(row, 4)?,
+                    culture: source.string(row, 5)?,
+                })
+            })?,
+            assembly_ref: source.rows(TableIndex::AssemblyRef, |row| {
+                Ok(AssemblyRef {
+                    version: Version::from_u64(reader.u64(row, 0)),
+                    flags: reader.u32(row, 1),
+                    public_key_or_token: source.blob(row, 2)?,
+                    name: source.string(row, 3)?,
+                    culture: source.string(row, 4)?,
+                    hash_value: source.blob(row, 5)?,
+                })
+            })?,
+            nested_class: source.rows(TableIndex::NestedClass, |row| {
+                Ok(NestedClass {
+                    nested: source.index(row, 0)?,
+                    enclosing: source.index(row, 1)?,
+                })
+            })?,
+            generic_param: source.rows(TableIndex::GenericParam, |row| {
+                Ok(GenericParam {
+                    number: reader.u32(row, 0) as u16,
+                    flags: reader.u32(row, 1) as u16,
+                    owner: source.coded(row, 2, TypeOrMethodDef::decode)?,
+                    name: source.string(row, 3)?,
+                })
+            })?,
+            generic_param_constraint: source.rows(TableIndex::GenericParamConstraint, |row| {
+                Ok(GenericParamConstraint {
+                    owner: source.index(row, 0)?,
+                    constraint: source.coded(row, 1, TypeDefOrRef::decode)?,
+                })
+            })?,
+        })
+    }
+}
+
+/// The file whose rows are being copied
+struct Source<'a> {
+    reader: &'a TypeReader,
+    file: u16,
+}
+
+impl<'a> Source<'a> {
+    fn rows<T, F: FnMut(Row) -> Result<T, Error>>(
+        &self,
+        table: TableIndex,
+        read: F,
+    ) -> Result<Vec<T>, Error> {
+        let row_count = self.reader.files[self.file as usize].tables[table as usize].row_count;
+
+        (0..row_count)
+            .map(|row| Row::new(row, table, self.file))
+            .map(read)
+            .collect()
+    }
+
+    fn string(&self, row: Row, column: u32) -> Result<String, Error> {
+        Ok(self.reader.try_str(row, column)?.to_string())
+    }
+
+    fn blob(&self, row: Row, column: u32) -> Result<Vec<u8>, Error> {
+        Ok(self.reader.try_blob_bytes(row, column)?.to_vec())
+    }
+
+    fn guid(&self, row: Row, column: u32) -> Result<Option<Guid>, Error> {
+        match self.reader.u32(row, column) {
+            0 => Ok(None),
+            index => Ok(Some(self.reader.files[self.file as usize].try_guid(index)?)),
+        }
+    }
+
+    /// Reads a one-based index as a zero-based index
+    fn index(&self, row: Row, column: u32) -> Result<u32, Error> {
+        self.reader.u32(row, column).checked_sub(1).ok_or_else(|| {
+            self.reader
+                .row_error(row, format!("column {} is null", column))
+        })
+    }
+
+    fn optional<T>(
+        &self,
+        row: Row,
+        column: u32,
+        decode: fn(u32) -> Option<T>,
+    ) -> Result<Option<T>, Error> {
+        match self.reader.u32(row, column) {
+            0 => Ok(None),
+            code => decode(code).map(Some).ok_or_else(|| {
+                self.reader.row_error(
+                    row,
+                    format!(
+                        "column {} has an unsupported coded index 0x{:x}",
+                        column, code
+                    ),
+                )
+            }),
+        }
+    }
+
+    fn coded<T>(&self, row: Row, column: u32, decode: fn(u32) -> Option<T>) -> Result<T, Error> {
+        self.optional(row, column, decode)?.ok_or_else(|| {
+            self.reader
+                .row_error(row, format!("column {} is null", column))
+        })
+    }
+}
@@ -0,0 +1,99 @@
+extern crate windows_winmd as winmd;
+
+fn reader() -> winmd::TypeReader {
+    let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("../../.windows/winmd/TestComponent.winmd");
+
+    winmd::TypeReader::try_from_paths(vec![path]).unwrap()
+}
+
+#[test]
+fn module() {
+    let reader = reader();
+    let modules: Vec<winmd::Module> = reader.modules().collect();
+    assert!(modules.len() == 1);
+
+    let module = modules[0];
+    assert!(module.name() == "TestComponent.winmd");
+    assert!(module.generation() == 0);
+    assert!(module.mvid() != winmd::Guid::default());
+    assert!(module.mvid() == module.file().guid(1));
+}
+
+#[test]
+fn assembly() {
+    let reader = reader();
+    let module = reader.modules().next().unwrap();
+    let assembly = module.assembly().unwrap();
+
+    assert!(assembly.name() == "TestComponent");
+    assert!(assembly.culture().is_empty());
+    assert!(assembly.version().to_string() == "255.255.255.255");
+    assert!(assembly.flags().windows_runtime());
+}
+
+#[test]
+fn assembly_refs() {
+    let reader = reader();
+    let module = reader.modules().next().unwrap();
+    let refs: Vec<winmd::AssemblyRef> = module.assembly_refs().collect();
+
+    let mscorlib = refs.iter().find(|r| r.name() == "mscorlib").unwrap();
+    assert!(!mscorlib.flags().public_key());
+    assert!(mscorlib.public_key_or_token() == [0xb7, 0x7a, 0x5c, 0x56, 0x19, 0x34, 0xe0, 0x89]);
+
+    let foundation = refs
+        .iter()
+        .find(|r| r.name() == "Windows.Foundation.FoundationContract")
+        .unwrap();
+    assert!(foundation.flags().windows_runtime());
+}
+
+#[test]
+fn resolution_scope() {
+    let reader = reader();
+    let def = reader.expect_type_def(("TestComponent", "Composable"));
+
+    let type_ref = match def.extends() {
+        winmd::TypeDefOrRef::TypeRef(type_ref) => type_ref,
+        _ => panic!("expected a TypeRef"),
+    };
+
+    assert!(type_ref.name() == ("System", "Object"));
+    assert!(type_ref.enclosing_type().is_none());
+
+    match type_ref.scope() {
+        Some(winmd::ResolutionScope::AssemblyRef(scope)) => assert!(scope.name() == "mscorlib"),
+        scope => panic!("unexpected scope {:?}", scope),
+    }
+}
+
+#[test]
+fn heaps() {
+    let reader = reader();
+    let file = &reader.files()[0];
+
+    assert!(file.try_guid(0).is_err());
+    assert!(file.try_guid(1000).is_err());
+    assert!(file.user_string(0).is_empty());
+    assert!(file.try_user_string(u32::MAX).is_err());
+}
+
+#[test]
+fn display() {
+    let guid = winmd::Guid::from_bytes([
+        0x8f, 0xe9, 0xef, 0x90, 0x4a, 0x59, 0x60, 0x46, 0xa2, 0xa7, 0xec, 0x7d, 0x14, 0x87, 0xf6,
+        0x09,
+    ]);
+
+    assert!(guid.to_string() == "90efe98f-594a-4660-a2a7-ec7d1487f609");
+
+    let version = winmd::Version {
+        major: 1,
+        minor: 2,
+        build: 3,
+        revision: 4,
+    };
+
+    assert!(version.to_string() == "1.2.3.4");
+}
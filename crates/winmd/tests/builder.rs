@@ -0,0 +1,103 @@
+extern crate windows_winmd as winmd;
+
+fn path() -> std::path::PathBuf {
+    let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("../../.windows/winmd/TestComponent.winmd");
+    path
+}
+
+#[test]
+fn explicit_path() {
+    let reader = winmd::TypeReader::builder()
+        .path(path())
+        .exclude_default(true)
+        .build()
+        .unwrap();
+
+    assert!(reader.files().len() == 1);
+    assert!(reader
+        .namespaces()
+        .any(|namespace| namespace == "TestComponent"));
+
+    let def = reader.expect_type_def(("TestComponent", "Composable"));
+    assert!(def.name() == ("TestComponent", "Composable"));
+}
+
+#[test]
+fn in_memory() {
+    let bytes = std::fs::read(path()).unwrap();
+
+    let reader = winmd::TypeReader::builder()
+        .bytes("TestComponent.winmd", bytes)
+        .exclude_default(true)
+        .build()
+        .unwrap();
+
+    assert!(reader.files()[0].name() == "TestComponent.winmd");
+    assert!(reader
+        .find_type_def(("TestComponent", "Composable"))
+        .is_some());
+}
+
+#[test]
+fn side_by_side() {
+    let first = winmd::TypeReader::builder()
+        .path(path())
+        .exclude_default(true)
+        .build()
+        .unwrap();
+
+    let second = winmd::TypeReader::builder()
+        .bytes("TestComponent.winmd", std::fs::read(path()).unwrap())
+        .exclude_default(true)
+        .build()
+        .unwrap();
+
+    let first = first.expect_type_def(("TestComponent", "Composable"));
+    let second = second.expect_type_def(("TestComponent", "Composable"));
+
+    assert!(first.row == second.row);
+    assert!(first.methods().count() == second.methods().count());
+}
+
+#[test]
+fn empty() {
+    let reader = winmd::TypeReader::builder()
+        .exclude_default(true)
+        .build()
+        .unwrap();
+
+    assert!(reader.files().is_empty());
+    assert!(reader.namespaces().next().is_none());
+}
+
+#[test]
+fn invalid_buffer() {
+    let result = winmd::TypeReader::builder()
+        .bytes("invalid.winmd", vec![0; 16])
+        .exclude_default(true)
+        .build();
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn memory_mapped() {
+    let mapped = winmd::TypeReader::builder()
+        .path(path())
+        .memory_map(true)
+        .exclude_default(true)
+        .build()
+        .unwrap();
+
+    let read = winmd::TypeReader::builder()
+        .path(path())
+        .exclude_default(true)
+        .build()
+        .unwrap();
+
+    assert!(mapped.namespaces().eq(read.namespaces()));
+    assert!(mapped
+        .namespace_types("TestComponent")
+        .eq(read.namespace_types("TestComponent")));
+}
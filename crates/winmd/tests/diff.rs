@@ -0,0 +1,157 @@
+extern crate windows_winmd as winmd;
+
+use winmd::diff::{compare, Change, ChangeKind};
+
+const OLD: &str = r#"
+namespace Test
+{
+    enum Color { Red, Green, Blue };
+
+    struct Point { Int32 X; Int32 Y; };
+
+    [uuid(11111111-2222-3333-4444-555555555555)]
+    interface IShape
+    {
+        Double Area();
+        void Move(Int32 dx, Int32 dy);
+        String Name();
+    }
+
+    interface IGone
+    {
+        void Stay();
+    }
+
+    runtimeclass Circle : IShape, IGone
+    {
+        Circle();
+        void Draw(Int32 value);
+    }
+}
+"#;
+
+const NEW: &str = r#"
+namespace Test
+{
+    enum Color { Red, Green, Blue, Yellow };
+
+    struct Point { Int32 X; Int64 Y; Int32 Z; };
+
+    [uuid(11111111-2222-3333-4444-666666666666)]
+    interface IShape
+    {
+        Double Area();
+        String Name();
+        void Move(Int32 deltaX, Int32 DY);
+        void Scale(Double factor);
+    }
+
+    interface IAdded
+    {
+    }
+
+    runtimeclass Circle : IShape
+    {
+        Circle();
+        void Draw(Int32 value);
+    }
+}
+"#;
+
+fn reader(source: &str) -> winmd::TypeReader {
+    winmd::TypeReader::builder()
+        .midl("Test.idl", source)
+        .exclude_default(true)
+        .build()
+        .unwrap()
+}
+
+fn changes(old: &str, new: &str) -> Vec<Change> {
+    compare(&reader(old), &reader(new)).unwrap()
+}
+
+#[test]
+fn unchanged() {
+    assert!(changes(OLD, OLD).is_empty());
+}
+
+#[test]
+fn report() {
+    let report: Vec<String> = changes(OLD, NEW).iter().map(ToString::to_string).collect();
+
+    assert_eq!(
+        report,
+        [
+            "breaking: interface removed `Test.Circle.Test.IGone`",
+            "compatible: enum value added `Test.Color.Yellow`: 3",
+            "compatible: type added `Test.IAdded`: Interface",
+            "breaking: type removed `Test.IGone`: Interface",
+            "breaking: guid changed `Test.IShape`: 11111111-2222-3333-4444-555555555555 -> 11111111-2222-3333-4444-666666666666",
+            "breaking: parameter renamed `Test.IShape.Move`: dx -> deltaX",
+            "compatible: parameter renamed `Test.IShape.Move`: dy -> DY",
+            "breaking: method added `Test.IShape.Scale`: (in factor: Double) -> Void",
+            "breaking: methods reordered `Test.IShape`: Area, Move, Name -> Area, Name, Move",
+            "breaking: field type changed `Test.Point.Y`: Int32 -> Int64",
+            "breaking: field added `Test.Point.Z`: Int32",
+        ]
+    );
+}
+
+#[test]
+fn classes() {
+    let old = "namespace Test { runtimeclass Widget { Widget(); void Draw(Int32 value); } }";
+    let new = "namespace Test { runtimeclass Widget { Widget(); void Draw(Int32 value); void Paint(); } }";
+
+    let changes = changes(old, new);
+
+    let widget: Vec<&Change> = changes
+        .iter()
+        .filter(|change| change.type_name == "Test.Widget")
+        .collect();
+
+    assert_eq!(widget.len(), 1);
+    assert_eq!(widget[0].kind, ChangeKind::MethodAdded);
+    assert_eq!(widget[0].member.as_deref(), Some("Paint"));
+    assert!(!widget[0].breaking);
+
+    // The synthesized default interface gains the method too, which breaks its vtable.
+    let interface: Vec<&Change> = changes
+        .iter()
+        .filter(|change| change.type_name == "Test.IWidget")
+        .collect();
+
+    assert_eq!(interface.len(), 1);
+    assert_eq!(interface[0].kind, ChangeKind::MethodAdded);
+    assert!(interface[0].breaking);
+}
+
+#[test]
+fn signatures() {
+    let old = "namespace Test { interface IFoo { Int32 Get(Int32 a); void Set(Int32 a); void Put(Int32 a); } }";
+    let new = "namespace Test { interface IFoo { Int64 Get(Int32 a); void Set(Int32 a, Int32 b); void Put(out Int32 a); } }";
+
+    let report: Vec<String> = changes(old, new).iter().map(ToString::to_string).collect();
+
+    assert_eq!(
+        report,
+        [
+            "breaking: return type changed `Test.IFoo.Get`: Int32 -> Int64",
+            "breaking: parameters changed `Test.IFoo.Set`: (in a: Int32) -> Void -> (in a: Int32, in b: Int32) -> Void",
+            "breaking: parameter type changed `Test.IFoo.Put`: in a: Int32 -> out a: ref Int32",
+        ]
+    );
+}
+
+#[test]
+fn categories() {
+    let changes = changes(
+        "namespace Test { struct Thing { Int32 A; }; }",
+        "namespace Test { enum Thing { A }; }",
+    );
+
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].kind, ChangeKind::CategoryChanged);
+    assert_eq!(changes[0].old.as_deref(), Some("Struct"));
+    assert_eq!(changes[0].new.as_deref(), Some("Enum"));
+    assert!(changes[0].breaking);
+}
@@ -0,0 +1,68 @@
+extern crate windows_winmd as winmd;
+
+fn path() -> std::path::PathBuf {
+    let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("../../.windows/winmd/TestComponent.winmd");
+    path
+}
+
+fn bytes() -> Vec<u8> {
+    std::fs::read(path()).unwrap()
+}
+
+#[test]
+fn no_duplicates() {
+    let reader = winmd::TypeReader::builder()
+        .path(path())
+        .exclude_default(true)
+        .build()
+        .unwrap();
+
+    assert!(reader.duplicate_types().next().is_none());
+
+    let def = reader.expect_type_def(("TestComponent", "Composable"));
+    assert!(def.file().name() == path().to_string_lossy());
+}
+
+#[test]
+fn earlier_file_wins() {
+    let reader = winmd::TypeReader::builder()
+        .bytes("first.winmd", bytes())
+        .bytes("second.winmd", bytes())
+        .exclude_default(true)
+        .build()
+        .unwrap();
+
+    let def = reader.expect_type_def(("TestComponent", "Composable"));
+    assert!(def.file().name() == "first.winmd");
+
+    let duplicates: Vec<winmd::DuplicateType> = reader.duplicate_types().collect();
+    assert!(!duplicates.is_empty());
+
+    for duplicate in &duplicates {
+        assert!(duplicate.used.name() == duplicate.ignored.name());
+        assert!(duplicate.used.file().name() == "first.winmd");
+        assert!(duplicate.ignored.file().name() == "second.winmd");
+    }
+
+    assert!(duplicates
+        .iter()
+        .any(|duplicate| duplicate.used.name() == ("TestComponent", "Composable")));
+}
+
+#[test]
+fn paths_before_buffers() {
+    let reader = winmd::TypeReader::builder()
+        .bytes("buffer.winmd", bytes())
+        .path(path())
+        .exclude_default(true)
+        .build()
+        .unwrap();
+
+    let def = reader.expect_type_def(("TestComponent", "Composable"));
+    assert!(def.file().name() == path().to_string_lossy());
+
+    for duplicate in reader.duplicate_types() {
+        assert!(duplicate.ignored.file().name() == "buffer.winmd");
+    }
+}
@@ -0,0 +1,42 @@
+extern crate windows_winmd as winmd;
+
+fn test_component() -> std::path::PathBuf {
+    let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("../../.windows/winmd/TestComponent.winmd");
+    path
+}
+
+#[test]
+fn valid_file() {
+    let reader = winmd::TypeReader::try_from_paths(vec![test_component()]).unwrap();
+    assert!(reader
+        .namespaces()
+        .any(|namespace| namespace == "TestComponent"));
+}
+
+#[test]
+fn missing_file() {
+    let error = match winmd::TypeReader::try_from_paths(vec!["missing.winmd".into()]) {
+        Err(error) => error,
+        Ok(_) => panic!("expected an error"),
+    };
+
+    assert!(matches!(error, winmd::Error::Io { .. }));
+    assert_eq!(error.file(), "missing.winmd");
+}
+
+#[test]
+fn truncated_file() {
+    let bytes = std::fs::read(test_component()).unwrap();
+
+    assert!(winmd::File::try_from_bytes("TestComponent.winmd", bytes.clone()).is_ok());
+
+    for len in &[0, 64, bytes.len() / 2] {
+        let error = match winmd::File::try_from_bytes("truncated.winmd", bytes[..*len].to_vec()) {
+            Err(error) => error,
+            Ok(_) => panic!("expected an error for length {}", len),
+        };
+
+        assert_eq!(error.file(), "truncated.winmd");
+    }
+}
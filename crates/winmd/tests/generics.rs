@@ -0,0 +1,77 @@
+extern crate windows_winmd as winmd;
+
+use winmd::writer::*;
+
+fn reader() -> winmd::TypeReader {
+    let mut writer = Writer::new("Generics.winmd");
+
+    // public interface IShape {}
+    let shape = writer.type_def(0x40a1, "Generics", "IShape", None);
+
+    // public interface IMap<K, out V> where K : IShape {}
+    let map = writer.type_def(0x40a1, "Generics", "IMap`2", None);
+    let key = writer.generic_param(TypeOrMethodDef::TypeDef(map), 0, "K");
+    writer.generic_param(TypeOrMethodDef::TypeDef(map), 0x0001, "V");
+    writer.generic_param_constraint(key, TypeDefOrRef::TypeDef(shape));
+
+    // public interface IFactory<in T> where T : class, IShape, new() {}
+    let factory = writer.type_def(0x40a1, "Generics", "IFactory`1", None);
+    let param = writer.generic_param(TypeOrMethodDef::TypeDef(factory), 0x0016, "T");
+    writer.generic_param_constraint(param, TypeDefOrRef::TypeDef(shape));
+
+    winmd::TypeReader::builder()
+        .bytes("Generics.winmd", writer.into_bytes())
+        .exclude_default(true)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn generic_params() {
+    let reader = reader();
+
+    let shape = reader.expect_type_def(("Generics", "IShape"));
+    assert_eq!(shape.generic_count(), 0);
+
+    let map = reader.expect_type_def(("Generics", "IMap`2"));
+    assert_eq!(map.generic_count(), 2);
+
+    let params: Vec<_> = map.generics().collect();
+    assert_eq!(params[0].name(), "K");
+    assert_eq!(params[0].number(), 0);
+    assert!(!params[0].flags().covariant());
+    assert_eq!(params[1].name(), "V");
+    assert_eq!(params[1].number(), 1);
+    assert!(params[1].flags().covariant());
+    assert!(!params[1].flags().contravariant());
+
+    for param in &params {
+        match param.owner() {
+            winmd::TypeOrMethodDef::TypeDef(owner) => assert_eq!(owner, map),
+            _ => panic!("expected a type owner"),
+        }
+    }
+}
+
+#[test]
+fn constraints() {
+    let reader = reader();
+    let shape = reader.expect_type_def(("Generics", "IShape"));
+
+    let map = reader.expect_type_def(("Generics", "IMap`2"));
+    let params: Vec<_> = map.generics().collect();
+    let constraints: Vec<_> = params[0].constraints().collect();
+    assert_eq!(constraints.len(), 1);
+    assert_eq!(constraints[0].owner(), params[0]);
+    assert_eq!(constraints[0].constraint().name(), shape.name());
+    assert_eq!(params[1].constraints().count(), 0);
+
+    let factory = reader.expect_type_def(("Generics", "IFactory`1"));
+    let param = factory.generics().next().unwrap();
+    let flags = param.flags();
+    assert!(flags.contravariant());
+    assert!(flags.reference_type());
+    assert!(!flags.value_type());
+    assert!(flags.default_constructor());
+    assert_eq!(param.constraints().count(), 1);
+}
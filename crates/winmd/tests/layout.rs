@@ -0,0 +1,66 @@
+extern crate windows_winmd as winmd;
+
+#[test]
+fn sequential_layout() {
+    let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("../../.windows/winmd/TestComponent.winmd");
+
+    let reader = winmd::TypeReader::try_from_paths(vec![path]).unwrap();
+    let def = reader.expect_type_def(("TestComponent", "Blittable"));
+
+    assert!(def.flags().sequential_layout());
+    assert!(!def.flags().explicit_layout());
+    assert!(def.class_layout().is_none());
+    assert!(def.fields().all(|field| field.offset().is_none()));
+}
+
+#[test]
+fn explicit_layout() {
+    use winmd::writer::*;
+
+    let mut writer = Writer::new("Layout.winmd");
+    let value_type = writer.type_ref(None, "System", "ValueType");
+
+    // Public | ExplicitLayout | Sealed
+    let def = writer.type_def(0x111, "Test", "Explicit", Some(value_type));
+    let first = writer.field(0x6, "first", &TypeSig::U8);
+    let second = writer.field(0x6, "second", &TypeSig::I64);
+
+    writer.tables.class_layout.push(ClassLayout {
+        packing_size: 4,
+        class_size: 24,
+        parent: def,
+    });
+
+    writer.tables.field_layout.push(FieldLayout {
+        offset: 12,
+        field: second,
+    });
+
+    writer.tables.field_layout.push(FieldLayout {
+        offset: 0,
+        field: first,
+    });
+
+    let reader = winmd::TypeReader::builder()
+        .bytes("Layout.winmd", writer.into_bytes())
+        .exclude_default(true)
+        .build()
+        .unwrap();
+
+    let def = reader.expect_type_def(("Test", "Explicit"));
+    assert!(def.flags().explicit_layout());
+    assert!(!def.flags().sequential_layout());
+
+    let layout = def.class_layout().unwrap();
+    assert_eq!(layout.packing_size(), 4);
+    assert_eq!(layout.class_size(), 24);
+    assert_eq!(layout.parent(), def);
+
+    let offsets: Vec<_> = def
+        .fields()
+        .map(|field| (field.name(), field.offset()))
+        .collect();
+
+    assert_eq!(offsets, [("first", Some(0)), ("second", Some(12))]);
+}
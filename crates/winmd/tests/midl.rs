@@ -0,0 +1,305 @@
+extern crate windows_winmd as winmd;
+
+use winmd::writer::*;
+
+/// Compares the output with a golden file, which is rewritten instead when `WINMD_BLESS` is set
+fn assert_golden(name: &str, actual: &str) {
+    let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("tests/midl");
+    path.push(name);
+
+    if std::env::var_os("WINMD_BLESS").is_some() {
+        std::fs::write(&path, actual).unwrap();
+    } else {
+        let expected = std::fs::read_to_string(&path)
+            .unwrap()
+            .replace("\r\n", "\n");
+        assert!(
+            expected == actual,
+            "{} is out of date, run with WINMD_BLESS=1 to update it:\n{}",
+            name,
+            actual
+        );
+    }
+}
+
+#[test]
+fn test_component() {
+    let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("../../.windows/winmd/TestComponent.winmd");
+
+    let reader = winmd::TypeReader::builder()
+        .paths(vec![path])
+        .exclude_default(true)
+        .build()
+        .unwrap();
+
+    assert_golden(
+        "TestComponent.idl",
+        &winmd::midl::namespace(&reader, "TestComponent"),
+    );
+
+    let def = reader.expect_type_def(("TestComponent", "Blittable"));
+    let text = winmd::midl::type_def(&def);
+    assert!(text.starts_with("[version(1)]\nstruct Blittable\n{\n    UInt8 A;\n"));
+    assert!(text.ends_with("};\n"));
+}
+
+#[test]
+fn enums() {
+    let mut writer = Writer::new("Enums.winmd");
+    let system_enum = writer.type_ref(None, "System", "Enum");
+    let flags = match writer.type_ref(None, "System", "FlagsAttribute") {
+        TypeDefOrRef::TypeRef(flags) => flags,
+        _ => unreachable!(),
+    };
+
+    let constructor = writer.member_ref(
+        MemberRefParent::TypeRef(flags),
+        ".ctor",
+        &MethodSig::new(TypeSig::Void, Vec::new()),
+    );
+
+    let values: [(&str, &[(&str, i32)]); 2] = [
+        ("Color", &[("Red", 0), ("Green", 1), ("Blue", 2)]),
+        ("Options", &[("None", 0), ("Fast", 1), ("Safe", 2)]),
+    ];
+
+    for (name, members) in values.iter() {
+        let def = writer.type_def(0x101, "Enums", name, Some(system_enum));
+        writer.field(0x606, "value__", &TypeSig::I32);
+
+        for (member, value) in members.iter() {
+            let field = writer.field(0x8056, member, &TypeSig::I32);
+            writer.constant(HasConstant::Field(field), &ConstantValue::I32(*value));
+        }
+
+        if *name == "Options" {
+            writer.attribute(
+                HasAttribute::TypeDef(def),
+                AttributeType::MemberRef(constructor),
+                &[],
+                &[],
+            );
+        }
+    }
+
+    let reader = winmd::TypeReader::builder()
+        .bytes("Enums.winmd", writer.into_bytes())
+        .exclude_default(true)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        winmd::midl::namespace(&reader, "Enums"),
+        "namespace Enums
+{
+    enum Color
+    {
+        Red = 0,
+        Green = 1,
+        Blue = 2,
+    };
+
+    [flags]
+    enum Options
+    {
+        None = 0,
+        Fast = 1,
+        Safe = 2,
+    };
+}
+"
+    );
+}
+
+const COMPONENT: &str = r#"
+import "Windows.Foundation.idl";
+
+namespace Test.Component
+{
+    [flags]
+    enum Options { None = 0, Fast = 0x1, Safe = 0x2, };
+    enum Color { Red, Green, Blue = 5, Purple };
+
+    struct Point { Int32 X; Int32 Y; Guid Id; };
+
+    attribute NoteAttribute { String Text; Int32 Level; };
+
+    [uuid(cc8647fe-6a28-5ae0-b4de-39ea156b5611)]
+    [version(2)]
+    interface IShape requires Windows.Foundation.IStringable
+    {
+        Double Area{ get; };
+        String Name;
+        [method_name("MoveBy")] void Move(Point offset, out Point result);
+        Int32[] Fill(ref Int32[] values, out Int32[] copy);
+        event Windows.Foundation.TypedEventHandler<IShape, Object> Changed;
+    }
+
+    interface IMap<K, V> requires IShape
+    {
+        V Lookup(K key);
+    }
+
+    delegate Boolean Filter<T>(T value);
+    delegate void Handler(IShape sender, Int32 value);
+
+    [Note("hello", 3)]
+    [version(1)]
+    runtimeclass Circle : IShape
+    {
+        Circle();
+        Circle(Double radius);
+        Double Radius;
+        static Circle Unit{ get; };
+        static event Handler Created;
+        Windows.Foundation.Collections.IVector<Point> Points();
+        IMap<String, Color> Colors();
+    }
+
+    unsealed runtimeclass Base
+    {
+        Base();
+    }
+
+    runtimeclass Derived : Base, IShape
+    {
+    }
+
+    static runtimeclass Helpers
+    {
+        static void Help();
+    }
+
+    apicontract Contract {};
+}
+"#;
+
+fn component() -> winmd::TypeReader {
+    winmd::TypeReader::builder()
+        .midl("Component.idl", COMPONENT)
+        .exclude_default(true)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn compile() {
+    let reader = component();
+
+    assert_golden(
+        "Component.idl",
+        &winmd::midl::namespace(&reader, "Test.Component"),
+    );
+}
+
+#[test]
+fn compile_metadata() {
+    let reader = component();
+
+    let shape = reader.expect_type_def(("Test.Component", "IShape"));
+    assert_eq!(shape.category(), winmd::TypeCategory::Interface);
+    assert_eq!(
+        shape.guid().unwrap().to_string(),
+        "cc8647fe-6a28-5ae0-b4de-39ea156b5611"
+    );
+
+    let map = reader.expect_type_def(("Test.Component", "IMap`2"));
+    assert_eq!(map.generic_count(), 2);
+
+    let circle = reader.expect_type_def(("Test.Component", "Circle"));
+    assert_eq!(circle.category(), winmd::TypeCategory::Class);
+    assert!(circle.flags().sealed());
+
+    let interfaces: Vec<_> = circle
+        .interfaces()
+        .map(|interface| (interface.interface().name(), interface.is_default()))
+        .collect();
+
+    assert_eq!(
+        interfaces,
+        [
+            (("Test.Component", "ICircle"), true),
+            (("Test.Component", "IShape"), false)
+        ]
+    );
+
+    let base = reader.expect_type_def(("Test.Component", "Base"));
+    assert!(!base.flags().sealed());
+
+    let options = reader.expect_type_def(("Test.Component", "Options"));
+    assert_eq!(options.category(), winmd::TypeCategory::Enum);
+    assert!(matches!(options.underlying_type(), winmd::ElementType::U32));
+
+    let statics = reader.expect_type_def(("Test.Component", "ICircleStatics"));
+    let created = statics.events().next().unwrap();
+    assert_eq!(created.name(), "Created");
+    assert_eq!(created.adder().unwrap().name(), "add_Created");
+
+    // GUIDs derived from the name are stable.
+    let handler = reader.expect_type_def(("Test.Component", "Handler"));
+    assert_eq!(
+        handler.guid(),
+        component()
+            .expect_type_def(("Test.Component", "Handler"))
+            .guid()
+    );
+}
+
+fn compile_error(source: &str) -> (u32, u32, String) {
+    match winmd::midl::compile("Error.idl", source) {
+        Err(winmd::Error::InvalidSource {
+            file,
+            line,
+            column,
+            message,
+        }) => {
+            assert_eq!(file, "Error.idl");
+            (line, column, message)
+        }
+        result => panic!("expected an error but got {:?}", result.map(|_| ())),
+    }
+}
+
+#[test]
+fn compile_errors() {
+    assert_eq!(
+        compile_error("namespace Test\n{\n    struct S { Widget W; };\n}"),
+        (3, 16, "unknown type `Widget`".to_string())
+    );
+
+    assert_eq!(
+        compile_error("namespace Test\n{\n    interface I { void M() }\n}"),
+        (3, 28, "expected `;` but found `}`".to_string())
+    );
+
+    assert_eq!(
+        compile_error("namespace Test { struct S {}; enum S { A }; }"),
+        (1, 31, "`Test.S` is declared more than once".to_string())
+    );
+
+    assert_eq!(
+        compile_error("struct S {};"),
+        (1, 1, "`S` must be declared within a namespace".to_string())
+    );
+
+    assert_eq!(
+        compile_error("namespace Test { [frobnicate] struct S {}; }"),
+        (1, 19, "unsupported attribute `frobnicate`".to_string())
+    );
+
+    let error = match winmd::TypeReader::builder()
+        .midl("Error.idl", "namespace Test { interface I requires; }")
+        .exclude_default(true)
+        .build()
+    {
+        Err(error) => error,
+        Ok(_) => panic!("expected an error"),
+    };
+
+    assert_eq!(
+        error.to_string(),
+        "Invalid source `Error.idl`:1:38: expected a name but found `;`"
+    );
+}
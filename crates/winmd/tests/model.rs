@@ -0,0 +1,161 @@
+extern crate windows_winmd as winmd;
+
+use winmd::model::*;
+
+const SOURCE: &str = r#"
+namespace Test
+{
+    enum Color { Red, Green = 5 };
+
+    struct Point { Int32 X; Int32 Y; };
+
+    [uuid(11111111-2222-3333-4444-555555555555)]
+    interface IShape
+    {
+        Point Center();
+        void Move(Point[] path, out Int32 moved);
+    }
+}
+"#;
+
+fn metadata() -> Metadata {
+    let reader = winmd::TypeReader::builder()
+        .midl("Test.idl", SOURCE)
+        .exclude_default(true)
+        .build()
+        .unwrap();
+
+    Metadata::new(&reader).unwrap()
+}
+
+fn find<'a>(metadata: &'a Metadata, name: &str) -> &'a TypeDefinition {
+    metadata
+        .namespaces
+        .iter()
+        .find(|namespace| namespace.name == "Test")
+        .unwrap()
+        .types
+        .iter()
+        .find(|def| def.name == name)
+        .unwrap()
+}
+
+fn point() -> Type {
+    Type::Named {
+        namespace: "Test".to_string(),
+        name: "Point".to_string(),
+        value_type: true,
+    }
+}
+
+#[test]
+fn types() {
+    let metadata = metadata();
+    assert_eq!(metadata.version, SCHEMA_VERSION);
+
+    let color = find(&metadata, "Color");
+    assert_eq!(color.category, winmd::TypeCategory::Enum);
+
+    let values: Vec<(&str, Option<&Value>)> = color
+        .fields
+        .iter()
+        .map(|field| (field.name.as_str(), field.constant.as_ref()))
+        .collect();
+
+    assert_eq!(
+        values,
+        [
+            ("value__", None),
+            ("Red", Some(&Value::I32(0))),
+            ("Green", Some(&Value::I32(5)))
+        ]
+    );
+
+    let point = find(&metadata, "Point");
+    assert_eq!(point.category, winmd::TypeCategory::Struct);
+    assert_eq!(point.fields[1].name, "Y");
+    assert_eq!(point.fields[1].ty, Type::I32);
+}
+
+#[test]
+fn methods() {
+    let metadata = metadata();
+    let shape = find(&metadata, "IShape");
+
+    assert_eq!(
+        shape.guid.as_deref(),
+        Some("11111111-2222-3333-4444-555555555555")
+    );
+    assert_eq!(shape.extends, None);
+
+    let guid = &shape.attributes[0];
+    assert_eq!(guid.ty.name, "GuidAttribute");
+    assert_eq!(
+        guid.args.as_ref().unwrap()[0].value,
+        Value::U32(0x1111_1111)
+    );
+
+    assert_eq!(shape.methods[0].name, "Center");
+    assert_eq!(shape.methods[0].return_type, point());
+
+    let params = &shape.methods[1].params;
+    assert_eq!(params.len(), 2);
+    assert_eq!(params[0].name, "path");
+    assert_eq!(
+        params[0].ty,
+        Type::Array {
+            element: Box::new(point())
+        }
+    );
+    assert_eq!(params[1].name, "moved");
+    assert_eq!(
+        params[1].ty,
+        Type::Reference {
+            element: Box::new(Type::I32)
+        }
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn json() {
+    let metadata = metadata();
+    let json = serde_json::to_value(&metadata).unwrap();
+
+    assert_eq!(json["version"], SCHEMA_VERSION);
+
+    let test = json["namespaces"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|namespace| namespace["name"] == "Test")
+        .unwrap();
+
+    let point = test["types"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|def| def["name"] == "Point")
+        .unwrap();
+
+    assert_eq!(
+        point.to_string(),
+        r#"{"attributes":[],"category":"struct","extends":{"kind":"named","name":"ValueType","namespace":"System","value_type":false},"fields":[{"attributes":[],"constant":null,"flags":6,"name":"X","ty":{"kind":"i32"}},{"attributes":[],"constant":null,"flags":6,"name":"Y","ty":{"kind":"i32"}}],"flags":16649,"generics":[],"guid":null,"interfaces":[],"methods":[],"name":"Point","nested_types":[]}"#
+    );
+
+    let color = test["types"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|def| def["name"] == "Color")
+        .unwrap();
+
+    assert_eq!(
+        color["fields"][2]["constant"].to_string(),
+        r#"{"kind":"i32","value":5}"#
+    );
+
+    // The model reads back unchanged.
+    let text = serde_json::to_string(&metadata).unwrap();
+    assert_eq!(serde_json::from_str::<Metadata>(&text).unwrap(), metadata);
+}
@@ -0,0 +1,81 @@
+extern crate windows_winmd as winmd;
+
+fn reader() -> winmd::TypeReader {
+    let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("../../.windows/winmd/TestComponent.winmd");
+
+    winmd::TypeReader::try_from_paths(vec![path]).unwrap()
+}
+
+#[test]
+fn top_level_types() {
+    let reader = reader();
+
+    for t in reader.namespace_types("TestComponent") {
+        if let winmd::Type::TypeDef(def) = t {
+            assert!(!def.flags().nested());
+            assert!(def.enclosing_type().is_none());
+            assert!(def.nested_types().next().is_none());
+        }
+    }
+}
+
+#[test]
+fn nested_struct() {
+    use winmd::writer::*;
+
+    let mut writer = Writer::new("Nested.winmd");
+    let value_type = writer.type_ref(None, "System", "ValueType");
+    let outer_ref = match writer.type_ref(Some(ResolutionScope::Module(0)), "Test", "Outer") {
+        TypeDefOrRef::TypeRef(index) => index,
+        _ => unreachable!(),
+    };
+    let inner_ref = writer.type_ref(Some(ResolutionScope::TypeRef(outer_ref)), "", "Inner");
+
+    // Public | SequentialLayout | Sealed
+    let outer = writer.type_def(0x109, "Test", "Outer", Some(value_type));
+    writer.field(0x6, "inner", &TypeSig::ValueType(inner_ref));
+
+    // NestedPublic | SequentialLayout | Sealed
+    let inner = writer.type_def(0x10a, "", "Inner", Some(value_type));
+    writer.field(0x6, "value", &TypeSig::I32);
+
+    writer.tables.nested_class.push(NestedClass {
+        nested: inner,
+        enclosing: outer,
+    });
+
+    let reader = winmd::TypeReader::builder()
+        .bytes("Nested.winmd", writer.into_bytes())
+        .exclude_default(true)
+        .build()
+        .unwrap();
+
+    let outer = reader.expect_type_def(("Test", "Outer"));
+    assert!(!outer.flags().nested());
+    assert!(outer.enclosing_type().is_none());
+
+    let nested: Vec<_> = outer.nested_types().collect();
+    assert_eq!(nested.len(), 1);
+
+    let inner = nested[0];
+    assert_eq!(inner.name(), ("", "Inner"));
+    assert!(inner.flags().nested());
+    assert_eq!(inner.enclosing_type(), Some(outer));
+    assert!(inner.nested_types().next().is_none());
+
+    let row_count = reader.files()[0].row_count(winmd::TableIndex::TypeRef);
+    let inner_ref = (0..row_count)
+        .map(|row| winmd::TypeRef {
+            reader: &reader,
+            row: winmd::Row::new(row, winmd::TableIndex::TypeRef, 0),
+        })
+        .find(|type_ref| type_ref.name() == ("", "Inner"))
+        .unwrap();
+
+    assert_eq!(
+        inner_ref.enclosing_type().unwrap().name(),
+        ("Test", "Outer")
+    );
+    assert_eq!(inner_ref.resolve(), inner);
+}
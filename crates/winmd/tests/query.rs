@@ -0,0 +1,220 @@
+extern crate windows_winmd as winmd;
+
+use winmd::writer::*;
+
+const METADATA: &str = "Windows.Foundation.Metadata";
+
+const SOURCE: &str = r#"
+namespace Test
+{
+    enum Color { Red, Green };
+
+    struct Point { Int32 X; Int32 Y; };
+
+    struct Line { Point Start; Point End; };
+
+    [experimental]
+    interface IShape
+    {
+        Point Center();
+        void Move(Point[] path);
+        Int32 Sides();
+    }
+
+    runtimeclass Circle
+    {
+        Circle();
+        [experimental] void Draw(Int32 value);
+        void Fill(Color color);
+    }
+}
+"#;
+
+/// A namespace of functions and constants imported from two modules
+fn apis() -> Vec<u8> {
+    let mut writer = Writer::new("Apis.winmd");
+    let object = writer.type_ref(None, "System", "Object");
+    let apis = writer.type_def(0x0181, "Test.Native", "Apis", Some(object));
+
+    writer.field(0x8056, "MAX_PATH", &TypeSig::U32);
+    writer.field(0x0016, "Instance", &TypeSig::ISize);
+
+    let device = writer.method_def(
+        0x2096,
+        "D3D12CreateDevice",
+        &MethodSig::new(TypeSig::I32, vec![TypeSig::ISize]),
+    );
+
+    let handle = writer.method_def(
+        0x2096,
+        "CloseHandle",
+        &MethodSig::new(TypeSig::Bool, vec![TypeSig::ISize]),
+    );
+
+    writer.tables.module_ref.push(ModuleRef {
+        name: "d3d12".to_string(),
+    });
+
+    writer.tables.module_ref.push(ModuleRef {
+        name: "KERNEL32.dll".to_string(),
+    });
+
+    writer.tables.impl_map.push(ImplMap {
+        flags: 0x0100,
+        member: MemberForwarded::MethodDef(handle),
+        import_name: "CloseHandle".to_string(),
+        import_scope: 1,
+    });
+
+    writer.tables.impl_map.push(ImplMap {
+        flags: 0x0100,
+        member: MemberForwarded::MethodDef(device),
+        import_name: "D3D12CreateDevice".to_string(),
+        import_scope: 0,
+    });
+
+    writer.tables.impl_map.push(ImplMap {
+        flags: 0x0100,
+        member: MemberForwarded::Field(1),
+        import_name: "Instance".to_string(),
+        import_scope: 1,
+    });
+
+    assert_eq!(apis, 1);
+    writer.into_bytes()
+}
+
+fn reader() -> winmd::TypeReader {
+    winmd::TypeReader::builder()
+        .bytes("Apis.winmd", apis())
+        .midl("Test.idl", SOURCE)
+        .exclude_default(true)
+        .build()
+        .unwrap()
+}
+
+fn type_names<'a, I: Iterator<Item = winmd::TypeDef<'a>>>(types: I) -> Vec<String> {
+    types
+        .map(|def| format!("{}.{}", def.name().0, def.name().1))
+        .collect()
+}
+
+fn method_names<'a, I: Iterator<Item = winmd::MethodDef<'a>>>(methods: I) -> Vec<String> {
+    methods
+        .map(|method| format!("{}.{}", method.parent().name().1, method.name()))
+        .collect()
+}
+
+fn field_names<'a, I: Iterator<Item = winmd::Field<'a>>>(fields: I) -> Vec<String> {
+    fields
+        .map(|field| format!("{}.{}", field.parent().name().1, field.name()))
+        .collect()
+}
+
+#[test]
+fn attributes() {
+    let reader = reader();
+    let query = reader
+        .query()
+        .attribute((METADATA, "ExperimentalAttribute"));
+
+    assert_eq!(type_names(query.types()), ["Test.IShape"]);
+    // Attributes on a class's members are carried by its default interface.
+    assert_eq!(method_names(query.methods()), ["ICircle.Draw"]);
+    assert!(query.fields().next().is_none());
+
+    // Every attribute must be present.
+    assert!(query
+        .clone()
+        .attribute(("System", "FlagsAttribute"))
+        .types()
+        .next()
+        .is_none());
+}
+
+#[test]
+fn modules() {
+    let reader = reader();
+
+    for module in &["d3d12.dll", "D3D12", "d3d12"] {
+        assert_eq!(
+            method_names(reader.query().module(module).methods()),
+            ["Apis.D3D12CreateDevice"]
+        );
+    }
+
+    let kernel32 = reader.query().module("kernel32");
+    assert_eq!(method_names(kernel32.methods()), ["Apis.CloseHandle"]);
+    assert_eq!(field_names(kernel32.fields()), ["Apis.Instance"]);
+    assert_eq!(type_names(kernel32.types()), ["Test.Native.Apis"]);
+
+    assert!(reader.query().module("user32").types().next().is_none());
+}
+
+#[test]
+fn references() {
+    let reader = reader();
+    let point = reader.query().references(("Test", "Point"));
+
+    assert_eq!(
+        method_names(point.methods()),
+        ["IShape.Center", "IShape.Move"]
+    );
+    assert_eq!(field_names(point.fields()), ["Line.Start", "Line.End"]);
+    assert_eq!(type_names(point.types()), ["Test.IShape", "Test.Line"]);
+
+    // Both the class and its default interface declare `Fill`.
+    let color = reader.query().references(("Test", "Color"));
+    assert_eq!(type_names(color.types()), ["Test.Circle", "Test.ICircle"]);
+}
+
+#[test]
+fn categories_and_flags() {
+    let reader = reader();
+
+    assert_eq!(
+        type_names(reader.query().category(winmd::TypeCategory::Struct).types()),
+        ["Test.Line", "Test.Point"]
+    );
+
+    // Methods and fields are only considered if their type is selected.
+    let interfaces = reader
+        .query()
+        .namespace("Test")
+        .category(winmd::TypeCategory::Interface);
+
+    assert_eq!(
+        method_names(interfaces.methods()),
+        [
+            "ICircle.Draw",
+            "ICircle.Fill",
+            "IShape.Center",
+            "IShape.Move",
+            "IShape.Sides"
+        ]
+    );
+
+    // Literal fields are constants.
+    assert_eq!(
+        field_names(
+            reader
+                .query()
+                .namespace("Test.Native")
+                .field_flags(0x40)
+                .fields()
+        ),
+        ["Apis.MAX_PATH"]
+    );
+
+    // Sealed types in a namespace.
+    assert_eq!(
+        type_names(
+            reader
+                .query()
+                .namespace("Test.Native")
+                .type_flags(0x100)
+                .types()
+        ),
+        ["Test.Native.Apis"]
+    );
+}
@@ -0,0 +1,55 @@
+extern crate windows_winmd as winmd;
+
+fn reader() -> winmd::TypeReader {
+    let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("../../.windows/winmd/TestComponent.winmd");
+
+    winmd::TypeReader::try_from_paths(vec![path]).unwrap()
+}
+
+#[test]
+fn properties() {
+    let reader = reader();
+    let def = reader.expect_type_def(("TestComponent", "IComposable"));
+
+    let properties: Vec<winmd::Property> = def.properties().collect();
+    assert!(properties.len() == 1);
+
+    let property = properties[0];
+    assert!(property.name() == "Value");
+    assert!(property.getter().unwrap().name() == "get_Value");
+    assert!(property.setter().unwrap().name() == "put_Value");
+
+    let getter = property.getter().unwrap();
+    assert!(getter.parent() == def);
+    assert!(getter.semantics().unwrap().flags().getter());
+    assert!(getter.category() == winmd::MethodCategory::Get);
+    assert!(property.setter().unwrap().category() == winmd::MethodCategory::Set);
+}
+
+#[test]
+fn events() {
+    let reader = reader();
+    let def = reader.expect_type_def(("TestComponent", "ITests"));
+
+    let events: Vec<winmd::Event> = def.events().collect();
+    assert!(events.len() == 2);
+
+    let event = events[0];
+    assert!(event.name() == "Event1");
+
+    let adder = event.adder().unwrap();
+    assert!(adder.name() == "add_Event1");
+    assert!(adder.category() == winmd::MethodCategory::Add);
+
+    let remover = event.remover().unwrap();
+    assert!(remover.name() == "remove_Event1");
+    assert!(remover.category() == winmd::MethodCategory::Remove);
+
+    let simple = def
+        .methods()
+        .find(|method| method.name() == "Simple")
+        .unwrap();
+    assert!(simple.semantics().is_none());
+    assert!(simple.category() == winmd::MethodCategory::Normal);
+}
@@ -0,0 +1,103 @@
+extern crate windows_winmd as winmd;
+
+use winmd::TypeSig;
+
+fn reader() -> winmd::TypeReader {
+    let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("../../.windows/winmd/TestComponent.winmd");
+
+    winmd::TypeReader::try_from_paths(vec![path]).unwrap()
+}
+
+#[test]
+fn all_signatures() {
+    let reader = reader();
+
+    for t in reader.namespace_types("TestComponent") {
+        if let winmd::Type::TypeDef(def) = t {
+            for field in def.fields() {
+                field.try_signature().unwrap();
+            }
+
+            for method in def.methods() {
+                method.try_signature().unwrap();
+            }
+
+            for property in def.properties() {
+                property.try_signature().unwrap();
+            }
+        }
+    }
+}
+
+#[test]
+fn method() {
+    let reader = reader();
+    let def = reader.expect_type_def(("TestComponent", "Array1Handler"));
+    let invoke = def.methods().find(|m| m.name() == "Invoke").unwrap();
+    let sig = invoke.signature();
+
+    assert!(sig.has_this());
+    assert!(sig.generic_count == 0);
+    assert!(sig.return_type.ty == TypeSig::SZArray(vec![], Box::new(TypeSig::Bool)));
+    assert!(sig.params.len() == 3);
+    assert!(!sig.params[0].by_ref);
+    assert!(sig.params[2].by_ref);
+    assert!(sig.params[2].ty == TypeSig::SZArray(vec![], Box::new(TypeSig::Bool)));
+
+    let def = reader.expect_type_def(("TestComponent", "Composable"));
+    let sig = def
+        .methods()
+        .find(|m| m.name() == "ExpectComposable")
+        .unwrap()
+        .signature();
+
+    assert!(!sig.has_this());
+    assert!(sig.return_type.ty == TypeSig::I32);
+}
+
+#[test]
+fn generic_instance() {
+    let reader = reader();
+    let def = reader.expect_type_def(("TestComponent", "Async4Handler"));
+    let invoke = def.methods().find(|m| m.name() == "Invoke").unwrap();
+
+    match invoke.signature().return_type.ty {
+        TypeSig::GenericInst {
+            def,
+            is_value_type,
+            args,
+        } => {
+            assert!(def.name() == ("Windows.Foundation", "IAsyncOperationWithProgress`2"));
+            assert!(!is_value_type);
+            assert!(args == vec![TypeSig::I32, TypeSig::I32]);
+        }
+        _ => panic!("expected a generic instance"),
+    }
+}
+
+#[test]
+fn field() {
+    let reader = reader();
+    let def = reader.expect_type_def(("TestComponent", "Blittable"));
+    let fields: Vec<winmd::ParamSig> = def.fields().map(|f| f.signature()).collect();
+
+    assert!(fields[0].ty == TypeSig::U8);
+    assert!(fields[8].ty == TypeSig::F64);
+
+    match &fields[9].ty {
+        TypeSig::ValueType(def) => assert!(def.name() == ("System", "Guid")),
+        _ => panic!("expected a value type"),
+    }
+}
+
+#[test]
+fn property() {
+    let reader = reader();
+    let def = reader.expect_type_def(("TestComponent", "IComposable"));
+    let sig = def.properties().next().unwrap().signature();
+
+    assert!(sig.has_this);
+    assert!(sig.ty.ty == TypeSig::I32);
+    assert!(sig.params.is_empty());
+}
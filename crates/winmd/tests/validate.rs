@@ -0,0 +1,173 @@
+extern crate windows_winmd as winmd;
+
+use winmd::writer::*;
+use winmd::Error;
+
+fn read(bytes: Vec<u8>) -> winmd::TypeReader {
+    winmd::TypeReader::builder()
+        .bytes("Test.winmd", bytes)
+        .exclude_default(true)
+        .build()
+        .unwrap()
+}
+
+/// A file with a struct that refers to another type, an attribute and an interface implementation
+fn writer() -> Writer {
+    let mut writer = Writer::new("Test.winmd");
+    let value_type = writer.type_ref(None, "System", "ValueType");
+    let object = writer.type_ref(None, "System", "Object");
+    let point = writer.type_def(0x4109, "Test", "Point", Some(value_type));
+    writer.field(0x6, "X", &TypeSig::I32);
+
+    let class = writer.type_def(0x4101, "Test", "Widget", Some(object));
+    let interface = writer.type_def(0x40a1, "Test", "IWidget", None);
+
+    writer.tables.interface_impl.push(InterfaceImpl {
+        class,
+        interface: TypeDefOrRef::TypeDef(interface),
+    });
+
+    writer.constant(HasConstant::Field(0), &ConstantValue::I32(1));
+
+    let constructor = writer.member_ref(
+        MemberRefParent::TypeDef(point),
+        ".ctor",
+        &MethodSig::new(TypeSig::Void, Vec::new()),
+    );
+
+    writer.attribute(
+        HasAttribute::TypeDef(class),
+        AttributeType::MemberRef(constructor),
+        &[],
+        &[],
+    );
+
+    writer
+}
+
+fn messages(errors: &[Error]) -> Vec<String> {
+    errors
+        .iter()
+        .map(|error| match error {
+            Error::InvalidRow {
+                table,
+                row,
+                message,
+                ..
+            } => format!("{:?} {}: {}", table, row, message),
+            _ => error.to_string(),
+        })
+        .collect()
+}
+
+#[test]
+fn valid() {
+    assert!(winmd::validate(&read(writer().into_bytes())).is_empty());
+
+    let reader = winmd::TypeReader::builder()
+        .midl(
+            "Test.idl",
+            "namespace Test { struct Point { Int32 X; Color C; }; enum Color { Red, Green }; }",
+        )
+        .exclude_default(true)
+        .build()
+        .unwrap();
+
+    assert_eq!(messages(&winmd::validate(&reader)), Vec::<String>::new());
+}
+
+#[test]
+fn test_component() {
+    let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("../../.windows/winmd/TestComponent.winmd");
+
+    let reader = winmd::TypeReader::builder()
+        .paths(vec![path])
+        .exclude_default(true)
+        .build()
+        .unwrap();
+
+    // Without the Windows metadata only the references to Windows.Foundation fail.
+    let errors = winmd::validate(&reader);
+    assert!(!errors.is_empty());
+
+    for message in messages(&errors) {
+        assert!(message.starts_with("TypeRef "), "{}", message);
+        assert!(
+            message.contains("refers to `Windows.Foundation."),
+            "{}",
+            message
+        );
+    }
+}
+
+#[test]
+fn invalid_indices() {
+    let mut writer = writer();
+
+    writer.tables.interface_impl.push(InterfaceImpl {
+        class: 9,
+        interface: TypeDefOrRef::TypeSpec(4),
+    });
+
+    writer.tables.type_def[3].method_list = 5;
+
+    assert_eq!(
+        messages(&winmd::validate(&read(writer.into_bytes()))),
+        [
+            "TypeDef 3: column 5 starts a MethodDef list at 6 but the table has 0 rows",
+            "InterfaceImpl 1: column 0 refers to TypeDef row 10 but the table has 4 rows",
+            "InterfaceImpl 1: column 1 refers to TypeSpec row 5 but the table has 0 rows",
+        ]
+    );
+}
+
+#[test]
+fn unresolved_type_refs() {
+    let mut writer = writer();
+    let missing = writer.type_ref(None, "Test", "Missing");
+    let outer = match writer.type_ref(None, "Test", "Point") {
+        TypeDefOrRef::TypeRef(outer) => outer,
+        _ => unreachable!(),
+    };
+
+    writer.type_ref(Some(ResolutionScope::TypeRef(outer)), "", "Inner");
+    writer.type_ref(None, "System.Runtime.CompilerServices", "IsConst");
+
+    writer.type_def(0x4109, "Test", "Holder", Some(missing));
+
+    assert_eq!(
+        messages(&winmd::validate(&read(writer.into_bytes()))),
+        [
+            "TypeRef 2: refers to `Test.Missing` which is not defined",
+            "TypeRef 4: refers to nested type `Inner` which is not defined",
+        ]
+    );
+}
+
+#[test]
+fn builder() {
+    let mut writer = writer();
+    writer.tables.type_def[3].method_list = 5;
+    let bytes = writer.into_bytes();
+
+    // The reader only checks the lists of types it indexes, so this file can be read.
+    assert!(winmd::TypeReader::builder()
+        .bytes("Test.winmd", bytes.clone())
+        .exclude_default(true)
+        .build()
+        .is_ok());
+
+    match winmd::TypeReader::builder()
+        .bytes("Test.winmd", bytes)
+        .exclude_default(true)
+        .validate(true)
+        .build()
+    {
+        Err(error) => assert_eq!(
+            error.to_string(),
+            "Invalid file `Test.winmd`: TypeDef row 3: column 5 starts a MethodDef list at 6 but the table has 0 rows"
+        ),
+        Ok(_) => panic!("expected a validation error"),
+    }
+}
@@ -0,0 +1,59 @@
+extern crate windows_winmd as winmd;
+
+use std::path::PathBuf;
+
+#[test]
+fn recursive() {
+    let mut root = PathBuf::from(env!("CARGO_TARGET_TMPDIR"));
+    root.push("find_winmd_files");
+    let _ = std::fs::remove_dir_all(&root);
+
+    for path in &[
+        "b/nested/Two.winmd",
+        "b/One.winmd",
+        "a/Three.winmd",
+        "a/notes.txt",
+    ] {
+        let path = root.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, b"").unwrap();
+    }
+
+    // Files are used whatever their extension and are only returned once.
+    let notes = root.join("a/notes.txt");
+
+    let files = winmd::find_winmd_files(&[
+        notes.clone(),
+        root.clone(),
+        root.join("b/One.winmd"),
+        root.join("missing"),
+    ]);
+
+    assert_eq!(
+        files,
+        [
+            notes,
+            root.join("a/Three.winmd"),
+            root.join("b/One.winmd"),
+            root.join("b/nested/Two.winmd"),
+        ]
+    );
+}
+
+#[test]
+fn search_paths() {
+    let first = PathBuf::from("/first");
+    let shared = PathBuf::from("/shared/metadata");
+
+    let value = std::env::join_paths(&[shared.clone(), PathBuf::from("relative")]).unwrap();
+    std::env::set_var(winmd::WINMD_PATH_VAR, value);
+    let paths = winmd::winmd_search_paths(std::slice::from_ref(&first));
+    std::env::remove_var(winmd::WINMD_PATH_VAR);
+
+    // Relative entries are resolved against the workspace root, as is `.windows/winmd`.
+    assert_eq!(paths.len(), 4);
+    assert_eq!(paths[0], first);
+    assert_eq!(paths[1], shared);
+    assert!(paths[2].is_absolute() && paths[2].ends_with("relative"));
+    assert_eq!(paths[3], paths[2].with_file_name(".windows").join("winmd"));
+}
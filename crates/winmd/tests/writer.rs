@@ -0,0 +1,360 @@
+extern crate windows_winmd as winmd;
+
+use winmd::writer;
+
+fn path() -> std::path::PathBuf {
+    let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("../../.windows/winmd/TestComponent.winmd");
+    path
+}
+
+fn read(name: &str, bytes: Vec<u8>) -> winmd::TypeReader {
+    winmd::TypeReader::builder()
+        .bytes(name, bytes)
+        .exclude_default(true)
+        .build()
+        .unwrap()
+}
+
+fn rows(reader: &winmd::TypeReader, table: winmd::TableIndex) -> impl Iterator<Item = winmd::Row> {
+    let row_count = reader.files()[0].row_count(table);
+    (0..row_count).map(move |row| winmd::Row::new(row, table, 0))
+}
+
+fn type_defs(reader: &winmd::TypeReader) -> impl Iterator<Item = winmd::TypeDef<'_>> + '_ {
+    rows(reader, winmd::TableIndex::TypeDef).map(move |row| winmd::TypeDef { reader, row })
+}
+
+fn copy(reader: &winmd::TypeReader) -> winmd::TypeReader {
+    let bytes = writer::Writer::try_from_file(reader, 0)
+        .unwrap()
+        .into_bytes();
+
+    read("copy.winmd", bytes)
+}
+
+type AttributeValue<'a> = (&'a [u8], Option<Vec<(String, winmd::AttributeArg<'a>)>>);
+
+/// The attribute's value blob along with its arguments, if they can be resolved
+fn attribute(attribute: winmd::Attribute<'_>) -> AttributeValue<'_> {
+    (
+        attribute.reader.blob_bytes(attribute.row, 2),
+        attribute.try_args().ok(),
+    )
+}
+
+fn assert_equivalent(original: &winmd::TypeReader, copy: &winmd::TypeReader) {
+    assert_eq!(
+        type_defs(original).count(),
+        type_defs(copy).count(),
+        "TypeDef count"
+    );
+
+    for (original, copy) in type_defs(original).zip(type_defs(copy)) {
+        assert_eq!(original.name(), copy.name());
+        assert_eq!(original.flags().0, copy.flags().0);
+
+        let original_attributes: Vec<_> = original.attributes().map(attribute).collect();
+        let copy_attributes: Vec<_> = copy.attributes().map(attribute).collect();
+        assert_eq!(original_attributes, copy_attributes);
+
+        let original_fields: Vec<_> = original.fields().collect();
+        let copy_fields: Vec<_> = copy.fields().collect();
+        assert_eq!(original_fields.len(), copy_fields.len());
+
+        for (original, copy) in original_fields.iter().zip(copy_fields.iter()) {
+            assert_eq!(original.name(), copy.name());
+            assert_eq!(original.flags().0, copy.flags().0);
+            assert_eq!(original.signature(), copy.signature());
+            assert_eq!(
+                original.constant().map(|c| c.reader.blob_bytes(c.row, 2)),
+                copy.constant().map(|c| c.reader.blob_bytes(c.row, 2))
+            );
+        }
+
+        let original_methods: Vec<_> = original.methods().collect();
+        let copy_methods: Vec<_> = copy.methods().collect();
+        assert_eq!(original_methods.len(), copy_methods.len());
+
+        for (original, copy) in original_methods.iter().zip(copy_methods.iter()) {
+            assert_eq!(original.name(), copy.name());
+            assert_eq!(original.flags().0, copy.flags().0);
+            assert_eq!(original.signature(), copy.signature());
+
+            let original_params: Vec<_> = original
+                .params()
+                .map(|p| (p.sequence(), p.name()))
+                .collect();
+            let copy_params: Vec<_> = copy.params().map(|p| (p.sequence(), p.name())).collect();
+            assert_eq!(original_params, copy_params);
+
+            let original_attributes: Vec<_> = original.attributes().map(attribute).collect();
+            let copy_attributes: Vec<_> = copy.attributes().map(attribute).collect();
+            assert_eq!(original_attributes, copy_attributes);
+        }
+    }
+}
+
+#[test]
+fn round_trip() {
+    let original = &read("TestComponent.winmd", std::fs::read(path()).unwrap());
+    let copy = &copy(original);
+
+    assert_equivalent(original, copy);
+
+    // Every row of every table survives the round trip.
+    assert!(
+        writer::Writer::try_from_file(original, 0).unwrap().tables
+            == writer::Writer::try_from_file(copy, 0).unwrap().tables
+    );
+
+    let module = copy.modules().next().unwrap();
+    assert_eq!(module.name(), "TestComponent.winmd");
+    assert_eq!(module.mvid(), original.modules().next().unwrap().mvid());
+    assert_eq!(module.assembly().unwrap().name(), "TestComponent");
+    assert_eq!(copy.files()[0].user_string(1), " ");
+
+    let def = copy.expect_type_def(("TestComponent", "Composable"));
+    assert_eq!(def.extends().name(), ("System", "Object"));
+}
+
+#[test]
+fn signatures() {
+    let reader = &read("TestComponent.winmd", std::fs::read(path()).unwrap());
+    let mut count = 0;
+
+    for row in rows(reader, winmd::TableIndex::Field) {
+        let field = winmd::Field { reader, row };
+        let sig = writer::ParamSig::from(&field.signature());
+        assert_eq!(sig.to_field_bytes(), reader.blob_bytes(row, 2));
+        count += 1;
+    }
+
+    for row in rows(reader, winmd::TableIndex::MethodDef) {
+        let method = winmd::MethodDef { reader, row };
+        let sig = writer::MethodSig::from(&method.signature());
+        assert_eq!(sig.to_bytes(), reader.blob_bytes(row, 4));
+        count += 1;
+    }
+
+    for row in rows(reader, winmd::TableIndex::Property) {
+        let property = winmd::Property { reader, row };
+        let sig = writer::PropertySig::from(&property.signature());
+        assert_eq!(sig.to_bytes(), reader.blob_bytes(row, 2));
+        count += 1;
+    }
+
+    for row in rows(reader, winmd::TableIndex::TypeSpec) {
+        let spec = winmd::TypeSpec { reader, row };
+        let sig = writer::TypeSig::from(&spec.signature());
+        assert_eq!(sig.to_bytes(), reader.blob_bytes(row, 0));
+        count += 1;
+    }
+
+    assert!(count > 200);
+}
+
+/// Builds a small component with an enum, a struct, an interface and an attribute
+fn component() -> Vec<u8> {
+    use writer::*;
+
+    let mut writer = Writer::new("Sample.winmd");
+    writer.tables.module[0].mvid = winmd::Guid(1, 2, 3, [4, 5, 6, 7, 8, 9, 10, 11]);
+
+    writer.tables.assembly_ref.push(AssemblyRef {
+        version: winmd::Version {
+            major: 4,
+            ..Default::default()
+        },
+        flags: 0,
+        public_key_or_token: vec![0xb7, 0x7a, 0x5c, 0x56, 0x19, 0x34, 0xe0, 0x89],
+        name: "mscorlib".to_string(),
+        culture: String::new(),
+        hash_value: Vec::new(),
+    });
+
+    let mscorlib = Some(ResolutionScope::AssemblyRef(0));
+    let enum_type = writer.type_ref(mscorlib, "System", "Enum");
+    let value_type = writer.type_ref(mscorlib, "System", "ValueType");
+    let attribute_type = writer.type_ref(mscorlib, "System", "Attribute");
+
+    // public sealed enum Color : int { Red = 0, Green = -1 }
+    let color = writer.type_def(0x4101, "Sample", "Color", Some(enum_type));
+    writer.field(0x0606, "value__", &TypeSig::I32);
+
+    for (name, value) in &[("Red", 0), ("Green", -1)] {
+        let field = writer.field(
+            0x8056,
+            name,
+            &TypeSig::ValueType(TypeDefOrRef::TypeDef(color)),
+        );
+        writer.constant(HasConstant::Field(field), &ConstantValue::I32(*value));
+    }
+
+    // public struct Point { int X; double Y; string Name; }
+    writer.type_def(0x4109, "Sample", "Point", Some(value_type));
+    writer.field(0x0006, "X", &TypeSig::I32);
+    writer.field(0x0006, "Y", &TypeSig::F64);
+    writer.field(0x0006, "Name", &TypeSig::String);
+
+    // public interface IShape { Point Center(Color color, ref int count); }
+    let shape = writer.type_def(0x40a1, "Sample", "IShape", None);
+    let mut sig = MethodSig::new(
+        TypeSig::ValueType(TypeDefOrRef::TypeDef(color + 1)),
+        vec![
+            TypeSig::ValueType(TypeDefOrRef::TypeDef(color)),
+            TypeSig::I32,
+        ],
+    );
+    sig.params[1].by_ref = true;
+    writer.method_def(0x05c6, "Center", &sig);
+    writer.param(0x0001, 1, "color");
+    writer.param(0x0002, 2, "count");
+
+    // public sealed class ColorAttribute : Attribute { ColorAttribute(Color color); string Label; }
+    writer.type_def(0x4101, "Sample", "ColorAttribute", Some(attribute_type));
+    writer.field(0x0006, "Label", &TypeSig::String);
+    let constructor = writer.method_def(
+        0x1886,
+        ".ctor",
+        &MethodSig::new(
+            TypeSig::Void,
+            vec![TypeSig::ValueType(TypeDefOrRef::TypeDef(color))],
+        ),
+    );
+    writer.param(0, 1, "color");
+
+    // [Color(Color.Green, Label = "shape")]
+    writer.attribute(
+        HasAttribute::TypeDef(shape),
+        AttributeType::MethodDef(constructor),
+        &[AttributeArg::Enum(
+            "Sample.Color".to_string(),
+            Box::new(AttributeArg::I32(-1)),
+        )],
+        &[NamedArg {
+            property: false,
+            name: "Label".to_string(),
+            value: AttributeArg::String(Some("shape".to_string())),
+        }],
+    );
+
+    writer.user_string("hello");
+    writer.into_bytes()
+}
+
+#[test]
+fn new_component() {
+    let reader = &read("Sample.winmd", component());
+
+    let module = reader.modules().next().unwrap();
+    assert_eq!(module.name(), "Sample.winmd");
+    assert_eq!(
+        module.mvid().to_string(),
+        "00000001-0002-0003-0405-060708090a0b"
+    );
+    assert_eq!(module.assembly_refs().next().unwrap().name(), "mscorlib");
+    assert_eq!(reader.files()[0].user_string(1), "hello");
+
+    // `<Module>` extends nothing but is still a class.
+    let module_type = winmd::TypeDef {
+        reader,
+        row: winmd::Row::new(0, winmd::TableIndex::TypeDef, 0),
+    };
+    assert_eq!(module_type.name(), ("", "<Module>"));
+    assert!(module_type.category() == winmd::TypeCategory::Class);
+
+    let color = reader.expect_type_def(("Sample", "Color"));
+    assert!(color.category() == winmd::TypeCategory::Enum);
+
+    let fields: Vec<_> = color.fields().collect();
+    assert_eq!(fields.len(), 3);
+    assert_eq!(fields[2].name(), "Green");
+    assert_eq!(fields[2].constant().unwrap().value().read_i32(), -1);
+    assert!(fields[1].constant().is_some());
+    assert!(fields[0].constant().is_none());
+
+    let point = reader.expect_type_def(("Sample", "Point"));
+    assert!(point.category() == winmd::TypeCategory::Struct);
+
+    let fields: Vec<_> = point
+        .fields()
+        .map(|field| (field.name(), field.signature().ty))
+        .collect();
+
+    assert_eq!(
+        fields,
+        [
+            ("X", winmd::TypeSig::I32),
+            ("Y", winmd::TypeSig::F64),
+            ("Name", winmd::TypeSig::String)
+        ]
+    );
+
+    let shape = reader.expect_type_def(("Sample", "IShape"));
+    assert!(shape.category() == winmd::TypeCategory::Interface);
+
+    let method = shape.methods().next().unwrap();
+    assert_eq!(method.name(), "Center");
+    let sig = method.signature();
+    assert!(sig.has_this());
+    match &sig.return_type.ty {
+        winmd::TypeSig::ValueType(def) => assert_eq!(def.name(), ("Sample", "Point")),
+        ty => panic!("expected a struct but found {:?}", ty),
+    }
+    assert!(sig.params[1].by_ref);
+
+    let params: Vec<_> = method.params().map(|param| param.name()).collect();
+    assert_eq!(params, ["color", "count"]);
+
+    let attribute = shape.attributes().next().unwrap();
+    let args = attribute.args();
+    assert_eq!(args.len(), 2);
+    assert_eq!(args[0].0, "");
+
+    match &args[0].1 {
+        winmd::AttributeArg::Enum(def, value) => {
+            assert_eq!(def.name(), ("Sample", "Color"));
+            assert_eq!(**value, winmd::AttributeArg::I32(-1));
+        }
+        arg => panic!("expected an enum but found {:?}", arg),
+    }
+
+    assert_eq!(
+        args[1],
+        (
+            "Label".to_string(),
+            winmd::AttributeArg::String("shape".to_string())
+        )
+    );
+}
+
+#[test]
+fn new_component_round_trip() {
+    let original = &read("Sample.winmd", component());
+    let copy = &copy(original);
+
+    assert_equivalent(original, copy);
+    assert_eq!(
+        copy.expect_type_def(("Sample", "Color"))
+            .fields()
+            .filter_map(|field| field.constant())
+            .count(),
+        2
+    );
+}
+
+#[test]
+fn write_to_disk() {
+    let path = std::env::temp_dir().join("windows_winmd_writer_test.winmd");
+    writer::Writer::new("Empty.winmd").write(&path).unwrap();
+
+    let reader = winmd::TypeReader::builder()
+        .path(&path)
+        .exclude_default(true)
+        .build()
+        .unwrap();
+
+    assert_eq!(reader.modules().next().unwrap().name(), "Empty.winmd");
+    std::fs::remove_file(path).unwrap();
+}